@@ -183,6 +183,8 @@ pub struct ToolCall {
     pub resolved_locations: Vec<Option<AgentLocation>>,
     pub raw_input: Option<serde_json::Value>,
     pub raw_output: Option<serde_json::Value>,
+    /// The name of the tool, when the agent reports it in the tool call's metadata.
+    pub tool_name: Option<SharedString>,
 }
 
 impl ToolCall {
@@ -212,6 +214,11 @@ impl ToolCall {
             }
         }
 
+        let tool_name = tool_call
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.get("tool_name")?.as_str())
+            .map(|tool_name| SharedString::from(tool_name.to_string()));
         let result = Self {
             id: tool_call.tool_call_id,
             label: cx
@@ -223,6 +230,7 @@ impl ToolCall {
             status,
             raw_input: tool_call.raw_input,
             raw_output: tool_call.raw_output,
+            tool_name,
         };
         Ok(result)
     }
@@ -1318,6 +1326,7 @@ impl AcpThread {
                     resolved_locations: Vec::new(),
                     raw_input: None,
                     raw_output: None,
+                    tool_name: None,
                 };
                 self.push_entry(AgentThreadEntry::ToolCall(failed_tool_call), cx);
                 return Ok(());
@@ -1899,6 +1908,51 @@ impl AcpThread {
         })
     }
 
+    /// Forks this thread into a new session containing the conversation before the given
+    /// message. When `restore_checkpoint` is true, the git working tree is also restored to
+    /// that message's checkpoint (if one exists), discarding the changes made since then.
+    pub fn fork(
+        &mut self,
+        id: UserMessageId,
+        restore_checkpoint: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<acp::SessionId>> {
+        let Some(fork) = self.connection.fork(&self.session_id, cx) else {
+            return Task::ready(Err(anyhow!("not supported")));
+        };
+        let Some((_, message)) = self.user_message(&id) else {
+            return Task::ready(Err(anyhow!("message not found")));
+        };
+
+        let checkpoint = message
+            .checkpoint
+            .as_ref()
+            .filter(|_| restore_checkpoint)
+            .map(|c| c.git_checkpoint.clone());
+
+        // Cancel any in-progress generation before restoring
+        let cancel_task = self.cancel(cx);
+        let git_store = self.project.read(cx).git_store().clone();
+
+        cx.spawn(async move |this, cx| {
+            cancel_task.await;
+            let session_id = cx.update(|cx| fork.run(id, cx))?.await?;
+            if let Some(checkpoint) = checkpoint {
+                git_store
+                    .update(cx, |git, cx| git.restore_checkpoint(checkpoint, cx))?
+                    .await?;
+                // The edits this thread made since the message are gone from the working
+                // tree, so there is nothing left for the user to review.
+                this.update(cx, |this, cx| {
+                    this.action_log()
+                        .update(cx, |action_log, cx| action_log.keep_all_edits(None, cx))
+                })?;
+            }
+
+            Ok(session_id)
+        })
+    }
+
     /// Rewinds this thread to before the entry at `index`, removing it and all
     /// subsequent entries while rejecting any action_log changes made from that point.
     /// Unlike `restore_checkpoint`, this method does not restore from git.
@@ -1994,6 +2048,13 @@ impl AcpThread {
             })
     }
 
+    fn user_message(&self, id: &UserMessageId) -> Option<(usize, &UserMessage)> {
+        self.entries.iter().enumerate().find_map(|(ix, entry)| {
+            let message = entry.user_message()?;
+            (message.id.as_ref() == Some(id)).then_some((ix, message))
+        })
+    }
+
     fn user_message_mut(&mut self, id: &UserMessageId) -> Option<(usize, &mut UserMessage)> {
         self.entries.iter_mut().enumerate().find_map(|(ix, entry)| {
             if let AgentThreadEntry::UserMessage(message) = entry {
@@ -58,6 +58,10 @@ pub trait AgentConnection {
         None
     }
 
+    fn fork(&self, _session_id: &acp::SessionId, _cx: &App) -> Option<Rc<dyn AgentSessionFork>> {
+        None
+    }
+
     fn set_title(
         &self,
         _session_id: &acp::SessionId,
@@ -99,6 +103,12 @@ pub trait AgentSessionTruncate {
     fn run(&self, message_id: UserMessageId, cx: &mut App) -> Task<Result<()>>;
 }
 
+pub trait AgentSessionFork {
+    /// Saves the conversation preceding the given message as a new session,
+    /// returning its id.
+    fn run(&self, message_id: UserMessageId, cx: &mut App) -> Task<Result<acp::SessionId>>;
+}
+
 pub trait AgentSessionResume {
     fn run(&self, cx: &mut App) -> Task<Result<acp::PromptResponse>>;
 }
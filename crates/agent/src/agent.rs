@@ -699,6 +699,32 @@ impl NativeAgent {
         })
     }
 
+    /// Saves the messages preceding the given user message as a new thread, returning
+    /// the id of the fork.
+    pub fn fork_thread(
+        &mut self,
+        session_id: &acp::SessionId,
+        message_id: &acp_thread::UserMessageId,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<acp::SessionId>> {
+        let Some(session) = self.sessions.get(session_id) else {
+            return Task::ready(Err(anyhow!("Session not found")));
+        };
+        let db_thread = match session.thread.read(cx).fork(message_id, cx) {
+            Ok(db_thread) => db_thread,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        let database_future = ThreadsDatabase::connect(cx);
+        let history = self.history.clone();
+        cx.spawn(async move |_, cx| {
+            let database = database_future.await.map_err(|err| anyhow!(err))?;
+            let id = acp::SessionId::new(uuid::Uuid::new_v4().to_string());
+            database.save_thread(id.clone(), db_thread.await).await?;
+            history.update(cx, |history, cx| history.reload(cx))?;
+            Ok(id)
+        })
+    }
+
     fn save_thread(&mut self, thread: Entity<Thread>, cx: &mut Context<Self>) {
         if thread.read(cx).is_empty() {
             return;
@@ -1070,6 +1096,17 @@ impl acp_thread::AgentConnection for NativeAgentConnection {
         })
     }
 
+    fn fork(
+        &self,
+        session_id: &acp::SessionId,
+        _cx: &App,
+    ) -> Option<Rc<dyn acp_thread::AgentSessionFork>> {
+        Some(Rc::new(NativeAgentSessionFork {
+            connection: self.clone(),
+            session_id: session_id.clone(),
+        }) as _)
+    }
+
     fn set_title(
         &self,
         session_id: &acp::SessionId,
@@ -1145,6 +1182,23 @@ impl acp_thread::AgentSessionResume for NativeAgentSessionResume {
     }
 }
 
+struct NativeAgentSessionFork {
+    connection: NativeAgentConnection,
+    session_id: acp::SessionId,
+}
+
+impl acp_thread::AgentSessionFork for NativeAgentSessionFork {
+    fn run(
+        &self,
+        message_id: acp_thread::UserMessageId,
+        cx: &mut App,
+    ) -> Task<Result<acp::SessionId>> {
+        self.connection.0.update(cx, |agent, cx| {
+            agent.fork_thread(&self.session_id, &message_id, cx)
+        })
+    }
+}
+
 struct NativeAgentSessionSetTitle {
     connection: NativeAgentConnection,
     session_id: acp::SessionId,
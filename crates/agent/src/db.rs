@@ -29,6 +29,9 @@ pub struct DbThreadMetadata {
     #[serde(alias = "summary")]
     pub title: SharedString,
     pub updated_at: DateTime<Utc>,
+    /// The thread this one was forked from.
+    #[serde(default)]
+    pub parent_id: Option<acp::SessionId>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -50,6 +53,8 @@ pub struct DbThread {
     pub completion_mode: Option<CompletionMode>,
     #[serde(default)]
     pub profile: Option<AgentProfileId>,
+    #[serde(default)]
+    pub parent_id: Option<acp::SessionId>,
 }
 
 impl DbThread {
@@ -209,6 +214,7 @@ impl DbThread {
             model: thread.model,
             completion_mode: thread.completion_mode,
             profile: thread.profile,
+            parent_id: None,
         })
     }
 }
@@ -306,6 +312,15 @@ impl ThreadsDatabase {
         "})?()
         .map_err(|e| anyhow!("Failed to create threads table: {}", e))?;
 
+        let has_parent_id =
+            connection.select::<String>("SELECT name FROM pragma_table_info('threads')")?()?
+                .iter()
+                .any(|column| column == "parent_id");
+        if !has_parent_id {
+            connection.exec("ALTER TABLE threads ADD COLUMN parent_id TEXT")?()
+                .map_err(|e| anyhow!("Failed to add parent_id column: {}", e))?;
+        }
+
         let db = Self {
             executor,
             connection: Arc::new(Mutex::new(connection)),
@@ -330,6 +345,7 @@ impl ThreadsDatabase {
 
         let title = thread.title.to_string();
         let updated_at = thread.updated_at.to_rfc3339();
+        let parent_id = thread.parent_id.clone().map(|parent_id| parent_id.0);
         let json_data = serde_json::to_string(&SerializedThread {
             thread,
             version: DbThread::VERSION,
@@ -341,11 +357,11 @@ impl ThreadsDatabase {
         let data_type = DataType::Zstd;
         let data = compressed;
 
-        let mut insert = connection.exec_bound::<(Arc<str>, String, String, DataType, Vec<u8>)>(indoc! {"
-            INSERT OR REPLACE INTO threads (id, summary, updated_at, data_type, data) VALUES (?, ?, ?, ?, ?)
+        let mut insert = connection.exec_bound::<(Arc<str>, String, String, DataType, Vec<u8>, Option<Arc<str>>)>(indoc! {"
+            INSERT OR REPLACE INTO threads (id, summary, updated_at, data_type, data, parent_id) VALUES (?, ?, ?, ?, ?, ?)
         "})?;
 
-        insert((id.0, title, updated_at, data_type, data))?;
+        insert((id.0, title, updated_at, data_type, data, parent_id))?;
 
         Ok(())
     }
@@ -356,19 +372,20 @@ impl ThreadsDatabase {
         self.executor.spawn(async move {
             let connection = connection.lock();
 
-            let mut select =
-                connection.select_bound::<(), (Arc<str>, String, String)>(indoc! {"
-                SELECT id, summary, updated_at FROM threads ORDER BY updated_at DESC
+            let mut select = connection
+                .select_bound::<(), (Arc<str>, String, String, Option<Arc<str>>)>(indoc! {"
+                SELECT id, summary, updated_at, parent_id FROM threads ORDER BY updated_at DESC
             "})?;
 
             let rows = select(())?;
             let mut threads = Vec::new();
 
-            for (id, summary, updated_at) in rows {
+            for (id, summary, updated_at, parent_id) in rows {
                 threads.push(DbThreadMetadata {
                     id: acp::SessionId::new(id),
                     title: summary.into(),
                     updated_at: DateTime::parse_from_rfc3339(&updated_at)?.with_timezone(&Utc),
+                    parent_id: parent_id.map(acp::SessionId::new),
                 });
             }
 
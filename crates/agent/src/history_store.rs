@@ -83,6 +83,14 @@ impl HistoryEntry {
         }
     }
 
+    /// The thread this entry was forked from.
+    pub fn parent_id(&self) -> Option<&acp::SessionId> {
+        match self {
+            HistoryEntry::AcpThread(thread) => thread.parent_id.as_ref(),
+            HistoryEntry::TextThread(_) => None,
+        }
+    }
+
     pub fn title(&self) -> &SharedString {
         match self {
             HistoryEntry::AcpThread(thread) => {
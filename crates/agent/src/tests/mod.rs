@@ -1,7 +1,7 @@
 use super::*;
 use acp_thread::{AgentConnection, AgentModelGroupName, AgentModelList, UserMessageId};
 use agent_client_protocol::{self as acp};
-use agent_settings::AgentProfileId;
+use agent_settings::{AgentProfileId, AgentSettings};
 use anyhow::Result;
 use client::{Client, UserStore};
 use cloud_llm_client::CompletionIntent;
@@ -34,7 +34,7 @@ use reqwest_client::ReqwestClient;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use settings::{Settings, SettingsStore};
+use settings::{Settings, SettingsStore, ToolPermissionMode};
 use std::{path::Path, rc::Rc, sync::Arc, time::Duration};
 use util::path;
 
@@ -590,6 +590,132 @@ async fn test_tool_authorization(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_tool_permission_rules(cx: &mut TestAppContext) {
+    let ThreadTest {
+        model, thread, fs, ..
+    } = setup(cx, TestModel::Fake).await;
+    let fake_model = model.as_fake();
+
+    fs.insert_file(
+        paths::settings_file(),
+        json!({
+            "agent": {
+                "tool_permissions": {
+                    "echo": {
+                        "default": "ask",
+                        "allow": ["hello*"],
+                        "deny": ["rm *"]
+                    }
+                }
+            }
+        })
+        .to_string()
+        .into_bytes(),
+    )
+    .await;
+    cx.run_until_parked();
+
+    let mut events = thread
+        .update(cx, |thread, cx| {
+            thread.add_tool(EchoTool);
+            thread.send(UserMessageId::new(), ["abc"], cx)
+        })
+        .unwrap();
+    cx.run_until_parked();
+
+    // Allowed and denied calls run without asking.
+    fake_model.send_last_completion_stream_event(LanguageModelCompletionEvent::ToolUse(
+        LanguageModelToolUse {
+            id: "tool_id_1".into(),
+            name: EchoTool::name().into(),
+            raw_input: json!({"text": "hello world"}).to_string(),
+            input: json!({"text": "hello world"}),
+            is_input_complete: true,
+            thought_signature: None,
+        },
+    ));
+    fake_model.end_last_completion_stream();
+    cx.run_until_parked();
+    let completion = fake_model.pending_completions().pop().unwrap();
+    assert_eq!(
+        completion.messages.last().unwrap().content,
+        vec![language_model::MessageContent::ToolResult(
+            LanguageModelToolResult {
+                tool_use_id: "tool_id_1".into(),
+                tool_name: EchoTool::name().into(),
+                is_error: false,
+                content: "hello world".into(),
+                output: Some("hello world".into())
+            }
+        )]
+    );
+
+    fake_model.send_last_completion_stream_event(LanguageModelCompletionEvent::ToolUse(
+        LanguageModelToolUse {
+            id: "tool_id_2".into(),
+            name: EchoTool::name().into(),
+            raw_input: json!({"text": "rm -rf /"}).to_string(),
+            input: json!({"text": "rm -rf /"}),
+            is_input_complete: true,
+            thought_signature: None,
+        },
+    ));
+    fake_model.end_last_completion_stream();
+    cx.run_until_parked();
+    let completion = fake_model.pending_completions().pop().unwrap();
+    let message = completion.messages.last().unwrap();
+    let language_model::MessageContent::ToolResult(result) = &message.content[0] else {
+        panic!("expected a tool result, got {:?}", message.content);
+    };
+    assert!(result.is_error);
+
+    // Other calls fall back to asking, even though the tool wouldn't ask by itself.
+    fake_model.send_last_completion_stream_event(LanguageModelCompletionEvent::ToolUse(
+        LanguageModelToolUse {
+            id: "tool_id_3".into(),
+            name: EchoTool::name().into(),
+            raw_input: json!({"text": "other"}).to_string(),
+            input: json!({"text": "other"}),
+            is_input_complete: true,
+            thought_signature: None,
+        },
+    ));
+    fake_model.end_last_completion_stream();
+    let tool_call_auth = next_tool_call_authorization(&mut events).await;
+    tool_call_auth
+        .response
+        .send(tool_call_auth.options[0].option_id.clone())
+        .unwrap();
+    cx.run_until_parked();
+    let completion = fake_model.pending_completions().pop().unwrap();
+    assert_eq!(
+        completion.messages.last().unwrap().content,
+        vec![language_model::MessageContent::ToolResult(
+            LanguageModelToolResult {
+                tool_use_id: "tool_id_3".into(),
+                tool_name: EchoTool::name().into(),
+                is_error: false,
+                content: "other".into(),
+                output: Some("other".into())
+            }
+        )]
+    );
+
+    // Always allowing the call remembers its subject as a rule.
+    cx.update(|cx| {
+        let permissions = &AgentSettings::get_global(cx).tool_permissions;
+        assert_eq!(
+            permissions.mode(EchoTool::name(), &["other".into()]),
+            Some(ToolPermissionMode::Allow)
+        );
+        assert_eq!(
+            permissions.mode(EchoTool::name(), &["another".into()]),
+            Some(ToolPermissionMode::Ask)
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_tool_hallucination(cx: &mut TestAppContext) {
     let ThreadTest { model, thread, .. } = setup(cx, TestModel::Fake).await;
@@ -995,6 +1121,132 @@ async fn test_profiles(cx: &mut TestAppContext) {
     assert_eq!(tool_names, vec![InfiniteTool::name()]);
 }
 
+#[gpui::test]
+async fn test_subagent(cx: &mut TestAppContext) {
+    let ThreadTest {
+        model, thread, fs, ..
+    } = setup(cx, TestModel::Fake).await;
+    let fake_model = model.as_fake();
+
+    fs.insert_file(
+        paths::settings_file(),
+        json!({
+            "agent": {
+                "default_profile": "parent",
+                "profiles": {
+                    "parent": {
+                        "name": "Parent",
+                        "tools": {
+                            SubagentTool::name(): true,
+                            ThinkingTool::name(): true,
+                        }
+                    },
+                    "child": {
+                        "name": "Child",
+                        "tools": {
+                            GrepTool::name(): true,
+                            ThinkingTool::name(): true,
+                        }
+                    }
+                }
+            }
+        })
+        .to_string()
+        .into_bytes(),
+    )
+    .await;
+    cx.run_until_parked();
+
+    thread
+        .update(cx, |thread, cx| {
+            thread.set_profile(AgentProfileId("parent".into()), cx);
+            thread.add_default_tools(Rc::new(FakeThreadEnvironment), cx);
+            thread.send(UserMessageId::new(), ["Delegate the search"], cx)
+        })
+        .unwrap();
+    cx.run_until_parked();
+    fake_model.send_last_completion_stream_event(LanguageModelCompletionEvent::ToolUse(
+        LanguageModelToolUse {
+            id: "tool_id_1".into(),
+            name: SubagentTool::name().into(),
+            raw_input: "{}".into(),
+            input: json!({
+                "label": "Search for callers",
+                "task": "Find the callers of `main`",
+                "profile": "child"
+            }),
+            is_input_complete: true,
+            thought_signature: None,
+        },
+    ));
+    fake_model.end_last_completion_stream();
+    cx.run_until_parked();
+
+    // The subagent runs with its own messages, and can only use the tools
+    // enabled in both profiles.
+    let completion = fake_model.pending_completions().pop().unwrap();
+    assert!(
+        completion
+            .messages
+            .last()
+            .unwrap()
+            .string_contents()
+            .starts_with("Find the callers of `main`")
+    );
+    let tool_names = completion
+        .tools
+        .iter()
+        .map(|tool| tool.name.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(tool_names, vec![ThinkingTool::name()]);
+
+    fake_model.send_last_completion_stream_text_chunk("`main` has no callers.");
+    fake_model.end_last_completion_stream();
+    cx.run_until_parked();
+
+    // The subagent's summary is returned to the parent.
+    let completion = fake_model.pending_completions().pop().unwrap();
+    assert_eq!(
+        completion.messages.last().unwrap().content,
+        vec![language_model::MessageContent::ToolResult(
+            LanguageModelToolResult {
+                tool_use_id: "tool_id_1".into(),
+                tool_name: SubagentTool::name().into(),
+                is_error: false,
+                content: "`main` has no callers.".into(),
+                output: Some(json!({
+                    "summary": "`main` has no callers.",
+                    "transcript": indoc! {"
+                        ## User
+
+                        Find the callers of `main`
+
+                        When you're done, reply with a concise summary of what you did and found. It will be passed back to the agent that delegated this task to you.
+
+                        ## Assistant
+
+                        `main` has no callers.
+                    "}
+                })),
+            }
+        )]
+    );
+}
+
+struct FakeThreadEnvironment;
+
+impl ThreadEnvironment for FakeThreadEnvironment {
+    fn create_terminal(
+        &self,
+        _command: String,
+        _cwd: Option<std::path::PathBuf>,
+        _output_byte_limit: Option<u64>,
+        _cx: &mut gpui::AsyncApp,
+    ) -> Task<Result<Rc<dyn TerminalHandle>>> {
+        Task::ready(Err(anyhow::anyhow!("Terminals aren't supported in tests")))
+    }
+}
+
 #[gpui::test]
 async fn test_mcp_tools(cx: &mut TestAppContext) {
     let ThreadTest {
@@ -1751,6 +2003,86 @@ async fn test_truncate_second_message(cx: &mut TestAppContext) {
     assert_first_message_state(cx);
 }
 
+#[gpui::test]
+async fn test_fork_thread(cx: &mut TestAppContext) {
+    let ThreadTest { model, thread, .. } = setup(cx, TestModel::Fake).await;
+    let fake_model = model.as_fake();
+
+    let first_message_id = UserMessageId::new();
+    thread
+        .update(cx, |thread, cx| {
+            thread.send(first_message_id.clone(), ["Message 1"], cx)
+        })
+        .unwrap();
+    cx.run_until_parked();
+    fake_model.send_last_completion_stream_text_chunk("Message 1 response");
+    fake_model.send_last_completion_stream_event(LanguageModelCompletionEvent::UsageUpdate(
+        language_model::TokenUsage {
+            input_tokens: 32_000,
+            output_tokens: 16_000,
+            cache_creation_input_tokens: 0,
+            cache_read_input_tokens: 0,
+        },
+    ));
+    fake_model.end_last_completion_stream();
+    cx.run_until_parked();
+
+    let second_message_id = UserMessageId::new();
+    thread
+        .update(cx, |thread, cx| {
+            thread.send(second_message_id.clone(), ["Message 2"], cx)
+        })
+        .unwrap();
+    cx.run_until_parked();
+    fake_model.send_last_completion_stream_text_chunk("Message 2 response");
+    fake_model.end_last_completion_stream();
+    cx.run_until_parked();
+
+    let fork = thread
+        .read_with(cx, |thread, cx| thread.fork(&second_message_id, cx))
+        .unwrap()
+        .await;
+    assert_eq!(fork.messages.len(), 2);
+    assert_eq!(
+        fork.parent_id.as_ref(),
+        Some(thread.read_with(cx, |thread, _| thread.id().clone())).as_ref()
+    );
+    assert_eq!(
+        fork.request_token_usage.keys().collect::<Vec<_>>(),
+        vec![&first_message_id]
+    );
+
+    // The forked thread is left untouched.
+    thread.read_with(cx, |thread, _| {
+        assert_eq!(
+            thread.to_markdown(),
+            indoc! {"
+                ## User
+
+                Message 1
+
+                ## Assistant
+
+                Message 1 response
+
+                ## User
+
+                Message 2
+
+                ## Assistant
+
+                Message 2 response
+            "}
+        );
+    });
+
+    assert!(
+        thread
+            .read_with(cx, |thread, cx| thread.fork(&UserMessageId::new(), cx))
+            .is_err()
+    );
+}
+
 #[gpui::test]
 async fn test_title_generation(cx: &mut TestAppContext) {
     let ThreadTest { model, thread, .. } = setup(cx, TestModel::Fake).await;
@@ -32,6 +32,10 @@ impl AgentTool for EchoTool {
         "Echo".into()
     }
 
+    fn permission_subjects(&self, input: &Self::Input, _cx: &App) -> Vec<String> {
+        vec![input.text.clone()]
+    }
+
     fn run(
         self: Arc<Self>,
         input: Self::Input,
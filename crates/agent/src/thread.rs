@@ -1,7 +1,8 @@
 use crate::{
     ContextServerRegistry, CopyPathTool, CreateDirectoryTool, DbLanguageModel, DbThread,
-    DeletePathTool, DiagnosticsTool, EditFileTool, FetchTool, FindPathTool, GrepTool,
-    ListDirectoryTool, MovePathTool, NowTool, OpenTool, ProjectSnapshot, ReadFileTool,
+    DeletePathTool, DiagnosticsTool, EditFileTool, FetchTool, FindPathTool, FindReferencesTool,
+    GoToDefinitionTool, GrepTool, ListDirectoryTool, MovePathTool, NowTool, OpenTool,
+    ProjectSnapshot, ReadFileTool, RenameSymbolTool, SearchSymbolsTool, SubagentTool,
     SystemPromptTemplate, Template, Templates, TerminalTool, ThinkingTool, WebSearchTool,
 };
 use acp_thread::{MentionUri, UserMessageId};
@@ -40,7 +41,7 @@ use project::Project;
 use prompt_store::ProjectContext;
 use schemars::{JsonSchema, Schema};
 use serde::{Deserialize, Serialize};
-use settings::{LanguageModelSelection, Settings, update_settings_file};
+use settings::{LanguageModelSelection, Settings, ToolPermissionMode, update_settings_file};
 use smol::stream::StreamExt;
 use std::{
     collections::BTreeMap,
@@ -191,6 +192,7 @@ impl UserMessage {
         const OPEN_SELECTIONS_TAG: &str = "<selections>";
         const OPEN_THREADS_TAG: &str = "<threads>";
         const OPEN_FETCH_TAG: &str = "<fetched_urls>";
+        const OPEN_RESOURCES_TAG: &str = "<context_server_resources>";
         const OPEN_RULES_TAG: &str =
             "<rules>\nThe user has specified the following rules that should be applied:\n";
 
@@ -200,6 +202,7 @@ impl UserMessage {
         let mut selection_context = OPEN_SELECTIONS_TAG.to_string();
         let mut thread_context = OPEN_THREADS_TAG.to_string();
         let mut fetch_context = OPEN_FETCH_TAG.to_string();
+        let mut resource_context = OPEN_RESOURCES_TAG.to_string();
         let mut rules_context = OPEN_RULES_TAG.to_string();
 
         for chunk in &self.content {
@@ -282,6 +285,14 @@ impl UserMessage {
                         MentionUri::Fetch { url } => {
                             write!(&mut fetch_context, "\nFetch: {}\n\n{}", url, content).ok();
                         }
+                        MentionUri::ContextServerResource { server_id, uri, .. } => {
+                            write!(
+                                &mut resource_context,
+                                "\nResource from {}: {}\n\n{}\n",
+                                server_id, uri, content
+                            )
+                            .ok();
+                        }
                     }
 
                     language_model::MessageContent::Text(uri.as_link().to_string())
@@ -335,6 +346,13 @@ impl UserMessage {
                 .push(language_model::MessageContent::Text(fetch_context));
         }
 
+        if resource_context.len() > OPEN_RESOURCES_TAG.len() {
+            resource_context.push_str("</context_server_resources>\n");
+            message
+                .content
+                .push(language_model::MessageContent::Text(resource_context));
+        }
+
         if rules_context.len() > OPEN_RULES_TAG.len() {
             rules_context.push_str("</user_rules>\n");
             message
@@ -581,6 +599,11 @@ enum CompletionError {
 
 pub struct Thread {
     id: acp::SessionId,
+    /// The thread this one was forked from.
+    parent_id: Option<acp::SessionId>,
+    /// For subagents, the profile of the thread that delegated work to this one,
+    /// which also restricts the tools this thread can use.
+    delegating_profile_id: Option<AgentProfileId>,
     prompt_id: PromptId,
     updated_at: DateTime<Utc>,
     title: Option<SharedString>,
@@ -638,6 +661,8 @@ impl Thread {
             watch::channel(Self::prompt_capabilities(model.as_deref()));
         Self {
             id: acp::SessionId::new(uuid::Uuid::new_v4().to_string()),
+            parent_id: None,
+            delegating_profile_id: None,
             prompt_id: PromptId::new(),
             updated_at: Utc::now(),
             title: None,
@@ -677,6 +702,52 @@ impl Thread {
         &self.id
     }
 
+    pub fn parent_id(&self) -> Option<&acp::SessionId> {
+        self.parent_id.as_ref()
+    }
+
+    /// Creates a thread to run a task delegated by this one, with its own messages
+    /// but recording its edits in this thread's action log.
+    ///
+    /// The subagent can only use the tools enabled in both its profile and this thread's.
+    pub fn new_subagent(
+        &self,
+        profile_id: Option<AgentProfileId>,
+        environment: Rc<dyn ThreadEnvironment>,
+        cx: &mut Context<Self>,
+    ) -> Result<Entity<Thread>> {
+        let profile_id = profile_id.unwrap_or_else(|| self.profile_id.clone());
+        anyhow::ensure!(
+            AgentSettings::get_global(cx)
+                .profiles
+                .contains_key(&profile_id),
+            "Profile not found: {profile_id}"
+        );
+
+        let delegating_profile_id = self.profile_id.clone();
+        let action_log = self.action_log.clone();
+        let completion_mode = self.completion_mode;
+        let summarization_model = self.summarization_model.clone();
+        Ok(cx.new(|cx| {
+            let mut thread = Thread::new(
+                self.project.clone(),
+                self.project_context.clone(),
+                self.context_server_registry.clone(),
+                self.templates.clone(),
+                self.model.clone(),
+                cx,
+            );
+            thread.profile_id = profile_id;
+            thread.delegating_profile_id = Some(delegating_profile_id);
+            thread.action_log = action_log;
+            thread.completion_mode = completion_mode;
+            thread.summarization_model = summarization_model;
+            thread.add_default_tools(environment, cx);
+            thread.remove_tool(SubagentTool::name());
+            thread
+        }))
+    }
+
     pub fn replay(
         &mut self,
         cx: &mut Context<Self>,
@@ -827,6 +898,8 @@ impl Thread {
 
         Self {
             id,
+            parent_id: db_thread.parent_id,
+            delegating_profile_id: None,
             prompt_id: PromptId::new(),
             title: if db_thread.title.is_empty() {
                 None
@@ -877,6 +950,7 @@ impl Thread {
             }),
             completion_mode: Some(self.completion_mode),
             profile: Some(self.profile_id.clone()),
+            parent_id: self.parent_id.clone(),
         };
 
         cx.background_spawn(async move {
@@ -991,6 +1065,8 @@ impl Thread {
         ));
         self.add_tool(FetchTool::new(self.project.read(cx).client().http_client()));
         self.add_tool(FindPathTool::new(self.project.clone()));
+        self.add_tool(FindReferencesTool::new(self.project.clone()));
+        self.add_tool(GoToDefinitionTool::new(self.project.clone()));
         self.add_tool(GrepTool::new(self.project.clone()));
         self.add_tool(ListDirectoryTool::new(self.project.clone()));
         self.add_tool(MovePathTool::new(self.project.clone()));
@@ -1001,6 +1077,12 @@ impl Thread {
             self.project.clone(),
             self.action_log.clone(),
         ));
+        self.add_tool(RenameSymbolTool::new(
+            cx.weak_entity(),
+            self.project.clone(),
+        ));
+        self.add_tool(SearchSymbolsTool::new(self.project.clone()));
+        self.add_tool(SubagentTool::new(cx.weak_entity(), environment.clone()));
         self.add_tool(TerminalTool::new(self.project.clone(), environment));
         self.add_tool(ThinkingTool);
         self.add_tool(WebSearchTool);
@@ -1070,6 +1152,40 @@ impl Thread {
         Ok(())
     }
 
+    /// Copies the messages preceding the given user message into a new thread
+    /// that records this one as its parent.
+    pub fn fork(&self, message_id: &UserMessageId, cx: &App) -> Result<Task<DbThread>> {
+        let position = self
+            .messages
+            .iter()
+            .position(
+                |msg| matches!(msg, Message::User(UserMessage { id, .. }) if id == message_id),
+            )
+            .context("Message not found")?;
+        let messages = self.messages[..position].to_vec();
+        let request_token_usage = messages
+            .iter()
+            .filter_map(|message| match message {
+                Message::User(message) => self
+                    .request_token_usage
+                    .get(&message.id)
+                    .map(|usage| (message.id.clone(), *usage)),
+                Message::Agent(_) | Message::Resume => None,
+            })
+            .collect();
+        let parent_id = self.id.clone();
+        let db_thread = self.to_db(cx);
+        Ok(cx.background_spawn(async move {
+            let mut thread = db_thread.await;
+            thread.messages = messages;
+            thread.request_token_usage = request_token_usage;
+            thread.detailed_summary = None;
+            thread.updated_at = Utc::now();
+            thread.parent_id = Some(parent_id);
+            thread
+        }))
+    }
+
     pub fn latest_request_token_usage(&self) -> Option<language_model::TokenUsage> {
         let last_user_message = self.last_user_message()?;
         let tokens = self.request_token_usage.get(&last_user_message.id)?;
@@ -1533,6 +1649,7 @@ impl Thread {
             }
         });
 
+        let title_for_authorization = title.to_string();
         if push_new_tool_use {
             event_stream.send_tool_call(
                 &tool_use.id,
@@ -1571,12 +1688,42 @@ impl Thread {
 
         let fs = self.project.read(cx).fs().clone();
         let tool_event_stream =
-            ToolCallEventStream::new(tool_use.id.clone(), event_stream.clone(), Some(fs));
+            ToolCallEventStream::new(tool_use.id.clone(), event_stream.clone(), Some(fs))
+                .with_permission(
+                    tool.name(),
+                    tool.permission_subjects(&tool_use.input, cx),
+                    cx,
+                );
+        if tool_event_stream.permission_mode() == Some(ToolPermissionMode::Deny) {
+            let content = format!(
+                "Permission to run tool {} with this input denied by the user's settings",
+                tool_use.name
+            );
+            return Some(Task::ready(LanguageModelToolResult {
+                content: LanguageModelToolResultContent::Text(Arc::from(content.clone())),
+                tool_use_id: tool_use.id,
+                tool_name: tool_use.name,
+                is_error: true,
+                output: Some(content.into()),
+            }));
+        }
         tool_event_stream.update_fields(
             acp::ToolCallUpdateFields::new().status(acp::ToolCallStatus::InProgress),
         );
         let supports_images = self.model().is_some_and(|model| model.supports_images());
-        let tool_result = tool.run(tool_use.input, tool_event_stream, cx);
+        let tool_result = if tool_event_stream.permission_mode() == Some(ToolPermissionMode::Ask) {
+            // The rules require asking even if the tool wouldn't, so ask before running it
+            // and don't ask again from within the tool.
+            let authorize = tool_event_stream.authorize(title_for_authorization, cx);
+            let input = tool_use.input;
+            cx.spawn(async move |_, cx| {
+                authorize.await?;
+                cx.update(|cx| tool.run(input, tool_event_stream.authorized(), cx))?
+                    .await
+            })
+        } else {
+            tool.run(tool_use.input, tool_event_stream, cx)
+        };
         log::debug!("Running tool {}", tool_use.name);
         Some(cx.foreground_executor().spawn(async move {
             let tool_result = tool_result.await.and_then(|output| {
@@ -1909,12 +2056,20 @@ impl Thread {
             }
         }
 
+        // Subagents can't use the tools the thread delegating to them can't use.
+        let delegating_profile = self
+            .delegating_profile_id
+            .as_ref()
+            .map(|profile_id| AgentSettings::get_global(cx).profiles.get(profile_id));
         let mut tools = self
             .tools
             .iter()
             .filter_map(|(tool_name, tool)| {
                 if tool.supports_provider(&model.provider_id())
                     && profile.is_tool_enabled(tool_name)
+                    && delegating_profile.is_none_or(|delegating_profile| {
+                        delegating_profile.is_some_and(|profile| profile.is_tool_enabled(tool_name))
+                    })
                 {
                     Some((truncate(tool_name), tool.clone()))
                 } else {
@@ -1928,7 +2083,13 @@ impl Thread {
         let mut duplicate_tool_names = HashSet::default();
         for (server_id, server_tools) in self.context_server_registry.read(cx).servers() {
             for (tool_name, tool) in server_tools {
-                if profile.is_context_server_tool_enabled(&server_id.0, &tool_name) {
+                if profile.is_context_server_tool_enabled(&server_id.0, &tool_name)
+                    && delegating_profile.is_none_or(|delegating_profile| {
+                        delegating_profile.is_some_and(|profile| {
+                            profile.is_context_server_tool_enabled(&server_id.0, &tool_name)
+                        })
+                    })
+                {
                     let tool_name = truncate(tool_name);
                     if !seen_tools.insert(tool_name.clone()) {
                         duplicate_tool_names.insert(tool_name.clone());
@@ -2199,6 +2360,12 @@ where
         true
     }
 
+    /// The values that the user's permission rules for this tool are matched against, like the
+    /// command a terminal runs or the paths a tool edits.
+    fn permission_subjects(&self, _input: &Self::Input, _cx: &App) -> Vec<String> {
+        Vec::new()
+    }
+
     /// Runs the tool with the provided input.
     fn run(
         self: Arc<Self>,
@@ -2239,6 +2406,9 @@ pub trait AnyAgentTool {
     fn supports_provider(&self, _provider: &LanguageModelProviderId) -> bool {
         true
     }
+    fn permission_subjects(&self, _input: &serde_json::Value, _cx: &App) -> Vec<String> {
+        Vec::new()
+    }
     fn run(
         self: Arc<Self>,
         input: serde_json::Value,
@@ -2285,6 +2455,12 @@ where
         T::supports_provider(provider)
     }
 
+    fn permission_subjects(&self, input: &serde_json::Value, cx: &App) -> Vec<String> {
+        serde_json::from_value(input.clone())
+            .map(|input| self.0.permission_subjects(&input, cx))
+            .unwrap_or_default()
+    }
+
     fn run(
         self: Arc<Self>,
         input: serde_json::Value,
@@ -2410,6 +2586,17 @@ pub struct ToolCallEventStream {
     tool_use_id: LanguageModelToolUseId,
     stream: ThreadEventStream,
     fs: Option<Arc<dyn Fs>>,
+    permission: Option<ToolCallPermission>,
+}
+
+/// What the user's permission rules say about a tool call.
+#[derive(Clone)]
+struct ToolCallPermission {
+    tool_name: SharedString,
+    subjects: Vec<String>,
+    mode: Option<ToolPermissionMode>,
+    /// Whether an `ask` pattern matched, in which case "always allow" can't be remembered.
+    always_ask: bool,
 }
 
 impl ToolCallEventStream {
@@ -2431,9 +2618,36 @@ impl ToolCallEventStream {
             tool_use_id,
             stream,
             fs,
+            permission: None,
         }
     }
 
+    /// Applies the user's permission rules for the tool to this call.
+    fn with_permission(mut self, tool_name: SharedString, subjects: Vec<String>, cx: &App) -> Self {
+        let permissions = &AgentSettings::get_global(cx).tool_permissions;
+        self.permission = Some(ToolCallPermission {
+            mode: permissions.mode(&tool_name, &subjects),
+            always_ask: permissions.always_asks(&tool_name, &subjects),
+            tool_name,
+            subjects,
+        });
+        self
+    }
+
+    fn permission_mode(&self) -> Option<ToolPermissionMode> {
+        self.permission
+            .as_ref()
+            .and_then(|permission| permission.mode)
+    }
+
+    /// Returns a stream for running the tool once the user has allowed the call.
+    fn authorized(mut self) -> Self {
+        if let Some(permission) = self.permission.as_mut() {
+            permission.mode = Some(ToolPermissionMode::Allow);
+        }
+        self
+    }
+
     pub fn update_fields(&self, fields: acp::ToolCallUpdateFields) {
         self.stream
             .update_tool_call_fields(&self.tool_use_id, fields);
@@ -2453,10 +2667,25 @@ impl ToolCallEventStream {
     }
 
     pub fn authorize(&self, title: impl Into<String>, cx: &mut App) -> Task<Result<()>> {
-        if agent_settings::AgentSettings::get_global(cx).always_allow_tool_actions {
-            return Task::ready(Ok(()));
+        match self.permission_mode() {
+            Some(ToolPermissionMode::Allow) => return Task::ready(Ok(())),
+            Some(ToolPermissionMode::Deny) => {
+                return Task::ready(Err(anyhow!(
+                    "Permission to run tool denied by the user's settings"
+                )));
+            }
+            Some(ToolPermissionMode::Ask) => {}
+            None => {
+                if AgentSettings::get_global(cx).always_allow_tool_actions {
+                    return Task::ready(Ok(()));
+                }
+            }
         }
 
+        let can_always_allow = self
+            .permission
+            .as_ref()
+            .is_none_or(|permission| !permission.always_ask);
         let (response_tx, response_rx) = oneshot::channel();
         self.stream
             .0
@@ -2466,37 +2695,47 @@ impl ToolCallEventStream {
                         self.tool_use_id.to_string(),
                         acp::ToolCallUpdateFields::new().title(title.into()),
                     ),
-                    options: vec![
-                        acp::PermissionOption::new(
-                            acp::PermissionOptionId::new("always_allow"),
-                            "Always Allow",
-                            acp::PermissionOptionKind::AllowAlways,
-                        ),
-                        acp::PermissionOption::new(
-                            acp::PermissionOptionId::new("allow"),
-                            "Allow",
-                            acp::PermissionOptionKind::AllowOnce,
-                        ),
-                        acp::PermissionOption::new(
-                            acp::PermissionOptionId::new("deny"),
-                            "Deny",
-                            acp::PermissionOptionKind::RejectOnce,
-                        ),
-                    ],
+                    options: can_always_allow
+                        .then(|| {
+                            acp::PermissionOption::new(
+                                acp::PermissionOptionId::new("always_allow"),
+                                "Always Allow",
+                                acp::PermissionOptionKind::AllowAlways,
+                            )
+                        })
+                        .into_iter()
+                        .chain([
+                            acp::PermissionOption::new(
+                                acp::PermissionOptionId::new("allow"),
+                                "Allow",
+                                acp::PermissionOptionKind::AllowOnce,
+                            ),
+                            acp::PermissionOption::new(
+                                acp::PermissionOptionId::new("deny"),
+                                "Deny",
+                                acp::PermissionOptionKind::RejectOnce,
+                            ),
+                        ])
+                        .collect(),
                     response: response_tx,
                 },
             )))
             .ok();
         let fs = self.fs.clone();
+        let permission = self.permission.clone();
         cx.spawn(async move |cx| match response_rx.await?.0.as_ref() {
             "always_allow" => {
                 if let Some(fs) = fs.clone() {
                     cx.update(|cx| {
-                        update_settings_file(fs, cx, |settings, _| {
-                            settings
-                                .agent
-                                .get_or_insert_default()
-                                .set_always_allow_tool_actions(true);
+                        update_settings_file(fs, cx, move |settings, _| {
+                            let agent = settings.agent.get_or_insert_default();
+                            // Remember the choice for this tool and its subjects only, so the
+                            // user's other rules keep applying.
+                            match permission {
+                                Some(permission) => agent
+                                    .allow_tool_action(&permission.tool_name, permission.subjects),
+                                None => agent.set_always_allow_tool_actions(true),
+                            }
                         });
                     })?;
                 }
@@ -7,19 +7,27 @@ mod edit_file_tool;
 
 mod fetch_tool;
 mod find_path_tool;
+mod find_references_tool;
+mod go_to_definition_tool;
 mod grep_tool;
 mod list_directory_tool;
 mod move_path_tool;
 mod now_tool;
 mod open_tool;
 mod read_file_tool;
+mod rename_symbol_tool;
+mod search_symbols_tool;
+mod subagent_tool;
+mod symbol_position;
 
 mod terminal_tool;
 mod thinking_tool;
 mod web_search_tool;
 
 use crate::AgentTool;
+use gpui::{App, Entity};
 use language_model::{LanguageModelRequestTool, LanguageModelToolSchemaFormat};
+use project::Project;
 
 pub use context_server_registry::*;
 pub use copy_path_tool::*;
@@ -30,17 +38,37 @@ pub use edit_file_tool::*;
 
 pub use fetch_tool::*;
 pub use find_path_tool::*;
+pub use find_references_tool::*;
+pub use go_to_definition_tool::*;
 pub use grep_tool::*;
 pub use list_directory_tool::*;
 pub use move_path_tool::*;
 pub use now_tool::*;
 pub use open_tool::*;
 pub use read_file_tool::*;
+pub use rename_symbol_tool::*;
+pub use search_symbols_tool::*;
+pub use subagent_tool::*;
 
 pub use terminal_tool::*;
 pub use thinking_tool::*;
 pub use web_search_tool::*;
 
+/// Resolves a path passed to a tool to the normalized, worktree-root-prefixed project path that
+/// permission rules are matched against, so that `..` components can't escape an allowed
+/// directory. Paths outside the project are returned unchanged.
+fn project_path_subject(project: &Entity<Project>, path: &str, cx: &App) -> String {
+    let project = project.read(cx);
+    project
+        .find_project_path(path, cx)
+        .and_then(|project_path| {
+            let worktree = project.worktree_for_id(project_path.worktree_id, cx)?;
+            let path = worktree.read(cx).root_name().join(&project_path.path);
+            Some(path.as_unix_str().to_string())
+        })
+        .unwrap_or_else(|| path.to_string())
+}
+
 macro_rules! tools {
     ($($tool:ty),* $(,)?) => {
         /// A list of all built-in tool names
@@ -86,12 +114,17 @@ tools! {
     EditFileTool,
     FetchTool,
     FindPathTool,
+    FindReferencesTool,
+    GoToDefinitionTool,
     GrepTool,
     ListDirectoryTool,
     MovePathTool,
     NowTool,
     OpenTool,
     ReadFileTool,
+    RenameSymbolTool,
+    SearchSymbolsTool,
+    SubagentTool,
     TerminalTool,
     ThinkingTool,
     WebSearchTool,
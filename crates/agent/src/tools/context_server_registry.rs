@@ -119,6 +119,7 @@ impl ContextServerRegistry {
                     }
                 }
             }
+            project::context_server_store::Event::ResourceUpdated { .. } => {}
         }
     }
 }
@@ -232,6 +233,9 @@ impl AnyAgentTool for ContextServerTool {
                     context_server::types::ToolResponseContent::Resource { .. } => {
                         log::warn!("Ignoring resource content from tool response");
                     }
+                    context_server::types::ToolResponseContent::ResourceLink(_) => {
+                        log::warn!("Ignoring resource link from tool response");
+                    }
                 }
             }
             Ok(AgentToolOutput {
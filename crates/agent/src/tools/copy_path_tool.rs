@@ -1,3 +1,4 @@
+use crate::tools::project_path_subject;
 use crate::{AgentTool, ToolCallEventStream};
 use agent_client_protocol::ToolKind;
 use anyhow::{Context as _, Result, anyhow};
@@ -72,6 +73,13 @@ impl AgentTool for CopyPathTool {
         }
     }
 
+    fn permission_subjects(&self, input: &Self::Input, cx: &App) -> Vec<String> {
+        vec![
+            project_path_subject(&self.project, &input.source_path, cx),
+            project_path_subject(&self.project, &input.destination_path, cx),
+        ]
+    }
+
     fn run(
         self: Arc<Self>,
         input: Self::Input,
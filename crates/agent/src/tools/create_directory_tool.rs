@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use util::markdown::MarkdownInlineCode;
 
+use crate::tools::project_path_subject;
 use crate::{AgentTool, ToolCallEventStream};
 
 /// Creates a new directory at the specified path within the project. Returns confirmation that the directory was created.
@@ -61,6 +62,10 @@ impl AgentTool for CreateDirectoryTool {
         }
     }
 
+    fn permission_subjects(&self, input: &Self::Input, cx: &App) -> Vec<String> {
+        vec![project_path_subject(&self.project, &input.path, cx)]
+    }
+
     fn run(
         self: Arc<Self>,
         input: Self::Input,
@@ -1,3 +1,4 @@
+use crate::tools::project_path_subject;
 use crate::{AgentTool, ToolCallEventStream};
 use action_log::ActionLog;
 use agent_client_protocol::ToolKind;
@@ -64,6 +65,10 @@ impl AgentTool for DeletePathTool {
         }
     }
 
+    fn permission_subjects(&self, input: &Self::Input, cx: &App) -> Vec<String> {
+        vec![project_path_subject(&self.project, &input.path, cx)]
+    }
+
     fn run(
         self: Arc<Self>,
         input: Self::Input,
@@ -1,6 +1,7 @@
 use crate::{
     AgentTool, Templates, Thread, ToolCallEventStream,
     edit_agent::{EditAgent, EditAgentOutput, EditAgentOutputEvent, EditFormat},
+    tools::project_path_subject,
 };
 use acp_thread::Diff;
 use agent_client_protocol::{self as acp, ToolCallLocation, ToolCallUpdateFields};
@@ -255,6 +256,14 @@ impl AgentTool for EditFileTool {
         }
     }
 
+    fn permission_subjects(&self, input: &Self::Input, cx: &App) -> Vec<String> {
+        vec![project_path_subject(
+            &self.project,
+            &input.path.to_string_lossy(),
+            cx,
+        )]
+    }
+
     fn run(
         self: Arc<Self>,
         input: Self::Input,
@@ -599,12 +608,14 @@ fn resolve_path(
 mod tests {
     use super::*;
     use crate::{ContextServerRegistry, Templates};
+    use agent_settings::ToolPermissions;
+    use collections::IndexMap;
     use fs::Fs;
     use gpui::{TestAppContext, UpdateGlobal};
     use language_model::fake_provider::FakeLanguageModel;
     use prompt_store::ProjectContext;
     use serde_json::json;
-    use settings::SettingsStore;
+    use settings::{SettingsStore, ToolPermissionMode, ToolPermissionRulesContent};
     use util::{path, rel_path::rel_path};
 
     #[gpui::test]
@@ -650,6 +661,80 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_permission_subjects_are_normalized(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = project::FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "Cargo.toml": "",
+                "src": {
+                    "main.rs": "",
+                },
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let language_registry = project.read_with(cx, |project, _cx| project.languages().clone());
+        let context_server_registry =
+            cx.new(|cx| ContextServerRegistry::new(project.read(cx).context_server_store(), cx));
+        let model = Arc::new(FakeLanguageModel::default());
+        let thread = cx.new(|cx| {
+            Thread::new(
+                project.clone(),
+                cx.new(|_cx| ProjectContext::default()),
+                context_server_registry,
+                Templates::new(),
+                Some(model),
+                cx,
+            )
+        });
+        let tool = EditFileTool::new(
+            project,
+            thread.downgrade(),
+            language_registry,
+            Templates::new(),
+        );
+        let permissions = ToolPermissions::from(IndexMap::from_iter([(
+            EditFileTool::name().into(),
+            ToolPermissionRulesContent {
+                default: Some(ToolPermissionMode::Deny),
+                allow: vec!["root/src/**".into()],
+                ask: Vec::new(),
+                deny: Vec::new(),
+            },
+        )]));
+
+        cx.update(|cx| {
+            let subjects = |path: &str| {
+                tool.permission_subjects(
+                    &EditFileToolInput {
+                        display_description: "Some edit".into(),
+                        path: path.into(),
+                        mode: EditFileMode::Edit,
+                    },
+                    cx,
+                )
+            };
+
+            assert_eq!(subjects("root/src/../Cargo.toml"), vec!["root/Cargo.toml"]);
+            assert_eq!(
+                permissions.mode(EditFileTool::name(), &subjects("root/src/../Cargo.toml")),
+                Some(ToolPermissionMode::Deny)
+            );
+            assert_eq!(
+                permissions.mode(EditFileTool::name(), &subjects("root/./src/main.rs")),
+                Some(ToolPermissionMode::Allow)
+            );
+            assert_eq!(
+                permissions.mode(EditFileTool::name(), &subjects(path!("/root/src/main.rs"))),
+                Some(ToolPermissionMode::Allow)
+            );
+        });
+    }
+
     #[gpui::test]
     async fn test_resolve_path_for_creating_file(cx: &mut TestAppContext) {
         let mode = &EditFileMode::Create;
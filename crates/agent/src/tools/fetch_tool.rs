@@ -137,6 +137,10 @@ impl AgentTool for FetchTool {
         }
     }
 
+    fn permission_subjects(&self, input: &Self::Input, _cx: &App) -> Vec<String> {
+        vec![input.url.clone()]
+    }
+
     fn run(
         self: Arc<Self>,
         input: Self::Input,
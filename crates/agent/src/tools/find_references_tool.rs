@@ -0,0 +1,246 @@
+use crate::tools::symbol_position::{format_locations, resolve_symbol_position};
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::{Context as _, Result};
+use gpui::{App, Entity, Task};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use ui::SharedString;
+use util::markdown::MarkdownInlineCode;
+
+/// Finds all references to a symbol using the language server, which understands the code's semantics.
+///
+/// Prefer this tool over `grep` when looking for the usages of a function, type, variable or field, since it won't match unrelated symbols with the same name.
+///
+/// <example>
+/// To find all references to `parse_config` defined on line 12 of `backend/src/config.rs`:
+/// {
+///     "path": "backend/src/config.rs",
+///     "line": 12,
+///     "symbol": "parse_config"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FindReferencesToolInput {
+    /// The path of a file where the symbol appears.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - lorem
+    /// - ipsum
+    ///
+    /// If the symbol appears in `dolor.rs` in `ipsum`, you should use the path `ipsum/dolor.rs`.
+    /// </example>
+    pub path: String,
+    /// The 1-based line number where the symbol appears.
+    pub line: u32,
+    /// The name of the symbol, exactly as it appears on that line.
+    pub symbol: String,
+}
+
+pub struct FindReferencesTool {
+    project: Entity<Project>,
+}
+
+impl FindReferencesTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+impl AgentTool for FindReferencesTool {
+    type Input = FindReferencesToolInput;
+    type Output = String;
+
+    fn name() -> &'static str {
+        "find_references"
+    }
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Search
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!("Find references to {}", MarkdownInlineCode(&input.symbol)).into(),
+            Err(_) => "Find references".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        _event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let position =
+            resolve_symbol_position(&self.project, &input.path, input.line, &input.symbol, cx);
+        let project = self.project.clone();
+        cx.spawn(async move |cx| {
+            let (buffer, position) = position.await?;
+            let references = project
+                .update(cx, |project, cx| project.references(&buffer, position, cx))?
+                .await?
+                .context("No language server supports finding references in this file")?;
+
+            if references.is_empty() {
+                return Ok(format!("No references to `{}` found.", input.symbol));
+            }
+            cx.update(|cx| {
+                format!(
+                    "Found {} references to `{}`:\n\n{}",
+                    references.len(),
+                    input.symbol,
+                    format_locations(&references, cx)
+                )
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt as _;
+    use gpui::TestAppContext;
+    use indoc::formatdoc;
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_find_references(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "src": {
+                    "lib.rs": "pub fn parse_config() {}\n",
+                    "main.rs": "fn main() {\n    lib::parse_config();\n}\n",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    references_provider: Some(lsp::OneOf::Left(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/lib.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let _handle = project.update(cx, |project, cx| {
+            project.register_buffer_with_language_servers(&buffer, cx)
+        });
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        fake_language_server.set_request_handler::<lsp::request::References, _, _>(
+            |params, _| async move {
+                assert_eq!(
+                    params.text_document_position.position,
+                    lsp::Position::new(0, 7)
+                );
+                let location = |path, row, start, end| {
+                    lsp::Location::new(
+                        lsp::Uri::from_file_path(path).unwrap(),
+                        lsp::Range::new(
+                            lsp::Position::new(row, start),
+                            lsp::Position::new(row, end),
+                        ),
+                    )
+                };
+                Ok(Some(vec![
+                    location(path!("/root/src/lib.rs"), 0, 7, 19),
+                    location(path!("/root/src/main.rs"), 1, 9, 21),
+                ]))
+            },
+        );
+
+        let tool = Arc::new(FindReferencesTool::new(project.clone()));
+        let output = cx
+            .update(|cx| {
+                tool.clone().run(
+                    FindReferencesToolInput {
+                        path: "root/src/lib.rs".into(),
+                        line: 1,
+                        symbol: "parse_config".into(),
+                    },
+                    ToolCallEventStream::test().0,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            formatdoc! {"
+                Found 2 references to `parse_config`:
+
+                {}:1: pub fn parse_config() {{}}
+                {}:2: lib::parse_config();
+                ",
+                path!("root/src/lib.rs"),
+                path!("root/src/main.rs"),
+            }
+        );
+
+        // Symbols that aren't on the given line are reported instead of guessed.
+        let error = cx
+            .update(|cx| {
+                tool.run(
+                    FindReferencesToolInput {
+                        path: "root/src/lib.rs".into(),
+                        line: 1,
+                        symbol: "load_config".into(),
+                    },
+                    ToolCallEventStream::test().0,
+                    cx,
+                )
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not find `load_config` on line 1 of root/src/lib.rs"
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use crate::tools::symbol_position::{format_locations, resolve_symbol_position};
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::{Context as _, Result};
+use gpui::{App, Entity, Task};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use ui::SharedString;
+use util::markdown::MarkdownInlineCode;
+
+/// Finds where a symbol is defined using the language server, which resolves imports, re-exports and overloads.
+///
+/// Use this tool to jump from a usage of a function, type or variable to its definition, including definitions in dependencies outside the project.
+///
+/// <example>
+/// To find the definition of `Config` used on line 40 of `backend/src/main.rs`:
+/// {
+///     "path": "backend/src/main.rs",
+///     "line": 40,
+///     "symbol": "Config"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GoToDefinitionToolInput {
+    /// The path of a file where the symbol is used.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - lorem
+    /// - ipsum
+    ///
+    /// If the symbol is used in `dolor.rs` in `ipsum`, you should use the path `ipsum/dolor.rs`.
+    /// </example>
+    pub path: String,
+    /// The 1-based line number where the symbol is used.
+    pub line: u32,
+    /// The name of the symbol, exactly as it appears on that line.
+    pub symbol: String,
+}
+
+pub struct GoToDefinitionTool {
+    project: Entity<Project>,
+}
+
+impl GoToDefinitionTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+impl AgentTool for GoToDefinitionTool {
+    type Input = GoToDefinitionToolInput;
+    type Output = String;
+
+    fn name() -> &'static str {
+        "go_to_definition"
+    }
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Search
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => {
+                format!("Go to definition of {}", MarkdownInlineCode(&input.symbol)).into()
+            }
+            Err(_) => "Go to definition".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        _event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let position =
+            resolve_symbol_position(&self.project, &input.path, input.line, &input.symbol, cx);
+        let project = self.project.clone();
+        cx.spawn(async move |cx| {
+            let (buffer, position) = position.await?;
+            let definitions = project
+                .update(cx, |project, cx| project.definitions(&buffer, position, cx))?
+                .await?
+                .context("No language server supports finding definitions in this file")?
+                .into_iter()
+                .map(|link| link.target)
+                .collect::<Vec<_>>();
+
+            if definitions.is_empty() {
+                return Ok(format!("No definition of `{}` found.", input.symbol));
+            }
+            cx.update(|cx| {
+                format!(
+                    "`{}` is defined at:\n\n{}",
+                    input.symbol,
+                    format_locations(&definitions, cx)
+                )
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt as _;
+    use gpui::TestAppContext;
+    use indoc::formatdoc;
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_go_to_definition(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "src": {
+                    "lib.rs": "pub fn parse_config() {}\n",
+                    "main.rs": "fn main() {\n    lib::parse_config();\n}\n",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    definition_provider: Some(lsp::OneOf::Left(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let _handle = project.update(cx, |project, cx| {
+            project.register_buffer_with_language_servers(&buffer, cx)
+        });
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        fake_language_server.set_request_handler::<lsp::request::GotoDefinition, _, _>(
+            |params, _| async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(1, 9)
+                );
+                Ok(Some(lsp::GotoDefinitionResponse::Scalar(
+                    lsp::Location::new(
+                        lsp::Uri::from_file_path(path!("/root/src/lib.rs")).unwrap(),
+                        lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 19)),
+                    ),
+                )))
+            },
+        );
+
+        let tool = Arc::new(GoToDefinitionTool::new(project.clone()));
+        let output = cx
+            .update(|cx| {
+                tool.clone().run(
+                    GoToDefinitionToolInput {
+                        path: "root/src/main.rs".into(),
+                        line: 2,
+                        symbol: "parse_config".into(),
+                    },
+                    ToolCallEventStream::test().0,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            formatdoc! {"
+                `parse_config` is defined at:
+
+                {}:1: pub fn parse_config() {{}}
+                ",
+                path!("root/src/lib.rs"),
+            }
+        );
+
+        // Lines past the end of the file are reported instead of guessed.
+        let error = cx
+            .update(|cx| {
+                tool.run(
+                    GoToDefinitionToolInput {
+                        path: "root/src/main.rs".into(),
+                        line: 10,
+                        symbol: "parse_config".into(),
+                    },
+                    ToolCallEventStream::test().0,
+                    cx,
+                )
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Line 10 is out of range in root/src/main.rs"
+        );
+    }
+}
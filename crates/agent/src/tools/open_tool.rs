@@ -1,4 +1,5 @@
 use crate::AgentTool;
+use crate::tools::project_path_subject;
 use agent_client_protocol::ToolKind;
 use anyhow::{Context as _, Result};
 use gpui::{App, AppContext, Entity, SharedString, Task};
@@ -57,6 +58,10 @@ impl AgentTool for OpenTool {
         }
     }
 
+    fn permission_subjects(&self, input: &Self::Input, cx: &App) -> Vec<String> {
+        vec![project_path_subject(&self.project, &input.path_or_url, cx)]
+    }
+
     fn run(
         self: Arc<Self>,
         input: Self::Input,
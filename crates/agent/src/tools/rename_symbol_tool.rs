@@ -0,0 +1,345 @@
+use crate::tools::project_path_subject;
+use crate::tools::symbol_position::resolve_symbol_position;
+use crate::{AgentTool, Thread, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::{Result, anyhow};
+use collections::HashSet;
+use gpui::{App, Entity, Task, WeakEntity};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, sync::Arc};
+use ui::SharedString;
+use util::markdown::MarkdownInlineCode;
+
+/// Renames a symbol and updates all of its references across the project using the language server.
+///
+/// Prefer this tool over editing files by hand when renaming a function, type, variable or field, since it only changes the references to that exact symbol.
+/// The edited files are saved automatically.
+///
+/// <example>
+/// To rename `parse_config` defined on line 12 of `backend/src/config.rs` to `load_config`:
+/// {
+///     "path": "backend/src/config.rs",
+///     "line": 12,
+///     "symbol": "parse_config",
+///     "new_name": "load_config"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RenameSymbolToolInput {
+    /// The path of a file where the symbol appears.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - lorem
+    /// - ipsum
+    ///
+    /// If the symbol appears in `dolor.rs` in `ipsum`, you should use the path `ipsum/dolor.rs`.
+    /// </example>
+    pub path: String,
+    /// The 1-based line number where the symbol appears.
+    pub line: u32,
+    /// The current name of the symbol, exactly as it appears on that line.
+    pub symbol: String,
+    /// The new name for the symbol.
+    pub new_name: String,
+}
+
+pub struct RenameSymbolTool {
+    thread: WeakEntity<Thread>,
+    project: Entity<Project>,
+}
+
+impl RenameSymbolTool {
+    pub fn new(thread: WeakEntity<Thread>, project: Entity<Project>) -> Self {
+        Self { thread, project }
+    }
+}
+
+impl AgentTool for RenameSymbolTool {
+    type Input = RenameSymbolToolInput;
+    type Output = String;
+
+    fn name() -> &'static str {
+        "rename_symbol"
+    }
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Edit
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!(
+                "Rename {} to {}",
+                MarkdownInlineCode(&input.symbol),
+                MarkdownInlineCode(&input.new_name)
+            )
+            .into(),
+            Err(_) => "Rename symbol".into(),
+        }
+    }
+
+    fn permission_subjects(&self, input: &Self::Input, cx: &App) -> Vec<String> {
+        vec![project_path_subject(&self.project, &input.path, cx)]
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let action_log = match self
+            .thread
+            .read_with(cx, |thread, _| thread.action_log().clone())
+        {
+            Ok(action_log) => action_log,
+            Err(_) => return Task::ready(Err(anyhow!("thread was dropped"))),
+        };
+        let authorize = event_stream.authorize(
+            format!(
+                "Rename {} to {}",
+                MarkdownInlineCode(&input.symbol),
+                MarkdownInlineCode(&input.new_name)
+            ),
+            cx,
+        );
+        let position =
+            resolve_symbol_position(&self.project, &input.path, input.line, &input.symbol, cx);
+        let project = self.project.clone();
+        cx.spawn(async move |cx| {
+            authorize.await?;
+            let (buffer, position) = position.await?;
+
+            let transaction = project
+                .update(cx, |project, cx| {
+                    project.perform_rename(buffer, position, input.new_name.clone(), cx)
+                })?
+                .await?;
+            if transaction.0.is_empty() {
+                return Err(anyhow!(
+                    "The language server didn't make any changes when renaming `{}`",
+                    input.symbol
+                ));
+            }
+
+            // The project has already applied the rename, so briefly undo it in each buffer to
+            // let the action log see the rename as the agent's edits, for the user to review.
+            let mut paths = HashSet::default();
+            for (buffer, transaction) in transaction.0 {
+                cx.update(|cx| {
+                    buffer.update(cx, |buffer, cx| buffer.undo_transaction(transaction.id, cx));
+                    action_log.update(cx, |log, cx| log.buffer_read(buffer.clone(), cx));
+                    buffer.update(cx, |buffer, cx| {
+                        buffer.redo_to_transaction(transaction.id, cx)
+                    });
+                    action_log.update(cx, |log, cx| log.buffer_edited(buffer.clone(), cx));
+                })?;
+                project
+                    .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))?
+                    .await?;
+                if let Some(path) = buffer.read_with(cx, |buffer, cx| {
+                    buffer.file().map(|file| file.full_path(cx))
+                })? {
+                    paths.insert(path);
+                }
+            }
+
+            let mut paths = paths.into_iter().collect::<Vec<_>>();
+            paths.sort();
+            let mut output = format!(
+                "Renamed `{}` to `{}` in {} files:\n\n",
+                input.symbol,
+                input.new_name,
+                paths.len()
+            );
+            for path in paths {
+                writeln!(output, "- {}", path.display())?;
+            }
+            Ok(output)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContextServerRegistry, Templates};
+    use futures::StreamExt as _;
+    use gpui::{AppContext as _, TestAppContext};
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher};
+    use language_model::fake_provider::FakeLanguageModel;
+    use project::FakeFs;
+    use prompt_store::ProjectContext;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_rename_is_reviewable(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "src": {
+                    "lib.rs": "pub fn parse_config() {}\n",
+                    "main.rs": "fn main() {\n    lib::parse_config();\n}\n",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    rename_provider: Some(lsp::OneOf::Left(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/lib.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let _handle = project.update(cx, |project, cx| {
+            project.register_buffer_with_language_servers(&buffer, cx)
+        });
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        fake_language_server.set_request_handler::<lsp::request::Rename, _, _>(
+            |params, _| async move {
+                assert_eq!(params.new_name, "load_config");
+                let rename = |row, start, end| {
+                    vec![lsp::TextEdit::new(
+                        lsp::Range::new(
+                            lsp::Position::new(row, start),
+                            lsp::Position::new(row, end),
+                        ),
+                        "load_config".to_string(),
+                    )]
+                };
+                Ok(Some(lsp::WorkspaceEdit {
+                    changes: Some(
+                        [
+                            (
+                                lsp::Uri::from_file_path(path!("/root/src/lib.rs")).unwrap(),
+                                rename(0, 7, 19),
+                            ),
+                            (
+                                lsp::Uri::from_file_path(path!("/root/src/main.rs")).unwrap(),
+                                rename(1, 9, 21),
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                    ..Default::default()
+                }))
+            },
+        );
+
+        let context_server_registry =
+            cx.new(|cx| ContextServerRegistry::new(project.read(cx).context_server_store(), cx));
+        let thread = cx.new(|cx| {
+            Thread::new(
+                project.clone(),
+                cx.new(|_cx| ProjectContext::default()),
+                context_server_registry,
+                Templates::new(),
+                Some(Arc::new(FakeLanguageModel::default())),
+                cx,
+            )
+        });
+        cx.update(|cx| {
+            let mut settings = agent_settings::AgentSettings::get_global(cx).clone();
+            settings.always_allow_tool_actions = true;
+            agent_settings::AgentSettings::override_global(settings, cx);
+        });
+
+        let (event_stream, _event_rx) = ToolCallEventStream::test();
+        let output = cx
+            .update(|cx| {
+                Arc::new(RenameSymbolTool::new(thread.downgrade(), project.clone())).run(
+                    RenameSymbolToolInput {
+                        path: "root/src/lib.rs".into(),
+                        line: 1,
+                        symbol: "parse_config".into(),
+                        new_name: "load_config".into(),
+                    },
+                    event_stream,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert!(output.contains("in 2 files"), "unexpected output: {output}");
+
+        // Both files show the rename as an edit for the user to review.
+        let action_log = thread.read_with(cx, |thread, _| thread.action_log().clone());
+        let mut changes = cx.read(|cx| {
+            action_log
+                .read(cx)
+                .changed_buffers(cx)
+                .into_iter()
+                .map(|(buffer, diff)| {
+                    let snapshot = buffer.read(cx).snapshot();
+                    let diff = diff.read(cx);
+                    (
+                        diff.base_text().text(),
+                        snapshot.text(),
+                        diff.hunks(&snapshot, cx).count(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
+        changes.sort();
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    "fn main() {\n    lib::parse_config();\n}\n".to_string(),
+                    "fn main() {\n    lib::load_config();\n}\n".to_string(),
+                    1,
+                ),
+                (
+                    "pub fn parse_config() {}\n".to_string(),
+                    "pub fn load_config() {}\n".to_string(),
+                    1,
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{AgentTool, ToolCallEventStream};
+use agent_client_protocol as acp;
+use anyhow::Result;
+use gpui::{App, Entity, Task};
+use project::{Project, lsp_store::SymbolLocation};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, sync::Arc};
+use ui::SharedString;
+use util::markdown::MarkdownInlineCode;
+
+/// The maximum number of symbols listed in the output.
+const MAX_SYMBOLS: usize = 100;
+
+/// Searches the symbols of the whole workspace, like functions, types and constants, using the language servers.
+///
+/// Use this tool to find where a symbol is defined when you know its name but not the file it lives in.
+/// The query is matched fuzzily, so partial names work too.
+///
+/// <example>
+/// To find the definition of the `ProjectSettings` struct:
+/// {
+///     "query": "ProjectSettings"
+/// }
+/// </example>
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SearchSymbolsToolInput {
+    /// The name, or part of the name, of the symbols to search for.
+    pub query: String,
+}
+
+pub struct SearchSymbolsTool {
+    project: Entity<Project>,
+}
+
+impl SearchSymbolsTool {
+    pub fn new(project: Entity<Project>) -> Self {
+        Self { project }
+    }
+}
+
+impl AgentTool for SearchSymbolsTool {
+    type Input = SearchSymbolsToolInput;
+    type Output = String;
+
+    fn name() -> &'static str {
+        "search_symbols"
+    }
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Search
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!("Search symbols for {}", MarkdownInlineCode(&input.query)).into(),
+            Err(_) => "Search symbols".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        _event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let symbols = self
+            .project
+            .update(cx, |project, cx| project.symbols(&input.query, cx));
+        let project = self.project.clone();
+        cx.spawn(async move |cx| {
+            let symbols = symbols.await?;
+            if symbols.is_empty() {
+                return Ok(format!("No symbols matching `{}` found.", input.query));
+            }
+
+            project.read_with(cx, |project, cx| {
+                let path_style = project.path_style(cx);
+                let mut output = format!("Found {} symbols:\n\n", symbols.len());
+                for symbol in symbols.iter().take(MAX_SYMBOLS) {
+                    let path = match &symbol.path {
+                        SymbolLocation::InProject(project_path) => {
+                            let Some(worktree) =
+                                project.worktree_for_id(project_path.worktree_id, cx)
+                            else {
+                                continue;
+                            };
+                            worktree
+                                .read(cx)
+                                .root_name()
+                                .join(&project_path.path)
+                                .display(path_style)
+                                .to_string()
+                        }
+                        SymbolLocation::OutsideProject { abs_path, .. } => {
+                            abs_path.display().to_string()
+                        }
+                    };
+                    writeln!(
+                        output,
+                        "{:?} {} at {}:{}",
+                        symbol.kind,
+                        symbol.name,
+                        path,
+                        symbol.range.start.0.row + 1
+                    )?;
+                }
+                if symbols.len() > MAX_SYMBOLS {
+                    writeln!(output, "... and {} more", symbols.len() - MAX_SYMBOLS)?;
+                }
+                Ok(output)
+            })?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt as _;
+    use gpui::TestAppContext;
+    use indoc::formatdoc;
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_search_symbols(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "src": {
+                    "lib.rs": "pub fn parse_config() {}\n",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    workspace_symbol_provider: Some(lsp::OneOf::Left(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/lib.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let _handle = project.update(cx, |project, cx| {
+            project.register_buffer_with_language_servers(&buffer, cx)
+        });
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        fake_language_server.set_request_handler::<lsp::WorkspaceSymbolRequest, _, _>(
+            |params, _| async move {
+                #[allow(deprecated)]
+                let symbol = |name: &str, path, row| lsp::SymbolInformation {
+                    name: name.to_string(),
+                    kind: lsp::SymbolKind::FUNCTION,
+                    tags: None,
+                    deprecated: None,
+                    container_name: None,
+                    location: lsp::Location::new(
+                        lsp::Uri::from_file_path(path).unwrap(),
+                        lsp::Range::new(lsp::Position::new(row, 7), lsp::Position::new(row, 19)),
+                    ),
+                };
+                let symbols = if params.query == "config" {
+                    vec![
+                        symbol("parse_config", path!("/root/src/lib.rs"), 0),
+                        symbol("default_config", path!("/external/config.rs"), 4),
+                    ]
+                } else {
+                    Vec::new()
+                };
+                Ok(Some(lsp::WorkspaceSymbolResponse::Flat(symbols)))
+            },
+        );
+
+        let tool = Arc::new(SearchSymbolsTool::new(project.clone()));
+        let output = cx
+            .update(|cx| {
+                tool.clone().run(
+                    SearchSymbolsToolInput {
+                        query: "config".into(),
+                    },
+                    ToolCallEventStream::test().0,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            formatdoc! {"
+                Found 2 symbols:
+
+                {kind:?} parse_config at {}:1
+                {kind:?} default_config at {}:5
+                ",
+                path!("root/src/lib.rs"),
+                path!("/external/config.rs"),
+                kind = lsp::SymbolKind::FUNCTION,
+            }
+        );
+
+        let output = cx
+            .update(|cx| {
+                tool.run(
+                    SearchSymbolsToolInput {
+                        query: "missing".into(),
+                    },
+                    ToolCallEventStream::test().0,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(output, "No symbols matching `missing` found.");
+    }
+}
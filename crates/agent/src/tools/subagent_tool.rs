@@ -0,0 +1,197 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use acp_thread::UserMessageId;
+use agent_client_protocol as acp;
+use agent_settings::AgentProfileId;
+use anyhow::{Result, anyhow, bail};
+use futures::StreamExt as _;
+use gpui::{App, SharedString, Task, WeakEntity};
+use language_model::LanguageModelToolResultContent;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{AgentTool, Thread, ThreadEnvironment, ThreadEvent, ToolCallEventStream};
+
+/// Delegates a self-contained task to a subagent, which works on it in a separate conversation and replies with a summary of what it did and found.
+///
+/// Use this tool to work on independent tasks in parallel, or to research a question without filling your context with intermediate results.
+/// Several subagents run at the same time when you call this tool multiple times in the same response.
+///
+/// The subagent doesn't see your conversation, so the task must include all the context needed to complete it.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SubagentToolInput {
+    /// A short, user-friendly description of the task, shown in the UI.
+    ///
+    /// <example>Find all callers of `parse_config`</example>
+    pub label: String,
+    /// The full instructions for the subagent.
+    pub task: String,
+    /// The agent profile whose tools the subagent can use, like `ask` for read-only work.
+    /// The subagent can only use the tools that are also available to you.
+    ///
+    /// Defaults to your own profile.
+    #[serde(default)]
+    pub profile: Option<AgentProfileId>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubagentToolOutput {
+    summary: String,
+    transcript: String,
+}
+
+impl From<SubagentToolOutput> for LanguageModelToolResultContent {
+    fn from(output: SubagentToolOutput) -> Self {
+        if output.summary.is_empty() {
+            "The subagent finished without a summary.".into()
+        } else {
+            output.summary.into()
+        }
+    }
+}
+
+pub struct SubagentTool {
+    thread: WeakEntity<Thread>,
+    environment: Rc<dyn ThreadEnvironment>,
+}
+
+impl SubagentTool {
+    pub fn new(thread: WeakEntity<Thread>, environment: Rc<dyn ThreadEnvironment>) -> Self {
+        Self {
+            thread,
+            environment,
+        }
+    }
+}
+
+impl AgentTool for SubagentTool {
+    type Input = SubagentToolInput;
+    type Output = SubagentToolOutput;
+
+    fn name() -> &'static str {
+        "subagent"
+    }
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Other
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => input.label.into(),
+            Err(_) => "Run subagent".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let subagent = match self
+            .thread
+            .update(cx, |thread, cx| {
+                thread.new_subagent(input.profile, self.environment.clone(), cx)
+            })
+            .and_then(|subagent| subagent)
+        {
+            Ok(subagent) => subagent,
+            Err(error) => return Task::ready(Err(error)),
+        };
+
+        let task = format!(
+            "{}\n\nWhen you're done, reply with a concise summary of what you did and found. \
+            It will be passed back to the agent that delegated this task to you.",
+            input.task
+        );
+        let events = subagent.update(cx, |subagent, cx| {
+            subagent.send(UserMessageId::new(), [task], cx)
+        });
+        let label = input.label;
+        cx.spawn(async move |cx| {
+            let mut events = events?;
+            // The summary is the text the subagent replies with after its last tool call.
+            let mut summary = String::new();
+            while let Some(event) = events.next().await {
+                // Rendering the transcript is linear in its length, so only do it when the
+                // subagent moves on to a tool call rather than on every streamed chunk.
+                let update_transcript = match event? {
+                    ThreadEvent::AgentText(text) => {
+                        summary.push_str(&text);
+                        false
+                    }
+                    ThreadEvent::ToolCall(_) => {
+                        summary.clear();
+                        true
+                    }
+                    ThreadEvent::ToolCallAuthorization(authorization) => {
+                        let title = authorization
+                            .tool_call
+                            .fields
+                            .title
+                            .unwrap_or_else(|| "Run tool".to_string());
+                        let authorize = cx
+                            .update(|cx| event_stream.authorize(format!("{label}: {title}"), cx))?;
+                        let option_id = if authorize.await.is_ok() {
+                            "allow"
+                        } else {
+                            "deny"
+                        };
+                        event_stream
+                            .update_fields(acp::ToolCallUpdateFields::new().title(label.clone()));
+                        authorization
+                            .response
+                            .send(acp::PermissionOptionId::new(option_id))
+                            .ok();
+                        false
+                    }
+                    ThreadEvent::Stop(acp::StopReason::EndTurn) => false,
+                    ThreadEvent::Stop(acp::StopReason::Cancelled) => {
+                        return Err(anyhow!("The subagent was canceled"));
+                    }
+                    ThreadEvent::Stop(reason) => bail!("The subagent stopped early: {reason:?}"),
+                    ThreadEvent::UserMessage(_)
+                    | ThreadEvent::AgentThinking(_)
+                    | ThreadEvent::ToolCallUpdate(_)
+                    | ThreadEvent::Retry(_) => false,
+                };
+
+                if update_transcript {
+                    let transcript =
+                        subagent.read_with(cx, |subagent, _| subagent.to_markdown())?;
+                    event_stream.update_fields(
+                        acp::ToolCallUpdateFields::new().content(vec![transcript.into()]),
+                    );
+                }
+            }
+
+            let transcript = subagent.read_with(cx, |subagent, _| subagent.to_markdown())?;
+            event_stream.update_fields(
+                acp::ToolCallUpdateFields::new().content(vec![transcript.clone().into()]),
+            );
+            Ok(SubagentToolOutput {
+                summary: summary.trim().to_string(),
+                transcript,
+            })
+        })
+    }
+
+    fn replay(
+        &self,
+        _input: Self::Input,
+        output: Self::Output,
+        event_stream: ToolCallEventStream,
+        _cx: &mut App,
+    ) -> Result<()> {
+        event_stream.update_fields(
+            acp::ToolCallUpdateFields::new().content(vec![output.transcript.into()]),
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt::Write as _;
+
+use anyhow::{Context as _, Result, anyhow};
+use gpui::{App, Entity, Task};
+use language::{Buffer, Location, Point, ToPoint as _};
+use project::Project;
+
+/// The maximum number of locations listed in a tool's output.
+const MAX_LOCATIONS: usize = 100;
+
+/// Opens the buffer at `path` and returns the position of `symbol` on its 1-based `line`.
+pub(crate) fn resolve_symbol_position(
+    project: &Entity<Project>,
+    path: &str,
+    line: u32,
+    symbol: &str,
+    cx: &mut App,
+) -> Task<Result<(Entity<Buffer>, Point)>> {
+    let Some(project_path) = project.read(cx).find_project_path(path, cx) else {
+        return Task::ready(Err(anyhow!("Could not find path {path} in project")));
+    };
+    let buffer = project.update(cx, |project, cx| project.open_buffer(project_path, cx));
+    let path = path.to_string();
+    let symbol = symbol.to_string();
+    cx.spawn(async move |cx| {
+        let buffer = buffer.await?;
+        let position = buffer.read_with(cx, |buffer, _| {
+            let row = line
+                .checked_sub(1)
+                .filter(|row| *row <= buffer.max_point().row)
+                .with_context(|| format!("Line {line} is out of range in {path}"))?;
+            let line_text = buffer
+                .text_for_range(Point::new(row, 0)..Point::new(row, buffer.line_len(row)))
+                .collect::<String>();
+            let column = symbol_column(&line_text, &symbol)
+                .with_context(|| format!("Could not find `{symbol}` on line {line} of {path}"))?;
+            anyhow::Ok(Point::new(row, column as u32))
+        })??;
+        Ok((buffer, position))
+    })
+}
+
+/// Returns the byte offset of `symbol` in `line`, preferring occurrences that aren't part of a
+/// longer identifier.
+fn symbol_column(line: &str, symbol: &str) -> Option<usize> {
+    if symbol.is_empty() {
+        return None;
+    }
+    let is_identifier_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut occurrences = line.match_indices(symbol).map(|(ix, _)| ix).peekable();
+    let first = *occurrences.peek()?;
+    Some(
+        occurrences
+            .find(|&ix| {
+                let before = line[..ix].chars().next_back();
+                let after = line[ix + symbol.len()..].chars().next();
+                !before.is_some_and(is_identifier_char) && !after.is_some_and(is_identifier_char)
+            })
+            .unwrap_or(first),
+    )
+}
+
+/// Lists each location as its path and line number, followed by the text of that line.
+pub(crate) fn format_locations(locations: &[Location], cx: &App) -> String {
+    let mut output = String::new();
+    for location in locations.iter().take(MAX_LOCATIONS) {
+        let buffer = location.buffer.read(cx);
+        let Some(path) = buffer.file().map(|file| file.full_path(cx)) else {
+            continue;
+        };
+        let row = location.range.start.to_point(buffer).row;
+        let line_text = buffer
+            .text_for_range(Point::new(row, 0)..Point::new(row, buffer.line_len(row)))
+            .collect::<String>();
+        writeln!(
+            output,
+            "{}:{}: {}",
+            path.display(),
+            row + 1,
+            line_text.trim()
+        )
+        .ok();
+    }
+    if locations.len() > MAX_LOCATIONS {
+        writeln!(output, "... and {} more", locations.len() - MAX_LOCATIONS).ok();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbol_column() {
+        assert_eq!(symbol_column("let foo = bar(foo);", "foo"), Some(4));
+        assert_eq!(symbol_column("let foobar = foo;", "foo"), Some(13));
+        assert_eq!(symbol_column("let foobar = 1;", "foo"), Some(4));
+        assert_eq!(symbol_column("let x = 1;", "foo"), None);
+        assert_eq!(symbol_column("let x = 1;", ""), None);
+    }
+}
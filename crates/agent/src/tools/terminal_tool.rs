@@ -86,6 +86,10 @@ impl AgentTool for TerminalTool {
         }
     }
 
+    fn permission_subjects(&self, input: &Self::Input, _cx: &App) -> Vec<String> {
+        agent_settings::shell_command_subjects(&input.command)
+    }
+
     fn run(
         self: Arc<Self>,
         input: Self::Input,
@@ -1,4 +1,5 @@
 mod agent_profile;
+mod tool_permissions;
 
 use std::sync::Arc;
 
@@ -14,6 +15,7 @@ use settings::{
 };
 
 pub use crate::agent_profile::*;
+pub use crate::tool_permissions::*;
 
 pub const SUMMARIZE_THREAD_PROMPT: &str = include_str!("prompts/summarize_thread_prompt.txt");
 pub const SUMMARIZE_THREAD_DETAILED_PROMPT: &str =
@@ -35,6 +37,7 @@ pub struct AgentSettings {
     pub default_view: DefaultAgentView,
     pub profiles: IndexMap<AgentProfileId, AgentProfileSettings>,
     pub always_allow_tool_actions: bool,
+    pub tool_permissions: ToolPermissions,
     pub notify_when_agent_waiting: NotifyWhenAgentWaiting,
     pub play_sound_when_agent_done: bool,
     pub single_file_review: bool,
@@ -167,6 +170,7 @@ impl Settings for AgentSettings {
                 .map(|(key, val)| (AgentProfileId(key), val.into()))
                 .collect(),
             always_allow_tool_actions: agent.always_allow_tool_actions.unwrap(),
+            tool_permissions: agent.tool_permissions.unwrap_or_default().into(),
             notify_when_agent_waiting: agent.notify_when_agent_waiting.unwrap(),
             play_sound_when_agent_done: agent.play_sound_when_agent_done.unwrap(),
             single_file_review: agent.single_file_review.unwrap(),
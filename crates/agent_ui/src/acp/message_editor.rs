@@ -71,6 +71,7 @@ impl PromptCompletionProviderDelegate for Entity<MessageEditor> {
                 PromptContextType::Thread,
                 PromptContextType::Fetch,
                 PromptContextType::Rules,
+                PromptContextType::Resource,
             ]);
         }
         supported
@@ -167,8 +168,9 @@ impl MessageEditor {
                 {
                     editor.update(cx, |editor, cx| {
                         let snapshot = editor.snapshot(window, cx);
-                        this.mention_set
-                            .update(cx, |mention_set, _cx| mention_set.remove_invalid(&snapshot));
+                        this.mention_set.update(cx, |mention_set, cx| {
+                            mention_set.remove_invalid(&snapshot, cx)
+                        });
 
                         let new_hints = this
                             .command_hint(snapshot.buffer())
@@ -459,9 +461,9 @@ impl MessageEditor {
         self.editor.update(cx, |editor, cx| {
             editor.clear(window, cx);
             editor.remove_creases(
-                self.mention_set.update(cx, |mention_set, _cx| {
+                self.mention_set.update(cx, |mention_set, cx| {
                     mention_set
-                        .clear()
+                        .clear(cx)
                         .map(|(crease_id, _)| crease_id)
                         .collect::<Vec<_>>()
                 }),
@@ -2152,6 +2154,7 @@ mod tests {
             id: acp::SessionId::new("thread-123"),
             title: "Previous Conversation".into(),
             updated_at: chrono::Utc::now(),
+            parent_id: None,
         };
 
         let message_editor = cx.update(|window, cx| {
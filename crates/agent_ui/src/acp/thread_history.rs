@@ -403,6 +403,16 @@ impl AcpThreadHistory {
         let hovered = Some(ix) == self.hovered_index;
         let timestamp = entry.updated_at().timestamp();
         let thread_timestamp = format.format_timestamp(timestamp, self.local_timezone);
+        let forked_from = entry.parent_id().map(|parent_id| {
+            match self
+                .history_store
+                .read(cx)
+                .thread_from_session_id(parent_id)
+            {
+                Some(parent) => SharedString::from(format!("Forked from {}", parent.title)),
+                None => SharedString::new_static("Forked from a deleted thread"),
+            }
+        });
 
         h_flex()
             .w_full()
@@ -418,9 +428,26 @@ impl AcpThreadHistory {
                             .gap_2()
                             .justify_between()
                             .child(
-                                HighlightedLabel::new(entry.title(), highlight_positions)
-                                    .size(LabelSize::Small)
-                                    .truncate(),
+                                h_flex()
+                                    .min_w_0()
+                                    .gap_1()
+                                    .when_some(forked_from, |this, forked_from| {
+                                        this.child(
+                                            div()
+                                                .id(("forked-thread", ix))
+                                                .child(
+                                                    Icon::new(IconName::GitBranch)
+                                                        .size(IconSize::XSmall)
+                                                        .color(Color::Muted),
+                                                )
+                                                .tooltip(Tooltip::text(forked_from)),
+                                        )
+                                    })
+                                    .child(
+                                        HighlightedLabel::new(entry.title(), highlight_positions)
+                                            .size(LabelSize::Small)
+                                            .truncate(),
+                                    ),
                             )
                             .child(
                                 Label::new(thread_timestamp)
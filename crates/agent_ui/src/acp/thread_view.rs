@@ -5,7 +5,10 @@ use acp_thread::{
 };
 use acp_thread::{AgentConnection, Plan};
 use action_log::{ActionLog, ActionLogTelemetry};
-use agent::{DbThreadMetadata, HistoryEntry, HistoryEntryId, HistoryStore, NativeAgentServer};
+use agent::{
+    AgentTool, DbThreadMetadata, HistoryEntry, HistoryEntryId, HistoryStore, NativeAgentServer,
+    SubagentTool,
+};
 use agent_client_protocol::{self as acp, PromptCapabilities};
 use agent_servers::{AgentServer, AgentServerDelegate};
 use agent_settings::{AgentProfileId, AgentSettings, CompletionMode};
@@ -26,9 +29,10 @@ use futures::FutureExt as _;
 use gpui::{
     Action, Animation, AnimationExt, AnyView, App, BorderStyle, ClickEvent, ClipboardItem,
     CursorStyle, EdgesRefinement, ElementId, Empty, Entity, FocusHandle, Focusable, Hsla, Length,
-    ListOffset, ListState, PlatformDisplay, SharedString, StyleRefinement, Subscription, Task,
-    TextStyle, TextStyleRefinement, UnderlineStyle, WeakEntity, Window, WindowHandle, div,
-    ease_in_out, linear_color_stop, linear_gradient, list, point, pulsating_between,
+    ListOffset, ListState, PlatformDisplay, PromptLevel, SharedString, StyleRefinement,
+    Subscription, Task, TextStyle, TextStyleRefinement, UnderlineStyle, WeakEntity, Window,
+    WindowHandle, div, ease_in_out, linear_color_stop, linear_gradient, list, point,
+    pulsating_between,
 };
 use language::Buffer;
 
@@ -1935,6 +1939,80 @@ impl AcpThreadView {
             .detach_and_log_err(cx);
     }
 
+    fn fork_thread(
+        &mut self,
+        message_id: &UserMessageId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(thread) = self.thread() else {
+            return;
+        };
+
+        let (parent_id, title, has_checkpoint) = {
+            let thread = thread.read(cx);
+            let has_checkpoint = thread
+                .entries()
+                .iter()
+                .filter_map(|entry| entry.user_message())
+                .find(|message| message.id.as_ref() == Some(message_id))
+                .is_some_and(|message| message.checkpoint.is_some());
+            (thread.session_id().clone(), thread.title(), has_checkpoint)
+        };
+        let thread = thread.clone();
+        let message_id = message_id.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            // Restoring the checkpoint discards the changes made since the message,
+            // so let the user choose whether to keep them.
+            let restore_checkpoint = if has_checkpoint {
+                let answer = cx
+                    .update(|window, cx| {
+                        window.prompt(
+                            PromptLevel::Warning,
+                            "Restore project files to this message?",
+                            Some(
+                                "Restoring discards all changes made to the project since this message, including the ones made by the agent.",
+                            ),
+                            &["Fork and Restore Files", "Fork Without Restoring", "Cancel"],
+                            cx,
+                        )
+                    })?
+                    .await?;
+                match answer {
+                    0 => true,
+                    1 => false,
+                    _ => return Ok(()),
+                }
+            } else {
+                false
+            };
+
+            let id = thread
+                .update(cx, |thread, cx| {
+                    thread.fork(message_id, restore_checkpoint, cx)
+                })?
+                .await?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                if let Some(panel) = workspace.panel::<AgentPanel>(cx) {
+                    panel.update(cx, |panel, cx| {
+                        panel.load_agent_thread(
+                            DbThreadMetadata {
+                                id,
+                                title,
+                                updated_at: chrono::Utc::now(),
+                                parent_id: Some(parent_id),
+                            },
+                            window,
+                            cx,
+                        )
+                    });
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn render_entry(
         &self,
         entry_ix: usize,
@@ -1971,6 +2049,10 @@ impl AcpThreadView {
                     .is_some_and(|checkpoint| checkpoint.show);
 
                 let agent_name = self.agent.name();
+                let supports_fork = self.thread().is_some_and(|thread| {
+                    let thread = thread.read(cx);
+                    thread.connection().fork(thread.session_id(), cx).is_some()
+                });
 
                 v_flex()
                     .id(("user_message", entry_ix))
@@ -2047,9 +2129,23 @@ impl AcpThreadView {
                                     .bg(cx.theme().colors().editor_background)
                                     .overflow_hidden();
 
-                                if message.id.is_some() {
+                                if let Some(message_id) = message.id.clone() {
                                     this.child(
                                         base_container
+                                            .when(supports_fork, |this| {
+                                                this.child(
+                                                    IconButton::new("fork", IconName::GitBranch)
+                                                        .disabled(self.is_loading_contents)
+                                                        .icon_color(Color::Muted)
+                                                        .icon_size(IconSize::XSmall)
+                                                        .tooltip(Tooltip::text(
+                                                            "Fork the thread from this message, optionally restoring the project files to this point."
+                                                        ))
+                                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                                            this.fork_thread(&message_id, window, cx);
+                                                        }))
+                                                )
+                                            })
                                             .child(
                                                 IconButton::new("cancel", IconName::Close)
                                                     .disabled(self.is_loading_contents)
@@ -2358,6 +2454,8 @@ impl AcpThreadView {
         let use_card_layout = needs_confirmation || is_edit || is_terminal_tool;
 
         let is_collapsible = !tool_call.content.is_empty() && !needs_confirmation;
+        let is_subagent =
+            tool_call.tool_name.as_deref() == Some(<SubagentTool as AgentTool>::name());
 
         let is_open = needs_confirmation || self.expanded_tool_calls.contains(&tool_call.id);
 
@@ -2401,7 +2499,11 @@ impl AcpThreadView {
                     | ToolCallStatus::Completed
                     | ToolCallStatus::Failed
                     | ToolCallStatus::Canceled => v_flex()
+                        .id(("tool-call-output", entry_ix))
                         .w_full()
+                        // A subagent's transcript can get long, so keep it scrollable
+                        // instead of pushing the rest of the thread out of view.
+                        .when(is_subagent, |this| this.max_h_96().overflow_y_scroll())
                         .children(tool_call.content.iter().enumerate().map(
                             |(content_ix, content)| {
                                 div().child(self.render_tool_call_content(
@@ -2498,7 +2600,40 @@ impl AcpThreadView {
                                     h_flex()
                                         .px_1()
                                         .gap_px()
-                                        .when(is_collapsible, |this| {
+                                        .when(is_collapsible && is_subagent, |this| {
+                                            this.child(
+                                                Button::new(
+                                                    ("toggle-transcript", entry_ix),
+                                                    if is_open {
+                                                        "Hide Transcript"
+                                                    } else {
+                                                        "Show Transcript"
+                                                    },
+                                                )
+                                                .icon(if is_open {
+                                                    IconName::ChevronUp
+                                                } else {
+                                                    IconName::ChevronDown
+                                                })
+                                                .icon_size(IconSize::XSmall)
+                                                .icon_position(IconPosition::End)
+                                                .icon_color(Color::Muted)
+                                                .label_size(LabelSize::XSmall)
+                                                .color(Color::Muted)
+                                                .on_click(cx.listener({
+                                                    let id = tool_call.id.clone();
+                                                    move |this: &mut Self, _, _, cx: &mut Context<Self>| {
+                                                        if is_open {
+                                                            this.expanded_tool_calls.remove(&id);
+                                                        } else {
+                                                            this.expanded_tool_calls.insert(id.clone());
+                                                        }
+                                                        cx.notify();
+                                                    }
+                                                })),
+                                            )
+                                        })
+                                        .when(is_collapsible && !is_subagent, |this| {
                                             this.child(
                                             Disclosure::new(("expand", entry_ix), is_open)
                                                 .opened_icon(IconName::ChevronUp)
@@ -4706,6 +4841,7 @@ impl AcpThreadView {
                                     id,
                                     title: name.into(),
                                     updated_at: Default::default(),
+                                    parent_id: None,
                                 },
                                 window,
                                 cx,
@@ -4736,6 +4872,7 @@ impl AcpThreadView {
                 MentionUri::Fetch { url } => {
                     cx.open_url(url.as_str());
                 }
+                MentionUri::ContextServerResource { .. } => {}
             })
         } else {
             cx.open_url(&url);
@@ -901,6 +901,7 @@ fn wait_for_context_server(
                 _ => {}
             }
         }
+        project::context_server_store::Event::ResourceUpdated { .. } => {}
     });
 
     cx.spawn(async move |_cx| {
@@ -7,6 +7,7 @@ mod buffer_codegen;
 mod completion_provider;
 mod context;
 mod context_server_configuration;
+mod context_server_requests;
 #[cfg(test)]
 mod evals;
 mod inline_assistant;
@@ -226,6 +227,7 @@ pub fn init(
     assistant_slash_command::init(cx);
     agent_panel::init(cx);
     context_server_configuration::init(language_registry.clone(), fs.clone(), cx);
+    context_server_requests::init(cx);
     TextThreadEditor::init(cx);
 
     register_slash_commands(cx);
@@ -452,6 +454,7 @@ mod tests {
             default_view: DefaultAgentView::Thread,
             profiles: Default::default(),
             always_allow_tool_actions: false,
+            tool_permissions: Default::default(),
             notify_when_agent_waiting: NotifyWhenAgentWaiting::default(),
             play_sound_when_agent_done: false,
             single_file_review: false,
@@ -7,14 +7,21 @@ use std::sync::atomic::AtomicBool;
 use acp_thread::MentionUri;
 use agent::{HistoryEntry, HistoryStore};
 use anyhow::Result;
+use context_server::{
+    ContextServer, ContextServerId,
+    protocol::{InitializedContextServerProtocol, ServerCapability},
+    types::ResourceTemplate,
+};
 use editor::{
     CompletionProvider, Editor, ExcerptId, code_context_menus::COMPLETION_MENU_MAX_WIDTH,
 };
+use futures::future::{Shared, join_all};
 use fuzzy::{PathMatch, StringMatch, StringMatchCandidate};
-use gpui::{App, Entity, Task, WeakEntity};
+use gpui::{App, BackgroundExecutor, Entity, Task, WeakEntity};
 use language::{Buffer, CodeLabel, CodeLabelBuilder, HighlightId};
 use lsp::CompletionContext;
 use ordered_float::OrderedFloat;
+use project::context_server_store::ContextServerResources;
 use project::lsp_store::{CompletionDocumentation, SymbolLocation};
 use project::{
     Completion, CompletionDisplayOptions, CompletionIntent, CompletionResponse,
@@ -55,6 +62,7 @@ pub(crate) enum PromptContextType {
     Fetch,
     Thread,
     Rules,
+    Resource,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -92,6 +100,7 @@ impl TryFrom<&str> for PromptContextType {
             "fetch" => Ok(Self::Fetch),
             "thread" => Ok(Self::Thread),
             "rule" => Ok(Self::Rules),
+            "resource" => Ok(Self::Resource),
             _ => Err(format!("Invalid context picker mode: {}", value)),
         }
     }
@@ -105,6 +114,7 @@ impl PromptContextType {
             Self::Fetch => "fetch",
             Self::Thread => "thread",
             Self::Rules => "rule",
+            Self::Resource => "resource",
         }
     }
 
@@ -115,6 +125,7 @@ impl PromptContextType {
             Self::Fetch => "Fetch",
             Self::Thread => "Threads",
             Self::Rules => "Rules",
+            Self::Resource => "MCP Resources",
         }
     }
 
@@ -125,6 +136,7 @@ impl PromptContextType {
             Self::Fetch => IconName::ToolWeb,
             Self::Thread => IconName::Thread,
             Self::Rules => IconName::Reader,
+            Self::Resource => IconName::ToolHammer,
         }
     }
 }
@@ -136,6 +148,7 @@ pub(crate) enum Match {
     RecentThread(HistoryEntry),
     Fetch(SharedString),
     Rules(RulesContextEntry),
+    Resource(ResourceMatch),
     Entry(EntryMatch),
 }
 
@@ -148,6 +161,7 @@ impl Match {
             Match::RecentThread(_) => 1.,
             Match::Symbol(_) => 1.,
             Match::Rules(_) => 1.,
+            Match::Resource(_) => 1.,
             Match::Fetch(_) => 1.,
         }
     }
@@ -499,6 +513,74 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
         })
     }
 
+    fn completion_for_resource(
+        resource: ResourceMatch,
+        source_range: Range<Anchor>,
+        source: Arc<T>,
+        editor: WeakEntity<Editor>,
+        mention_set: WeakEntity<MentionSet>,
+        workspace: Entity<Workspace>,
+        label_max_chars: usize,
+        cx: &mut App,
+    ) -> Completion {
+        let label = build_code_label_for_path(
+            &resource.label,
+            Some(&resource.server_id.0),
+            None,
+            label_max_chars,
+            cx,
+        );
+        let documentation = resource
+            .description
+            .map(|description| CompletionDocumentation::MultiLinePlainText(description.into()));
+        match resource.target {
+            ResourceTarget::Resource { uri, name } => {
+                let uri = MentionUri::ContextServerResource {
+                    server_id: resource.server_id.0.to_string(),
+                    uri,
+                    name,
+                };
+                let new_text = format!("{} ", uri.as_link());
+                let new_text_len = new_text.len();
+                let icon_path = uri.icon_path(cx);
+                Completion {
+                    replace_range: source_range.clone(),
+                    new_text,
+                    label,
+                    documentation,
+                    source: project::CompletionSource::Custom,
+                    icon_path: Some(icon_path),
+                    match_start: None,
+                    snippet_deduplication_key: None,
+                    insert_text_mode: None,
+                    confirm: Some(confirm_completion_callback(
+                        uri.name().into(),
+                        source_range.start,
+                        new_text_len - 1,
+                        uri,
+                        source,
+                        editor,
+                        mention_set,
+                        workspace,
+                    )),
+                }
+            }
+            ResourceTarget::Partial(text) => Completion {
+                replace_range: source_range,
+                new_text: format!("@{} {}", PromptContextType::Resource.keyword(), text),
+                label,
+                documentation,
+                source: project::CompletionSource::Custom,
+                icon_path: Some(PromptContextType::Resource.icon().path().into()),
+                match_start: None,
+                snippet_deduplication_key: None,
+                insert_text_mode: None,
+                // Keep the completion menu open to continue expanding the template
+                confirm: Some(Arc::new(|_, _, _| true)),
+            },
+        }
+    }
+
     pub(crate) fn completion_for_action(
         action: PromptContextAction,
         source_range: Range<Anchor>,
@@ -655,6 +737,18 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
                 }
             }
 
+            Some(PromptContextType::Resource) => {
+                let search_resources_task =
+                    search_context_server_resources(query, cancellation_flag, &workspace, cx);
+                cx.background_spawn(async move {
+                    search_resources_task
+                        .await
+                        .into_iter()
+                        .map(Match::Resource)
+                        .collect()
+                })
+            }
+
             Some(PromptContextType::Rules) => {
                 if let Some(prompt_store) = self.prompt_store.as_ref() {
                     let search_rules_task =
@@ -852,6 +946,14 @@ impl<T: PromptCompletionProviderDelegate> PromptCompletionProvider<T> {
             entries.push(PromptContextEntry::Mode(PromptContextType::Fetch));
         }
 
+        if self
+            .source
+            .supports_context(PromptContextType::Resource, cx)
+            && !context_servers_with_resources(workspace, cx).is_empty()
+        {
+            entries.push(PromptContextEntry::Mode(PromptContextType::Resource));
+        }
+
         entries
     }
 }
@@ -1070,6 +1172,17 @@ impl<T: PromptCompletionProviderDelegate> CompletionProvider for PromptCompletio
                                     cx,
                                 )),
 
+                                Match::Resource(resource) => Some(Self::completion_for_resource(
+                                    resource,
+                                    source_range.clone(),
+                                    source.clone(),
+                                    editor.clone(),
+                                    mention_set.clone(),
+                                    workspace.clone(),
+                                    label_max_chars,
+                                    cx,
+                                )),
+
                                 Match::Fetch(url) => Self::completion_for_fetch(
                                     source_range.clone(),
                                     url,
@@ -1598,6 +1711,366 @@ pub(crate) fn search_rules(
     })
 }
 
+fn context_servers_with_resources(
+    workspace: &Entity<Workspace>,
+    cx: &App,
+) -> Vec<Arc<ContextServer>> {
+    workspace
+        .read(cx)
+        .project()
+        .read(cx)
+        .context_server_store()
+        .read(cx)
+        .running_servers()
+        .into_iter()
+        .filter(|server| {
+            server
+                .client()
+                .is_some_and(|client| client.capable(ServerCapability::Resources))
+        })
+        .collect()
+}
+
+/// Lists the resources of the running context servers matching the query, along
+/// with the resource templates the query is expanding.
+pub(crate) fn search_context_server_resources(
+    query: String,
+    cancellation_flag: Arc<AtomicBool>,
+    workspace: &Entity<Workspace>,
+    cx: &mut App,
+) -> Task<Vec<ResourceMatch>> {
+    let server_store = workspace.read(cx).project().read(cx).context_server_store();
+    let servers = context_servers_with_resources(workspace, cx)
+        .into_iter()
+        .map(|server| {
+            let resources = server_store.update(cx, |server_store, cx| {
+                server_store.resources_for_server(&server, cx)
+            });
+            (server, resources)
+        })
+        .collect::<Vec<_>>();
+    let executor = cx.background_executor().clone();
+    cx.spawn(async move |_| {
+        let server_matches = servers.into_iter().map(|(server, resources)| {
+            search_server_resources(
+                server,
+                resources,
+                &query,
+                &cancellation_flag,
+                executor.clone(),
+            )
+        });
+        join_all(server_matches)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    })
+}
+
+async fn search_server_resources(
+    server: Arc<ContextServer>,
+    resources: Shared<Task<Arc<ContextServerResources>>>,
+    query: &str,
+    cancellation_flag: &Arc<AtomicBool>,
+    executor: BackgroundExecutor,
+) -> Vec<ResourceMatch> {
+    let Some(client) = server.client() else {
+        return Vec::new();
+    };
+    let server_id = server.id();
+    let listing = resources.await;
+    let resources = &listing.resources;
+    let templates = &listing.templates;
+
+    let mut matches = Vec::new();
+    let mut candidates = resources
+        .iter()
+        .enumerate()
+        .map(|(ix, resource)| StringMatchCandidate::new(ix, &resource.name))
+        .collect::<Vec<_>>();
+    let mut template_matches = Vec::new();
+    for (ix, template) in templates.iter().enumerate() {
+        let parts = parse_uri_template(&template.uri_template);
+        match expand_uri_template(&parts, query) {
+            TemplateExpansion::Mismatch => {
+                candidates.push(StringMatchCandidate::new(
+                    resources.len() + ix,
+                    &template.name,
+                ));
+            }
+            expansion => {
+                template_matches
+                    .extend(expansion_matches(&client, &server_id, template, expansion).await);
+            }
+        }
+    }
+
+    let candidate_ids = if query.is_empty() {
+        candidates
+            .iter()
+            .map(|candidate| candidate.id)
+            .collect::<Vec<_>>()
+    } else {
+        fuzzy::match_strings(
+            &candidates,
+            query,
+            false,
+            true,
+            100,
+            cancellation_flag,
+            executor,
+        )
+        .await
+        .into_iter()
+        .map(|mat| mat.candidate_id)
+        .collect()
+    };
+    for id in candidate_ids {
+        if let Some(resource) = resources.get(id) {
+            matches.push(ResourceMatch {
+                server_id: server_id.clone(),
+                label: resource.name.clone(),
+                description: resource.description.clone(),
+                target: ResourceTarget::Resource {
+                    uri: resource.uri.clone(),
+                    name: resource.name.clone(),
+                },
+            });
+        } else if let Some(template) = templates.get(id - resources.len()) {
+            // Start expanding the template from its first variable.
+            let parts = parse_uri_template(&template.uri_template);
+            let prefix = match parts.first() {
+                Some(TemplatePart::Literal(literal)) => literal,
+                _ => "",
+            };
+            let expansion = TemplateExpansion::Literal {
+                expanded: prefix.to_string(),
+                is_complete: !parts
+                    .iter()
+                    .any(|part| matches!(part, TemplatePart::Variable(_))),
+            };
+            matches.extend(expansion_matches(&client, &server_id, template, expansion).await);
+        }
+    }
+    matches.append(&mut template_matches);
+    matches
+}
+
+/// Suggests how to continue expanding a resource template, completing the value
+/// of a variable through the server when it supports argument completion.
+async fn expansion_matches(
+    client: &InitializedContextServerProtocol,
+    server_id: &ContextServerId,
+    template: &ResourceTemplate,
+    expansion: TemplateExpansion<'_>,
+) -> Vec<ResourceMatch> {
+    let resource_match = |label: &str, description: Option<String>, text: String, name| {
+        let target = match name {
+            Some(name) => ResourceTarget::Resource {
+                uri: url::Url::parse(&text).ok()?,
+                name,
+            },
+            None => ResourceTarget::Partial(text),
+        };
+        Some(ResourceMatch {
+            server_id: server_id.clone(),
+            label: label.to_string(),
+            description,
+            target,
+        })
+    };
+
+    match expansion {
+        TemplateExpansion::Mismatch => Vec::new(),
+        TemplateExpansion::Complete(uri) => resource_match(
+            &template.name,
+            template.description.clone(),
+            uri.to_string(),
+            Some(template.name.clone()),
+        )
+        .into_iter()
+        .collect(),
+        TemplateExpansion::Literal {
+            expanded,
+            is_complete,
+        } => resource_match(
+            &template.name,
+            template.description.clone(),
+            expanded,
+            is_complete.then(|| template.name.clone()),
+        )
+        .into_iter()
+        .collect(),
+        TemplateExpansion::Variable {
+            name,
+            prefix,
+            value,
+            suffix,
+            is_last,
+        } => {
+            let mut values = Vec::new();
+            if client.capable(ServerCapability::Completions) {
+                let response = client
+                    .request::<context_server::types::requests::CompletionComplete>(
+                        context_server::types::CompletionCompleteParams {
+                            reference: context_server::types::CompletionReference::Resource(
+                                context_server::types::ResourceReference {
+                                    ty: context_server::types::PromptReferenceType::Resource,
+                                    uri: template.uri_template.clone(),
+                                },
+                            ),
+                            argument: context_server::types::CompletionArgument {
+                                name: name.to_string(),
+                                value: value.to_string(),
+                            },
+                            meta: None,
+                        },
+                    )
+                    .await;
+                if let Some(response) = response.log_err() {
+                    values = response.completion.values;
+                }
+            }
+            // The value typed so far can be used as is once nothing else is left to expand.
+            if is_last && !value.is_empty() && !values.iter().any(|completed| completed == value) {
+                values.push(value.to_string());
+            }
+
+            values
+                .into_iter()
+                .filter_map(|completed| {
+                    resource_match(
+                        &completed,
+                        Some(template.name.clone()),
+                        format!("{prefix}{completed}{suffix}"),
+                        is_last.then(|| format!("{}: {}", template.name, completed)),
+                    )
+                })
+                .collect()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum TemplatePart<'a> {
+    Literal(&'a str),
+    Variable(&'a str),
+}
+
+/// Splits a URI template into its literal parts and the names of its variables.
+///
+/// Operators like `{+path}` are accepted, but their values are inserted as typed.
+fn parse_uri_template(template: &str) -> Vec<TemplatePart<'_>> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{')
+        && let Some(len) = rest[start..].find('}')
+    {
+        if start > 0 {
+            parts.push(TemplatePart::Literal(&rest[..start]));
+        }
+        let name =
+            rest[start + 1..start + len].trim_start_matches(['+', '#', '.', '/', ';', '?', '&']);
+        parts.push(TemplatePart::Variable(name));
+        rest = &rest[start + len + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Literal(rest));
+    }
+    parts
+}
+
+/// How far a query got in expanding a URI template.
+#[derive(Debug, PartialEq)]
+enum TemplateExpansion<'a> {
+    /// The query doesn't follow the template.
+    Mismatch,
+    /// The query stops within a literal part, which is completed in `expanded`.
+    Literal { expanded: String, is_complete: bool },
+    /// The query stops within the value of the `name` variable, which follows `prefix`
+    /// and is followed by `suffix`.
+    Variable {
+        name: &'a str,
+        prefix: &'a str,
+        value: &'a str,
+        suffix: &'a str,
+        is_last: bool,
+    },
+    /// The query is a full expansion of the template.
+    Complete(&'a str),
+}
+
+fn expand_uri_template<'a>(parts: &[TemplatePart<'a>], query: &'a str) -> TemplateExpansion<'a> {
+    let mut rest = query;
+    for (ix, part) in parts.iter().enumerate() {
+        match part {
+            TemplatePart::Literal(literal) => {
+                if let Some(remaining) = rest.strip_prefix(literal) {
+                    rest = remaining;
+                } else if literal.starts_with(rest) {
+                    let consumed = query.len() - rest.len();
+                    return TemplateExpansion::Literal {
+                        expanded: format!("{}{literal}", &query[..consumed]),
+                        is_complete: ix + 1 == parts.len(),
+                    };
+                } else {
+                    return TemplateExpansion::Mismatch;
+                }
+            }
+            TemplatePart::Variable(name) => {
+                let next_literal = match parts.get(ix + 1) {
+                    Some(TemplatePart::Literal(literal)) => Some(*literal),
+                    _ => None,
+                };
+                // The value ends where the next literal starts, or where the query
+                // starts typing it.
+                let value_end = next_literal.and_then(|literal| {
+                    rest.find(literal).or_else(|| {
+                        rest.char_indices()
+                            .map(|(ix, _)| ix)
+                            .find(|&ix| ix > 0 && literal.starts_with(&rest[ix..]))
+                    })
+                });
+                match value_end {
+                    Some(end) if end > 0 => rest = &rest[end..],
+                    Some(_) if !rest.is_empty() => return TemplateExpansion::Mismatch,
+                    _ => {
+                        return TemplateExpansion::Variable {
+                            name,
+                            prefix: &query[..query.len() - rest.len()],
+                            value: rest,
+                            suffix: next_literal.unwrap_or_default(),
+                            is_last: !parts[ix + 1..]
+                                .iter()
+                                .any(|part| matches!(part, TemplatePart::Variable(_))),
+                        };
+                    }
+                }
+            }
+        }
+    }
+    if rest.is_empty() {
+        TemplateExpansion::Complete(query)
+    } else {
+        TemplateExpansion::Mismatch
+    }
+}
+
+pub struct ResourceMatch {
+    server_id: ContextServerId,
+    label: String,
+    description: Option<String>,
+    target: ResourceTarget,
+}
+
+enum ResourceTarget {
+    /// A resource to mention.
+    Resource { uri: url::Url, name: String },
+    /// A partially expanded resource template to keep completing.
+    Partial(String),
+}
+
 pub struct SymbolMatch {
     pub symbol: Symbol,
 }
@@ -1729,6 +2202,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_expand_uri_template() {
+        let parts = parse_uri_template("repo://{owner}/{repo}/issues");
+        assert_eq!(
+            parts,
+            vec![
+                TemplatePart::Literal("repo://"),
+                TemplatePart::Variable("owner"),
+                TemplatePart::Literal("/"),
+                TemplatePart::Variable("repo"),
+                TemplatePart::Literal("/issues"),
+            ]
+        );
+
+        assert_eq!(
+            expand_uri_template(&parts, ""),
+            TemplateExpansion::Literal {
+                expanded: "repo://".to_string(),
+                is_complete: false,
+            }
+        );
+        assert_eq!(
+            expand_uri_template(&parts, "repo://zed-ind"),
+            TemplateExpansion::Variable {
+                name: "owner",
+                prefix: "repo://",
+                value: "zed-ind",
+                suffix: "/",
+                is_last: false,
+            }
+        );
+        assert_eq!(
+            expand_uri_template(&parts, "repo://zed-industries/z"),
+            TemplateExpansion::Variable {
+                name: "repo",
+                prefix: "repo://zed-industries/",
+                value: "z",
+                suffix: "/issues",
+                is_last: true,
+            }
+        );
+        assert_eq!(
+            expand_uri_template(&parts, "repo://zed-industries/zed/iss"),
+            TemplateExpansion::Literal {
+                expanded: "repo://zed-industries/zed/issues".to_string(),
+                is_complete: true,
+            }
+        );
+        assert_eq!(
+            expand_uri_template(&parts, "repo://zed-industries/zed/issues"),
+            TemplateExpansion::Complete("repo://zed-industries/zed/issues")
+        );
+        assert_eq!(
+            expand_uri_template(&parts, "issues"),
+            TemplateExpansion::Mismatch
+        );
+        assert_eq!(
+            expand_uri_template(&parts, "repo:///zed"),
+            TemplateExpansion::Mismatch
+        );
+
+        let parts = parse_uri_template("file:///{+path}");
+        assert_eq!(
+            parts,
+            vec![
+                TemplatePart::Literal("file:///"),
+                TemplatePart::Variable("path")
+            ]
+        );
+        assert_eq!(
+            expand_uri_template(&parts, "file:///src/main.rs"),
+            TemplateExpansion::Variable {
+                name: "path",
+                prefix: "file:///",
+                value: "src/main.rs",
+                suffix: "",
+                is_last: true,
+            }
+        );
+    }
+
     #[test]
     fn test_slash_command_completion_parse() {
         assert_eq!(
@@ -0,0 +1,244 @@
+mod elicitation_modal;
+mod sampling_modal;
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use context_server::{ContextServerClientDelegate, ContextServerId, types};
+use futures::{StreamExt as _, channel::oneshot, lock::Mutex};
+use gpui::{App, AsyncApp, Task, WindowHandle};
+use language_model::{
+    LanguageModel, LanguageModelCompletionEvent, LanguageModelRegistry, LanguageModelRequest,
+    LanguageModelRequestMessage, MessageContent, Role, StopReason,
+};
+use project::DisableAiSettings;
+use settings::Settings as _;
+use workspace::Workspace;
+
+use elicitation_modal::ElicitationModal;
+use sampling_modal::SamplingModal;
+
+pub(crate) fn init(cx: &mut App) {
+    context_server::set_client_delegate(Rc::new(ContextServerRequests::default()), cx);
+}
+
+/// Answers the requests context servers send to Zed, asking the user in the active
+/// workspace before sampling from one of their language models or sending their input.
+#[derive(Default)]
+struct ContextServerRequests {
+    /// Held while a request is shown, so that concurrent requests are shown one after another,
+    /// instead of dismissing each other's modals.
+    modal_lock: Arc<Mutex<()>>,
+}
+
+impl ContextServerClientDelegate for ContextServerRequests {
+    fn create_message(
+        &self,
+        server_id: ContextServerId,
+        params: types::CreateMessageRequest,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<types::CreateMessageResult>> {
+        let modal_lock = self.modal_lock.clone();
+        cx.spawn(async move |cx| {
+            let model = cx.update(|cx| {
+                anyhow::ensure!(
+                    !DisableAiSettings::get_global(cx).disable_ai,
+                    "AI features are disabled"
+                );
+                sampling_model(params.model_preferences.as_ref(), cx)
+                    .context("No language model is configured to sample from")
+            })??;
+            let request = sampling_request(&params)?;
+            let max_tokens = u64::from(params.max_tokens);
+
+            let (approve_tx, approve_rx) = oneshot::channel();
+            let modal_guard = modal_lock.lock().await;
+            let workspace = active_workspace(cx)?;
+            workspace.update(cx, |workspace, window, cx| {
+                let model = model.clone();
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    SamplingModal::new(server_id, params, model, approve_tx, window, cx)
+                })
+            })?;
+            let approved = approve_rx.await.unwrap_or(false);
+            drop(modal_guard);
+            anyhow::ensure!(approved, "The user rejected the sampling request");
+
+            let mut events = model.stream_completion(request, cx).await?;
+            let mut text = String::new();
+            let mut stop_reason = None;
+            while let Some(event) = events.next().await {
+                match event? {
+                    LanguageModelCompletionEvent::Text(chunk) => text.push_str(&chunk),
+                    LanguageModelCompletionEvent::Stop(reason) => stop_reason = Some(reason),
+                    // Models can't be asked to stop at the server's limit, so the completion is
+                    // cut off once its reported usage reaches it.
+                    LanguageModelCompletionEvent::UsageUpdate(usage)
+                        if usage.output_tokens >= max_tokens =>
+                    {
+                        stop_reason = Some(StopReason::MaxTokens);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            Ok(types::CreateMessageResult {
+                role: types::Role::Assistant,
+                content: types::MessageContent::Text {
+                    text,
+                    annotations: None,
+                },
+                model: model.id().0.to_string(),
+                stop_reason: stop_reason.map(sampling_stop_reason),
+            })
+        })
+    }
+
+    fn elicit(
+        &self,
+        server_id: ContextServerId,
+        params: types::ElicitParams,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<types::ElicitResult>> {
+        let modal_lock = self.modal_lock.clone();
+        cx.spawn(async move |cx| {
+            let (result_tx, result_rx) = oneshot::channel();
+            let _modal_guard = modal_lock.lock().await;
+            let workspace = active_workspace(cx)?;
+            workspace.update(cx, |workspace, window, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    ElicitationModal::new(server_id, params, result_tx, window, cx)
+                })
+            })?;
+            Ok(result_rx.await.unwrap_or(types::ElicitResult {
+                action: types::ElicitAction::Cancel,
+                content: None,
+            }))
+        })
+    }
+}
+
+fn active_workspace(cx: &AsyncApp) -> Result<WindowHandle<Workspace>> {
+    cx.update(|cx| {
+        cx.active_window()
+            .and_then(|window| window.downcast::<Workspace>())
+            .or_else(|| {
+                cx.windows()
+                    .into_iter()
+                    .find_map(|window| window.downcast::<Workspace>())
+            })
+    })?
+    .context("No workspace to show the context server request in")
+}
+
+/// Picks the first authenticated model matching the server's hints, falling back to
+/// the default model.
+fn sampling_model(
+    preferences: Option<&types::ModelPreferences>,
+    cx: &App,
+) -> Option<Arc<dyn LanguageModel>> {
+    let registry = LanguageModelRegistry::read_global(cx);
+    let hints = preferences
+        .and_then(|preferences| preferences.hints.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|hint| hint.name.as_deref());
+    for hint in hints {
+        if let Some(model) = registry
+            .available_models(cx)
+            .find(|model| model.id().0.contains(hint) || model.name().0.contains(hint))
+        {
+            return Some(model);
+        }
+    }
+    registry.default_model().map(|configured| configured.model)
+}
+
+/// Names the reason the completion stopped the way the sampling result reports it.
+fn sampling_stop_reason(stop_reason: StopReason) -> String {
+    match stop_reason {
+        StopReason::EndTurn => "endTurn",
+        StopReason::MaxTokens => "maxTokens",
+        StopReason::ToolUse => "toolUse",
+        StopReason::Refusal => "refusal",
+    }
+    .to_string()
+}
+
+fn sampling_request(params: &types::CreateMessageRequest) -> Result<LanguageModelRequest> {
+    let system_prompt = params.system_prompt.iter().map(|system_prompt| {
+        Ok(LanguageModelRequestMessage {
+            role: Role::System,
+            content: vec![MessageContent::Text(system_prompt.clone())],
+            cache: false,
+            reasoning_details: None,
+        })
+    });
+    let messages = params.messages.iter().map(|message| {
+        let types::MessageContent::Text { text, .. } = &message.content else {
+            anyhow::bail!("Only text messages can be sampled");
+        };
+        Ok(LanguageModelRequestMessage {
+            role: match message.role {
+                types::Role::User => Role::User,
+                types::Role::Assistant => Role::Assistant,
+            },
+            content: vec![MessageContent::Text(text.clone())],
+            cache: false,
+            reasoning_details: None,
+        })
+    });
+    Ok(LanguageModelRequest {
+        messages: system_prompt.chain(messages).collect::<Result<_>>()?,
+        stop: params.stop_sequences.clone().unwrap_or_default(),
+        temperature: params.temperature.map(|temperature| temperature as f32),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_request() {
+        let params: types::CreateMessageRequest = serde_json::from_value(serde_json::json!({
+            "messages": [
+                { "role": "user", "content": { "type": "text", "text": "What is 2 + 2?" } },
+                { "role": "assistant", "content": { "type": "text", "text": "4" } },
+                { "role": "user", "content": { "type": "text", "text": "And 3 + 3?" } }
+            ],
+            "systemPrompt": "You are a calculator.",
+            "maxTokens": 100,
+            "stopSequences": ["\n"],
+            "temperature": 0.5
+        }))
+        .unwrap();
+        let request = sampling_request(&params).unwrap();
+        assert_eq!(
+            request
+                .messages
+                .iter()
+                .map(|message| (message.role, message.string_contents()))
+                .collect::<Vec<_>>(),
+            vec![
+                (Role::System, "You are a calculator.".to_string()),
+                (Role::User, "What is 2 + 2?".to_string()),
+                (Role::Assistant, "4".to_string()),
+                (Role::User, "And 3 + 3?".to_string()),
+            ]
+        );
+        assert_eq!(request.stop, vec!["\n".to_string()]);
+        assert_eq!(request.temperature, Some(0.5));
+
+        let params: types::CreateMessageRequest = serde_json::from_value(serde_json::json!({
+            "messages": [
+                { "role": "user", "content": { "type": "image", "data": "", "mimeType": "image/png" } }
+            ],
+            "maxTokens": 100
+        }))
+        .unwrap();
+        assert!(sampling_request(&params).is_err());
+    }
+}
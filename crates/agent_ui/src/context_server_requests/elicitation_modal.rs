@@ -0,0 +1,407 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use collections::IndexMap;
+use context_server::{
+    ContextServerId,
+    types::{ElicitAction, ElicitParams, ElicitResult, PrimitiveSchema},
+};
+use editor::Editor;
+use futures::channel::oneshot;
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ScrollHandle};
+use serde_json::Value;
+use ui::{
+    Checkbox, ContextMenu, DropdownMenu, KeyBinding, Modal, ModalFooter, ModalHeader, Section,
+    ToggleState, prelude::*,
+};
+use workspace::ModalView;
+
+/// Renders the form a context server asks the user to fill in, sending back the
+/// values when the user accepts it.
+///
+/// Dismissing the modal cancels the request.
+pub(super) struct ElicitationModal {
+    server_id: ContextServerId,
+    message: SharedString,
+    fields: Vec<Field>,
+    result_tx: Option<oneshot::Sender<ElicitResult>>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+}
+
+struct Field {
+    name: String,
+    schema: PrimitiveSchema,
+    required: bool,
+    input: FieldInput,
+    error: Option<SharedString>,
+}
+
+enum FieldInput {
+    Text(Entity<Editor>),
+    Choice {
+        options: Vec<String>,
+        labels: Vec<SharedString>,
+        selected: Option<usize>,
+    },
+    Toggle(bool),
+}
+
+impl Field {
+    fn new(
+        name: String,
+        schema: PrimitiveSchema,
+        required: bool,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        let input = match &schema {
+            PrimitiveSchema::String {
+                options: Some(options),
+                enum_names,
+                default,
+                ..
+            } => FieldInput::Choice {
+                labels: enum_names
+                    .as_ref()
+                    .filter(|names| names.len() == options.len())
+                    .unwrap_or(options)
+                    .iter()
+                    .map(|label| label.clone().into())
+                    .collect(),
+                selected: default
+                    .as_ref()
+                    .and_then(|default| options.iter().position(|option| option == default)),
+                options: options.clone(),
+            },
+            PrimitiveSchema::String { default, .. } => {
+                FieldInput::Text(text_input(default.clone(), window, cx))
+            }
+            PrimitiveSchema::Number { default, .. } => {
+                FieldInput::Text(text_input(default.map(|n| n.to_string()), window, cx))
+            }
+            PrimitiveSchema::Integer { default, .. } => {
+                FieldInput::Text(text_input(default.map(|n| n.to_string()), window, cx))
+            }
+            PrimitiveSchema::Boolean { default, .. } => {
+                FieldInput::Toggle(default.unwrap_or(false))
+            }
+        };
+        Self {
+            name,
+            schema,
+            required,
+            input,
+            error: None,
+        }
+    }
+
+    /// Returns the value entered for the field, or `None` when it was left empty.
+    fn value(&self, cx: &App) -> Result<Option<Value>, SharedString> {
+        let editor = match &self.input {
+            FieldInput::Toggle(value) => return Ok(Some(Value::Bool(*value))),
+            FieldInput::Choice {
+                options, selected, ..
+            } => return Ok(selected.map(|ix| Value::String(options[ix].clone()))),
+            FieldInput::Text(editor) => editor,
+        };
+        let text = editor.read(cx).text(cx);
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        match &self.schema {
+            PrimitiveSchema::Number {
+                minimum, maximum, ..
+            } => parse_in_range(text, *minimum, *maximum, "Enter a number")
+                .map(|number| Some(Value::from(number))),
+            PrimitiveSchema::Integer {
+                minimum, maximum, ..
+            } => parse_in_range(text, *minimum, *maximum, "Enter a whole number")
+                .map(|number| Some(Value::from(number))),
+            _ => Ok(Some(Value::String(text.to_string()))),
+        }
+    }
+}
+
+fn text_input(text: Option<String>, window: &mut Window, cx: &mut App) -> Entity<Editor> {
+    cx.new(|cx| {
+        let mut editor = Editor::single_line(window, cx);
+        if let Some(text) = text {
+            editor.set_text(text, window, cx);
+        }
+        editor
+    })
+}
+
+fn parse_in_range<T: FromStr + PartialOrd + Display>(
+    text: &str,
+    minimum: Option<T>,
+    maximum: Option<T>,
+    invalid_message: &'static str,
+) -> Result<T, SharedString> {
+    let value = text
+        .parse::<T>()
+        .map_err(|_| SharedString::from(invalid_message))?;
+    if let Some(minimum) = minimum
+        && value < minimum
+    {
+        return Err(format!("Must be at least {minimum}").into());
+    }
+    if let Some(maximum) = maximum
+        && value > maximum
+    {
+        return Err(format!("Must be at most {maximum}").into());
+    }
+    Ok(value)
+}
+
+impl ElicitationModal {
+    pub fn new(
+        server_id: ContextServerId,
+        params: ElicitParams,
+        result_tx: oneshot::Sender<ElicitResult>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let schema = params.requested_schema;
+        let fields = schema
+            .properties
+            .into_iter()
+            .map(|(name, property)| {
+                let required = schema.required.contains(&name);
+                Field::new(name, property, required, window, cx)
+            })
+            .collect();
+        Self {
+            server_id,
+            message: params.message.into(),
+            fields,
+            result_tx: Some(result_tx),
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+        }
+    }
+
+    fn respond(
+        &mut self,
+        action: ElicitAction,
+        content: Option<IndexMap<String, Value>>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(result_tx) = self.result_tx.take() {
+            result_tx.send(ElicitResult { action, content }).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn accept(&mut self, cx: &mut Context<Self>) {
+        let mut content = IndexMap::default();
+        let mut is_valid = true;
+        for field in &mut self.fields {
+            field.error = match field.value(cx) {
+                Ok(Some(value)) => {
+                    content.insert(field.name.clone(), value);
+                    None
+                }
+                Ok(None) if field.required => Some("This field is required".into()),
+                Ok(None) => None,
+                Err(error) => Some(error),
+            };
+            is_valid &= field.error.is_none();
+        }
+
+        if is_valid {
+            self.respond(ElicitAction::Accept, Some(content), cx);
+        } else {
+            cx.notify();
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        self.accept(cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        self.respond(ElicitAction::Cancel, None, cx);
+    }
+
+    fn select_option(&mut self, field_ix: usize, option_ix: usize, cx: &mut Context<Self>) {
+        if let Some(FieldInput::Choice { selected, .. }) =
+            self.fields.get_mut(field_ix).map(|field| &mut field.input)
+        {
+            *selected = Some(option_ix);
+            cx.notify();
+        }
+    }
+
+    fn toggle(&mut self, field_ix: usize, cx: &mut Context<Self>) {
+        if let Some(FieldInput::Toggle(value)) =
+            self.fields.get_mut(field_ix).map(|field| &mut field.input)
+        {
+            *value = !*value;
+            cx.notify();
+        }
+    }
+
+    fn render_field(
+        &self,
+        ix: usize,
+        field: &Field,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let title: SharedString = field
+            .schema
+            .title()
+            .unwrap_or(field.name.as_str())
+            .to_string()
+            .into();
+        let input = match &field.input {
+            FieldInput::Text(editor) => div()
+                .p_1()
+                .rounded_md()
+                .border_1()
+                .border_color(if field.error.is_some() {
+                    cx.theme().status().error_border
+                } else {
+                    cx.theme().colors().border_variant
+                })
+                .bg(cx.theme().colors().editor_background)
+                .child(editor.clone())
+                .into_any_element(),
+            FieldInput::Choice {
+                labels, selected, ..
+            } => {
+                let this = cx.weak_entity();
+                let labels = labels.clone();
+                let label = selected
+                    .and_then(|selected| labels.get(selected).cloned())
+                    .unwrap_or_else(|| "Select an option".into());
+                DropdownMenu::new(
+                    ("elicitation-choice", ix),
+                    label,
+                    ContextMenu::build(window, cx, move |mut menu, _, _| {
+                        for (option_ix, label) in labels.iter().enumerate() {
+                            let this = this.clone();
+                            menu = menu.entry(label.clone(), None, move |_, cx| {
+                                this.update(cx, |this, cx| this.select_option(ix, option_ix, cx))
+                                    .ok();
+                            });
+                        }
+                        menu
+                    }),
+                )
+                .into_any_element()
+            }
+            FieldInput::Toggle(value) => {
+                Checkbox::new(("elicitation-toggle", ix), ToggleState::from(*value))
+                    .label(title.clone())
+                    .on_click(cx.listener(move |this, _, _, cx| this.toggle(ix, cx)))
+                    .into_any_element()
+            }
+        };
+
+        v_flex()
+            .gap_1()
+            .when(!matches!(field.input, FieldInput::Toggle(_)), |this| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        .child(Label::new(title).size(LabelSize::Small))
+                        .when(field.required, |this| {
+                            this.child(
+                                Label::new("Required")
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                            )
+                        }),
+                )
+            })
+            .child(input)
+            .when_some(field.schema.description(), |this, description| {
+                this.child(
+                    Label::new(description.to_string())
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                )
+            })
+            .when_some(field.error.clone(), |this, error| {
+                this.child(
+                    Label::new(error)
+                        .size(LabelSize::XSmall)
+                        .color(Color::Error),
+                )
+            })
+    }
+}
+
+impl EventEmitter<DismissEvent> for ElicitationModal {}
+impl ModalView for ElicitationModal {}
+impl Focusable for ElicitationModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.fields
+            .iter()
+            .find_map(|field| match &field.input {
+                FieldInput::Text(editor) => Some(editor.focus_handle(cx)),
+                _ => None,
+            })
+            .unwrap_or_else(|| self.focus_handle.clone())
+    }
+}
+
+impl Render for ElicitationModal {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle(cx);
+        let fields = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(ix, field)| self.render_field(ix, field, window, cx).into_any_element())
+            .collect::<Vec<_>>();
+
+        div()
+            .elevation_3(cx)
+            .w(rems(34.))
+            .max_h(rems(40.))
+            .key_context("ContextServerElicitationModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .child(
+                Modal::new(
+                    "context-server-elicitation",
+                    Some(self.scroll_handle.clone()),
+                )
+                .header(
+                    ModalHeader::new()
+                        .headline(format!("{} Requests Input", self.server_id))
+                        .description(self.message.clone()),
+                )
+                .section(Section::new().child(v_flex().gap_3().children(fields)))
+                .footer(
+                    ModalFooter::new().end_slot(
+                        h_flex()
+                            .gap_2()
+                            .child(Button::new("decline-elicitation", "Decline").on_click(
+                                cx.listener(|this, _, _, cx| {
+                                    this.respond(ElicitAction::Decline, None, cx)
+                                }),
+                            ))
+                            .child(
+                                Button::new("submit-elicitation", "Submit")
+                                    .key_binding(
+                                        KeyBinding::for_action_in(
+                                            &menu::Confirm,
+                                            &focus_handle,
+                                            cx,
+                                        )
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                    )
+                                    .on_click(cx.listener(|this, _, _, cx| this.accept(cx))),
+                            ),
+                    ),
+                ),
+            )
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use context_server::{ContextServerId, types};
+use futures::channel::oneshot;
+use gpui::{DismissEvent, EventEmitter, FocusHandle, Focusable, ScrollHandle};
+use language_model::LanguageModel;
+use ui::{KeyBinding, Modal, ModalFooter, ModalHeader, Section, prelude::*};
+use workspace::ModalView;
+
+/// Shows the messages a context server wants to sample a completion for, so the user
+/// can allow or deny using their language model.
+///
+/// Dismissing the modal denies the request.
+pub(super) struct SamplingModal {
+    server_id: ContextServerId,
+    params: types::CreateMessageRequest,
+    model_name: SharedString,
+    approve_tx: Option<oneshot::Sender<bool>>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+}
+
+impl SamplingModal {
+    pub fn new(
+        server_id: ContextServerId,
+        params: types::CreateMessageRequest,
+        model: Arc<dyn LanguageModel>,
+        approve_tx: oneshot::Sender<bool>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            server_id,
+            params,
+            model_name: model.name().0,
+            approve_tx: Some(approve_tx),
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+        }
+    }
+
+    fn respond(&mut self, approved: bool, cx: &mut Context<Self>) {
+        if let Some(approve_tx) = self.approve_tx.take() {
+            approve_tx.send(approved).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        self.respond(true, cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        self.respond(false, cx);
+    }
+
+    fn render_message(
+        &self,
+        role: &'static str,
+        content: SharedString,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        v_flex()
+            .gap_0p5()
+            .child(Label::new(role).size(LabelSize::XSmall).color(Color::Muted))
+            .child(
+                div()
+                    .p_2()
+                    .rounded_md()
+                    .border_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .bg(cx.theme().colors().editor_background)
+                    .child(Label::new(content).size(LabelSize::Small)),
+            )
+    }
+}
+
+fn message_text(content: &types::MessageContent) -> SharedString {
+    match content {
+        types::MessageContent::Text { text, .. } => text.clone().into(),
+        types::MessageContent::Image { mime_type, .. } => format!("[{mime_type} image]").into(),
+        types::MessageContent::Audio { mime_type, .. } => format!("[{mime_type} audio]").into(),
+        types::MessageContent::Resource { resource, .. } => format!("[{}]", resource.uri).into(),
+    }
+}
+
+impl EventEmitter<DismissEvent> for SamplingModal {}
+impl ModalView for SamplingModal {}
+impl Focusable for SamplingModal {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for SamplingModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let system_prompt = self
+            .params
+            .system_prompt
+            .clone()
+            .map(|system_prompt| self.render_message("System", system_prompt.into(), cx));
+        let messages = self
+            .params
+            .messages
+            .iter()
+            .map(|message| {
+                let role = match message.role {
+                    types::Role::User => "User",
+                    types::Role::Assistant => "Assistant",
+                };
+                self.render_message(role, message_text(&message.content), cx)
+            })
+            .collect::<Vec<_>>();
+
+        div()
+            .elevation_3(cx)
+            .w(rems(34.))
+            .max_h(rems(40.))
+            .key_context("ContextServerSamplingModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .child(
+                Modal::new("context-server-sampling", Some(self.scroll_handle.clone()))
+                    .header(
+                        ModalHeader::new()
+                            .headline(format!("{} Wants to Use a Language Model", self.server_id))
+                            .description(format!(
+                                "Allow the server to sample a completion from {}?",
+                                self.model_name
+                            )),
+                    )
+                    .section(
+                        Section::new()
+                            .child(v_flex().gap_2().children(system_prompt).children(messages)),
+                    )
+                    .footer(
+                        ModalFooter::new().end_slot(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Button::new("deny-sampling", "Deny")
+                                        .key_binding(
+                                            KeyBinding::for_action_in(
+                                                &menu::Cancel,
+                                                &focus_handle,
+                                                cx,
+                                            )
+                                            .map(|kb| kb.size(rems_from_px(12.))),
+                                        )
+                                        .on_click(
+                                            cx.listener(|this, _, _, cx| this.respond(false, cx)),
+                                        ),
+                                )
+                                .child(
+                                    Button::new("allow-sampling", "Allow")
+                                        .key_binding(
+                                            KeyBinding::for_action_in(
+                                                &menu::Confirm,
+                                                &focus_handle,
+                                                cx,
+                                            )
+                                            .map(|kb| kb.size(rems_from_px(12.))),
+                                        )
+                                        .on_click(
+                                            cx.listener(|this, _, _, cx| this.respond(true, cx)),
+                                        ),
+                                ),
+                        ),
+                    ),
+            )
+    }
+}
@@ -401,7 +401,7 @@ impl<T: 'static> PromptEditor<T> {
             editor
         });
 
-        self.mention_set.update(cx, |mention_set, _cx| {
+        self.mention_set.update(cx, |mention_set, cx| {
             debug_assert_eq!(
                 creases.len(),
                 mention_set.creases().len(),
@@ -409,11 +409,11 @@ impl<T: 'static> PromptEditor<T> {
             );
 
             let mentions = mention_set
-                .clear()
+                .clear(cx)
                 .zip(creases)
                 .map(|((_, value), id)| (id, value))
                 .collect::<HashMap<_, _>>();
-            mention_set.set_mentions(mentions);
+            mention_set.set_mentions(mentions, cx);
         });
 
         self.assign_completion_provider(cx);
@@ -464,8 +464,9 @@ impl<T: 'static> PromptEditor<T> {
             EditorEvent::Edited { .. } => {
                 let snapshot = editor.update(cx, |editor, cx| editor.snapshot(window, cx));
 
-                self.mention_set
-                    .update(cx, |mention_set, _cx| mention_set.remove_invalid(&snapshot));
+                self.mention_set.update(cx, |mention_set, cx| {
+                    mention_set.remove_invalid(&snapshot, cx)
+                });
 
                 if let Some(workspace) = window.root::<Workspace>().flatten() {
                     workspace.update(cx, |workspace, cx| {
@@ -1099,6 +1100,7 @@ impl PromptCompletionProviderDelegate for PromptEditorCompletionProviderDelegate
             PromptContextType::Thread,
             PromptContextType::Fetch,
             PromptContextType::Rules,
+            PromptContextType::Resource,
         ]
     }
 
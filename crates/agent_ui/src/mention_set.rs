@@ -5,6 +5,9 @@ use agent_servers::{AgentServer, AgentServerDelegate};
 use anyhow::{Context as _, Result, anyhow};
 use assistant_slash_commands::codeblock_fence_for_path;
 use collections::{HashMap, HashSet};
+use context_server::{
+    ContextServer, ContextServerId, protocol::InitializedContextServerProtocol, types,
+};
 use editor::{
     Anchor, Editor, EditorSnapshot, ExcerptId, FoldPlaceholder, ToOffset,
     display_map::{Crease, CreaseId, CreaseMetadata, FoldId},
@@ -13,7 +16,7 @@ use editor::{
 use futures::{AsyncReadExt as _, FutureExt as _, future::Shared};
 use gpui::{
     Animation, AnimationExt as _, AppContext, ClipboardEntry, Context, Empty, Entity, EntityId,
-    Image, ImageFormat, Img, SharedString, Task, WeakEntity, pulsating_between,
+    Image, ImageFormat, Img, SharedString, Subscription, Task, WeakEntity, pulsating_between,
 };
 use http_client::{AsyncBody, HttpClientWithUrl};
 use itertools::Either;
@@ -21,7 +24,10 @@ use language::Buffer;
 use language_model::LanguageModelImage;
 use multi_buffer::MultiBufferRow;
 use postage::stream::Stream as _;
-use project::{Project, ProjectItem, ProjectPath, Worktree};
+use project::{
+    Project, ProjectItem, ProjectPath, Worktree,
+    context_server_store::{self, ContextServerStore},
+};
 use prompt_store::{PromptId, PromptStore};
 use rope::Point;
 use std::{
@@ -62,6 +68,10 @@ pub struct MentionSet {
     history_store: Entity<HistoryStore>,
     prompt_store: Option<Entity<PromptStore>>,
     mentions: HashMap<CreaseId, (MentionUri, MentionTask)>,
+    /// The context server resources we asked to be notified about, so they can be
+    /// unsubscribed once they are no longer mentioned.
+    resource_subscriptions: HashMap<(ContextServerId, url::Url), Arc<ContextServer>>,
+    context_server_subscriptions: Vec<Subscription>,
 }
 
 impl MentionSet {
@@ -75,6 +85,8 @@ impl MentionSet {
             history_store,
             prompt_store,
             mentions: HashMap::default(),
+            resource_subscriptions: HashMap::default(),
+            context_server_subscriptions: Vec::new(),
         }
     }
 
@@ -105,20 +117,22 @@ impl MentionSet {
         })
     }
 
-    pub fn remove_invalid(&mut self, snapshot: &EditorSnapshot) {
+    pub fn remove_invalid(&mut self, snapshot: &EditorSnapshot, cx: &mut Context<Self>) {
         for (crease_id, crease) in snapshot.crease_snapshot.creases() {
             if !crease.range().start.is_valid(snapshot.buffer_snapshot()) {
                 self.mentions.remove(&crease_id);
             }
         }
+        self.unsubscribe_unmentioned_resources(cx);
     }
 
     pub fn insert_mention(&mut self, crease_id: CreaseId, uri: MentionUri, task: MentionTask) {
         self.mentions.insert(crease_id, (uri, task));
     }
 
-    pub fn remove_mention(&mut self, crease_id: &CreaseId) {
+    pub fn remove_mention(&mut self, crease_id: &CreaseId, cx: &mut Context<Self>) {
         self.mentions.remove(crease_id);
+        self.unsubscribe_unmentioned_resources(cx);
     }
 
     pub fn creases(&self) -> HashSet<CreaseId> {
@@ -129,14 +143,57 @@ impl MentionSet {
         self.mentions.values().map(|(uri, _)| uri.clone()).collect()
     }
 
-    pub fn set_mentions(&mut self, mentions: HashMap<CreaseId, (MentionUri, MentionTask)>) {
+    pub fn set_mentions(
+        &mut self,
+        mentions: HashMap<CreaseId, (MentionUri, MentionTask)>,
+        cx: &mut Context<Self>,
+    ) {
         self.mentions = mentions;
+        self.unsubscribe_unmentioned_resources(cx);
     }
 
-    pub fn clear(&mut self) -> impl Iterator<Item = (CreaseId, (MentionUri, MentionTask))> {
+    pub fn clear(
+        &mut self,
+        cx: &mut Context<Self>,
+    ) -> impl Iterator<Item = (CreaseId, (MentionUri, MentionTask))> {
+        self.unsubscribe_unmentioned_resources(cx);
         self.mentions.drain()
     }
 
+    /// Unsubscribes from the context server resources that are no longer mentioned.
+    ///
+    /// This is deferred, so that mentions which are cleared and restored within the
+    /// same update keep their subscription.
+    fn unsubscribe_unmentioned_resources(&mut self, cx: &mut Context<Self>) {
+        if self.resource_subscriptions.is_empty() {
+            return;
+        }
+        let this = cx.weak_entity();
+        cx.defer(move |cx| {
+            this.update(cx, |this, cx| {
+                let mentioned = this
+                    .mentions
+                    .values()
+                    .filter_map(|(mention_uri, _)| match mention_uri {
+                        MentionUri::ContextServerResource { server_id, uri, .. } => {
+                            Some((ContextServerId(server_id.as_str().into()), uri.clone()))
+                        }
+                        _ => None,
+                    })
+                    .collect::<HashSet<_>>();
+                this.resource_subscriptions
+                    .retain(|(server_id, uri), server| {
+                        let is_mentioned = mentioned.contains(&(server_id.clone(), uri.clone()));
+                        if !is_mentioned {
+                            unsubscribe_from_resource(server, uri.clone(), cx);
+                        }
+                        is_mentioned
+                    });
+            })
+            .ok();
+        });
+    }
+
     pub fn confirm_mention_completion(
         &mut self,
         crease_text: SharedString,
@@ -229,6 +286,12 @@ impl MentionSet {
                 ..
             } => self.confirm_mention_for_symbol(abs_path, line_range, cx),
             MentionUri::Rule { id, .. } => self.confirm_mention_for_rule(id, cx),
+            MentionUri::ContextServerResource { server_id, uri, .. } => self
+                .confirm_mention_for_context_server_resource(
+                    ContextServerId(server_id.into()),
+                    uri,
+                    cx,
+                ),
             MentionUri::PastedImage => {
                 debug_panic!("pasted image URI should not be included in completions");
                 Task::ready(Err(anyhow!(
@@ -255,7 +318,7 @@ impl MentionSet {
                         // Remove mention
                         editor.edit([(start_anchor..end_anchor, "")], cx);
                     });
-                    this.mentions.remove(&crease_id);
+                    this.remove_mention(&crease_id, cx);
                 })
                 .ok();
             }
@@ -387,6 +450,106 @@ impl MentionSet {
         })
     }
 
+    fn confirm_mention_for_context_server_resource(
+        &mut self,
+        server_id: ContextServerId,
+        uri: url::Url,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Mention>> {
+        let Some(project) = self.project.upgrade() else {
+            return Task::ready(Err(anyhow!("project not found")));
+        };
+        let server_store = project.read(cx).context_server_store();
+        if self.context_server_subscriptions.is_empty() {
+            self.context_server_subscriptions = vec![
+                cx.subscribe(&server_store, Self::handle_context_server_event),
+                cx.on_release(|this, cx| {
+                    for ((_, uri), server) in this.resource_subscriptions.drain() {
+                        unsubscribe_from_resource(&server, uri, cx);
+                    }
+                }),
+            ];
+        }
+        let Some(server) = server_store.read(cx).get_running_server(&server_id) else {
+            return Task::ready(Err(anyhow!("context server {server_id} is not running")));
+        };
+        cx.spawn(async move |this, cx| {
+            let client = server.client().context("context server not initialized")?;
+            let mention = read_context_server_resource(&client, uri.clone()).await?;
+            // Subscribing lets the server tell us when the resource changes, so the
+            // mention can be refreshed before the message is sent.
+            let supports_subscriptions = client
+                .initialize
+                .capabilities
+                .resources
+                .as_ref()
+                .is_some_and(|resources| resources.subscribe == Some(true));
+            if supports_subscriptions
+                && client
+                    .request::<types::requests::ResourcesSubscribe>(
+                        types::ResourcesSubscribeParams {
+                            uri: uri.clone(),
+                            meta: None,
+                        },
+                    )
+                    .await
+                    .log_err()
+                    .is_some()
+            {
+                this.update(cx, |this, cx| {
+                    this.resource_subscriptions
+                        .insert((server_id, uri), server.clone());
+                    // The mention may have been removed while we were subscribing.
+                    this.unsubscribe_unmentioned_resources(cx);
+                })?;
+            }
+            Ok(mention)
+        })
+    }
+
+    fn handle_context_server_event(
+        &mut self,
+        server_store: Entity<ContextServerStore>,
+        event: &context_server_store::Event,
+        cx: &mut Context<Self>,
+    ) {
+        let context_server_store::Event::ResourceUpdated { server_id, uri } = event else {
+            return;
+        };
+        let Some(server) = server_store.read(cx).get_running_server(server_id) else {
+            return;
+        };
+        let Ok(uri) = url::Url::parse(uri) else {
+            return;
+        };
+
+        let mut reload_task = None;
+        for (mention_uri, task) in self.mentions.values_mut() {
+            if let MentionUri::ContextServerResource {
+                server_id: mentioned_server_id,
+                uri: mentioned_uri,
+                ..
+            } = mention_uri
+                && mentioned_server_id.as_str() == server_id.0.as_ref()
+                && mentioned_uri == &uri
+            {
+                *task = reload_task
+                    .get_or_insert_with(|| {
+                        let server = server.clone();
+                        let uri = uri.clone();
+                        cx.spawn(async move |_, _| {
+                            let client = server.client().ok_or("context server not initialized")?;
+                            read_context_server_resource(&client, uri)
+                                .await
+                                .map_err(|error| error.to_string())
+                        })
+                        .shared()
+                    })
+                    .clone();
+            }
+        }
+    }
+
     pub fn confirm_mention_for_selection(
         &mut self,
         source_range: Range<text::Anchor>,
@@ -660,8 +823,8 @@ pub(crate) fn paste_images_as_context(
                     })
                     .ok();
                 mention_set
-                    .update(cx, |mention_set, _cx| {
-                        mention_set.remove_mention(&crease_id)
+                    .update(cx, |mention_set, cx| {
+                        mention_set.remove_mention(&crease_id, cx)
                     })
                     .ok();
             }
@@ -1018,6 +1181,52 @@ impl Render for ImageHover {
     }
 }
 
+fn unsubscribe_from_resource(server: &Arc<ContextServer>, uri: url::Url, cx: &mut App) {
+    let Some(client) = server.client() else {
+        return;
+    };
+    cx.spawn(async move |_| {
+        client
+            .request::<types::requests::ResourcesUnsubscribe>(types::ResourcesUnsubscribeParams {
+                uri,
+                meta: None,
+            })
+            .await
+            .log_err();
+    })
+    .detach();
+}
+
+async fn read_context_server_resource(
+    client: &InitializedContextServerProtocol,
+    uri: url::Url,
+) -> Result<Mention> {
+    let response = client
+        .request::<types::requests::ResourcesRead>(types::ResourcesReadParams { uri, meta: None })
+        .await?;
+    let mut content = String::new();
+    let mut has_text = false;
+    for contents in response.contents {
+        match contents {
+            types::ResourceContentsType::Text(text) => {
+                if has_text {
+                    content.push('\n');
+                }
+                content.push_str(&text.text);
+                has_text = true;
+            }
+            types::ResourceContentsType::Blob(blob) => {
+                log::warn!("Ignoring binary content of resource {}", blob.uri);
+            }
+        }
+    }
+    anyhow::ensure!(has_text, "The resource has no text content");
+    Ok(Mention::Text {
+        content,
+        tracked_buffers: Vec::new(),
+    })
+}
+
 async fn fetch_url_content(http_client: Arc<HttpClientWithUrl>, url: String) -> Result<String> {
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
     enum ContentType {
@@ -889,6 +889,7 @@ impl TextThreadStore {
                     _ => {}
                 }
             }
+            project::context_server_store::Event::ResourceUpdated { .. } => {}
         }
     }
 
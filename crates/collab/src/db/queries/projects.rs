@@ -1009,6 +1009,10 @@ impl Database {
                         is_last_update: true,
                         merge_message: db_repository_entry.merge_message,
                         stash_entries: Vec::new(),
+                        rebase_progress: None,
+                        sequencer_state: None,
+                        bisect_state: None,
+                        submodules: Vec::new(),
                         remote_upstream_url: db_repository_entry.remote_upstream_url.clone(),
                         remote_origin_url: db_repository_entry.remote_origin_url.clone(),
                     });
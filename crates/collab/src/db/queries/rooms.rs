@@ -796,6 +796,10 @@ impl Database {
                             is_last_update: true,
                             merge_message: db_repository.merge_message,
                             stash_entries: Vec::new(),
+                            rebase_progress: None,
+                            sequencer_state: None,
+                            bisect_state: None,
+                            submodules: Vec::new(),
                             remote_upstream_url: db_repository.remote_upstream_url.clone(),
                             remote_origin_url: db_repository.remote_origin_url.clone(),
                         });
@@ -21,7 +21,10 @@ use util::{ResultExt, TryFutureExt};
 
 use crate::{
     transport::{StdioTransport, Transport},
-    types::{CancelledParams, ClientNotification, Notification as _, notifications::Cancelled},
+    types::{
+        CancelledParams, ClientNotification, Notification as _, Request as _,
+        notifications::Cancelled, requests::Ping,
+    },
 };
 
 const JSON_RPC_VERSION: &str = "2.0";
@@ -51,6 +54,7 @@ pub(crate) struct Client {
     outbound_tx: channel::Sender<String>,
     name: Arc<str>,
     notification_handlers: Arc<Mutex<HashMap<&'static str, NotificationHandler>>>,
+    request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
     response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
     #[allow(clippy::type_complexity)]
     #[allow(dead_code)]
@@ -100,7 +104,6 @@ struct AnyResponse<'a> {
 }
 
 #[derive(Serialize, Deserialize)]
-#[allow(dead_code)]
 pub(crate) struct Response<T> {
     pub jsonrpc: &'static str,
     pub id: RequestId,
@@ -113,7 +116,6 @@ pub(crate) struct Response<T> {
 pub(crate) enum CspResult<T> {
     #[serde(rename = "result")]
     Ok(Option<T>),
-    #[allow(dead_code)]
     Error(Option<Error>),
 }
 
@@ -201,6 +203,7 @@ impl Client {
             let notification_handlers = notification_handlers.clone();
             let response_handlers = response_handlers.clone();
             let request_handlers = request_handlers.clone();
+            let outbound_tx = outbound_tx.clone();
             let transport = transport.clone();
             async move |cx| {
                 Self::handle_input(
@@ -208,6 +211,7 @@ impl Client {
                     notification_handlers,
                     request_handlers,
                     response_handlers,
+                    outbound_tx,
                     cx,
                 )
                 .log_err()
@@ -237,6 +241,7 @@ impl Client {
         Ok(Self {
             server_id,
             notification_handlers,
+            request_handlers,
             response_handlers,
             name: server_name,
             next_id: Default::default(),
@@ -255,11 +260,14 @@ impl Client {
     /// parses them as JSON-RPC responses or notifications, and dispatches them
     /// to the appropriate handlers. It processes both responses (which are matched
     /// to pending requests) and notifications (which trigger registered handlers).
+    /// Requests from the server without a registered handler are answered with an error,
+    /// except for pings, which get an empty result.
     async fn handle_input(
         transport: Arc<dyn Transport>,
         notification_handlers: Arc<Mutex<HashMap<&'static str, NotificationHandler>>>,
         request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
         response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
+        outbound_tx: channel::Sender<String>,
         cx: &mut AsyncApp,
     ) -> anyhow::Result<()> {
         let mut receiver = transport.receive();
@@ -274,6 +282,19 @@ impl Client {
                         request.params.unwrap_or(RawValue::NULL),
                         cx.clone(),
                     );
+                } else if request.method == Ping::METHOD {
+                    let response = response_message(request.id, Ok(serde_json::json!({})));
+                    outbound_tx.try_send(response).log_err();
+                } else {
+                    log::debug!("unhandled request from context server: {}", request.method);
+                    let response = response_message::<()>(
+                        request.id,
+                        Err(Error {
+                            message: format!("Method not found: {}", request.method),
+                            code: METHOD_NOT_FOUND,
+                        }),
+                    );
+                    outbound_tx.try_send(response).log_err();
                 }
             } else if let Ok(response) = serde_json::from_str::<AnyResponse>(&message) {
                 if let Some(handlers) = response_handlers.lock().as_mut()
@@ -458,6 +479,64 @@ impl Client {
     ) {
         self.notification_handlers.lock().insert(method, f);
     }
+
+    /// Registers a handler for requests sent by the context server, replying with
+    /// the result of the future it returns.
+    pub fn on_request<P, R, F, Fut>(&self, method: &'static str, mut f: F)
+    where
+        P: DeserializeOwned,
+        R: Serialize + 'static,
+        F: 'static + Send + FnMut(P, AsyncApp) -> Fut,
+        Fut: 'static + Future<Output = Result<R>>,
+    {
+        let outbound_tx = self.outbound_tx.clone();
+        self.request_handlers.lock().insert(
+            method,
+            Box::new(
+                move |id, params, cx| match serde_json::from_str::<P>(params.get()) {
+                    Ok(params) => {
+                        let response = f(params, cx.clone());
+                        let outbound_tx = outbound_tx.clone();
+                        cx.foreground_executor()
+                            .spawn(async move {
+                                let response = response_message(
+                                    id,
+                                    response.await.map_err(|error| Error {
+                                        message: error.to_string(),
+                                        code: INTERNAL_ERROR,
+                                    }),
+                                );
+                                outbound_tx.try_send(response).log_err();
+                            })
+                            .detach();
+                    }
+                    Err(error) => {
+                        let response = response_message::<()>(
+                            id,
+                            Err(Error {
+                                message: error.to_string(),
+                                code: INVALID_PARAMS,
+                            }),
+                        );
+                        outbound_tx.try_send(response).log_err();
+                    }
+                },
+            ),
+        );
+    }
+}
+
+fn response_message<T: Serialize>(id: RequestId, result: Result<T, Error>) -> String {
+    let value = match result {
+        Ok(result) => CspResult::Ok(Some(result)),
+        Err(error) => CspResult::Error(Some(error)),
+    };
+    serde_json::to_string(&Response {
+        jsonrpc: JSON_RPC_VERSION,
+        id,
+        value,
+    })
+    .unwrap()
 }
 
 #[derive(Debug)]
@@ -1,5 +1,6 @@
 pub mod client;
 pub mod listener;
+pub mod oauth;
 pub mod protocol;
 #[cfg(any(test, feature = "test-support"))]
 pub mod test;
@@ -9,17 +10,21 @@ pub mod types;
 use collections::HashMap;
 use http_client::HttpClient;
 use std::path::Path;
+use std::rc::Rc;
 use std::sync::Arc;
 use std::{fmt::Display, path::PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use client::Client;
-use gpui::AsyncApp;
+use credentials_provider::CredentialsProvider;
+use gpui::{App, AsyncApp, Global, Task};
 use parking_lot::RwLock;
 pub use settings::ContextServerCommand;
 use url::Url;
 
+use crate::oauth::OAuthHandle;
 use crate::transport::HttpTransport;
+use crate::types::Request as _;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ContextServerId(pub Arc<str>);
@@ -30,6 +35,43 @@ impl Display for ContextServerId {
     }
 }
 
+/// Answers the requests context servers send to the client, such as sampling a
+/// completion from a language model or asking the user for input.
+pub trait ContextServerClientDelegate: 'static {
+    fn create_message(
+        &self,
+        server_id: ContextServerId,
+        params: types::CreateMessageRequest,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<types::CreateMessageResult>>;
+
+    fn elicit(
+        &self,
+        server_id: ContextServerId,
+        params: types::ElicitParams,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<types::ElicitResult>>;
+}
+
+struct GlobalContextServerClientDelegate(Rc<dyn ContextServerClientDelegate>);
+
+impl Global for GlobalContextServerClientDelegate {}
+
+/// Sets the delegate answering sampling and elicitation requests. Servers started
+/// before a delegate is set don't advertise these capabilities.
+pub fn set_client_delegate(delegate: Rc<dyn ContextServerClientDelegate>, cx: &mut App) {
+    cx.set_global(GlobalContextServerClientDelegate(delegate));
+}
+
+fn client_delegate(cx: &AsyncApp) -> Option<Rc<dyn ContextServerClientDelegate>> {
+    cx.update(|cx| {
+        cx.try_global::<GlobalContextServerClientDelegate>()
+            .map(|delegate| delegate.0.clone())
+    })
+    .ok()
+    .flatten()
+}
+
 enum ContextServerTransport {
     Stdio(ContextServerCommand, Option<PathBuf>),
     Custom(Arc<dyn crate::transport::Transport>),
@@ -62,13 +104,29 @@ impl ContextServer {
         endpoint: &Url,
         headers: HashMap<String, String>,
         http_client: Arc<dyn HttpClient>,
-        executor: gpui::BackgroundExecutor,
+        cx: &App,
     ) -> Result<Self> {
         let transport = match endpoint.scheme() {
             "http" | "https" => {
                 log::info!("Using HTTP transport for {}", endpoint);
-                let transport =
-                    HttpTransport::new(http_client, endpoint.to_string(), headers, executor);
+                // Servers configured with their own credentials don't need to be authorized
+                let uses_oauth = !headers
+                    .keys()
+                    .any(|key| key.eq_ignore_ascii_case("authorization"));
+                let mut transport = HttpTransport::new(
+                    http_client.clone(),
+                    endpoint.to_string(),
+                    headers,
+                    cx.background_executor().clone(),
+                );
+                if uses_oauth {
+                    transport = transport.with_oauth(OAuthHandle::new(
+                        endpoint.clone(),
+                        http_client,
+                        <dyn CredentialsProvider>::global(cx),
+                        cx,
+                    ));
+                }
                 Arc::new(transport) as _
             }
             _ => anyhow::bail!("unsupported MCP url scheme {}", endpoint.scheme()),
@@ -93,7 +151,7 @@ impl ContextServer {
     }
 
     pub async fn start(&self, cx: &AsyncApp) -> Result<()> {
-        self.initialize(self.new_client(cx)?).await
+        self.initialize(self.new_client(cx)?, cx).await
     }
 
     /// Starts the context server, making sure handlers are registered before initialization happens
@@ -109,7 +167,7 @@ impl ContextServer {
         for (method, handler) in notification_handlers {
             client.on_notification(method, handler);
         }
-        self.initialize(client).await
+        self.initialize(client, cx).await
     }
 
     fn new_client(&self, cx: &AsyncApp) -> Result<Client> {
@@ -135,14 +193,15 @@ impl ContextServer {
         })
     }
 
-    async fn initialize(&self, client: Client) -> Result<()> {
+    async fn initialize(&self, client: Client, cx: &AsyncApp) -> Result<()> {
         log::debug!("starting context server {}", self.id);
+        let capabilities = self.register_client_request_handlers(&client, cx);
         let protocol = crate::protocol::ModelContextProtocol::new(client);
         let client_info = types::Implementation {
             name: "Zed".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
         };
-        let initialized_protocol = protocol.initialize(client_info).await?;
+        let initialized_protocol = protocol.initialize(client_info, capabilities).await?;
 
         log::debug!(
             "context server {} initialized: {:?}",
@@ -154,6 +213,48 @@ impl ContextServer {
         Ok(())
     }
 
+    /// Handles sampling and elicitation requests through the client delegate, if any,
+    /// returning the capabilities to advertise to the server.
+    fn register_client_request_handlers(
+        &self,
+        client: &Client,
+        cx: &AsyncApp,
+    ) -> types::ClientCapabilities {
+        let mut capabilities = types::ClientCapabilities {
+            experimental: None,
+            sampling: None,
+            roots: None,
+            elicitation: None,
+        };
+        if client_delegate(cx).is_none() {
+            return capabilities;
+        }
+
+        client.on_request(types::requests::CreateMessage::METHOD, {
+            let server_id = self.id();
+            move |params: types::CreateMessageRequest, mut cx| {
+                let server_id = server_id.clone();
+                async move {
+                    let delegate = client_delegate(&cx).context("sampling is not supported")?;
+                    delegate.create_message(server_id, params, &mut cx).await
+                }
+            }
+        });
+        client.on_request(types::requests::Elicit::METHOD, {
+            let server_id = self.id();
+            move |params: types::ElicitParams, mut cx| {
+                let server_id = server_id.clone();
+                async move {
+                    let delegate = client_delegate(&cx).context("elicitation is not supported")?;
+                    delegate.elicit(server_id, params, &mut cx).await
+                }
+            }
+        });
+        capabilities.sampling = Some(serde_json::json!({}));
+        capabilities.elicitation = Some(serde_json::json!({}));
+        capabilities
+    }
+
     pub fn stop(&self) -> Result<()> {
         let mut client = self.client.write();
         if let Some(protocol) = client.take() {
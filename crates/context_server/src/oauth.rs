@@ -0,0 +1,891 @@
+//! Authorizes requests to HTTP context servers with the MCP OAuth flow.
+//!
+//! When a server rejects a request as unauthorized, its protected resource metadata
+//! names the authorization server to use. Zed registers itself there as a client,
+//! opens the authorization page in the browser and receives the authorization code
+//! on a loopback redirect, exchanging it for tokens with PKCE. The tokens are kept
+//! in the credentials provider and refreshed once the server rejects them.
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context as _, Result, anyhow};
+use base64::prelude::*;
+use collections::HashMap;
+use credentials_provider::CredentialsProvider;
+use futures::{AsyncReadExt as _, AsyncWriteExt as _, channel::oneshot};
+use gpui::{App, AsyncApp};
+use http_client::{AsyncBody, HttpClient, Request, Response, http::Method};
+use rand::Rng as _;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest as _, Sha256};
+use smol::{channel, net::TcpListener};
+use url::Url;
+use util::ResultExt as _;
+
+const CLIENT_NAME: &str = "Zed";
+const REDIRECT_PATH: &str = "/callback";
+const AUTHORIZATION_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// Tokens expiring within this margin are refreshed before they are used.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+/// A handle to the task authorizing requests to one context server.
+///
+/// The task runs on the foreground, as it reads and writes the credentials provider
+/// and opens the browser, while the handle can be used from the transport.
+#[derive(Clone)]
+pub struct OAuthHandle {
+    requests_tx: channel::Sender<AuthRequest>,
+}
+
+enum AuthRequest {
+    AccessToken(oneshot::Sender<Option<String>>),
+    Reauthorize {
+        www_authenticate: Option<String>,
+        rejected_token: Option<String>,
+        result_tx: oneshot::Sender<Result<String>>,
+    },
+}
+
+impl OAuthHandle {
+    pub fn new(
+        server_url: Url,
+        http_client: Arc<dyn HttpClient>,
+        credentials_provider: Arc<dyn CredentialsProvider>,
+        cx: &App,
+    ) -> Self {
+        let (requests_tx, requests_rx) = channel::unbounded();
+        let authorizer = Authorizer {
+            server_url,
+            http_client,
+            credentials_provider,
+            credentials: None,
+            credentials_loaded: false,
+        };
+        cx.spawn(async move |cx| authorizer.run(requests_rx, cx).await)
+            .detach();
+        Self { requests_tx }
+    }
+
+    /// Returns the access token to send, if the server was authorized before.
+    pub async fn access_token(&self) -> Option<String> {
+        let (token_tx, token_rx) = oneshot::channel();
+        self.requests_tx
+            .send(AuthRequest::AccessToken(token_tx))
+            .await
+            .ok()?;
+        token_rx.await.ok().flatten()
+    }
+
+    /// Returns a new access token after the server rejected a request, refreshing
+    /// the rejected token or going through the authorization flow again.
+    ///
+    /// `www_authenticate` is the `WWW-Authenticate` header of the rejected response.
+    pub async fn reauthorize(
+        &self,
+        www_authenticate: Option<String>,
+        rejected_token: Option<String>,
+    ) -> Result<String> {
+        let (result_tx, result_rx) = oneshot::channel();
+        self.requests_tx
+            .send(AuthRequest::Reauthorize {
+                www_authenticate,
+                rejected_token,
+                result_tx,
+            })
+            .await
+            .map_err(|_| anyhow!("context server authorization stopped"))?;
+        result_rx.await?
+    }
+}
+
+/// The client registration and tokens for a server, as kept in the credentials provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct OAuthCredentials {
+    client_id: String,
+    client_secret: Option<String>,
+    token_endpoint: Url,
+    access_token: String,
+    refresh_token: Option<String>,
+    /// When the access token expires, in seconds since the Unix epoch.
+    expires_at: Option<u64>,
+}
+
+impl OAuthCredentials {
+    fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| {
+            UNIX_EPOCH + Duration::from_secs(expires_at) <= SystemTime::now() + EXPIRY_MARGIN
+        })
+    }
+}
+
+/// See [RFC 9728](https://datatracker.ietf.org/doc/html/rfc9728).
+#[derive(Debug, Deserialize)]
+struct ProtectedResourceMetadata {
+    #[serde(default)]
+    authorization_servers: Vec<Url>,
+    #[serde(default)]
+    scopes_supported: Vec<String>,
+}
+
+/// See [RFC 8414](https://datatracker.ietf.org/doc/html/rfc8414).
+#[derive(Debug, Deserialize)]
+struct AuthorizationServerMetadata {
+    authorization_endpoint: Url,
+    token_endpoint: Url,
+    #[serde(default)]
+    registration_endpoint: Option<Url>,
+    #[serde(default)]
+    code_challenge_methods_supported: Vec<String>,
+}
+
+impl AuthorizationServerMetadata {
+    /// The default endpoints servers without metadata use, as of the 2025-03-26 revision of MCP.
+    fn default_for(issuer: &Url) -> Result<Self> {
+        Ok(Self {
+            authorization_endpoint: issuer.join("/authorize")?,
+            token_endpoint: issuer.join("/token")?,
+            registration_endpoint: Some(issuer.join("/register")?),
+            code_challenge_methods_supported: Vec::new(),
+        })
+    }
+}
+
+/// See [RFC 7591](https://datatracker.ietf.org/doc/html/rfc7591).
+#[derive(Debug, Serialize)]
+struct ClientRegistrationRequest<'a> {
+    client_name: &'a str,
+    redirect_uris: [&'a str; 1],
+    grant_types: [&'a str; 2],
+    response_types: [&'a str; 1],
+    token_endpoint_auth_method: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClientRegistration {
+    client_id: String,
+    #[serde(default)]
+    client_secret: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+struct Authorizer {
+    server_url: Url,
+    http_client: Arc<dyn HttpClient>,
+    credentials_provider: Arc<dyn CredentialsProvider>,
+    credentials: Option<OAuthCredentials>,
+    credentials_loaded: bool,
+}
+
+impl Authorizer {
+    async fn run(mut self, requests_rx: channel::Receiver<AuthRequest>, cx: &mut AsyncApp) {
+        while let Ok(request) = requests_rx.recv().await {
+            match request {
+                AuthRequest::AccessToken(token_tx) => {
+                    let token = self.access_token(cx).await;
+                    token_tx.send(token).ok();
+                }
+                AuthRequest::Reauthorize {
+                    www_authenticate,
+                    rejected_token,
+                    result_tx,
+                } => {
+                    let result = self
+                        .reauthorize(www_authenticate.as_deref(), rejected_token.as_deref(), cx)
+                        .await;
+                    result_tx.send(result).ok();
+                }
+            }
+        }
+    }
+
+    async fn load_credentials(&mut self, cx: &AsyncApp) {
+        if self.credentials_loaded {
+            return;
+        }
+        self.credentials_loaded = true;
+        self.credentials = self
+            .credentials_provider
+            .read_credentials(self.server_url.as_str(), cx)
+            .await
+            .log_err()
+            .flatten()
+            .and_then(|(_, credentials)| serde_json::from_slice(&credentials).log_err());
+    }
+
+    async fn save_credentials(&mut self, credentials: OAuthCredentials, cx: &AsyncApp) -> String {
+        if let Some(serialized) = serde_json::to_vec(&credentials).log_err() {
+            self.credentials_provider
+                .write_credentials(
+                    self.server_url.as_str(),
+                    &credentials.client_id,
+                    &serialized,
+                    cx,
+                )
+                .await
+                .log_err();
+        }
+        let access_token = credentials.access_token.clone();
+        self.credentials = Some(credentials);
+        access_token
+    }
+
+    async fn access_token(&mut self, cx: &AsyncApp) -> Option<String> {
+        self.load_credentials(cx).await;
+        let credentials = self.credentials.clone()?;
+        if credentials.is_expired()
+            && credentials.refresh_token.is_some()
+            && let Some(refreshed) = self.refresh(&credentials).await.log_err()
+        {
+            return Some(self.save_credentials(refreshed, cx).await);
+        }
+        Some(credentials.access_token)
+    }
+
+    async fn reauthorize(
+        &mut self,
+        www_authenticate: Option<&str>,
+        rejected_token: Option<&str>,
+        cx: &mut AsyncApp,
+    ) -> Result<String> {
+        self.load_credentials(cx).await;
+        if let Some(credentials) = self.credentials.clone() {
+            // Another request may have been rejected and authorized again in the meantime.
+            if rejected_token != Some(credentials.access_token.as_str()) {
+                return Ok(credentials.access_token);
+            }
+            if credentials.refresh_token.is_some()
+                && let Some(refreshed) = self.refresh(&credentials).await.log_err()
+            {
+                return Ok(self.save_credentials(refreshed, cx).await);
+            }
+        }
+
+        let credentials = self.authorize(www_authenticate, cx).await?;
+        Ok(self.save_credentials(credentials, cx).await)
+    }
+
+    async fn refresh(&self, credentials: &OAuthCredentials) -> Result<OAuthCredentials> {
+        let refresh_token = credentials
+            .refresh_token
+            .as_deref()
+            .context("no refresh token")?;
+        let mut params = vec![
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", credentials.client_id.as_str()),
+            ("resource", self.server_url.as_str()),
+        ];
+        if let Some(client_secret) = &credentials.client_secret {
+            params.push(("client_secret", client_secret.as_str()));
+        }
+        let response =
+            request_token(&*self.http_client, &credentials.token_endpoint, &params).await?;
+        Ok(OAuthCredentials {
+            access_token: response.access_token,
+            refresh_token: response
+                .refresh_token
+                .or_else(|| credentials.refresh_token.clone()),
+            expires_at: expires_at(response.expires_in),
+            ..credentials.clone()
+        })
+    }
+
+    async fn authorize(
+        &self,
+        www_authenticate: Option<&str>,
+        cx: &mut AsyncApp,
+    ) -> Result<OAuthCredentials> {
+        let (metadata, scopes) = self.discover(www_authenticate).await?;
+        anyhow::ensure!(
+            metadata.code_challenge_methods_supported.is_empty()
+                || metadata
+                    .code_challenge_methods_supported
+                    .iter()
+                    .any(|method| method == "S256"),
+            "the authorization server of {} doesn't support PKCE",
+            self.server_url
+        );
+
+        let redirect = LoopbackRedirect::bind().await?;
+        let registration = self.register(&metadata, &redirect.uri).await?;
+        let code_verifier = random_string();
+        let state = random_string();
+
+        let mut authorization_url = metadata.authorization_endpoint.clone();
+        {
+            let mut query = authorization_url.query_pairs_mut();
+            query
+                .append_pair("response_type", "code")
+                .append_pair("client_id", &registration.client_id)
+                .append_pair("redirect_uri", &redirect.uri)
+                .append_pair("code_challenge", &code_challenge(&code_verifier))
+                .append_pair("code_challenge_method", "S256")
+                .append_pair("state", &state)
+                .append_pair("resource", self.server_url.as_str());
+            if !scopes.is_empty() {
+                query.append_pair("scope", &scopes.join(" "));
+            }
+        }
+        log::info!("authorizing context server {}", self.server_url);
+        cx.update(|cx| cx.open_url(authorization_url.as_str()))?;
+
+        let timeout = cx.background_executor().timer(AUTHORIZATION_TIMEOUT);
+        let code = smol::future::or(redirect.receive_code(&state), async {
+            timeout.await;
+            Err(anyhow!("timed out waiting for authorization"))
+        })
+        .await?;
+
+        let mut params = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", redirect.uri.as_str()),
+            ("client_id", registration.client_id.as_str()),
+            ("code_verifier", code_verifier.as_str()),
+            ("resource", self.server_url.as_str()),
+        ];
+        if let Some(client_secret) = &registration.client_secret {
+            params.push(("client_secret", client_secret.as_str()));
+        }
+        let response = request_token(&*self.http_client, &metadata.token_endpoint, &params).await?;
+        Ok(OAuthCredentials {
+            client_id: registration.client_id,
+            client_secret: registration.client_secret,
+            token_endpoint: metadata.token_endpoint,
+            access_token: response.access_token,
+            refresh_token: response.refresh_token,
+            expires_at: expires_at(response.expires_in),
+        })
+    }
+
+    /// Finds the authorization server of the context server and the scopes to request.
+    async fn discover(
+        &self,
+        www_authenticate: Option<&str>,
+    ) -> Result<(AuthorizationServerMetadata, Vec<String>)> {
+        let resource_metadata_urls = match www_authenticate.and_then(resource_metadata_url) {
+            Some(url) => vec![url],
+            None => well_known_urls(&self.server_url, &["oauth-protected-resource"]),
+        };
+        let mut resource_metadata = None;
+        for url in resource_metadata_urls {
+            match get_json::<ProtectedResourceMetadata>(&*self.http_client, &url).await {
+                Ok(metadata) => {
+                    resource_metadata = Some(metadata);
+                    break;
+                }
+                Err(error) => log::debug!("no protected resource metadata at {url}: {error:#}"),
+            }
+        }
+
+        // Servers predating protected resource metadata are their own authorization server.
+        let (issuer, scopes) = match resource_metadata {
+            Some(metadata) => (
+                metadata
+                    .authorization_servers
+                    .into_iter()
+                    .next()
+                    .context("the server doesn't name an authorization server")?,
+                metadata.scopes_supported,
+            ),
+            None => (self.server_url.join("/")?, Vec::new()),
+        };
+
+        for url in well_known_urls(
+            &issuer,
+            &["oauth-authorization-server", "openid-configuration"],
+        ) {
+            match get_json::<AuthorizationServerMetadata>(&*self.http_client, &url).await {
+                Ok(metadata) => return Ok((metadata, scopes)),
+                Err(error) => {
+                    log::debug!("no authorization server metadata at {url}: {error:#}")
+                }
+            }
+        }
+        Ok((AuthorizationServerMetadata::default_for(&issuer)?, scopes))
+    }
+
+    async fn register(
+        &self,
+        metadata: &AuthorizationServerMetadata,
+        redirect_uri: &str,
+    ) -> Result<ClientRegistration> {
+        let registration_endpoint = metadata
+            .registration_endpoint
+            .as_ref()
+            .context("the authorization server doesn't support dynamic client registration")?;
+        let body = serde_json::to_vec(&ClientRegistrationRequest {
+            client_name: CLIENT_NAME,
+            redirect_uris: [redirect_uri],
+            grant_types: ["authorization_code", "refresh_token"],
+            response_types: ["code"],
+            token_endpoint_auth_method: "none",
+        })?;
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(registration_endpoint.as_str())
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json")
+            .body(AsyncBody::from(body))?;
+        let mut response = self.http_client.send(request).await?;
+        read_json(registration_endpoint, &mut response).await
+    }
+}
+
+/// Listens on a local port for the browser to be redirected back with the authorization code.
+struct LoopbackRedirect {
+    listener: TcpListener,
+    uri: String,
+}
+
+impl LoopbackRedirect {
+    async fn bind() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        Ok(Self {
+            listener,
+            uri: format!("http://127.0.0.1:{port}{REDIRECT_PATH}"),
+        })
+    }
+
+    async fn receive_code(&self, state: &str) -> Result<String> {
+        loop {
+            let (mut stream, _) = self.listener.accept().await?;
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).await?;
+                if read == 0 || request.len() > 16 * 1024 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+
+            let request = String::from_utf8_lossy(&request);
+            let Some(url) = request
+                .lines()
+                .next()
+                .and_then(|line| line.split(' ').nth(1))
+                .and_then(|target| Url::parse(&format!("http://127.0.0.1{target}")).ok())
+                .filter(|url| url.path() == REDIRECT_PATH)
+            else {
+                stream
+                    .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                    .await
+                    .log_err();
+                continue;
+            };
+
+            let code = authorization_code(&url, state);
+            let body = match &code {
+                Ok(_) => "Zed is now authorized. You can close this page.".to_string(),
+                Err(error) => format!("Authorization failed: {error}"),
+            };
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.log_err();
+            return code;
+        }
+    }
+}
+
+fn authorization_code(redirect_url: &Url, state: &str) -> Result<String> {
+    let params = redirect_url.query_pairs().collect::<HashMap<_, _>>();
+    if let Some(error) = params.get("error") {
+        match params.get("error_description") {
+            Some(description) => anyhow::bail!("{error}: {description}"),
+            None => anyhow::bail!("{error}"),
+        }
+    }
+    anyhow::ensure!(
+        params.get("state").map(|param| param.as_ref()) == Some(state),
+        "the authorization response doesn't match the request"
+    );
+    params
+        .get("code")
+        .map(|code| code.to_string())
+        .context("the authorization response has no code")
+}
+
+/// Extracts the `resource_metadata` parameter of a `WWW-Authenticate` header.
+fn resource_metadata_url(www_authenticate: &str) -> Option<Url> {
+    let (_, value) = www_authenticate.split_once("resource_metadata=")?;
+    let value = match value.strip_prefix('"') {
+        Some(value) => value.split('"').next()?,
+        None => value.split([',', ' ']).next()?,
+    };
+    Url::parse(value).ok()
+}
+
+/// Returns the well-known metadata URLs for a URL, trying the ones with the URL's path
+/// inserted after the well-known suffix before the ones without it.
+fn well_known_urls(url: &Url, suffixes: &[&str]) -> Vec<Url> {
+    let path = url.path().trim_end_matches('/');
+    let mut paths = Vec::new();
+    if !path.is_empty() {
+        paths.extend(
+            suffixes
+                .iter()
+                .map(|suffix| format!("/.well-known/{suffix}{path}")),
+        );
+    }
+    paths.extend(
+        suffixes
+            .iter()
+            .map(|suffix| format!("/.well-known/{suffix}")),
+    );
+    paths
+        .into_iter()
+        .filter_map(|path| url.join(&path).ok())
+        .collect()
+}
+
+async fn get_json<T: DeserializeOwned>(http_client: &dyn HttpClient, url: &Url) -> Result<T> {
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri(url.as_str())
+        .header("Accept", "application/json")
+        .body(AsyncBody::empty())?;
+    let mut response = http_client.send(request).await?;
+    read_json(url, &mut response).await
+}
+
+async fn request_token(
+    http_client: &dyn HttpClient,
+    token_endpoint: &Url,
+    params: &[(&str, &str)],
+) -> Result<TokenResponse> {
+    let body = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(token_endpoint.as_str())
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .header("Accept", "application/json")
+        .body(AsyncBody::from(body))?;
+    let mut response = http_client.send(request).await?;
+    read_json(token_endpoint, &mut response).await
+}
+
+async fn read_json<T: DeserializeOwned>(
+    url: &Url,
+    response: &mut Response<AsyncBody>,
+) -> Result<T> {
+    let mut body = String::new();
+    response.body_mut().read_to_string(&mut body).await?;
+    anyhow::ensure!(
+        response.status().is_success(),
+        "{url} responded with {}: {body}",
+        response.status()
+    );
+    serde_json::from_str(&body).with_context(|| format!("invalid response from {url}"))
+}
+
+fn expires_at(expires_in: Option<u64>) -> Option<u64> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+    Some(now.as_secs() + expires_in?)
+}
+
+fn random_string() -> String {
+    let mut bytes = [0; 32];
+    rand::rng().fill(&mut bytes);
+    BASE64_URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// The S256 PKCE challenge for a verifier.
+fn code_challenge(code_verifier: &str) -> String {
+    BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::Future,
+        io::{Read as _, Write as _},
+        pin::Pin,
+    };
+
+    use gpui::TestAppContext;
+    use http_client::FakeHttpClient;
+    use parking_lot::Mutex;
+    use serde_json::json;
+
+    use super::*;
+
+    const SERVER_URL: &str = "https://mcp.example.com/mcp";
+    const AUTHORIZATION_SERVER_URL: &str = "https://auth.example.com";
+
+    #[derive(Default)]
+    struct FakeCredentialsProvider {
+        credentials: Mutex<HashMap<String, (String, Vec<u8>)>>,
+    }
+
+    impl CredentialsProvider for FakeCredentialsProvider {
+        fn read_credentials<'a>(
+            &'a self,
+            url: &'a str,
+            _cx: &'a AsyncApp,
+        ) -> Pin<Box<dyn Future<Output = Result<Option<(String, Vec<u8>)>>> + 'a>> {
+            Box::pin(async move { Ok(self.credentials.lock().get(url).cloned()) })
+        }
+
+        fn write_credentials<'a>(
+            &'a self,
+            url: &'a str,
+            username: &'a str,
+            password: &'a [u8],
+            _cx: &'a AsyncApp,
+        ) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>> {
+            Box::pin(async move {
+                self.credentials
+                    .lock()
+                    .insert(url.to_string(), (username.to_string(), password.to_vec()));
+                Ok(())
+            })
+        }
+
+        fn delete_credentials<'a>(
+            &'a self,
+            url: &'a str,
+            _cx: &'a AsyncApp,
+        ) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>> {
+            Box::pin(async move {
+                self.credentials.lock().remove(url);
+                Ok(())
+            })
+        }
+    }
+
+    #[derive(Default)]
+    struct AuthorizationServerState {
+        code_challenge: Option<String>,
+        registered_redirect_uri: Option<String>,
+        token_requests: Vec<HashMap<String, String>>,
+    }
+
+    fn json_response(status: u16, body: serde_json::Value) -> Response<AsyncBody> {
+        Response::builder()
+            .status(status)
+            .header("Content-Type", "application/json")
+            .body(AsyncBody::from(body.to_string()))
+            .unwrap()
+    }
+
+    /// Serves the metadata, registration and token endpoints of a mock authorization server.
+    fn fake_authorization_server(
+        state: Arc<Mutex<AuthorizationServerState>>,
+    ) -> Arc<dyn HttpClient> {
+        FakeHttpClient::create(move |request| {
+            let state = state.clone();
+            async move {
+                let uri = request.uri().to_string();
+                let mut body = String::new();
+                request.into_body().read_to_string(&mut body).await?;
+                let form = url::form_urlencoded::parse(body.as_bytes())
+                    .into_owned()
+                    .collect::<HashMap<_, _>>();
+
+                Ok(match uri.as_str() {
+                    "https://mcp.example.com/.well-known/oauth-protected-resource/mcp" => {
+                        json_response(
+                            200,
+                            json!({
+                                "resource": SERVER_URL,
+                                "authorization_servers": [AUTHORIZATION_SERVER_URL],
+                                "scopes_supported": ["read", "write"],
+                            }),
+                        )
+                    }
+                    "https://auth.example.com/.well-known/oauth-authorization-server" => {
+                        json_response(
+                            200,
+                            json!({
+                                "issuer": AUTHORIZATION_SERVER_URL,
+                                "authorization_endpoint": "https://auth.example.com/authorize",
+                                "token_endpoint": "https://auth.example.com/token",
+                                "registration_endpoint": "https://auth.example.com/register",
+                                "code_challenge_methods_supported": ["S256"],
+                            }),
+                        )
+                    }
+                    "https://auth.example.com/register" => {
+                        let registration: serde_json::Value = serde_json::from_str(&body)?;
+                        state.lock().registered_redirect_uri = registration["redirect_uris"][0]
+                            .as_str()
+                            .map(|uri| uri.to_string());
+                        json_response(201, json!({ "client_id": "zed-client" }))
+                    }
+                    "https://auth.example.com/token" => {
+                        let mut state = state.lock();
+                        state.token_requests.push(form.clone());
+                        match form["grant_type"].as_str() {
+                            "authorization_code"
+                                if form["code"] == "auth-code"
+                                    && Some(code_challenge(&form["code_verifier"]))
+                                        == state.code_challenge =>
+                            {
+                                json_response(
+                                    200,
+                                    json!({
+                                        "access_token": "access-1",
+                                        "refresh_token": "refresh-1",
+                                        "expires_in": 3600,
+                                    }),
+                                )
+                            }
+                            "refresh_token" if form["refresh_token"] == "refresh-1" => {
+                                json_response(200, json!({ "access_token": "access-2" }))
+                            }
+                            _ => json_response(400, json!({ "error": "invalid_grant" })),
+                        }
+                    }
+                    _ => json_response(404, json!({})),
+                })
+            }
+        })
+    }
+
+    #[gpui::test]
+    async fn test_oauth_authorization_and_refresh(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+        let state = Arc::new(Mutex::new(AuthorizationServerState::default()));
+        let credentials_provider = Arc::new(FakeCredentialsProvider::default());
+        let oauth = cx.update(|cx| {
+            OAuthHandle::new(
+                Url::parse(SERVER_URL).unwrap(),
+                fake_authorization_server(state.clone()),
+                credentials_provider.clone(),
+                cx,
+            )
+        });
+        assert_eq!(oauth.access_token().await, None);
+
+        let authorization = cx.executor().spawn({
+            let oauth = oauth.clone();
+            async move {
+                oauth
+                    .reauthorize(Some("Bearer error=\"invalid_token\"".to_string()), None)
+                    .await
+            }
+        });
+
+        // Binding the loopback listener happens off the test executor.
+        let authorization_url = loop {
+            cx.run_until_parked();
+            if let Some(url) = cx.opened_url() {
+                break Url::parse(&url).unwrap();
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(authorization_url.path(), "/authorize");
+        let params = authorization_url
+            .query_pairs()
+            .into_owned()
+            .collect::<HashMap<_, _>>();
+        assert_eq!(params["client_id"], "zed-client");
+        assert_eq!(params["code_challenge_method"], "S256");
+        assert_eq!(params["resource"], SERVER_URL);
+        assert_eq!(params["scope"], "read write");
+        assert_eq!(
+            state.lock().registered_redirect_uri.as_ref(),
+            Some(&params["redirect_uri"])
+        );
+        state.lock().code_challenge = Some(params["code_challenge"].clone());
+
+        // Act as the browser being redirected back after the user approved.
+        let redirect_uri = Url::parse(&params["redirect_uri"]).unwrap();
+        let mut browser =
+            std::net::TcpStream::connect(("127.0.0.1", redirect_uri.port().unwrap())).unwrap();
+        write!(
+            browser,
+            "GET {}?code=auth-code&state={} HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n",
+            redirect_uri.path(),
+            params["state"]
+        )
+        .unwrap();
+
+        assert_eq!(authorization.await.unwrap(), "access-1");
+        let mut page = String::new();
+        browser.read_to_string(&mut page).unwrap();
+        assert!(page.starts_with("HTTP/1.1 200 OK"), "{page}");
+
+        let (client_id, stored) = credentials_provider.credentials.lock()[SERVER_URL].clone();
+        let stored: OAuthCredentials = serde_json::from_slice(&stored).unwrap();
+        assert_eq!(client_id, "zed-client");
+        assert_eq!(stored.access_token, "access-1");
+        assert_eq!(stored.refresh_token.as_deref(), Some("refresh-1"));
+        assert_eq!(oauth.access_token().await.as_deref(), Some("access-1"));
+
+        // A rejected token is refreshed, keeping the refresh token.
+        assert_eq!(
+            oauth
+                .reauthorize(None, Some("access-1".to_string()))
+                .await
+                .unwrap(),
+            "access-2"
+        );
+        // Requests rejected with the token that was already replaced use the new one.
+        assert_eq!(
+            oauth
+                .reauthorize(None, Some("access-1".to_string()))
+                .await
+                .unwrap(),
+            "access-2"
+        );
+        let token_requests = state.lock().token_requests.clone();
+        assert_eq!(token_requests.len(), 2);
+        assert_eq!(token_requests[1]["grant_type"], "refresh_token");
+        assert_eq!(token_requests[1]["client_id"], "zed-client");
+        let (_, stored) = credentials_provider.credentials.lock()[SERVER_URL].clone();
+        let stored: OAuthCredentials = serde_json::from_slice(&stored).unwrap();
+        assert_eq!(stored.access_token, "access-2");
+        assert_eq!(stored.refresh_token.as_deref(), Some("refresh-1"));
+    }
+
+    #[test]
+    fn test_resource_metadata_url() {
+        assert_eq!(
+            resource_metadata_url(
+                r#"Bearer resource_metadata="https://mcp.example.com/.well-known/oauth-protected-resource", scope="read""#
+            )
+            .unwrap()
+            .as_str(),
+            "https://mcp.example.com/.well-known/oauth-protected-resource"
+        );
+        assert_eq!(
+            resource_metadata_url(r#"Bearer error="invalid_token""#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_well_known_urls() {
+        let urls = well_known_urls(
+            &Url::parse("https://auth.example.com/tenant/").unwrap(),
+            &["oauth-authorization-server", "openid-configuration"],
+        );
+        assert_eq!(
+            urls.iter().map(|url| url.as_str()).collect::<Vec<_>>(),
+            [
+                "https://auth.example.com/.well-known/oauth-authorization-server/tenant",
+                "https://auth.example.com/.well-known/openid-configuration/tenant",
+                "https://auth.example.com/.well-known/oauth-authorization-server",
+                "https://auth.example.com/.well-known/openid-configuration",
+            ]
+        );
+    }
+}
@@ -27,6 +27,7 @@ impl ModelContextProtocol {
     fn supported_protocols() -> Vec<types::ProtocolVersion> {
         vec![
             types::ProtocolVersion(types::LATEST_PROTOCOL_VERSION.to_string()),
+            types::ProtocolVersion(types::VERSION_2025_03_26.to_string()),
             types::ProtocolVersion(types::VERSION_2024_11_05.to_string()),
         ]
     }
@@ -34,14 +35,11 @@ impl ModelContextProtocol {
     pub async fn initialize(
         self,
         client_info: types::Implementation,
+        capabilities: types::ClientCapabilities,
     ) -> Result<InitializedContextServerProtocol> {
         let params = types::InitializeParams {
             protocol_version: types::ProtocolVersion(types::LATEST_PROTOCOL_VERSION.to_string()),
-            capabilities: types::ClientCapabilities {
-                experimental: None,
-                sampling: None,
-                roots: None,
-            },
+            capabilities,
             meta: None,
             client_info,
         };
@@ -79,6 +77,7 @@ pub struct InitializedContextServerProtocol {
 pub enum ServerCapability {
     Experimental,
     Logging,
+    Completions,
     Prompts,
     Resources,
     Tools,
@@ -90,6 +89,7 @@ impl InitializedContextServerProtocol {
         match capability {
             ServerCapability::Experimental => self.initialize.capabilities.experimental.is_some(),
             ServerCapability::Logging => self.initialize.capabilities.logging.is_some(),
+            ServerCapability::Completions => self.initialize.capabilities.completions.is_some(),
             ServerCapability::Prompts => self.initialize.capabilities.prompts.is_some(),
             ServerCapability::Resources => self.initialize.capabilities.resources.is_some(),
             ServerCapability::Tools => self.initialize.capabilities.tools.is_some(),
@@ -1,13 +1,17 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Context as _, Result, anyhow};
 use async_trait::async_trait;
 use collections::HashMap;
 use futures::{Stream, StreamExt};
 use gpui::BackgroundExecutor;
-use http_client::{AsyncBody, HttpClient, Request, Response, http::Method};
+use http_client::{
+    AsyncBody, HttpClient, Request, Response, StatusCode,
+    http::{Method, header::WWW_AUTHENTICATE},
+};
 use parking_lot::Mutex as SyncMutex;
 use smol::channel;
 use std::{pin::Pin, sync::Arc};
 
+use crate::oauth::OAuthHandle;
 use crate::transport::Transport;
 
 // Constants from MCP spec
@@ -27,6 +31,7 @@ pub struct HttpTransport {
     error_rx: channel::Receiver<String>,
     // Authentication headers to include in requests
     headers: HashMap<String, String>,
+    oauth: Option<OAuthHandle>,
 }
 
 impl HttpTransport {
@@ -49,14 +54,21 @@ impl HttpTransport {
             error_tx,
             error_rx,
             headers,
+            oauth: None,
         }
     }
 
-    /// Send a message and handle the response based on content type
-    async fn send_message(&self, message: String) -> Result<()> {
-        let is_notification =
-            !message.contains("\"id\":") || message.contains("notifications/initialized");
+    /// Authorizes requests with the MCP OAuth flow when the server rejects them.
+    pub fn with_oauth(mut self, oauth: OAuthHandle) -> Self {
+        self.oauth = Some(oauth);
+        self
+    }
 
+    fn build_request(
+        &self,
+        message: &str,
+        access_token: Option<&str>,
+    ) -> Result<Request<AsyncBody>> {
         let mut request_builder = Request::builder()
             .method(Method::POST)
             .uri(&self.endpoint)
@@ -70,14 +82,48 @@ impl HttpTransport {
             request_builder = request_builder.header(key.as_str(), value.as_str());
         }
 
+        if let Some(access_token) = access_token {
+            request_builder =
+                request_builder.header("Authorization", format!("Bearer {access_token}"));
+        }
+
         // Add session ID if we have one (except for initialize)
         if let Some(ref session_id) = *self.session_id.lock() {
             request_builder = request_builder.header(HEADER_SESSION_ID, session_id.as_str());
         }
 
-        let request = request_builder.body(AsyncBody::from(message.into_bytes()))?;
+        Ok(request_builder.body(AsyncBody::from(message.to_string()))?)
+    }
+
+    /// Send a message and handle the response based on content type
+    async fn send_message(&self, message: String) -> Result<()> {
+        let is_notification =
+            !message.contains("\"id\":") || message.contains("notifications/initialized");
+
+        let access_token = match &self.oauth {
+            Some(oauth) => oauth.access_token().await,
+            None => None,
+        };
+        let request = self.build_request(&message, access_token.as_deref())?;
         let mut response = self.http_client.send(request).await?;
 
+        // Authorize and retry once if the server rejects the request
+        if response.status() == StatusCode::UNAUTHORIZED
+            && let Some(oauth) = &self.oauth
+        {
+            let www_authenticate = response
+                .headers()
+                .get(WWW_AUTHENTICATE)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string());
+            let access_token = oauth
+                .reauthorize(www_authenticate, access_token)
+                .await
+                .context("Failed to authorize with the context server")?;
+            let request = self.build_request(&message, Some(&access_token))?;
+            response = self.http_client.send(request).await?;
+        }
+
         // Handle different response types based on status and content-type
         match response.status() {
             status if status.is_success() => {
@@ -233,6 +279,7 @@ impl Drop for HttpTransport {
         let endpoint = self.endpoint.clone();
         let session_id = self.session_id.lock().clone();
         let headers = self.headers.clone();
+        let oauth = self.oauth.clone();
 
         if let Some(session_id) = session_id {
             self.executor
@@ -246,6 +293,12 @@ impl Drop for HttpTransport {
                     for (key, value) in headers {
                         request_builder = request_builder.header(key.as_str(), value.as_str());
                     }
+                    if let Some(oauth) = oauth
+                        && let Some(access_token) = oauth.access_token().await
+                    {
+                        request_builder = request_builder
+                            .header("Authorization", format!("Bearer {access_token}"));
+                    }
 
                     let request = request_builder.body(AsyncBody::empty());
 
@@ -1,11 +1,12 @@
-use collections::HashMap;
+use collections::{HashMap, IndexMap};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::client::RequestId;
 
-pub const LATEST_PROTOCOL_VERSION: &str = "2025-03-26";
+pub const LATEST_PROTOCOL_VERSION: &str = "2025-06-18";
+pub const VERSION_2025_03_26: &str = "2025-03-26";
 pub const VERSION_2024_11_05: &str = "2024-11-05";
 
 pub mod requests {
@@ -77,6 +78,13 @@ pub mod requests {
         ListResourceTemplatesResponse
     );
     request!("roots/list", ListRoots, (), ListRootsResponse);
+    request!(
+        "sampling/createMessage",
+        CreateMessage,
+        CreateMessageRequest,
+        CreateMessageResult
+    );
+    request!("elicitation/create", Elicit, ElicitParams, ElicitResult);
 }
 
 pub trait Request {
@@ -233,7 +241,8 @@ pub struct PromptReference {
 pub struct ResourceReference {
     #[serde(rename = "type")]
     pub ty: PromptReferenceType,
-    pub uri: Url,
+    /// The URI of a resource, or the URI template of a resource template.
+    pub uri: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -313,7 +322,7 @@ pub struct CreateMessageRequest {
     pub metadata: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateMessageResult {
     pub role: Role,
@@ -323,6 +332,112 @@ pub struct CreateMessageResult {
     pub stop_reason: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElicitParams {
+    pub message: String,
+    pub requested_schema: ElicitationSchema,
+}
+
+/// The form a server asks the user to fill in. Only flat objects with primitive
+/// properties are allowed.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElicitationSchema {
+    pub properties: IndexMap<String, PrimitiveSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum PrimitiveSchema {
+    #[serde(rename = "string", rename_all = "camelCase")]
+    String {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+        options: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        enum_names: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        default: Option<String>,
+    },
+    #[serde(rename = "number")]
+    Number {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        minimum: Option<f64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        maximum: Option<f64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        default: Option<f64>,
+    },
+    #[serde(rename = "integer")]
+    Integer {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        minimum: Option<i64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        maximum: Option<i64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        default: Option<i64>,
+    },
+    #[serde(rename = "boolean")]
+    Boolean {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        default: Option<bool>,
+    },
+}
+
+impl PrimitiveSchema {
+    pub fn title(&self) -> Option<&str> {
+        match self {
+            PrimitiveSchema::String { title, .. }
+            | PrimitiveSchema::Number { title, .. }
+            | PrimitiveSchema::Integer { title, .. }
+            | PrimitiveSchema::Boolean { title, .. } => title.as_deref(),
+        }
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            PrimitiveSchema::String { description, .. }
+            | PrimitiveSchema::Number { description, .. }
+            | PrimitiveSchema::Integer { description, .. }
+            | PrimitiveSchema::Boolean { description, .. } => description.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElicitResult {
+    pub action: ElicitAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<IndexMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ElicitAction {
+    Accept,
+    Decline,
+    Cancel,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PromptMessage {
@@ -446,6 +561,8 @@ pub struct ClientCapabilities {
     pub sampling: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub roots: Option<RootsCapabilities>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elicitation: Option<serde_json::Value>,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize)]
@@ -709,6 +826,8 @@ pub enum ToolResponseContent {
     Audio { data: String, mime_type: String },
     #[serde(rename = "resource")]
     Resource { resource: ResourceContents },
+    #[serde(rename = "resource_link")]
+    ResourceLink(Resource),
 }
 
 impl ToolResponseContent {
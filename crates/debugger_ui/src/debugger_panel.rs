@@ -5,9 +5,10 @@ use crate::session::running::breakpoint_list::BreakpointList;
 
 use crate::{
     ClearAllBreakpoints, Continue, CopyDebugAdapterArguments, Detach, FocusBreakpointList,
-    FocusConsole, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal, FocusVariables,
-    NewProcessModal, NewProcessMode, Pause, RerunSession, StepInto, StepOut, StepOver, Stop,
-    ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker, persistence, spawn_task_or_modal,
+    FocusConsole, FocusDisassembly, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal,
+    FocusVariables, NewProcessModal, NewProcessMode, Pause, RerunSession, StepInto, StepOut,
+    StepOver, Stop, ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker, persistence,
+    spawn_task_or_modal,
 };
 use anyhow::{Context as _, Result, anyhow};
 use collections::IndexMap;
@@ -386,6 +387,7 @@ impl DebugPanel {
         let adapter = curr_session.read(cx).adapter();
         let binary = curr_session.read(cx).binary().cloned().unwrap();
         let task_context = curr_session.read(cx).task_context().clone();
+        let instruction_breakpoints = curr_session.read(cx).instruction_breakpoints().clone();
 
         let curr_session_id = curr_session.read(cx).session_id();
         self.sessions_with_children
@@ -401,6 +403,7 @@ impl DebugPanel {
                 let session = dap_store.new_session(label, adapter, task_context, None, quirks, cx);
 
                 let task = session.update(cx, |session, cx| {
+                    session.set_instruction_breakpoints(instruction_breakpoints, cx);
                     session.boot(binary, worktree, dap_store_handle.downgrade(), cx)
                 });
                 (session, task)
@@ -1740,6 +1743,15 @@ impl Render for DebugPanel {
                     .ok();
                 }
             })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusDisassembly, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+                    })
+                    .ok();
+                }
+            })
             .on_action({
                 let this = this.clone();
                 move |_: &ToggleThreadPicker, window, cx| {
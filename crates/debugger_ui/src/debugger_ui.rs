@@ -52,6 +52,10 @@ actions!(
         StepOut,
         /// Steps back to the previous statement.
         StepBack,
+        /// Steps over the current machine instruction.
+        StepOverInstruction,
+        /// Steps into the current machine instruction.
+        StepIntoInstruction,
         /// Stops the debugging session.
         Stop,
         /// Toggles whether to ignore all breakpoints.
@@ -72,6 +76,8 @@ actions!(
         FocusLoadedSources,
         /// Focuses on the terminal panel.
         FocusTerminal,
+        /// Focuses on the disassembly panel.
+        FocusDisassembly,
         /// Shows the stack trace for the current thread.
         ShowStackTrace,
         /// Toggles the thread picker dropdown.
@@ -194,6 +200,22 @@ pub fn init(cx: &mut App) {
                             active_item.update(cx, |item, cx| item.step_out(cx)).ok();
                         }
                     })
+                    .on_action({
+                        let active_item = active_item.clone();
+                        move |_: &StepOverInstruction, _, cx| {
+                            active_item
+                                .update(cx, |item, cx| item.step_over_instruction(cx))
+                                .ok();
+                        }
+                    })
+                    .on_action({
+                        let active_item = active_item.clone();
+                        move |_: &StepIntoInstruction, _, cx| {
+                            active_item
+                                .update(cx, |item, cx| item.step_in_instruction(cx))
+                                .ok();
+                        }
+                    })
                     .when(supports_step_back, |div| {
                         let active_item = active_item.clone();
                         div.on_action(move |_: &StepBack, _, cx| {
@@ -357,6 +379,7 @@ pub fn init(cx: &mut App) {
 
                                     let source_breakpoint = SourceBreakpoint {
                                         row: position.row,
+                                        column: None,
                                         path,
                                         message: None,
                                         condition: None,
@@ -13,7 +13,7 @@ use gpui::{
     Task, UniformListScrollHandle, WeakEntity, actions, uniform_list,
 };
 use itertools::Itertools;
-use language::Point;
+use language::{Point, PointUtf16};
 use project::{
     Project,
     debugger::{
@@ -37,12 +37,15 @@ actions!(
         /// Navigates to the previous breakpoint property in the list.
         PreviousBreakpointProperty,
         /// Navigates to the next breakpoint property in the list.
-        NextBreakpointProperty
+        NextBreakpointProperty,
+        /// Adds a breakpoint that stops when a function with the given name is entered.
+        AddFunctionBreakpoint
     ]
 );
 #[derive(Clone, Copy, PartialEq)]
 pub(crate) enum SelectedBreakpointKind {
     Source,
+    Function,
     Exception,
     Data,
 }
@@ -73,6 +76,8 @@ enum ActiveBreakpointStripMode {
     Log,
     Condition,
     HitCondition,
+    /// Entering the name of a new function breakpoint.
+    FunctionName,
 }
 
 impl BreakpointList {
@@ -117,28 +122,46 @@ impl BreakpointList {
     fn edit_line_breakpoint(
         &self,
         path: Arc<Path>,
-        row: u32,
+        position: PointUtf16,
         action: BreakpointEditAction,
         cx: &mut App,
     ) {
-        Self::edit_line_breakpoint_inner(&self.breakpoint_store, path, row, action, cx);
+        Self::edit_line_breakpoint_inner(&self.breakpoint_store, path, position, action, cx);
     }
     fn edit_line_breakpoint_inner(
         breakpoint_store: &Entity<BreakpointStore>,
         path: Arc<Path>,
-        row: u32,
+        position: PointUtf16,
         action: BreakpointEditAction,
         cx: &mut App,
     ) {
         breakpoint_store.update(cx, |breakpoint_store, cx| {
-            if let Some((buffer, breakpoint)) = breakpoint_store.breakpoint_at_row(&path, row, cx) {
+            if let Some((buffer, breakpoint)) =
+                breakpoint_store.breakpoint_at_position(&path, position, cx)
+            {
                 breakpoint_store.toggle_breakpoint(buffer, breakpoint, action, cx);
             } else {
-                log::error!("Couldn't find breakpoint at row event though it exists: row {row}")
+                log::error!(
+                    "Couldn't find breakpoint at position event though it exists: {position:?}"
+                )
             }
         })
     }
 
+    fn edit_function_breakpoint(&self, name: Arc<str>, action: BreakpointEditAction, cx: &mut App) {
+        Self::edit_function_breakpoint_inner(&self.breakpoint_store, name, action, cx);
+    }
+    fn edit_function_breakpoint_inner(
+        breakpoint_store: &Entity<BreakpointStore>,
+        name: Arc<str>,
+        action: BreakpointEditAction,
+        cx: &mut App,
+    ) {
+        breakpoint_store.update(cx, |breakpoint_store, cx| {
+            breakpoint_store.edit_function_breakpoint(name, action, cx);
+        })
+    }
+
     fn go_to_line_breakpoint(
         &mut self,
         path: Arc<Path>,
@@ -179,6 +202,9 @@ impl BreakpointList {
                     bp.breakpoint.state
                         == project::debugger::breakpoint_store::BreakpointState::Enabled,
                 ),
+                BreakpointEntryKind::FunctionBreakpoint(bp) => {
+                    (SelectedBreakpointKind::Function, bp.0.state.is_enabled())
+                }
                 BreakpointEntryKind::ExceptionBreakpoint(bp) => {
                     (SelectedBreakpointKind::Exception, bp.is_enabled)
                 }
@@ -200,28 +226,43 @@ impl BreakpointList {
             ActiveBreakpointStripMode::Log => "Set Log Message",
             ActiveBreakpointStripMode::Condition => "Set Condition",
             ActiveBreakpointStripMode::HitCondition => "Set Hit Condition",
+            ActiveBreakpointStripMode::FunctionName => "Function Name",
         };
-        let mut is_exception_breakpoint = true;
-        let active_value = self.selected_ix.and_then(|ix| {
-            self.breakpoints.get(ix).and_then(|bp| {
-                if let BreakpointEntryKind::LineBreakpoint(bp) = &bp.kind {
-                    is_exception_breakpoint = false;
-                    match prop {
-                        ActiveBreakpointStripMode::Log => bp.breakpoint.message.clone(),
-                        ActiveBreakpointStripMode::Condition => bp.breakpoint.condition.clone(),
-                        ActiveBreakpointStripMode::HitCondition => {
-                            bp.breakpoint.hit_condition.clone()
+        let mut is_read_only = prop != ActiveBreakpointStripMode::FunctionName;
+        let active_value = self
+            .selected_ix
+            .filter(|_| prop != ActiveBreakpointStripMode::FunctionName)
+            .and_then(|ix| {
+                self.breakpoints.get(ix).and_then(|bp| match &bp.kind {
+                    BreakpointEntryKind::LineBreakpoint(bp) => {
+                        is_read_only = false;
+                        match prop {
+                            ActiveBreakpointStripMode::Log => bp.breakpoint.message.clone(),
+                            ActiveBreakpointStripMode::Condition => bp.breakpoint.condition.clone(),
+                            ActiveBreakpointStripMode::HitCondition => {
+                                bp.breakpoint.hit_condition.clone()
+                            }
+                            ActiveBreakpointStripMode::FunctionName => None,
                         }
                     }
-                } else {
-                    None
-                }
-            })
-        });
+                    // Function breakpoints can't log messages.
+                    BreakpointEntryKind::FunctionBreakpoint(bp)
+                        if prop != ActiveBreakpointStripMode::Log =>
+                    {
+                        is_read_only = false;
+                        match prop {
+                            ActiveBreakpointStripMode::Condition => bp.0.condition.clone(),
+                            ActiveBreakpointStripMode::HitCondition => bp.0.hit_condition.clone(),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+            });
 
         self.input.update(cx, |this, cx| {
             this.set_placeholder_text(placeholder, window, cx);
-            this.set_read_only(is_exception_breakpoint);
+            this.set_read_only(is_read_only);
             this.set_text(active_value.as_deref().unwrap_or(""), window, cx);
         });
     }
@@ -232,13 +273,27 @@ impl BreakpointList {
             self.scroll_handle
                 .scroll_to_item(ix, ScrollStrategy::Center);
         }
-        if let Some(mode) = self.strip_mode {
+        if let Some(mode) = self
+            .strip_mode
+            .filter(|mode| *mode != ActiveBreakpointStripMode::FunctionName)
+        {
             self.set_active_breakpoint_property(mode, window, cx);
         }
 
         cx.notify();
     }
 
+    fn add_function_breakpoint(
+        &mut self,
+        _: &AddFunctionBreakpoint,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_active_breakpoint_property(ActiveBreakpointStripMode::FunctionName, window, cx);
+        self.input.focus_handle(cx).focus(window);
+        cx.notify();
+    }
+
     fn select_next(&mut self, _: &menu::SelectNext, window: &mut Window, cx: &mut Context<Self>) {
         if self.strip_mode.is_some() && self.input.focus_handle(cx).contains_focused(window, cx) {
             cx.propagate();
@@ -309,7 +364,11 @@ impl BreakpointList {
     }
 
     fn dismiss(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
-        if self.input.focus_handle(cx).contains_focused(window, cx) {
+        if self.strip_mode == Some(ActiveBreakpointStripMode::FunctionName) {
+            self.strip_mode.take();
+            self.focus_handle.focus(window);
+            cx.notify();
+        } else if self.input.focus_handle(cx).contains_focused(window, cx) {
             self.focus_handle.focus(window);
         } else if self.strip_mode.is_some() {
             self.strip_mode.take();
@@ -319,6 +378,22 @@ impl BreakpointList {
         }
     }
     fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.strip_mode == Some(ActiveBreakpointStripMode::FunctionName) {
+            let handle = self.input.focus_handle(cx);
+            if handle.is_focused(window) {
+                let name = self.input.read(cx).text(cx);
+                self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+                    breakpoint_store.add_function_breakpoint(&name, cx);
+                });
+                self.strip_mode.take();
+                self.focus_handle.focus(window);
+                cx.notify();
+            } else {
+                handle.focus(window);
+            }
+            return;
+        }
+
         let Some(entry) = self.selected_ix.and_then(|ix| self.breakpoints.get_mut(ix)) else {
             return;
         };
@@ -335,34 +410,53 @@ impl BreakpointList {
                             Self::edit_line_breakpoint_inner(
                                 &self.breakpoint_store,
                                 line_breakpoint.breakpoint.path.clone(),
-                                line_breakpoint.breakpoint.row,
+                                line_breakpoint.position(),
                                 BreakpointEditAction::EditLogMessage(Arc::from(text)),
                                 cx,
                             );
                         }
                     }
-                    ActiveBreakpointStripMode::Condition => {
-                        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &entry.kind {
+                    ActiveBreakpointStripMode::Condition => match &entry.kind {
+                        BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                             Self::edit_line_breakpoint_inner(
                                 &self.breakpoint_store,
                                 line_breakpoint.breakpoint.path.clone(),
-                                line_breakpoint.breakpoint.row,
+                                line_breakpoint.position(),
                                 BreakpointEditAction::EditCondition(Arc::from(text)),
                                 cx,
                             );
                         }
-                    }
-                    ActiveBreakpointStripMode::HitCondition => {
-                        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &entry.kind {
+                        BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                            Self::edit_function_breakpoint_inner(
+                                &self.breakpoint_store,
+                                function_breakpoint.0.name.clone(),
+                                BreakpointEditAction::EditCondition(Arc::from(text)),
+                                cx,
+                            );
+                        }
+                        _ => {}
+                    },
+                    ActiveBreakpointStripMode::HitCondition => match &entry.kind {
+                        BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                             Self::edit_line_breakpoint_inner(
                                 &self.breakpoint_store,
                                 line_breakpoint.breakpoint.path.clone(),
-                                line_breakpoint.breakpoint.row,
+                                line_breakpoint.position(),
                                 BreakpointEditAction::EditHitCondition(Arc::from(text)),
                                 cx,
                             );
                         }
-                    }
+                        BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                            Self::edit_function_breakpoint_inner(
+                                &self.breakpoint_store,
+                                function_breakpoint.0.name.clone(),
+                                BreakpointEditAction::EditHitCondition(Arc::from(text)),
+                                cx,
+                            );
+                        }
+                        _ => {}
+                    },
+                    ActiveBreakpointStripMode::FunctionName => {}
                 }
                 self.focus_handle.focus(window);
             } else {
@@ -377,7 +471,8 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.go_to_line_breakpoint(path, row, window, cx);
             }
-            BreakpointEntryKind::DataBreakpoint(_)
+            BreakpointEntryKind::FunctionBreakpoint(_)
+            | BreakpointEntryKind::DataBreakpoint(_)
             | BreakpointEntryKind::ExceptionBreakpoint(_) => {}
         }
     }
@@ -399,8 +494,12 @@ impl BreakpointList {
         match &mut entry.kind {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 let path = line_breakpoint.breakpoint.path.clone();
-                let row = line_breakpoint.breakpoint.row;
-                self.edit_line_breakpoint(path, row, BreakpointEditAction::InvertState, cx);
+                let position = line_breakpoint.position();
+                self.edit_line_breakpoint(path, position, BreakpointEditAction::InvertState, cx);
+            }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                let name = function_breakpoint.0.name.clone();
+                self.edit_function_breakpoint(name, BreakpointEditAction::InvertState, cx);
             }
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => {
                 let id = exception_breakpoint.id.clone();
@@ -424,10 +523,18 @@ impl BreakpointList {
             return;
         };
 
-        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &mut entry.kind {
-            let path = line_breakpoint.breakpoint.path.clone();
-            let row = line_breakpoint.breakpoint.row;
-            self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
+        match &mut entry.kind {
+            BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
+                let path = line_breakpoint.breakpoint.path.clone();
+                let position = line_breakpoint.position();
+                self.edit_line_breakpoint(path, position, BreakpointEditAction::Toggle, cx);
+            }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                let name = function_breakpoint.0.name.clone();
+                self.edit_function_breakpoint(name, BreakpointEditAction::Toggle, cx);
+            }
+            BreakpointEntryKind::DataBreakpoint(_)
+            | BreakpointEntryKind::ExceptionBreakpoint(_) => {}
         }
         cx.notify();
     }
@@ -444,7 +551,9 @@ impl BreakpointList {
             Some(ActiveBreakpointStripMode::HitCondition) => {
                 Some(ActiveBreakpointStripMode::Condition)
             }
-            None => Some(ActiveBreakpointStripMode::HitCondition),
+            Some(ActiveBreakpointStripMode::FunctionName) | None => {
+                Some(ActiveBreakpointStripMode::HitCondition)
+            }
         };
         if let Some(mode) = next_mode {
             self.set_active_breakpoint_property(mode, window, cx);
@@ -466,7 +575,9 @@ impl BreakpointList {
                 Some(ActiveBreakpointStripMode::HitCondition)
             }
             Some(ActiveBreakpointStripMode::HitCondition) => None,
-            None => Some(ActiveBreakpointStripMode::Log),
+            Some(ActiveBreakpointStripMode::FunctionName) | None => {
+                Some(ActiveBreakpointStripMode::Log)
+            }
         };
         if let Some(mode) = next_mode {
             self.set_active_breakpoint_property(mode, window, cx);
@@ -585,6 +696,7 @@ impl BreakpointList {
 
         let remove_breakpoint_tooltip = selection_kind.map(|(kind, _)| match kind {
             SelectedBreakpointKind::Source => "Remove breakpoint from a breakpoint list",
+            SelectedBreakpointKind::Function => "Remove function breakpoint from a breakpoint list",
             SelectedBreakpointKind::Exception => {
                 "Exception Breakpoints cannot be removed from the breakpoint list"
             }
@@ -603,6 +715,29 @@ impl BreakpointList {
         });
 
         h_flex()
+            .child(
+                IconButton::new("add-function-breakpoint-breakpoint-list", IconName::Plus)
+                    .icon_size(IconSize::Small)
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |_window, cx| {
+                            Tooltip::with_meta_in(
+                                "Add Function Breakpoint",
+                                Some(&AddFunctionBreakpoint),
+                                "Stop when a function with the given name is entered",
+                                &focus_handle,
+                                cx,
+                            )
+                        }
+                    })
+                    .on_click({
+                        let focus_handle = focus_handle.clone();
+                        move |_, window, cx| {
+                            focus_handle.focus(window);
+                            window.dispatch_action(AddFunctionBreakpoint.boxed_clone(), cx)
+                        }
+                    }),
+            )
             .child(
                 IconButton::new(
                     "disable-breakpoint-breakpoint-list",
@@ -649,9 +784,10 @@ impl BreakpointList {
                             }
                         })
                     })
-                    .disabled(
-                        selection_kind.map(|kind| kind.0) != Some(SelectedBreakpointKind::Source),
-                    )
+                    .disabled(!matches!(
+                        selection_kind.map(|kind| kind.0),
+                        Some(SelectedBreakpointKind::Source | SelectedBreakpointKind::Function)
+                    ))
                     .on_click({
                         move |_, window, cx| {
                             focus_handle.focus(window);
@@ -680,7 +816,7 @@ impl Render for BreakpointList {
                         .is_visible()
                         .then(|| worktree.read(cx).root_name().join(&relative_path))
                 });
-            breakpoints.sort_by_key(|breakpoint| breakpoint.row);
+            breakpoints.sort_by_key(|breakpoint| (breakpoint.row, breakpoint.column));
             let weak = weak.clone();
             breakpoints.into_iter().filter_map(move |breakpoint| {
                 debug_assert_eq!(&path, &breakpoint.path);
@@ -709,6 +845,18 @@ impl Render for BreakpointList {
                 })
             })
         });
+        let function_breakpoints = self
+            .breakpoint_store
+            .read(cx)
+            .function_breakpoints()
+            .iter()
+            .map(|breakpoint| BreakpointEntry {
+                kind: BreakpointEntryKind::FunctionBreakpoint(FunctionBreakpoint(
+                    breakpoint.clone(),
+                )),
+                weak: weak.clone(),
+            })
+            .collect::<Vec<_>>();
         let exception_breakpoints = self.session.as_ref().into_iter().flat_map(|session| {
             session
                 .read(cx)
@@ -733,6 +881,7 @@ impl Render for BreakpointList {
         });
         self.breakpoints.extend(
             breakpoints
+                .chain(function_breakpoints)
                 .chain(data_breakpoints)
                 .chain(exception_breakpoints),
         );
@@ -744,10 +893,13 @@ impl Render for BreakpointList {
             .iter()
             .map(|entry| match &entry.kind {
                 BreakpointEntryKind::LineBreakpoint(line_bp) => {
-                    let name_and_line = format!("{}:{}", line_bp.name, line_bp.line);
+                    let name_and_line = line_bp.label();
                     let dir_len = line_bp.dir.as_ref().map(|d| d.len()).unwrap_or(0);
                     (name_and_line.len() + dir_len) as f32 * text_pixels
                 }
+                BreakpointEntryKind::FunctionBreakpoint(function_bp) => {
+                    function_bp.0.name.len() as f32 * text_pixels
+                }
                 BreakpointEntryKind::ExceptionBreakpoint(exc_bp) => {
                     exc_bp.data.label.len() as f32 * text_pixels
                 }
@@ -771,6 +923,7 @@ impl Render for BreakpointList {
             .on_action(cx.listener(Self::unset_breakpoint))
             .on_action(cx.listener(Self::next_breakpoint_property))
             .on_action(cx.listener(Self::previous_breakpoint_property))
+            .on_action(cx.listener(Self::add_function_breakpoint))
             .size_full()
             .pt_1()
             .child(self.render_list(cx))
@@ -819,6 +972,17 @@ struct LineBreakpoint {
 }
 
 impl LineBreakpoint {
+    fn position(&self) -> PointUtf16 {
+        PointUtf16::new(self.breakpoint.row, self.breakpoint.column.unwrap_or(0))
+    }
+
+    fn label(&self) -> String {
+        match self.breakpoint.column {
+            Some(column) => format!("{}:{}:{}", self.name, self.line, column + 1),
+            None => format!("{}:{}", self.name, self.line),
+        }
+    }
+
     fn render(
         &mut self,
         props: SupportedBreakpointProperties,
@@ -835,12 +999,14 @@ impl LineBreakpoint {
         };
         let path = self.breakpoint.path.clone();
         let row = self.breakpoint.row;
+        let position = self.position();
         let is_enabled = self.breakpoint.state.is_enabled();
 
         let indicator = div()
             .id(SharedString::from(format!(
-                "breakpoint-ui-toggle-{:?}/{}:{}",
-                self.dir, self.name, self.line
+                "breakpoint-ui-toggle-{:?}/{}",
+                self.dir,
+                self.label()
             )))
             .child(
                 Icon::new(icon_name)
@@ -869,7 +1035,7 @@ impl LineBreakpoint {
                     weak.update(cx, |breakpoint_list, cx| {
                         breakpoint_list.edit_line_breakpoint(
                             path.clone(),
-                            row,
+                            position,
                             BreakpointEditAction::InvertState,
                             cx,
                         );
@@ -880,8 +1046,9 @@ impl LineBreakpoint {
             .on_mouse_down(MouseButton::Left, move |_, _, _| {});
 
         ListItem::new(SharedString::from(format!(
-            "breakpoint-ui-item-{:?}/{}:{}",
-            self.dir, self.name, self.line
+            "breakpoint-ui-item-{:?}/{}",
+            self.dir,
+            self.label()
         )))
         .toggle_state(is_selected)
         .inset(true)
@@ -901,8 +1068,9 @@ impl LineBreakpoint {
         .child(
             h_flex()
                 .id(SharedString::from(format!(
-                    "breakpoint-ui-on-click-go-to-line-{:?}/{}:{}",
-                    self.dir, self.name, self.line
+                    "breakpoint-ui-on-click-go-to-line-{:?}/{}",
+                    self.dir,
+                    self.label()
                 )))
                 .w_full()
                 .gap_1()
@@ -923,7 +1091,7 @@ impl LineBreakpoint {
                         .id("label-container")
                         .gap_0p5()
                         .child(
-                            Label::new(format!("{}:{}", self.name, self.line))
+                            Label::new(self.label())
                                 .size(LabelSize::Small)
                                 .line_height_style(ui::LineHeightStyle::UiLabel),
                         )
@@ -962,6 +1130,112 @@ impl LineBreakpoint {
     }
 }
 
+#[derive(Clone, Debug)]
+struct FunctionBreakpoint(project::debugger::breakpoint_store::FunctionBreakpoint);
+
+impl FunctionBreakpoint {
+    fn render(
+        &self,
+        props: SupportedBreakpointProperties,
+        strip_mode: Option<ActiveBreakpointStripMode>,
+        ix: usize,
+        is_selected: bool,
+        focus_handle: FocusHandle,
+        list: WeakEntity<BreakpointList>,
+    ) -> ListItem {
+        let is_enabled = self.0.state.is_enabled();
+        let color = if is_enabled {
+            Color::Debugger
+        } else {
+            Color::Muted
+        };
+        let name = self.0.name.clone();
+
+        ListItem::new(SharedString::from(format!(
+            "function-breakpoint-ui-item-{}",
+            self.0.name
+        )))
+        .toggle_state(is_selected)
+        .inset(true)
+        .on_click({
+            let list = list.clone();
+            move |_, window, cx| {
+                list.update(cx, |list, cx| list.select_ix(Some(ix), window, cx))
+                    .ok();
+            }
+        })
+        .on_secondary_mouse_down(|_, _, cx| {
+            cx.stop_propagation();
+        })
+        .start_slot(
+            div()
+                .id(SharedString::from(format!(
+                    "function-breakpoint-ui-item-{}-click-handler",
+                    self.0.name
+                )))
+                .child(Icon::new(IconName::Code).color(color).size(IconSize::Small))
+                .tooltip({
+                    let focus_handle = focus_handle.clone();
+                    move |_window, cx| {
+                        Tooltip::for_action_in(
+                            if is_enabled {
+                                "Disable Function Breakpoint"
+                            } else {
+                                "Enable Function Breakpoint"
+                            },
+                            &ToggleEnableBreakpoint,
+                            &focus_handle,
+                            cx,
+                        )
+                    }
+                })
+                .on_click({
+                    let list = list.clone();
+                    move |_, _, cx| {
+                        list.update(cx, |this, cx| {
+                            this.edit_function_breakpoint(
+                                name.clone(),
+                                BreakpointEditAction::InvertState,
+                                cx,
+                            );
+                        })
+                        .ok();
+                    }
+                }),
+        )
+        .child(
+            h_flex()
+                .w_full()
+                .gap_1()
+                .min_h(rems_from_px(26.))
+                .justify_between()
+                .child(
+                    v_flex()
+                        .py_1()
+                        .gap_1()
+                        .justify_center()
+                        .id(("function-breakpoint-label", ix))
+                        .child(
+                            Label::new(self.0.name.to_string())
+                                .size(LabelSize::Small)
+                                .line_height_style(ui::LineHeightStyle::UiLabel),
+                        ),
+                )
+                .child(BreakpointOptionsStrip {
+                    props,
+                    breakpoint: BreakpointEntry {
+                        kind: BreakpointEntryKind::FunctionBreakpoint(self.clone()),
+                        weak: list,
+                    },
+                    is_selected,
+                    focus_handle,
+                    strip_mode,
+                    index: ix,
+                }),
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 struct ExceptionBreakpoint {
     id: String,
@@ -1173,6 +1447,7 @@ impl ExceptionBreakpoint {
 #[derive(Clone, Debug)]
 enum BreakpointEntryKind {
     LineBreakpoint(LineBreakpoint),
+    FunctionBreakpoint(FunctionBreakpoint),
     ExceptionBreakpoint(ExceptionBreakpoint),
     DataBreakpoint(DataBreakpoint),
 }
@@ -1201,6 +1476,15 @@ impl BreakpointEntry {
                 focus_handle,
                 self.weak.clone(),
             ),
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => function_breakpoint
+                .render(
+                    props.for_function_breakpoints(),
+                    strip_mode,
+                    ix,
+                    is_selected,
+                    focus_handle,
+                    self.weak.clone(),
+                ),
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => exception_breakpoint
                 .render(
                     props.for_exception_breakpoints(),
@@ -1224,8 +1508,15 @@ impl BreakpointEntry {
     fn id(&self) -> SharedString {
         match &self.kind {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => format!(
-                "source-breakpoint-control-strip-{:?}:{}",
-                line_breakpoint.breakpoint.path, line_breakpoint.breakpoint.row
+                "source-breakpoint-control-strip-{:?}:{}:{:?}",
+                line_breakpoint.breakpoint.path,
+                line_breakpoint.breakpoint.row,
+                line_breakpoint.breakpoint.column
+            )
+            .into(),
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => format!(
+                "function-breakpoint-control-strip--{}",
+                function_breakpoint.0.name
             )
             .into(),
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => format!(
@@ -1255,6 +1546,9 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.condition.is_some()
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                function_breakpoint.0.condition.is_some()
+            }
             // We don't support conditions on exception/data breakpoints
             _ => false,
         }
@@ -1265,6 +1559,9 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.hit_condition.is_some()
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                function_breakpoint.0.hit_condition.is_some()
+            }
             _ => false,
         }
     }
@@ -1305,6 +1602,10 @@ impl From<&Capabilities> for SupportedBreakpointProperties {
 }
 
 impl SupportedBreakpointProperties {
+    fn for_function_breakpoints(self) -> Self {
+        // Function breakpoints can't log messages.
+        self & (Self::CONDITION | Self::HIT_CONDITION)
+    }
     fn for_exception_breakpoints(self) -> Self {
         // TODO: we don't yet support conditions for exception breakpoints at the data layer, hence all props are disabled here.
         Self::empty()
@@ -0,0 +1,461 @@
+use std::ops::Range;
+
+use collections::HashMap;
+use dap::{DisassembledInstruction, StackFrameId};
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, ScrollStrategy, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use language::{Buffer, Point};
+use project::{
+    Project,
+    debugger::{
+        parse_memory_address,
+        session::{Session, ThreadStatus},
+    },
+};
+use ui::{Tooltip, VisibleOnHover, WithScrollbar, prelude::*};
+use util::ResultExt as _;
+
+use crate::{
+    StepIntoInstruction, StepOverInstruction,
+    session::running::{
+        RunningState,
+        stack_frame_list::{StackFrameList, StackFrameListEvent},
+    },
+};
+
+/// The number of instructions disassembled before the instruction pointer.
+const INSTRUCTIONS_BEFORE: u64 = 50;
+/// The number of instructions disassembled from the instruction pointer onwards.
+const INSTRUCTIONS_AFTER: u64 = 100;
+
+enum DisassemblyEntry {
+    Source { line: u32, text: SharedString },
+    Instruction(DisassembledInstruction),
+}
+
+pub(crate) struct DisassemblyView {
+    session: Entity<Session>,
+    stack_frame_list: Entity<StackFrameList>,
+    running_state: WeakEntity<RunningState>,
+    project: Entity<Project>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    entries: Vec<DisassemblyEntry>,
+    /// The address of the instruction that the selected stack frame is paused at.
+    instruction_pointer: Option<u64>,
+    _refresh_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        stack_frame_list: Entity<StackFrameList>,
+        running_state: WeakEntity<RunningState>,
+        project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(stack_frame_id) => {
+                    this.disassemble_stack_frame(*stack_frame_id, cx);
+                }
+                StackFrameListEvent::BuiltEntries => {}
+            }),
+            cx.observe(&session, |_, _, cx| cx.notify()),
+        ];
+
+        let mut this = Self {
+            session,
+            stack_frame_list,
+            running_state,
+            project,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            entries: Vec::new(),
+            instruction_pointer: None,
+            _refresh_task: Task::ready(()),
+            _subscriptions,
+        };
+        if let Some(stack_frame_id) = this.stack_frame_list.read(cx).opened_stack_frame_id() {
+            this.disassemble_stack_frame(stack_frame_id, cx);
+        }
+        this
+    }
+
+    fn disassemble_stack_frame(&mut self, stack_frame_id: StackFrameId, cx: &mut Context<Self>) {
+        let instruction_pointer = self
+            .stack_frame_list
+            .read(cx)
+            .flatten_entries(true, true)
+            .into_iter()
+            .find(|stack_frame| stack_frame.id == stack_frame_id)
+            .and_then(|stack_frame| stack_frame.instruction_pointer_reference);
+        let Some(instruction_pointer) = instruction_pointer else {
+            self.entries.clear();
+            self.instruction_pointer = None;
+            cx.notify();
+            return;
+        };
+
+        let instructions = self.session.update(cx, |session, cx| {
+            session.disassemble(
+                instruction_pointer.clone(),
+                -(INSTRUCTIONS_BEFORE as i64),
+                INSTRUCTIONS_BEFORE + INSTRUCTIONS_AFTER,
+                cx,
+            )
+        });
+        let project = self.project.clone();
+        self._refresh_task = cx.spawn(async move |this, cx| {
+            let Some(instructions) = instructions.await else {
+                return;
+            };
+
+            let mut buffers = HashMap::<String, Option<Entity<Buffer>>>::default();
+            for path in instructions
+                .iter()
+                .filter_map(|instruction| instruction.location.as_ref()?.path.clone())
+            {
+                if buffers.contains_key(&path) {
+                    continue;
+                }
+                let buffer = project
+                    .update(cx, |project, cx| project.open_local_buffer(&path, cx))
+                    .log_err();
+                let buffer = match buffer {
+                    Some(buffer) => buffer.await.log_err(),
+                    None => None,
+                };
+                buffers.insert(path, buffer);
+            }
+
+            this.update(cx, |this, cx| {
+                this.entries = interleave_source_lines(instructions, |path, line| {
+                    let buffer = buffers.get(path)?.as_ref()?.read(cx);
+                    let row = line.checked_sub(1)?;
+                    (row <= buffer.max_point().row).then(|| {
+                        buffer
+                            .text_for_range(
+                                Point::new(row, 0)..Point::new(row, buffer.line_len(row)),
+                            )
+                            .collect::<String>()
+                    })
+                });
+                this.instruction_pointer = parse_memory_address(&instruction_pointer);
+                if let Some(ix) = this.entries.iter().position(|entry| {
+                    matches!(entry, DisassemblyEntry::Instruction(instruction)
+                        if parse_memory_address(&instruction.address) == this.instruction_pointer)
+                }) {
+                    this.scroll_handle
+                        .scroll_to_item(ix, ScrollStrategy::Center);
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn thread_status(&self, cx: &App) -> Option<ThreadStatus> {
+        self.running_state
+            .read_with(cx, |state, cx| state.thread_status(cx))
+            .ok()
+            .flatten()
+    }
+
+    fn render_entry(&mut self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        match &self.entries[ix] {
+            DisassemblyEntry::Source { line, text } => h_flex()
+                .id(("disassembly-source", ix))
+                .w_full()
+                .gap_2()
+                .pl_6()
+                .text_color(cx.theme().colors().text_muted)
+                .child(
+                    Label::new(line.to_string())
+                        .color(Color::Muted)
+                        .size(LabelSize::Small),
+                )
+                .child(text.clone())
+                .into_any(),
+            DisassemblyEntry::Instruction(instruction) => {
+                let is_current = self.instruction_pointer.is_some()
+                    && parse_memory_address(&instruction.address) == self.instruction_pointer;
+                let has_breakpoint = self
+                    .session
+                    .read(cx)
+                    .has_instruction_breakpoint(&instruction.address);
+                let supports_breakpoints = self
+                    .session
+                    .read(cx)
+                    .capabilities()
+                    .supports_instruction_breakpoints
+                    .unwrap_or_default();
+                let address = instruction.address.clone();
+
+                h_flex()
+                    .id(("disassembly-instruction", ix))
+                    .w_full()
+                    .gap_2()
+                    .group("disassembly-instruction")
+                    .when(is_current, |this| {
+                        this.bg(cx.theme().colors().editor_debugger_active_line_background)
+                    })
+                    .hover(|style| style.bg(cx.theme().colors().element_hover))
+                    .child(
+                        div()
+                            .id(("disassembly-breakpoint", ix))
+                            .w_4()
+                            .flex_none()
+                            .when(supports_breakpoints, |this| {
+                                this.cursor_pointer()
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.session.update(cx, |session, cx| {
+                                            session.toggle_instruction_breakpoint(&address, cx);
+                                        });
+                                    }))
+                                    .tooltip(Tooltip::text("Toggle Instruction Breakpoint"))
+                            })
+                            .map(|this| {
+                                if has_breakpoint {
+                                    this.child(
+                                        Icon::new(IconName::DebugBreakpoint)
+                                            .size(IconSize::XSmall)
+                                            .color(Color::Debugger),
+                                    )
+                                } else if supports_breakpoints {
+                                    this.child(
+                                        div().visible_on_hover("disassembly-instruction").child(
+                                            Icon::new(IconName::DebugBreakpoint)
+                                                .size(IconSize::XSmall)
+                                                .color(Color::Hint),
+                                        ),
+                                    )
+                                } else {
+                                    this
+                                }
+                            }),
+                    )
+                    .child(
+                        div()
+                            .flex_none()
+                            .text_color(cx.theme().colors().text_muted)
+                            .child(instruction.address.clone()),
+                    )
+                    .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                        this.child(
+                            div()
+                                .flex_none()
+                                .w_40()
+                                .overflow_hidden()
+                                .text_color(cx.theme().colors().text_placeholder)
+                                .child(bytes),
+                        )
+                    })
+                    .child(instruction.instruction.clone())
+                    .when_some(instruction.symbol.clone(), |this, symbol| {
+                        this.child(
+                            div()
+                                .text_color(cx.theme().colors().text_muted)
+                                .child(format!("<{symbol}>")),
+                        )
+                    })
+                    .into_any()
+            }
+        }
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_stopped = self.thread_status(cx) == Some(ThreadStatus::Stopped);
+        let focus_handle = self.focus_handle.clone();
+
+        h_flex()
+            .w_full()
+            .gap_1()
+            .pb_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                IconButton::new("step-over-instruction", IconName::DebugStepOver)
+                    .icon_size(IconSize::Small)
+                    .disabled(!is_stopped)
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.running_state
+                            .update(cx, |state, cx| state.step_over_instruction(cx))
+                            .ok();
+                    }))
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |_window, cx| {
+                            Tooltip::for_action_in(
+                                "Step Over Instruction",
+                                &StepOverInstruction,
+                                &focus_handle,
+                                cx,
+                            )
+                        }
+                    }),
+            )
+            .child(
+                IconButton::new("step-into-instruction", IconName::DebugStepInto)
+                    .icon_size(IconSize::Small)
+                    .disabled(!is_stopped)
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.running_state
+                            .update(cx, |state, cx| state.step_in_instruction(cx))
+                            .ok();
+                    }))
+                    .tooltip(move |_window, cx| {
+                        Tooltip::for_action_in(
+                            "Step Into Instruction",
+                            &StepIntoInstruction,
+                            &focus_handle,
+                            cx,
+                        )
+                    }),
+            )
+    }
+
+    fn render_list(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "disassembly",
+            self.entries.len(),
+            cx.processor(|this, range: Range<usize>, _window, cx| {
+                range.map(|ix| this.render_entry(ix, cx)).collect()
+            }),
+        )
+        .track_scroll(&self.scroll_handle)
+        .size_full()
+    }
+}
+
+/// Inserts the source line of each instruction above it when it differs from the previous
+/// instruction's. Adapters omit an instruction's location when it's the same as the previous
+/// one, so the last location seen applies to the following instructions.
+fn interleave_source_lines(
+    instructions: Vec<DisassembledInstruction>,
+    mut source_line: impl FnMut(&str, u32) -> Option<String>,
+) -> Vec<DisassemblyEntry> {
+    let mut entries = Vec::with_capacity(instructions.len());
+    let mut path = None;
+    let mut last_line = None;
+    for instruction in instructions {
+        if let Some(location) = &instruction.location {
+            if location.path != path {
+                last_line = None;
+            }
+            path = location.path.clone();
+        }
+        if let Some((path, line)) = path.as_deref().zip(instruction.line)
+            && last_line != Some(line)
+        {
+            last_line = Some(line);
+            if let Some(text) = source_line(path, line as u32) {
+                entries.push(DisassemblyEntry::Source {
+                    line: line as u32,
+                    text: text.trim_end().to_string().into(),
+                });
+            }
+        }
+        entries.push(DisassemblyEntry::Instruction(instruction));
+    }
+    entries
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .font_buffer(cx)
+            .text_buffer(cx)
+            .child(self.render_header(cx))
+            .child(
+                div()
+                    .flex_1()
+                    .size_full()
+                    .child(self.render_list(window, cx))
+                    .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instruction(
+        address: &str,
+        path: Option<&str>,
+        line: Option<u32>,
+    ) -> DisassembledInstruction {
+        DisassembledInstruction {
+            address: address.into(),
+            instruction_bytes: None,
+            instruction: "nop".into(),
+            symbol: None,
+            location: path.map(|path| dap::Source {
+                name: None,
+                path: Some(path.into()),
+                source_reference: None,
+                presentation_hint: None,
+                origin: None,
+                sources: None,
+                adapter_data: None,
+                checksums: None,
+            }),
+            line: line.map(Into::into),
+            column: None,
+            end_line: None,
+            end_column: None,
+            presentation_hint: None,
+        }
+    }
+
+    #[test]
+    fn test_interleave_source_lines() {
+        let instructions = vec![
+            instruction("0x10", Some("/src/main.c"), Some(1)),
+            instruction("0x14", None, Some(1)),
+            instruction("0x18", None, Some(2)),
+            instruction("0x1c", Some("/src/util.c"), Some(2)),
+            instruction("0x20", None, None),
+            instruction("0x24", None, Some(99)),
+        ];
+        let entries = interleave_source_lines(instructions, |path, line| {
+            (line < 10).then(|| format!("{path}:{line}  "))
+        });
+
+        let entries = entries
+            .iter()
+            .map(|entry| match entry {
+                DisassemblyEntry::Source { line, text } => format!("{line}: {text}"),
+                DisassemblyEntry::Instruction(instruction) => instruction.address.clone(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            [
+                "1: /src/main.c:1",
+                "0x10",
+                "0x14",
+                "2: /src/main.c:2",
+                "0x18",
+                "2: /src/util.c:2",
+                "0x1c",
+                "0x20",
+                "0x24",
+            ]
+        );
+    }
+}
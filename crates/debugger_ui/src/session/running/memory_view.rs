@@ -14,6 +14,7 @@ use gpui::{
     Subscription, Task, TextStyle, UniformList, UniformListScrollHandle, WeakEntity, actions,
     anchored, deferred, uniform_list,
 };
+use itertools::Itertools;
 use notifications::status_toast::{StatusToast, ToastIcon};
 use project::debugger::{MemoryCell, dap_command::DataBreakpointContext, session::Session};
 use settings::Settings;
@@ -26,7 +27,16 @@ use workspace::Workspace;
 
 use crate::{ToggleDataBreakpoint, session::running::stack_frame_list::StackFrameList};
 
-actions!(debugger, [GoToSelectedAddress]);
+actions!(
+    debugger,
+    [
+        GoToSelectedAddress,
+        /// Edits the selected memory range as hex bytes.
+        EditMemoryAsHex,
+        /// Edits the selected memory range as ASCII text.
+        EditMemoryAsAscii,
+    ]
+);
 
 pub(crate) struct MemoryView {
     workspace: WeakEntity<Workspace>,
@@ -37,6 +47,7 @@ pub(crate) struct MemoryView {
     session: Entity<Session>,
     width_picker_handle: PopoverMenuHandle<ContextMenu>,
     is_writing_memory: bool,
+    write_format: MemoryWriteFormat,
     open_context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
 }
 
@@ -65,6 +76,87 @@ impl Drag {
         }
     }
 }
+
+/// How the text typed into the query bar is turned into the bytes written to memory.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum MemoryWriteFormat {
+    #[default]
+    Hex,
+    Ascii,
+}
+
+impl MemoryWriteFormat {
+    fn label(self) -> &'static str {
+        match self {
+            MemoryWriteFormat::Hex => "Hex",
+            MemoryWriteFormat::Ascii => "ASCII",
+        }
+    }
+
+    fn placeholder_text(self) -> &'static str {
+        match self {
+            MemoryWriteFormat::Hex => "Write Hex Bytes to Selected Memory Range",
+            MemoryWriteFormat::Ascii => "Write ASCII Text to Selected Memory Range",
+        }
+    }
+
+    fn toggled(self) -> Self {
+        match self {
+            MemoryWriteFormat::Hex => MemoryWriteFormat::Ascii,
+            MemoryWriteFormat::Ascii => MemoryWriteFormat::Hex,
+        }
+    }
+
+    /// Formats the current contents of memory for editing, if they can be shown in this format.
+    fn format(self, bytes: &[u8]) -> Option<String> {
+        match self {
+            MemoryWriteFormat::Hex => {
+                Some(bytes.iter().map(|byte| format!("{byte:02X}")).join(" "))
+            }
+            MemoryWriteFormat::Ascii => bytes
+                .iter()
+                .all(|byte| byte.is_ascii_graphic() || *byte == b' ')
+                .then(|| bytes.iter().map(|byte| char::from(*byte)).collect()),
+        }
+    }
+
+    /// Parses the bytes to write, which must fit in a selection of `max_len` bytes.
+    fn parse(self, text: &str, max_len: u64) -> Result<Vec<u8>, SharedString> {
+        let bytes = match self {
+            MemoryWriteFormat::Hex => {
+                let digits = text
+                    .split_whitespace()
+                    .map(|word| {
+                        word.strip_prefix("0x")
+                            .or_else(|| word.strip_prefix("0X"))
+                            .unwrap_or(word)
+                    })
+                    .collect::<String>();
+                hex::decode(&digits).map_err(|_| {
+                    SharedString::from(format!("`{text}` isn't a list of hex bytes"))
+                })?
+            }
+            MemoryWriteFormat::Ascii => {
+                if !text.is_ascii() {
+                    return Err("Only ASCII text can be written to memory".into());
+                }
+                text.as_bytes().to_vec()
+            }
+        };
+        if bytes.is_empty() {
+            Err("There's nothing to write to memory".into())
+        } else if bytes.len() as u64 > max_len {
+            Err(format!(
+                "Can't write {} bytes to a selection of {max_len} bytes",
+                bytes.len()
+            )
+            .into())
+        } else {
+            Ok(bytes)
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 enum SelectedMemoryRange {
     DragUnderway(Drag),
@@ -183,11 +275,12 @@ impl MemoryView {
             session,
             width_picker_handle: Default::default(),
             is_writing_memory: true,
+            write_format: MemoryWriteFormat::default(),
             open_context_menu: None,
         };
-        this.change_query_bar_mode(false, window, cx);
+        this.stop_writing_memory(window, cx);
         cx.on_focus_out(&this.focus_handle, window, |this, _, window, cx| {
-            this.change_query_bar_mode(false, window, cx);
+            this.stop_writing_memory(window, cx);
             cx.notify();
         })
         .detach();
@@ -388,29 +481,97 @@ impl MemoryView {
         cx.notify();
     }
 
-    fn change_query_bar_mode(
+    /// Switches the query bar to editing the selected memory range, filled in with its current
+    /// contents in the given format.
+    fn start_writing_memory(
         &mut self,
-        is_writing_memory: bool,
+        format: MemoryWriteFormat,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if is_writing_memory == self.is_writing_memory {
+        let Some(SelectedMemoryRange::DragComplete(drag)) = self.view_state().selection.clone()
+        else {
+            return;
+        };
+        let session = self.session.read(cx);
+        if !session
+            .capabilities()
+            .supports_write_memory_request
+            .unwrap_or_default()
+        {
+            let adapter_name = session.adapter();
+            // We cannot write memory with this adapter.
+            self.show_error_toast(
+                format!("Debug Adapter `{adapter_name}` does not support writing to memory"),
+                cx,
+            );
             return;
         }
+
+        let contents = self
+            .session
+            .update(cx, |session, cx| {
+                session
+                    .read_memory(drag.memory_range(), cx)
+                    .map(|cell| cell.0)
+                    .collect::<Option<Vec<_>>>()
+            })
+            .and_then(|bytes| format.format(&bytes))
+            .unwrap_or_default();
+        self.write_format = format;
+        self.is_writing_memory = true;
+        self.query_editor.update(cx, |this, cx| {
+            this.set_placeholder_text(format.placeholder_text(), window, cx);
+            this.set_text(contents, window, cx);
+            this.select_all(&editor::actions::SelectAll, window, cx);
+        });
+        self.query_editor.focus_handle(cx).focus(window);
+        cx.notify();
+    }
+
+    fn stop_writing_memory(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if !self.is_writing_memory {
-            self.query_editor.update(cx, |this, cx| {
-                this.clear(window, cx);
-                this.set_placeholder_text("Write to Selected Memory Range", window, cx);
-            });
-            self.is_writing_memory = true;
-            self.query_editor.focus_handle(cx).focus(window);
-        } else {
-            self.query_editor.update(cx, |this, cx| {
-                this.clear(window, cx);
-                this.set_placeholder_text("Go to Memory Address / Expression", window, cx);
-            });
-            self.is_writing_memory = false;
+            return;
         }
+        self.query_editor.update(cx, |this, cx| {
+            this.clear(window, cx);
+            this.set_placeholder_text("Go to Memory Address / Expression", window, cx);
+        });
+        self.is_writing_memory = false;
+    }
+
+    fn edit_memory_as_hex(
+        &mut self,
+        _: &EditMemoryAsHex,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.start_writing_memory(MemoryWriteFormat::Hex, window, cx);
+    }
+
+    fn edit_memory_as_ascii(
+        &mut self,
+        _: &EditMemoryAsAscii,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.start_writing_memory(MemoryWriteFormat::Ascii, window, cx);
+    }
+
+    fn show_error_toast(&self, message: impl Into<SharedString>, cx: &mut App) {
+        _ = self.workspace.update(cx, |this, cx| {
+            this.toggle_status_toast(
+                StatusToast::new(message, cx, |this, cx| {
+                    cx.spawn(async move |this, cx| {
+                        cx.background_executor().timer(Duration::from_secs(2)).await;
+                        _ = this.update(cx, |_, cx| cx.emit(DismissEvent));
+                    })
+                    .detach();
+                    this.icon(ToastIcon::new(IconName::XCircle).color(Color::Error))
+                }),
+                cx,
+            );
+        });
     }
 
     fn toggle_data_breakpoint(
@@ -461,54 +622,35 @@ impl MemoryView {
         if let Some(SelectedMemoryRange::DragComplete(drag)) = selection {
             // Go into memory writing mode.
             if !self.is_writing_memory {
-                let should_return = self.session.update(cx, |session, cx| {
-                    if !session
-                        .capabilities()
-                        .supports_write_memory_request
-                        .unwrap_or_default()
-                    {
-                        let adapter_name = session.adapter();
-                        // We cannot write memory with this adapter.
-                        _ = self.workspace.update(cx, |this, cx| {
-                            this.toggle_status_toast(
-                                StatusToast::new(format!(
-                                    "Debug Adapter `{adapter_name}` does not support writing to memory"
-                                ), cx, |this, cx| {
-                                    cx.spawn(async move |this, cx| {
-                                        cx.background_executor().timer(Duration::from_secs(2)).await;
-                                        _ = this.update(cx, |_, cx| {
-                                            cx.emit(DismissEvent)
-                                        });
-                                    }).detach();
-                                    this.icon(ToastIcon::new(IconName::XCircle).color(Color::Error))
-                                }),
-                                cx,
-                            );
-                        });
-                        true
-                    } else {
-                        false
-                    }
-                });
-                if should_return {
-                    return;
-                }
-
-                self.change_query_bar_mode(true, window, cx);
+                self.start_writing_memory(self.write_format, window, cx);
             } else if self.query_editor.focus_handle(cx).is_focused(window) {
-                let mut text = self.query_editor.read(cx).text(cx);
-                if text.chars().any(|c| !c.is_ascii_hexdigit()) {
-                    // Interpret this text as a string and oh-so-conveniently convert it.
-                    text = text.bytes().map(|byte| format!("{:02x}", byte)).collect();
-                }
-                self.session.update(cx, |this, cx| {
-                    let range = drag.memory_range();
-
-                    if let Ok(as_hex) = hex::decode(text) {
-                        this.write_memory(*range.start(), &as_hex, cx);
+                let range = drag.memory_range();
+                let text = self.query_editor.read(cx).text(cx);
+                let data = match self
+                    .write_format
+                    .parse(&text, range.end() - range.start() + 1)
+                {
+                    Ok(data) => data,
+                    Err(error) => {
+                        // Keep the text around so that it can be fixed.
+                        self.show_error_toast(error, cx);
+                        return;
                     }
+                };
+                let write = self.session.update(cx, |session, cx| {
+                    session.write_memory(*range.start(), &data, cx)
                 });
-                self.change_query_bar_mode(false, window, cx);
+                cx.spawn(async move |this, cx| {
+                    let response = write.await;
+                    _ = this.update(cx, |this, cx| {
+                        if response.is_none() {
+                            this.show_error_toast("Failed to write to memory", cx);
+                        }
+                        cx.notify();
+                    });
+                })
+                .detach();
+                self.stop_writing_memory(window, cx);
             }
 
             cx.notify();
@@ -621,6 +763,7 @@ impl MemoryView {
             let caps = session.read(cx).capabilities();
             let supports_data_breakpoints = caps.supports_data_breakpoints.unwrap_or_default()
                 && caps.supports_data_breakpoint_bytes.unwrap_or_default();
+            let supports_write_memory = caps.supports_write_memory_request.unwrap_or_default();
             let memory_unreadable = LazyCell::new(|| {
                 session.update(cx, |this, cx| {
                     this.read_memory(range.clone(), cx)
@@ -634,6 +777,20 @@ impl MemoryView {
                 GoToSelectedAddress.boxed_clone(),
             );
 
+            if supports_write_memory {
+                menu = menu
+                    .action_disabled_when(
+                        *memory_unreadable,
+                        "Edit as Hex",
+                        EditMemoryAsHex.boxed_clone(),
+                    )
+                    .action_disabled_when(
+                        *memory_unreadable,
+                        "Edit as ASCII",
+                        EditMemoryAsAscii.boxed_clone(),
+                    );
+            }
+
             if supports_data_breakpoints {
                 menu = menu.action_disabled_when(
                     *memory_unreadable,
@@ -866,6 +1023,8 @@ impl Render for MemoryView {
             .p_1()
             .on_action(cx.listener(Self::confirm))
             .on_action(cx.listener(Self::toggle_data_breakpoint))
+            .on_action(cx.listener(Self::edit_memory_as_hex))
+            .on_action(cx.listener(Self::edit_memory_as_ascii))
             .on_action(cx.listener(Self::page_down))
             .on_action(cx.listener(Self::page_up))
             .size_full()
@@ -898,7 +1057,22 @@ impl Render for MemoryView {
                                     .child(Icon::new(icon).size(ui::IconSize::XSmall))
                                     .tooltip(Tooltip::text(tooltip_text)),
                             )
-                            .child(self.render_query_bar(cx)),
+                            .child(self.render_query_bar(cx))
+                            .when(self.is_writing_memory, |this| {
+                                let write_format = self.write_format;
+                                this.child(
+                                    Button::new("memory-view-write-format", write_format.label())
+                                        .label_size(LabelSize::Small)
+                                        .tooltip(Tooltip::text("Toggle Between Hex and ASCII"))
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            this.start_writing_memory(
+                                                write_format.toggled(),
+                                                window,
+                                                cx,
+                                            );
+                                        })),
+                                )
+                            }),
                     )
                     .child(self.render_width_picker(window, cx)),
             )
@@ -933,3 +1107,30 @@ impl Render for MemoryView {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_write_format() {
+        let hex = MemoryWriteFormat::Hex;
+        assert_eq!(hex.format(&[0xde, 0xad, 0x0f]).as_deref(), Some("DE AD 0F"));
+        assert_eq!(hex.parse("DE AD 0f", 4), Ok(vec![0xde, 0xad, 0x0f]));
+        assert_eq!(
+            hex.parse("0xdead beef", 4),
+            Ok(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert!(hex.parse("dea", 4).is_err());
+        assert!(hex.parse("hello", 4).is_err());
+        assert!(hex.parse("dead beef 00", 4).is_err());
+        assert!(hex.parse("  ", 4).is_err());
+
+        let ascii = MemoryWriteFormat::Ascii;
+        assert_eq!(ascii.format(b"cafe 42").as_deref(), Some("cafe 42"));
+        assert_eq!(ascii.format(&[b'a', 0]), None);
+        assert_eq!(ascii.parse("cafe", 4), Ok(b"cafe".to_vec()));
+        assert!(ascii.parse("café", 8).is_err());
+        assert!(ascii.parse("cafe!", 4).is_err());
+    }
+}
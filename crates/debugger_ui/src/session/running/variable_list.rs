@@ -2,8 +2,8 @@ use crate::session::running::{RunningState, memory_view::MemoryView};
 
 use super::stack_frame_list::{StackFrameList, StackFrameListEvent};
 use dap::{
-    ScopePresentationHint, StackFrameId, VariablePresentationHint, VariablePresentationHintKind,
-    VariableReference,
+    Capabilities, ScopePresentationHint, StackFrameId, VariablePresentationHint,
+    VariablePresentationHintAttributes, VariablePresentationHintKind, VariableReference,
 };
 use editor::Editor;
 use gpui::{
@@ -134,6 +134,60 @@ impl DapEntry {
     }
 }
 
+/// The request that assigns a new value to a variable list entry.
+#[derive(Debug, PartialEq)]
+enum ValueAssignment {
+    Variable {
+        variables_reference: VariableReference,
+        name: String,
+    },
+    Expression(String),
+}
+
+impl ValueAssignment {
+    /// Picks how the entry's value can be changed with the adapter's capabilities, preferring
+    /// `setExpression` for variables that have an `evaluateName`, as the DAP spec recommends.
+    fn for_entry(
+        entry: &DapEntry,
+        parent_reference: VariableReference,
+        capabilities: &Capabilities,
+    ) -> Option<Self> {
+        let supports_set_expression = capabilities.supports_set_expression.unwrap_or_default();
+        match entry {
+            DapEntry::Watcher(watcher) => (supports_set_expression
+                && !is_read_only(watcher.presentation_hint.as_ref()))
+            .then(|| Self::Expression(watcher.expression.to_string())),
+            DapEntry::Variable(variable) => {
+                if is_read_only(variable.presentation_hint.as_ref()) {
+                    None
+                } else if let Some(evaluate_name) = variable
+                    .evaluate_name
+                    .clone()
+                    .filter(|_| supports_set_expression)
+                {
+                    Some(Self::Expression(evaluate_name))
+                } else if capabilities.supports_set_variable.unwrap_or_default() {
+                    Some(Self::Variable {
+                        variables_reference: parent_reference,
+                        name: variable.name.clone(),
+                    })
+                } else {
+                    None
+                }
+            }
+            DapEntry::Scope(_) => None,
+        }
+    }
+}
+
+fn is_read_only(presentation_hint: Option<&VariablePresentationHint>) -> bool {
+    presentation_hint
+        .and_then(|hint| hint.attributes.as_ref())
+        .is_some_and(|attributes| {
+            attributes.contains(&VariablePresentationHintAttributes::ReadOnly)
+        })
+}
+
 #[derive(Debug, Clone, PartialEq)]
 struct ListEntry {
     entry: DapEntry,
@@ -535,12 +589,12 @@ impl VariableList {
 
     fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
         if let Some((var_path, editor)) = self.edited_path.take() {
-            let Some(state) = self.entry_states.get(&var_path) else {
-                return;
-            };
-
-            let variables_reference = state.parent_reference;
-            let Some(name) = var_path.leaf_name else {
+            let Some(assignment) = self
+                .entries
+                .iter()
+                .find(|entry| entry.path == var_path)
+                .and_then(|entry| self.value_assignment(entry, cx))
+            else {
                 return;
             };
 
@@ -550,18 +604,29 @@ impl VariableList {
 
             let value = editor.read(cx).text(cx);
 
-            self.session.update(cx, |session, cx| {
-                session.set_variable_value(
-                    stack_frame_id,
+            self.session.update(cx, |session, cx| match assignment {
+                ValueAssignment::Variable {
                     variables_reference,
-                    name.into(),
-                    value,
-                    cx,
-                )
+                    name,
+                } => {
+                    session.set_variable_value(stack_frame_id, variables_reference, name, value, cx)
+                }
+                ValueAssignment::Expression(expression) => {
+                    session.set_expression(stack_frame_id, expression, value, cx)
+                }
             });
         }
     }
 
+    fn value_assignment(&self, entry: &ListEntry, cx: &App) -> Option<ValueAssignment> {
+        let parent_reference = self.entry_states.get(&entry.path)?.parent_reference;
+        ValueAssignment::for_entry(
+            &entry.entry,
+            parent_reference,
+            self.session.read(cx).capabilities(),
+        )
+    }
+
     fn collapse_selected_entry(
         &mut self,
         _: &CollapseSelectedEntry,
@@ -656,13 +721,10 @@ impl VariableList {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let (supports_set_variable, supports_data_breakpoints, supports_go_to_memory) =
+        let can_edit_value = self.value_assignment(&entry, cx).is_some();
+        let (supports_data_breakpoints, supports_go_to_memory) =
             self.session.read_with(cx, |session, _| {
                 (
-                    session
-                        .capabilities()
-                        .supports_set_variable
-                        .unwrap_or_default(),
                     session
                         .capabilities()
                         .supports_data_breakpoints
@@ -706,7 +768,7 @@ impl VariableList {
                     menu.when_some(entry.as_variable(), |menu, _| {
                         menu.action("Copy Name", CopyVariableName.boxed_clone())
                             .action("Copy Value", CopyVariableValue.boxed_clone())
-                            .when(supports_set_variable, |menu| {
+                            .when(can_edit_value, |menu| {
                                 menu.action("Edit Value", EditVariable.boxed_clone())
                             })
                             .when(supports_go_to_memory, |menu| {
@@ -747,7 +809,7 @@ impl VariableList {
                     .when(entry.as_watcher().is_some(), |menu| {
                         menu.action("Copy Name", CopyVariableName.boxed_clone())
                             .action("Copy Value", CopyVariableValue.boxed_clone())
-                            .when(supports_set_variable, |menu| {
+                            .when(can_edit_value, |menu| {
                                 menu.action("Edit Value", EditVariable.boxed_clone())
                             })
                             .action("Remove Watch", RemoveWatch.boxed_clone())
@@ -904,6 +966,10 @@ impl VariableList {
             return;
         };
 
+        if self.value_assignment(entry, cx).is_none() {
+            return;
+        }
+
         let variable_value = match &entry.entry {
             DapEntry::Watcher(watcher) => watcher.value.to_string(),
             DapEntry::Variable(variable) => variable.value.clone(),
@@ -1122,13 +1188,7 @@ impl VariableList {
                     } else {
                         this.text_color(cx.theme().colors().text_muted)
                             .when(
-                                !self.disabled
-                                    && self
-                                        .session
-                                        .read(cx)
-                                        .capabilities()
-                                        .supports_set_variable
-                                        .unwrap_or_default(),
+                                !self.disabled && self.value_assignment(entry, cx).is_some(),
                                 |this| {
                                     let path = entry.path.clone();
                                     let variable_value = value.clone();
@@ -1655,4 +1715,72 @@ mod tests {
             "😀->hap...->cool"
         );
     }
+
+    #[test]
+    fn test_value_assignment() {
+        let variable = |evaluate_name: Option<&str>, read_only: bool| {
+            DapEntry::Variable(dap::Variable {
+                name: "x".into(),
+                value: "1".into(),
+                type_: None,
+                presentation_hint: read_only.then(|| VariablePresentationHint {
+                    kind: None,
+                    attributes: Some(vec![VariablePresentationHintAttributes::ReadOnly]),
+                    visibility: None,
+                    lazy: None,
+                }),
+                evaluate_name: evaluate_name.map(Into::into),
+                variables_reference: 0,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                declaration_location_reference: None,
+                value_location_reference: None,
+            })
+        };
+        let watcher = DapEntry::Watcher(Watcher {
+            expression: "a.x".into(),
+            value: "1".into(),
+            variables_reference: 0,
+            presentation_hint: None,
+        });
+        let set_variable = Capabilities {
+            supports_set_variable: Some(true),
+            ..Default::default()
+        };
+        let set_expression = Capabilities {
+            supports_set_expression: Some(true),
+            ..Default::default()
+        };
+        let both = Capabilities {
+            supports_set_variable: Some(true),
+            supports_set_expression: Some(true),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ValueAssignment::for_entry(&variable(Some("a.x"), false), 3, &set_variable),
+            Some(ValueAssignment::Variable {
+                variables_reference: 3,
+                name: "x".into()
+            })
+        );
+        assert_eq!(
+            ValueAssignment::for_entry(&variable(Some("a.x"), false), 3, &both),
+            Some(ValueAssignment::Expression("a.x".into()))
+        );
+        assert_eq!(
+            ValueAssignment::for_entry(&variable(None, false), 3, &set_expression),
+            None
+        );
+        assert_eq!(
+            ValueAssignment::for_entry(&variable(Some("a.x"), true), 3, &both),
+            None
+        );
+        assert_eq!(
+            ValueAssignment::for_entry(&watcher, 3, &both),
+            Some(ValueAssignment::Expression("a.x".into()))
+        );
+        assert_eq!(ValueAssignment::for_entry(&watcher, 3, &set_variable), None);
+    }
 }
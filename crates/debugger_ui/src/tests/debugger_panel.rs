@@ -9,8 +9,8 @@ use dap::{
     adapters::DebugTaskDefinition,
     client::SessionId,
     requests::{
-        Continue, Disconnect, Launch, Next, RunInTerminal, SetBreakpoints, StackTrace,
-        StartDebugging, StepBack, StepIn, StepOut, Threads,
+        Continue, Disconnect, Initialize, Launch, Next, RunInTerminal, SetBreakpoints,
+        SetFunctionBreakpoints, StackTrace, StartDebugging, StepBack, StepIn, StepOut, Threads,
     },
 };
 use editor::{
@@ -20,7 +20,10 @@ use editor::{
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use project::{
     FakeFs, Project,
-    debugger::session::{ThreadId, ThreadStatus},
+    debugger::{
+        breakpoint_store::BreakpointEditAction,
+        session::{ThreadId, ThreadStatus},
+    },
 };
 use serde_json::json;
 use std::{
@@ -1360,6 +1363,86 @@ async fn test_unsetting_breakpoints_on_clear_breakpoint_action(
     cx.run_until_parked();
 }
 
+#[gpui::test]
+async fn test_send_function_breakpoints(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+    breakpoint_store.update(cx, |store, cx| store.add_function_breakpoint("main", cx));
+
+    let sent_breakpoints = Arc::new(parking_lot::Mutex::new(Vec::<Vec<String>>::new()));
+    let session = start_debug_session(&workspace, cx, {
+        let sent_breakpoints = sent_breakpoints.clone();
+        move |client| {
+            client.on_request::<Initialize, _>(move |_, _| {
+                Ok(dap::Capabilities {
+                    supports_function_breakpoints: Some(true),
+                    ..Default::default()
+                })
+            });
+            let sent_breakpoints = sent_breakpoints.clone();
+            client.on_request::<SetFunctionBreakpoints, _>(move |_, args| {
+                sent_breakpoints.lock().push(
+                    args.breakpoints
+                        .into_iter()
+                        .map(|breakpoint| breakpoint.name)
+                        .collect(),
+                );
+                Ok(dap::SetFunctionBreakpointsResponse {
+                    breakpoints: Vec::new(),
+                })
+            });
+        }
+    })
+    .unwrap();
+    cx.run_until_parked();
+
+    let take_sent_breakpoints = || std::mem::take(&mut *sent_breakpoints.lock());
+    assert_eq!(
+        take_sent_breakpoints(),
+        [vec!["main".to_string()]],
+        "Function breakpoints must be sent while initializing the session"
+    );
+
+    breakpoint_store.update(cx, |store, cx| store.add_function_breakpoint("helper", cx));
+    cx.run_until_parked();
+    assert_eq!(
+        take_sent_breakpoints(),
+        [vec!["main".to_string(), "helper".to_string()]]
+    );
+
+    breakpoint_store.update(cx, |store, cx| {
+        store.edit_function_breakpoint("main".into(), BreakpointEditAction::InvertState, cx)
+    });
+    cx.run_until_parked();
+    assert_eq!(
+        take_sent_breakpoints(),
+        [vec!["helper".to_string()]],
+        "Disabled function breakpoints must not be sent"
+    );
+
+    session
+        .update(cx, |session, cx| session.toggle_ignore_breakpoints(cx))
+        .await;
+    cx.run_until_parked();
+    assert_eq!(
+        take_sent_breakpoints(),
+        [Vec::<String>::new()],
+        "Ignoring breakpoints must unset function breakpoints"
+    );
+
+    session
+        .update(cx, |session, cx| session.toggle_ignore_breakpoints(cx))
+        .await;
+    cx.run_until_parked();
+    assert_eq!(take_sent_breakpoints(), [vec!["helper".to_string()]]);
+}
+
 #[gpui::test]
 async fn test_debug_session_is_shutdown_when_attach_and_launch_request_fails(
     executor: BackgroundExecutor,
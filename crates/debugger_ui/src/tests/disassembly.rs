@@ -0,0 +1,144 @@
+use crate::tests::{
+    active_debug_session_panel, init_test, init_test_workspace, start_debug_session,
+};
+use dap::{
+    client::DebugAdapterClient,
+    requests::{Disassemble, Initialize, SetInstructionBreakpoints},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use std::sync::Arc;
+use util::path;
+
+fn instruction(address: &str, instruction: &str) -> dap::DisassembledInstruction {
+    dap::DisassembledInstruction {
+        address: address.into(),
+        instruction_bytes: None,
+        instruction: instruction.into(),
+        symbol: None,
+        location: None,
+        line: None,
+        column: None,
+        end_line: None,
+        end_column: None,
+        presentation_hint: None,
+    }
+}
+
+#[gpui::test]
+async fn test_disassemble_and_instruction_breakpoints(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let sent_breakpoints = Arc::new(parking_lot::Mutex::new(Vec::<Vec<String>>::new()));
+    let configure = {
+        let sent_breakpoints = sent_breakpoints.clone();
+        move |client: &Arc<DebugAdapterClient>| {
+            client.on_request::<Initialize, _>(move |_, _| {
+                Ok(dap::Capabilities {
+                    supports_disassemble_request: Some(true),
+                    supports_instruction_breakpoints: Some(true),
+                    ..Default::default()
+                })
+            });
+            client.on_request::<Disassemble, _>(move |_, args| {
+                assert_eq!(args.memory_reference, "0x1000");
+                assert_eq!(args.instruction_offset, Some(-1));
+                assert_eq!(args.instruction_count, 3);
+                Ok(dap::DisassembleResponse {
+                    instructions: vec![
+                        instruction("0x0ffc", "nop"),
+                        instruction("0x1000", "mov eax, 1"),
+                        instruction("0x1004", "ret"),
+                    ],
+                })
+            });
+            let sent_breakpoints = sent_breakpoints.clone();
+            client.on_request::<SetInstructionBreakpoints, _>(move |_, args| {
+                sent_breakpoints.lock().push(
+                    args.breakpoints
+                        .into_iter()
+                        .map(|breakpoint| breakpoint.instruction_reference)
+                        .collect(),
+                );
+                Ok(dap::SetInstructionBreakpointsResponse {
+                    breakpoints: Vec::new(),
+                })
+            });
+        }
+    };
+
+    let session = start_debug_session(&workspace, cx, configure.clone()).unwrap();
+    cx.run_until_parked();
+    assert!(
+        sent_breakpoints.lock().is_empty(),
+        "No instruction breakpoints should be sent before any are set"
+    );
+
+    let instructions = session
+        .update(cx, |session, cx| {
+            session.disassemble("0x1000".into(), -1, 3, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        instructions
+            .iter()
+            .map(|instruction| instruction.address.as_str())
+            .collect::<Vec<_>>(),
+        ["0x0ffc", "0x1000", "0x1004"]
+    );
+
+    session.update(cx, |session, cx| {
+        session.toggle_instruction_breakpoint("0x00001000", cx);
+        session.toggle_instruction_breakpoint("0x1004", cx);
+    });
+    cx.run_until_parked();
+    session.update(cx, |session, _| {
+        assert!(session.has_instruction_breakpoint("0x1000"));
+        assert!(session.has_instruction_breakpoint("0X1004"));
+        assert!(!session.has_instruction_breakpoint("0x0ffc"));
+    });
+    assert_eq!(
+        sent_breakpoints.lock().last().unwrap(),
+        &["0x1000".to_string(), "0x1004".to_string()]
+    );
+
+    // Toggling an address formatted differently removes its breakpoint.
+    session.update(cx, |session, cx| {
+        session.toggle_instruction_breakpoint("0X1004", cx);
+    });
+    cx.run_until_parked();
+    assert_eq!(
+        sent_breakpoints.lock().last().unwrap(),
+        &["0x1000".to_string()]
+    );
+
+    // The breakpoints are sent again while initializing the restarted session.
+    sent_breakpoints.lock().clear();
+    let _subscription = project::debugger::test::intercept_debug_sessions(cx, configure);
+    session.update(cx, |session, cx| session.restart(None, cx));
+    cx.run_until_parked();
+
+    let restarted_session = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state().read(cx).session().clone()
+    });
+    assert_ne!(
+        restarted_session.read_with(cx, |session, _| session.session_id()),
+        session.read_with(cx, |session, _| session.session_id())
+    );
+    restarted_session.read_with(cx, |session, _| {
+        assert!(session.has_instruction_breakpoint("0x1000"));
+    });
+    assert_eq!(
+        sent_breakpoints.lock().as_slice(),
+        [vec!["0x1000".to_string()]]
+    );
+}
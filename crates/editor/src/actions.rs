@@ -778,6 +778,8 @@ actions!(
         Backtab,
         /// Toggles a breakpoint at the current line.
         ToggleBreakpoint,
+        /// Toggles a breakpoint on the statement at the cursor, rather than the whole line.
+        ToggleInlineBreakpoint,
         /// Toggles the case of selected text.
         ToggleCase,
         /// Disables the breakpoint at the current line.
@@ -36,6 +36,7 @@ mod persistence;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
+mod semantic_tokens;
 mod split;
 pub mod tasks;
 
@@ -88,7 +89,7 @@ use code_context_menus::{
     AvailableCodeAction, CodeActionContents, CodeActionsItem, CodeActionsMenu, CodeContextMenu,
     CompletionsMenu, ContextMenuOrigin,
 };
-use collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use convert_case::{Case, Casing};
 use dap::TelemetrySpawnLocation;
 use display_map::*;
@@ -144,10 +145,10 @@ use multi_buffer::{
 use parking_lot::Mutex;
 use persistence::DB;
 use project::{
-    BreakpointWithPosition, CodeAction, Completion, CompletionDisplayOptions, CompletionIntent,
-    CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, InlayHint, InlayId,
-    InvalidationStrategy, Location, LocationLink, LspAction, PrepareRenameResponse, Project,
-    ProjectItem, ProjectPath, ProjectTransaction, TaskSourceKind,
+    BreakpointWithPosition, BufferSemanticTokens, CodeAction, Completion, CompletionDisplayOptions,
+    CompletionIntent, CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight,
+    InlayHint, InlayId, InvalidationStrategy, Location, LocationLink, LspAction,
+    PrepareRenameResponse, Project, ProjectItem, ProjectPath, ProjectTransaction, TaskSourceKind,
     debugger::{
         breakpoint_store::{
             Breakpoint, BreakpointEditAction, BreakpointSessionState, BreakpointState,
@@ -189,7 +190,10 @@ use std::{
     time::{Duration, Instant},
 };
 use task::{ResolvedTask, RunnableTag, TaskTemplate, TaskVariables};
-use text::{BufferId, FromAnchor, OffsetUtf16, Rope, ToOffset as _};
+use text::{
+    BufferId, FromAnchor, OffsetUtf16, PointUtf16, Rope, ToOffset as _, ToPointUtf16 as _,
+    Unclipped,
+};
 use theme::{
     AccentColors, ActiveTheme, PlayerColor, StatusColors, SyntaxTheme, Theme, ThemeSettings,
     observe_buffer_font_size_adjustment,
@@ -236,6 +240,7 @@ pub(crate) const CODE_ACTION_TIMEOUT: Duration = Duration::from_secs(5);
 pub(crate) const FORMAT_TIMEOUT: Duration = Duration::from_secs(5);
 pub(crate) const SCROLL_CENTER_TOP_BOTTOM_DEBOUNCE_TIMEOUT: Duration = Duration::from_secs(1);
 pub const FETCH_COLORS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(150);
+pub const FETCH_SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(150);
 
 pub(crate) const EDIT_PREDICTION_KEY_CONTEXT: &str = "edit_prediction";
 pub(crate) const EDIT_PREDICTION_CONFLICT_KEY_CONTEXT: &str = "edit_prediction_conflict";
@@ -271,6 +276,7 @@ impl ReportEditorEvent {
 }
 
 pub enum ActiveDebugLine {}
+enum BreakpointLocationMarker {}
 pub enum DebugStackFrameLine {}
 enum DocumentHighlightRead {}
 enum DocumentHighlightWrite {}
@@ -1174,6 +1180,7 @@ pub struct Editor {
     tasks: BTreeMap<(BufferId, BufferRow), RunnableTasks>,
     tasks_update_task: Option<Task<()>>,
     breakpoint_store: Option<Entity<BreakpointStore>>,
+    breakpoint_location_markers_task: Task<()>,
     gutter_breakpoint_indicator: (Option<PhantomBreakpointIndicator>, Option<Task<()>>),
     hovered_diff_hunk_row: Option<DisplayRow>,
     pull_diagnostics_task: Task<()>,
@@ -1203,6 +1210,9 @@ pub struct Editor {
     colors: Option<LspColorData>,
     post_scroll_update: Task<()>,
     refresh_colors_task: Task<()>,
+    semantic_tokens: HashMap<BufferId, BufferSemanticTokens>,
+    semantic_token_styles: semantic_tokens::SemanticTokenStyles,
+    refresh_semantic_tokens_task: Task<()>,
     inlay_hints: Option<LspInlayHintData>,
     folding_newlines: Task<()>,
     select_next_is_case_sensitive: Option<bool>,
@@ -1973,6 +1983,9 @@ impl Editor {
                     project::Event::RefreshCodeLens => {
                         // we always query lens with actions, without storing them, always refreshing them
                     }
+                    project::Event::RefreshSemanticTokens => {
+                        editor.refresh_semantic_tokens(None, window, cx);
+                    }
                     project::Event::RefreshInlayHints {
                         server_id,
                         request_id,
@@ -2125,6 +2138,7 @@ impl Editor {
                     BreakpointStoreEvent::ClearDebugLines => {
                         editor.clear_row_highlights::<ActiveDebugLine>();
                         editor.refresh_inline_values(cx);
+                        editor.refresh_breakpoint_location_markers(cx);
                     }
                     BreakpointStoreEvent::SetDebugLine => {
                         if editor.go_to_active_debug_line(window, cx) {
@@ -2132,8 +2146,13 @@ impl Editor {
                         }
 
                         editor.refresh_inline_values(cx);
+                        editor.refresh_breakpoint_location_markers(cx);
                     }
-                    _ => {}
+                    BreakpointStoreEvent::BreakpointsUpdated(..)
+                    | BreakpointStoreEvent::BreakpointsCleared(_) => {
+                        editor.refresh_breakpoint_location_markers(cx);
+                    }
+                    BreakpointStoreEvent::FunctionBreakpointsUpdated => {}
                 },
             ));
             let git_store = project.read(cx).git_store().clone();
@@ -2373,10 +2392,14 @@ impl Editor {
                 })
                 .unwrap_or_default(),
             tasks_update_task: None,
+            breakpoint_location_markers_task: Task::ready(()),
             pull_diagnostics_task: Task::ready(()),
             pull_diagnostics_background_task: Task::ready(()),
             colors: None,
             refresh_colors_task: Task::ready(()),
+            semantic_tokens: HashMap::default(),
+            semantic_token_styles: Default::default(),
+            refresh_semantic_tokens_task: Task::ready(()),
             inlay_hints: None,
             next_color_inlay_id: 0,
             post_scroll_update: Task::ready(()),
@@ -2428,6 +2451,7 @@ impl Editor {
                 }));
         }
         editor.tasks_update_task = Some(editor.refresh_runnables(window, cx));
+        editor.refresh_breakpoint_location_markers(cx);
         editor._subscriptions.extend(project_subscriptions);
 
         editor._subscriptions.push(cx.subscribe_in(
@@ -2455,6 +2479,7 @@ impl Editor {
                                 .update_in(cx, |editor, window, cx| {
                                     editor.register_visible_buffers(cx);
                                     editor.refresh_colors_for_visible_range(None, window, cx);
+                                    editor.refresh_semantic_tokens(None, window, cx);
                                     editor.refresh_inlay_hints(
                                         InlayHintRefreshReason::NewLinesShown,
                                         cx,
@@ -11361,7 +11386,7 @@ impl Editor {
                         snapshot
                             .display_snapshot
                             .buffer_snapshot()
-                            .anchor_after(Point::new(cursor_position.row, 0))
+                            .anchor_before(Point::new(cursor_position.row, 0))
                     });
 
                 let breakpoint = self
@@ -11451,6 +11476,170 @@ impl Editor {
         }
     }
 
+    /// Toggles a breakpoint on a statement within the line at each cursor, snapped to the closest
+    /// column where the debug adapter reports it can stop.
+    pub fn toggle_inline_breakpoint(
+        &mut self,
+        _: &crate::actions::ToggleInlineBreakpoint,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(breakpoint_store) = self.breakpoint_store.clone() else {
+            return;
+        };
+        let session = self.breakpoint_locations_session(cx);
+
+        let multibuffer = self.buffer.read(cx);
+        let mut toggles = Vec::new();
+        for selection in self.selections.disjoint_anchors_arc().iter() {
+            let head = selection.head();
+            let Some(buffer) = multibuffer.buffer_for_anchor(head, cx) else {
+                continue;
+            };
+            let Some(abs_path) = BreakpointStore::abs_path_from_buffer(&buffer, cx) else {
+                continue;
+            };
+            let position = head.text_anchor.to_point_utf16(&buffer.read(cx).snapshot());
+            let locations = session.as_ref().map_or_else(
+                || Task::ready(Ok(Vec::new())),
+                |session| {
+                    session
+                        .read(cx)
+                        .breakpoint_locations(&abs_path, position.row, cx)
+                },
+            );
+            toggles.push((buffer, abs_path, position, locations));
+        }
+
+        cx.spawn(async move |_, cx| {
+            for (buffer, abs_path, position, locations) in toggles {
+                let locations = locations.await.log_err().unwrap_or_default();
+                let column = snap_to_breakpoint_location(position.column, &locations);
+                breakpoint_store
+                    .update(cx, |breakpoint_store, cx| {
+                        let snapshot = buffer.read(cx).snapshot();
+                        let point = snapshot.clip_point_utf16(
+                            Unclipped(PointUtf16::new(position.row, column)),
+                            Bias::Left,
+                        );
+                        let breakpoint = breakpoint_store
+                            .breakpoint_at_position(&abs_path, point, cx)
+                            .map(|(_, breakpoint)| breakpoint)
+                            .unwrap_or_else(|| BreakpointWithPosition {
+                                position: snapshot.anchor_before(point),
+                                bp: Breakpoint::new_standard(),
+                            });
+                        breakpoint_store.toggle_breakpoint(
+                            buffer,
+                            breakpoint,
+                            BreakpointEditAction::Toggle,
+                            cx,
+                        );
+                    })
+                    .ok();
+            }
+        })
+        .detach();
+    }
+
+    /// The session to ask for breakpoint locations, preferring the one that's stopped.
+    fn breakpoint_locations_session(&self, cx: &App) -> Option<Entity<Session>> {
+        let project = self.project.as_ref()?.read(cx);
+        project
+            .active_debug_session(cx)
+            .map(|(session, _)| session)
+            .or_else(|| {
+                project
+                    .dap_store()
+                    .read(cx)
+                    .sessions()
+                    .find(|session| {
+                        session
+                            .read(cx)
+                            .capabilities()
+                            .supports_breakpoint_locations_request
+                            .unwrap_or_default()
+                    })
+                    .cloned()
+            })
+    }
+
+    /// Highlights inline breakpoints, and the columns where the debug adapter can stop on the
+    /// lines that have breakpoints.
+    fn refresh_breakpoint_location_markers(&mut self, cx: &mut Context<Self>) {
+        let Some(breakpoint_store) = self.breakpoint_store.clone() else {
+            return;
+        };
+        let session = self.breakpoint_locations_session(cx);
+
+        let multibuffer = self.buffer.read(cx);
+        let multibuffer_snapshot = multibuffer.snapshot(cx);
+        let mut markers = Vec::new();
+        let mut location_requests = Vec::new();
+        for (excerpt_id, buffer_snapshot, excerpt_range) in multibuffer_snapshot.excerpts() {
+            let Some(buffer) = multibuffer.buffer(buffer_snapshot.remote_id()) else {
+                continue;
+            };
+            let Some(abs_path) = BreakpointStore::abs_path_from_buffer(&buffer, cx) else {
+                continue;
+            };
+
+            let mut rows = BTreeSet::new();
+            for (breakpoint, _) in breakpoint_store.read(cx).breakpoints(
+                &buffer,
+                Some(excerpt_range.context.clone()),
+                buffer_snapshot,
+                cx,
+            ) {
+                let position = breakpoint.position.to_point_utf16(buffer_snapshot);
+                if position.column > 0 {
+                    markers.push(breakpoint_location_marker(
+                        excerpt_id,
+                        buffer_snapshot,
+                        position,
+                    ));
+                }
+                rows.insert(position.row);
+            }
+
+            if let Some(session) = &session {
+                for row in rows {
+                    let locations = session.read(cx).breakpoint_locations(&abs_path, row, cx);
+                    location_requests.push((excerpt_id, buffer_snapshot.clone(), row, locations));
+                }
+            }
+        }
+
+        self.breakpoint_location_markers_task = cx.spawn(async move |editor, cx| {
+            for (excerpt_id, buffer_snapshot, row, locations) in location_requests {
+                let Some(columns) = locations.await.log_err() else {
+                    continue;
+                };
+                markers.extend(columns.into_iter().map(|column| {
+                    breakpoint_location_marker(
+                        excerpt_id,
+                        &buffer_snapshot,
+                        PointUtf16::new(row, column),
+                    )
+                }));
+            }
+
+            editor
+                .update(cx, |editor, cx| {
+                    if markers.is_empty() {
+                        editor.clear_background_highlights::<BreakpointLocationMarker>(cx);
+                    } else {
+                        editor.highlight_background::<BreakpointLocationMarker>(
+                            &markers,
+                            |_, theme| theme.colors().debugger_accent.opacity(0.3),
+                            cx,
+                        );
+                    }
+                })
+                .ok();
+        });
+    }
+
     pub fn edit_breakpoint_at_anchor(
         &mut self,
         breakpoint_position: Anchor,
@@ -22230,6 +22419,15 @@ impl Editor {
                 self.colorize_brackets(true, cx);
             }
 
+            if language_settings_changed
+                || !self.semantic_token_styles.is_for_theme(cx.theme().syntax())
+            {
+                self.highlight_semantic_tokens(cx);
+            }
+            if language_settings_changed {
+                self.refresh_semantic_tokens(None, window, cx);
+            }
+
             if let Some(inlay_splice) = self.colors.as_mut().and_then(|colors| {
                 colors.render_mode_updated(EditorSettings::get_global(cx).lsp_document_colors)
             }) {
@@ -23143,6 +23341,7 @@ impl Editor {
     ) {
         self.pull_diagnostics(for_buffer, window, cx);
         self.refresh_colors_for_visible_range(for_buffer, window, cx);
+        self.refresh_semantic_tokens(for_buffer, window, cx);
     }
 
     fn register_visible_buffers(&mut self, cx: &mut Context<Self>) {
@@ -26145,3 +26344,30 @@ pub fn multibuffer_context_lines(cx: &App) -> u32 {
         .unwrap_or(2)
         .min(32)
 }
+
+/// Picks the last breakpoint location at or before the column, or the first one on the line when
+/// the column is before all of them.
+fn snap_to_breakpoint_location(column: u32, locations: &[u32]) -> u32 {
+    locations
+        .iter()
+        .rev()
+        .find(|location| **location <= column)
+        .or(locations.first())
+        .copied()
+        .unwrap_or(column)
+}
+
+/// The range of the character at a breakpoint location, used to mark it in the editor.
+fn breakpoint_location_marker(
+    excerpt_id: ExcerptId,
+    buffer_snapshot: &BufferSnapshot,
+    position: PointUtf16,
+) -> Range<Anchor> {
+    let start = buffer_snapshot.clip_point_utf16(Unclipped(position), Bias::Left);
+    let end = buffer_snapshot.clip_point_utf16(
+        Unclipped(PointUtf16::new(position.row, position.column + 1)),
+        Bias::Right,
+    );
+    Anchor::in_buffer(excerpt_id, buffer_snapshot.anchor_before(start))
+        ..Anchor::in_buffer(excerpt_id, buffer_snapshot.anchor_after(end))
+}
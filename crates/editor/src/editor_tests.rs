@@ -23720,6 +23720,15 @@ fn add_log_breakpoint_at_cursor(
     );
 }
 
+#[test]
+fn test_snap_to_breakpoint_location() {
+    assert_eq!(snap_to_breakpoint_location(7, &[]), 7);
+    assert_eq!(snap_to_breakpoint_location(2, &[4, 10, 16]), 4);
+    assert_eq!(snap_to_breakpoint_location(10, &[4, 10, 16]), 10);
+    assert_eq!(snap_to_breakpoint_location(13, &[4, 10, 16]), 10);
+    assert_eq!(snap_to_breakpoint_location(40, &[4, 10, 16]), 16);
+}
+
 #[gpui::test]
 async fn test_breakpoint_toggling(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -0,0 +1,268 @@
+//! Semantic token highlights, provided by language servers via `textDocument/semanticTokens`.
+//! Layered on top of the tree-sitter highlights, and mapped onto the theme syntax styles
+//! by the token type and modifiers, e.g. `variable.mutable`, `function.unsafe` or `parameter`.
+
+use std::{ops::Range, sync::Arc};
+
+use collections::HashMap;
+use futures::future::join_all;
+use gpui::{Context, Task, Window};
+use itertools::Itertools as _;
+use language::language_settings;
+use lsp::LanguageServerId;
+use multi_buffer::Anchor;
+use project::{BufferSemanticTokens, ServerSemanticTokens};
+use text::BufferId;
+use theme::SyntaxTheme;
+use ui::ActiveTheme as _;
+
+use crate::{Editor, FETCH_SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT};
+
+struct SemanticTokenHighlight;
+
+/// The theme styles of the semantic tokens, resolved once per syntax theme.
+#[derive(Default)]
+pub(super) struct SemanticTokenStyles {
+    syntax_theme: Option<Arc<SyntaxTheme>>,
+    /// Indices into the syntax theme highlights, by the server and the token type and modifiers.
+    style_ixs: HashMap<(LanguageServerId, u32, u32), Option<usize>>,
+}
+
+impl SemanticTokenStyles {
+    pub(super) fn is_for_theme(&self, syntax_theme: &Arc<SyntaxTheme>) -> bool {
+        self.syntax_theme
+            .as_ref()
+            .is_some_and(|known_theme| Arc::ptr_eq(known_theme, syntax_theme))
+    }
+
+    fn style_ix(
+        &mut self,
+        server_id: LanguageServerId,
+        server_tokens: &ServerSemanticTokens,
+        token: &project::SemanticToken,
+    ) -> Option<usize> {
+        let syntax_theme = self.syntax_theme.as_ref()?;
+        *self
+            .style_ixs
+            .entry((server_id, token.token_type, token.token_modifiers))
+            .or_insert_with(|| token_style_ix(syntax_theme, server_tokens, token))
+    }
+}
+
+impl Editor {
+    pub(super) fn refresh_semantic_tokens(
+        &mut self,
+        buffer_id: Option<BufferId>,
+        _: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.ignore_lsp_data() {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+
+        let visible_buffers = self
+            .visible_excerpts(true, cx)
+            .into_values()
+            .map(|(buffer, ..)| buffer)
+            .filter(|editor_buffer| {
+                let editor_buffer = editor_buffer.read(cx);
+                let editor_buffer_id = editor_buffer.remote_id();
+                buffer_id.is_none_or(|buffer_id| buffer_id == editor_buffer_id)
+                    && self.registered_buffers.contains_key(&editor_buffer_id)
+                    && language_settings::language_settings(
+                        editor_buffer.language().map(|language| language.name()),
+                        editor_buffer.file(),
+                        cx,
+                    )
+                    .semantic_tokens
+            })
+            .unique_by(|buffer| buffer.read(cx).remote_id())
+            .collect::<Vec<_>>();
+
+        let all_tokens_task = project.read(cx).lsp_store().update(cx, |lsp_store, cx| {
+            visible_buffers
+                .into_iter()
+                .filter_map(|buffer| {
+                    let buffer_id = buffer.read(cx).remote_id();
+                    let known_cache_version = self
+                        .semantic_tokens
+                        .get(&buffer_id)
+                        .and_then(|tokens| tokens.cache_version);
+                    let tokens_task = lsp_store.semantic_tokens(known_cache_version, buffer, cx)?;
+                    Some(async move { (buffer_id, tokens_task.await) })
+                })
+                .collect::<Vec<_>>()
+        });
+
+        if all_tokens_task.is_empty() {
+            self.refresh_semantic_tokens_task = Task::ready(());
+            return;
+        }
+
+        self.refresh_semantic_tokens_task = cx.spawn(async move |editor, cx| {
+            cx.background_executor()
+                .timer(FETCH_SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT)
+                .await;
+
+            let all_tokens = join_all(all_tokens_task).await;
+            editor
+                .update(cx, |editor, cx| {
+                    let mut updated = false;
+                    for (buffer_id, tokens) in all_tokens {
+                        match tokens {
+                            Ok(tokens) => {
+                                editor.semantic_tokens.insert(buffer_id, tokens);
+                                updated = true;
+                            }
+                            Err(e) => {
+                                log::error!("Failed to fetch semantic tokens: {e:#}");
+                            }
+                        }
+                    }
+                    if updated {
+                        editor.highlight_semantic_tokens(cx);
+                    }
+                })
+                .ok();
+        });
+    }
+
+    /// Re-creates the semantic token highlights from the tokens fetched,
+    /// resolving their styles against the current theme.
+    pub(super) fn highlight_semantic_tokens(&mut self, cx: &mut Context<Self>) {
+        if !self.mode.is_full() {
+            return;
+        }
+
+        let multi_buffer_snapshot = self.buffer().read(cx).snapshot(cx);
+        let syntax_theme = cx.theme().syntax().clone();
+        if !self.semantic_token_styles.is_for_theme(&syntax_theme) {
+            self.semantic_token_styles = SemanticTokenStyles {
+                syntax_theme: Some(syntax_theme.clone()),
+                style_ixs: HashMap::default(),
+            };
+        }
+        let mut ranges_by_style = HashMap::<usize, Vec<Range<Anchor>>>::default();
+        let mut buffers_to_remove = Vec::new();
+
+        let mut excerpts_by_buffer = HashMap::default();
+        for (excerpt_id, buffer_snapshot, excerpt_range) in multi_buffer_snapshot.excerpts() {
+            excerpts_by_buffer
+                .entry(buffer_snapshot.remote_id())
+                .or_insert_with(Vec::new)
+                .push((excerpt_id, buffer_snapshot, excerpt_range.context));
+        }
+
+        for (buffer_id, buffer_tokens) in &self.semantic_tokens {
+            let Some(excerpts) = excerpts_by_buffer.get(buffer_id) else {
+                buffers_to_remove.push(*buffer_id);
+                continue;
+            };
+            let Some((_, buffer_snapshot, _)) = excerpts.first() else {
+                continue;
+            };
+            if !language_settings::language_settings(
+                buffer_snapshot.language().map(|language| language.name()),
+                buffer_snapshot.file(),
+                cx,
+            )
+            .semantic_tokens
+            {
+                buffers_to_remove.push(*buffer_id);
+                continue;
+            }
+
+            for (server_id, server_tokens) in &buffer_tokens.servers {
+                for token in &server_tokens.tokens {
+                    let Some(style_ix) =
+                        self.semantic_token_styles
+                            .style_ix(*server_id, server_tokens, token)
+                    else {
+                        continue;
+                    };
+
+                    for (excerpt_id, buffer_snapshot, excerpt_range) in excerpts {
+                        if excerpt_range
+                            .start
+                            .cmp(&token.range.start, buffer_snapshot)
+                            .is_gt()
+                            || excerpt_range
+                                .end
+                                .cmp(&token.range.end, buffer_snapshot)
+                                .is_lt()
+                        {
+                            continue;
+                        }
+                        if let Some(range) = multi_buffer_snapshot
+                            .anchor_range_in_excerpt(*excerpt_id, token.range.clone())
+                        {
+                            ranges_by_style.entry(style_ix).or_default().push(range);
+                        }
+                    }
+                }
+            }
+        }
+
+        for buffer_id in buffers_to_remove {
+            self.semantic_tokens.remove(&buffer_id);
+        }
+
+        self.clear_highlights::<SemanticTokenHighlight>(cx);
+        for (style_ix, mut ranges) in ranges_by_style {
+            let Some((_, style)) = syntax_theme.highlights.get(style_ix) else {
+                continue;
+            };
+            ranges.sort_by(|a, b| a.start.cmp(&b.start, &multi_buffer_snapshot));
+            self.highlight_text_key::<SemanticTokenHighlight>(style_ix, ranges, *style, false, cx);
+        }
+    }
+}
+
+/// Resolves the token style from the theme: the most specific `{type}.{modifier}` style is preferred,
+/// then the `{type}` one, then the closest syntax styles tree-sitter grammars use for the same entities.
+///
+/// Returns the index of the style in the syntax theme highlights.
+fn token_style_ix(
+    syntax_theme: &SyntaxTheme,
+    server_tokens: &ServerSemanticTokens,
+    token: &project::SemanticToken,
+) -> Option<usize> {
+    let token_type = server_tokens.token_type(token)?;
+    server_tokens
+        .token_modifiers(token)
+        .map(|modifier| format!("{token_type}.{modifier}"))
+        .chain(Some(token_type.to_string()))
+        .find_map(|name| syntax_style_ix(syntax_theme, &name))
+        .or_else(|| {
+            fallback_syntax_names(token_type)
+                .iter()
+                .find_map(|name| syntax_style_ix(syntax_theme, name))
+        })
+}
+
+fn syntax_style_ix(syntax_theme: &SyntaxTheme, name: &str) -> Option<usize> {
+    syntax_theme
+        .highlights
+        .iter()
+        .position(|(highlight_name, _)| highlight_name == name)
+}
+
+fn fallback_syntax_names(token_type: &str) -> &'static [&'static str] {
+    match token_type {
+        "namespace" => &["namespace", "module", "type"],
+        "class" | "struct" | "interface" | "enum" | "typeAlias" | "builtinType" => &["type"],
+        "typeParameter" => &["type.parameter", "type"],
+        "parameter" => &["variable.parameter", "variable"],
+        "enumMember" => &["variant", "constant"],
+        "event" => &["property"],
+        "method" => &["function.method", "function"],
+        "macro" => &["function.special", "function"],
+        "decorator" => &["attribute"],
+        "regexp" => &["string.regex", "string"],
+        "modifier" => &["keyword"],
+        _ => &[],
+    }
+}
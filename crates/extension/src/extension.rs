@@ -39,6 +39,14 @@ pub trait WorktreeDelegate: Send + Sync + 'static {
     async fn shell_env(&self) -> Vec<(String, String)>;
 }
 
+#[async_trait]
+pub trait SubprojectDelegate: Send + Sync + 'static {
+    fn worktree_root_path(&self) -> String;
+    fn path(&self) -> String;
+    async fn read_text_file(&self, path: &RelPath) -> Result<String>;
+    async fn shell_env(&self) -> Vec<(String, String)>;
+}
+
 pub trait ProjectDelegate: Send + Sync + 'static {
     fn worktree_ids(&self) -> Vec<u64>;
 }
@@ -168,6 +176,19 @@ pub trait Extension: Send + Sync + 'static {
         locator_name: String,
         config: SpawnInTerminal,
     ) -> Result<DebugRequest>;
+
+    async fn list_toolchains(
+        &self,
+        toolchain_provider_id: Arc<str>,
+        subproject: Arc<dyn SubprojectDelegate>,
+    ) -> Result<ToolchainList>;
+
+    async fn resolve_toolchain(
+        &self,
+        toolchain_provider_id: Arc<str>,
+        path: PathBuf,
+        env: EnvVars,
+    ) -> Result<Toolchain>;
 }
 
 pub fn parse_wasm_extension_version(extension_id: &str, wasm_bytes: &[u8]) -> Result<Version> {
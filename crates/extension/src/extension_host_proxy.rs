@@ -8,7 +8,7 @@ use language::{BinaryStatus, LanguageMatcher, LanguageName, LoadedLanguage};
 use lsp::LanguageServerName;
 use parking_lot::RwLock;
 
-use crate::{Extension, SlashCommand};
+use crate::{Extension, SlashCommand, ToolchainProviderManifestEntry};
 
 #[derive(Default)]
 struct GlobalExtensionHostProxy(Arc<ExtensionHostProxy>);
@@ -29,6 +29,7 @@ pub struct ExtensionHostProxy {
     slash_command_proxy: RwLock<Option<Arc<dyn ExtensionSlashCommandProxy>>>,
     context_server_proxy: RwLock<Option<Arc<dyn ExtensionContextServerProxy>>>,
     debug_adapter_provider_proxy: RwLock<Option<Arc<dyn ExtensionDebugAdapterProviderProxy>>>,
+    toolchain_proxy: RwLock<Option<Arc<dyn ExtensionToolchainProxy>>>,
 }
 
 impl ExtensionHostProxy {
@@ -54,6 +55,7 @@ impl ExtensionHostProxy {
             slash_command_proxy: RwLock::default(),
             context_server_proxy: RwLock::default(),
             debug_adapter_provider_proxy: RwLock::default(),
+            toolchain_proxy: RwLock::default(),
         }
     }
 
@@ -90,6 +92,10 @@ impl ExtensionHostProxy {
             .write()
             .replace(Arc::new(proxy));
     }
+
+    pub fn register_toolchain_proxy(&self, proxy: impl ExtensionToolchainProxy) {
+        self.toolchain_proxy.write().replace(Arc::new(proxy));
+    }
 }
 
 pub trait ExtensionThemeProxy: Send + Sync + 'static {
@@ -446,3 +452,41 @@ impl ExtensionDebugAdapterProviderProxy for ExtensionHostProxy {
         proxy.unregister_debug_locator(locator_name)
     }
 }
+
+pub trait ExtensionToolchainProxy: Send + Sync + 'static {
+    fn register_toolchain_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        provider: ToolchainProviderManifestEntry,
+        fs: Arc<dyn Fs>,
+        cx: &mut App,
+    );
+
+    fn unregister_toolchain_provider(&self, language: &LanguageName);
+}
+
+impl ExtensionToolchainProxy for ExtensionHostProxy {
+    fn register_toolchain_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        provider: ToolchainProviderManifestEntry,
+        fs: Arc<dyn Fs>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.toolchain_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_toolchain_provider(extension, provider_id, provider, fs, cx)
+    }
+
+    fn unregister_toolchain_provider(&self, language: &LanguageName) {
+        let Some(proxy) = self.toolchain_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_toolchain_provider(language)
+    }
+}
@@ -93,6 +93,8 @@ pub struct ExtensionManifest {
     pub debug_adapters: BTreeMap<Arc<str>, DebugAdapterManifestEntry>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub debug_locators: BTreeMap<Arc<str>, DebugLocatorManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub toolchain_providers: BTreeMap<Arc<str>, ToolchainProviderManifestEntry>,
 }
 
 impl ExtensionManifest {
@@ -121,6 +123,7 @@ impl ExtensionManifest {
         !self.language_servers.is_empty()
             || !self.debug_adapters.is_empty()
             || !self.debug_locators.is_empty()
+            || !self.toolchain_providers.is_empty()
     }
 }
 
@@ -288,6 +291,22 @@ pub struct DebugAdapterManifestEntry {
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub struct DebugLocatorManifestEntry {}
 
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct ToolchainProviderManifestEntry {
+    /// The language to provide toolchains for.
+    pub language: LanguageName,
+    /// The term used in the UI to refer to toolchains, e.g. "Ruby Version".
+    pub term: String,
+    /// A placeholder shown when the user adds a toolchain by path.
+    #[serde(default)]
+    pub new_toolchain_placeholder: Option<String>,
+    /// The name of the manifest file marking the root of a subproject, e.g. "Gemfile".
+    ///
+    /// When unset, each worktree is a single subproject.
+    #[serde(default)]
+    pub manifest_name: Option<String>,
+}
+
 impl ExtensionManifest {
     pub async fn load(fs: Arc<dyn Fs>, extension_dir: &Path) -> Result<Self> {
         let extension_name = extension_dir
@@ -358,6 +377,7 @@ fn manifest_from_old_manifest(
         capabilities: Vec::new(),
         debug_adapters: Default::default(),
         debug_locators: Default::default(),
+        toolchain_providers: Default::default(),
     }
 }
 
@@ -391,6 +411,7 @@ mod tests {
             capabilities: vec![],
             debug_adapters: Default::default(),
             debug_locators: Default::default(),
+            toolchain_providers: Default::default(),
         }
     }
 
@@ -519,4 +540,31 @@ args = ["--serve"]
         assert_eq!(target.cmd, "./agent");
         assert_eq!(target.args, vec!["--serve"]);
     }
+
+    #[test]
+    fn parse_manifest_with_toolchain_provider() {
+        let toml_src = r#"
+id = "example.ruby"
+name = "Ruby"
+version = "1.0.0"
+schema_version = 1
+
+[toolchain_providers.rbenv]
+language = "Ruby"
+term = "Ruby Version"
+manifest_name = "Gemfile"
+"#;
+
+        let manifest: ExtensionManifest = toml::from_str(toml_src).expect("manifest should parse");
+        assert!(manifest.allow_remote_load());
+        assert_eq!(
+            manifest.toolchain_providers.get("rbenv"),
+            Some(&ToolchainProviderManifestEntry {
+                language: "Ruby".into(),
+                term: "Ruby Version".to_string(),
+                new_toolchain_placeholder: None,
+                manifest_name: Some("Gemfile".to_string()),
+            })
+        );
+    }
 }
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use task::ShellKind;
+
+use crate::EnvVars;
+
+/// A toolchain provided by an extension.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Toolchain {
+    /// The user-facing name of the toolchain.
+    pub name: String,
+    /// The absolute path of the toolchain.
+    pub path: String,
+    /// The directories to prepend to `PATH` when using the toolchain.
+    pub bin_paths: Vec<PathBuf>,
+    /// The environment variables to set when using the toolchain.
+    pub env: EnvVars,
+    /// The scripts that activate the toolchain in a terminal.
+    pub activation_scripts: Vec<ToolchainActivationScript>,
+}
+
+/// The commands that activate a toolchain in a shell.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolchainActivationScript {
+    /// The shell that the commands are for.
+    pub shell: ShellKind,
+    /// The commands to run in the shell.
+    pub commands: Vec<String>,
+}
+
+/// The toolchains available for a subproject.
+#[derive(Debug, Clone, Default)]
+pub struct ToolchainList {
+    /// The available toolchains.
+    pub toolchains: Vec<Toolchain>,
+    /// The index of the toolchain to select by default, if any.
+    pub default: Option<usize>,
+}
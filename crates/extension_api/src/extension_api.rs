@@ -16,8 +16,8 @@ pub use serde_json;
 // that we may want to shadow to provide a cleaner Rust API.
 pub use wit::{
     CodeLabel, CodeLabelSpan, CodeLabelSpanLiteral, Command, DownloadedFileType, EnvVars,
-    KeyValueStore, LanguageServerInstallationStatus, Project, Range, Worktree, download_file,
-    make_file_executable,
+    KeyValueStore, LanguageServerInstallationStatus, Project, Range, Subproject, Worktree,
+    download_file, make_file_executable,
     zed::extension::context_server::ContextServerConfiguration,
     zed::extension::dap::{
         AttachRequest, BuildTaskDefinition, BuildTaskDefinitionTemplatePayload, BuildTaskTemplate,
@@ -37,6 +37,7 @@ pub use wit::{
     zed::extension::slash_command::{
         SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput, SlashCommandOutputSection,
     },
+    zed::extension::toolchain::{ActivationScript, ShellKind, Toolchain, ToolchainList},
 };
 
 // Undocumented WIT re-exports.
@@ -259,6 +260,25 @@ pub trait Extension: Send + Sync {
     ) -> Result<DebugRequest, String> {
         Err("`run_dap_locator` not implemented".to_string())
     }
+
+    /// Returns the toolchains available for the given subproject.
+    fn list_toolchains(
+        &mut self,
+        _toolchain_provider_id: &ToolchainProviderId,
+        _subproject: &Subproject,
+    ) -> Result<ToolchainList> {
+        Err("`list_toolchains` not implemented".to_string())
+    }
+
+    /// Returns the toolchain at the given path, which was added by the user.
+    fn resolve_toolchain(
+        &mut self,
+        _toolchain_provider_id: &ToolchainProviderId,
+        _path: String,
+        _env: EnvVars,
+    ) -> Result<Toolchain> {
+        Err("`resolve_toolchain` not implemented".to_string())
+    }
 }
 
 /// Registers the provided type as a Zed extension.
@@ -518,6 +538,23 @@ impl wit::Guest for Component {
     ) -> Result<DebugRequest, String> {
         extension().run_dap_locator(locator_name, build_task)
     }
+
+    fn list_toolchains(
+        toolchain_provider_id: String,
+        subproject: &Subproject,
+    ) -> Result<ToolchainList, String> {
+        let toolchain_provider_id = ToolchainProviderId(toolchain_provider_id);
+        extension().list_toolchains(&toolchain_provider_id, subproject)
+    }
+
+    fn resolve_toolchain(
+        toolchain_provider_id: String,
+        path: String,
+        env: EnvVars,
+    ) -> Result<Toolchain, String> {
+        let toolchain_provider_id = ToolchainProviderId(toolchain_provider_id);
+        extension().resolve_toolchain(&toolchain_provider_id, path, env)
+    }
 }
 
 /// The ID of a language server.
@@ -552,6 +589,22 @@ impl fmt::Display for ContextServerId {
     }
 }
 
+/// The ID of a toolchain provider.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct ToolchainProviderId(String);
+
+impl AsRef<str> for ToolchainProviderId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ToolchainProviderId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl CodeLabelSpan {
     /// Returns a [`CodeLabelSpan::CodeRange`].
     pub fn code_range(range: impl Into<wit::Range>) -> Self {
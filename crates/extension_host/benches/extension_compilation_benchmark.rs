@@ -148,6 +148,7 @@ fn manifest() -> ExtensionManifest {
         )],
         debug_adapters: Default::default(),
         debug_locators: Default::default(),
+        toolchain_providers: Default::default(),
     }
 }
 
@@ -113,6 +113,7 @@ mod tests {
             capabilities: vec![],
             debug_adapters: Default::default(),
             debug_locators: Default::default(),
+            toolchain_providers: Default::default(),
         }
     }
 
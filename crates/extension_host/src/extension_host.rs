@@ -18,7 +18,7 @@ use extension::{
     ExtensionContextServerProxy, ExtensionDebugAdapterProviderProxy, ExtensionEvents,
     ExtensionGrammarProxy, ExtensionHostProxy, ExtensionLanguageProxy,
     ExtensionLanguageServerProxy, ExtensionSlashCommandProxy, ExtensionSnippetProxy,
-    ExtensionThemeProxy,
+    ExtensionThemeProxy, ExtensionToolchainProxy,
 };
 use fs::{Fs, RemoveOptions};
 use futures::future::join_all;
@@ -1220,6 +1220,10 @@ impl ExtensionStore {
             for command_name in extension.manifest.slash_commands.keys() {
                 self.proxy.unregister_slash_command(command_name.clone());
             }
+            for toolchain_provider in extension.manifest.toolchain_providers.values() {
+                self.proxy
+                    .unregister_toolchain_provider(&toolchain_provider.language);
+            }
         }
 
         self.wasm_extensions
@@ -1449,6 +1453,17 @@ impl ExtensionStore {
                         this.proxy
                             .register_debug_locator(extension.clone(), debug_adapter.clone());
                     }
+
+                    for (toolchain_provider_id, toolchain_provider) in &manifest.toolchain_providers
+                    {
+                        this.proxy.register_toolchain_provider(
+                            extension.clone(),
+                            toolchain_provider_id.clone(),
+                            toolchain_provider.clone(),
+                            this.fs.clone(),
+                            cx,
+                        );
+                    }
                 }
 
                 this.wasm_extensions.extend(wasm_extensions);
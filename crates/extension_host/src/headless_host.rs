@@ -5,7 +5,7 @@ use client::{TypedEnvelope, proto};
 use collections::{HashMap, HashSet};
 use extension::{
     Extension, ExtensionDebugAdapterProviderProxy, ExtensionHostProxy, ExtensionLanguageProxy,
-    ExtensionLanguageServerProxy, ExtensionManifest,
+    ExtensionLanguageServerProxy, ExtensionManifest, ExtensionToolchainProxy,
 };
 use fs::{Fs, RemoveOptions, RenameOptions};
 use futures::future::join_all;
@@ -32,6 +32,7 @@ pub struct HeadlessExtensionStore {
     pub loaded_extensions: HashMap<Arc<str>, Arc<str>>,
     pub loaded_languages: HashMap<Arc<str>, Vec<LanguageName>>,
     pub loaded_language_servers: HashMap<Arc<str>, Vec<(LanguageServerName, LanguageName)>>,
+    pub loaded_toolchain_providers: HashMap<Arc<str>, Vec<LanguageName>>,
 }
 
 impl HeadlessExtensionStore {
@@ -58,6 +59,7 @@ impl HeadlessExtensionStore {
             loaded_extensions: Default::default(),
             loaded_languages: Default::default(),
             loaded_language_servers: Default::default(),
+            loaded_toolchain_providers: Default::default(),
         })
     }
 
@@ -212,6 +214,23 @@ impl HeadlessExtensionStore {
             log::info!("Loaded debug locator: {}", debug_locator);
         }
 
+        for (toolchain_provider_id, toolchain_provider) in &manifest.toolchain_providers {
+            this.update(cx, |this, cx| {
+                this.loaded_toolchain_providers
+                    .entry(manifest.id.clone())
+                    .or_default()
+                    .push(toolchain_provider.language.clone());
+                this.proxy.register_toolchain_provider(
+                    wasm_extension.clone(),
+                    toolchain_provider_id.clone(),
+                    toolchain_provider.clone(),
+                    this.fs.clone(),
+                    cx,
+                );
+            })?;
+            log::info!("Loaded toolchain provider: {}", toolchain_provider_id);
+        }
+
         Ok(())
     }
 
@@ -228,6 +247,14 @@ impl HeadlessExtensionStore {
             .unwrap_or_default();
         self.proxy.remove_languages(&languages_to_remove, &[]);
 
+        for language in self
+            .loaded_toolchain_providers
+            .remove(extension_id)
+            .unwrap_or_default()
+        {
+            self.proxy.unregister_toolchain_provider(&language);
+        }
+
         let servers_to_remove = self
             .loaded_language_servers
             .remove(extension_id)
@@ -7,9 +7,9 @@ use async_trait::async_trait;
 use dap::{DebugRequest, StartDebuggingRequestArgumentsRequest};
 use extension::{
     CodeLabel, Command, Completion, ContextServerConfiguration, DebugAdapterBinary,
-    DebugTaskDefinition, ExtensionCapability, ExtensionHostProxy, KeyValueStoreDelegate,
-    ProjectDelegate, SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput, Symbol,
-    WorktreeDelegate,
+    DebugTaskDefinition, EnvVars, ExtensionCapability, ExtensionHostProxy, KeyValueStoreDelegate,
+    ProjectDelegate, SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput,
+    SubprojectDelegate, Symbol, Toolchain, ToolchainList, WorktreeDelegate,
 };
 use fs::{Fs, normalize_path};
 use futures::future::LocalBoxFuture;
@@ -488,6 +488,42 @@ impl extension::Extension for WasmExtension {
         })
         .await?
     }
+
+    async fn list_toolchains(
+        &self,
+        toolchain_provider_id: Arc<str>,
+        subproject: Arc<dyn SubprojectDelegate>,
+    ) -> Result<ToolchainList> {
+        self.call(|extension, store| {
+            async move {
+                let subproject_resource = store.data_mut().table().push(subproject)?;
+                extension
+                    .call_list_toolchains(store, toolchain_provider_id, subproject_resource)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))
+            }
+            .boxed()
+        })
+        .await?
+    }
+
+    async fn resolve_toolchain(
+        &self,
+        toolchain_provider_id: Arc<str>,
+        path: PathBuf,
+        env: EnvVars,
+    ) -> Result<Toolchain> {
+        self.call(|extension, store| {
+            async move {
+                extension
+                    .call_resolve_toolchain(store, toolchain_provider_id, path, env)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))
+            }
+            .boxed()
+        })
+        .await?
+    }
 }
 
 pub struct WasmState {
@@ -31,7 +31,8 @@ use wasmtime::{
 #[cfg(test)]
 pub use latest::CodeLabelSpanLiteral;
 pub use latest::{
-    CodeLabel, CodeLabelSpan, Command, DebugAdapterBinary, ExtensionProject, Range, SlashCommand,
+    CodeLabel, CodeLabelSpan, Command, DebugAdapterBinary, ExtensionProject, ExtensionSubproject,
+    Range, SlashCommand,
     zed::extension::context_server::ContextServerConfiguration,
     zed::extension::lsp::{
         Completion, CompletionKind, CompletionLabelDetails, InsertTextFormat, Symbol, SymbolKind,
@@ -1111,6 +1112,40 @@ impl Extension {
             _ => anyhow::bail!("`dap_locator_create_scenario` not available prior to v0.6.0"),
         }
     }
+
+    pub async fn call_list_toolchains(
+        &self,
+        store: &mut Store<WasmState>,
+        toolchain_provider_id: Arc<str>,
+        subproject: Resource<ExtensionSubproject>,
+    ) -> Result<Result<extension::ToolchainList, String>> {
+        match self {
+            Extension::V0_8_0(ext) => Ok(ext
+                .call_list_toolchains(store, &toolchain_provider_id, subproject)
+                .await?
+                .map(Into::into)),
+            _ => anyhow::bail!("`list_toolchains` not available prior to v0.8.0"),
+        }
+    }
+
+    pub async fn call_resolve_toolchain(
+        &self,
+        store: &mut Store<WasmState>,
+        toolchain_provider_id: Arc<str>,
+        path: PathBuf,
+        env: extension::EnvVars,
+    ) -> Result<Result<extension::Toolchain, String>> {
+        match self {
+            Extension::V0_8_0(ext) => {
+                let path = path.to_str().context("toolchain path is not valid UTF-8")?;
+                Ok(ext
+                    .call_resolve_toolchain(store, &toolchain_provider_id, path, &env)
+                    .await?
+                    .map(Into::into))
+            }
+            _ => anyhow::bail!("`resolve_toolchain` not available prior to v0.8.0"),
+        }
+    }
 }
 
 trait ToWasmtimeResult<T> {
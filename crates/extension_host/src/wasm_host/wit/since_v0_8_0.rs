@@ -14,7 +14,8 @@ use async_compression::futures::bufread::GzipDecoder;
 use async_tar::Archive;
 use async_trait::async_trait;
 use extension::{
-    ExtensionLanguageServerProxy, KeyValueStoreDelegate, ProjectDelegate, WorktreeDelegate,
+    ExtensionLanguageServerProxy, KeyValueStoreDelegate, ProjectDelegate, SubprojectDelegate,
+    WorktreeDelegate,
 };
 use futures::{AsyncReadExt, lock::Mutex};
 use futures::{FutureExt as _, io::BufReader};
@@ -47,6 +48,7 @@ wasmtime::component::bindgen!({
          "worktree": ExtensionWorktree,
          "project": ExtensionProject,
          "key-value-store": ExtensionKeyValueStore,
+         "subproject": ExtensionSubproject,
          "zed:extension/http-client/http-response-stream": ExtensionHttpResponseStream
     },
 });
@@ -61,6 +63,7 @@ mod settings {
 pub type ExtensionWorktree = Arc<dyn WorktreeDelegate>;
 pub type ExtensionProject = Arc<dyn ProjectDelegate>;
 pub type ExtensionKeyValueStore = Arc<dyn KeyValueStoreDelegate>;
+pub type ExtensionSubproject = Arc<dyn SubprojectDelegate>;
 pub type ExtensionHttpResponseStream = Arc<Mutex<::http_client::Response<AsyncBody>>>;
 
 pub fn linker(executor: &BackgroundExecutor) -> &'static Linker<WasmState> {
@@ -513,6 +516,58 @@ impl TryFrom<ContextServerConfiguration> for extension::ContextServerConfigurati
     }
 }
 
+impl From<toolchain::ShellKind> for task::ShellKind {
+    fn from(value: toolchain::ShellKind) -> Self {
+        match value {
+            toolchain::ShellKind::Posix => Self::Posix,
+            toolchain::ShellKind::Csh => Self::Csh,
+            toolchain::ShellKind::Tcsh => Self::Tcsh,
+            toolchain::ShellKind::Rc => Self::Rc,
+            toolchain::ShellKind::Fish => Self::Fish,
+            toolchain::ShellKind::PowerShell => Self::PowerShell,
+            toolchain::ShellKind::Pwsh => Self::Pwsh,
+            toolchain::ShellKind::Nushell => Self::Nushell,
+            toolchain::ShellKind::Cmd => Self::Cmd,
+            toolchain::ShellKind::Xonsh => Self::Xonsh,
+            toolchain::ShellKind::Elvish => Self::Elvish,
+        }
+    }
+}
+
+impl From<toolchain::ActivationScript> for extension::ToolchainActivationScript {
+    fn from(value: toolchain::ActivationScript) -> Self {
+        Self {
+            shell: value.shell.into(),
+            commands: value.commands,
+        }
+    }
+}
+
+impl From<Toolchain> for extension::Toolchain {
+    fn from(value: Toolchain) -> Self {
+        Self {
+            name: value.name,
+            path: value.path,
+            bin_paths: value.bin_paths.into_iter().map(PathBuf::from).collect(),
+            env: value.env,
+            activation_scripts: value
+                .activation_scripts
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+impl From<ToolchainList> for extension::ToolchainList {
+    fn from(value: ToolchainList) -> Self {
+        Self {
+            toolchains: value.toolchains.into_iter().map(Into::into).collect(),
+            default: value.default_toolchain.map(|ix| ix as usize),
+        }
+    }
+}
+
 impl HostKeyValueStore for WasmState {
     async fn insert(
         &mut self,
@@ -594,6 +649,49 @@ impl HostWorktree for WasmState {
     }
 }
 
+impl HostSubproject for WasmState {
+    async fn worktree_root_path(
+        &mut self,
+        delegate: Resource<Arc<dyn SubprojectDelegate>>,
+    ) -> wasmtime::Result<String> {
+        let delegate = self.table.get(&delegate)?;
+        Ok(delegate.worktree_root_path())
+    }
+
+    async fn path(
+        &mut self,
+        delegate: Resource<Arc<dyn SubprojectDelegate>>,
+    ) -> wasmtime::Result<String> {
+        let delegate = self.table.get(&delegate)?;
+        Ok(delegate.path())
+    }
+
+    async fn read_text_file(
+        &mut self,
+        delegate: Resource<Arc<dyn SubprojectDelegate>>,
+        path: String,
+    ) -> wasmtime::Result<Result<String, String>> {
+        let delegate = self.table.get(&delegate)?;
+        Ok(delegate
+            .read_text_file(&RelPath::new(Path::new(&path), PathStyle::Posix)?)
+            .await
+            .map_err(|error| error.to_string()))
+    }
+
+    async fn shell_env(
+        &mut self,
+        delegate: Resource<Arc<dyn SubprojectDelegate>>,
+    ) -> wasmtime::Result<EnvVars> {
+        let delegate = self.table.get(&delegate)?;
+        Ok(delegate.shell_env().await.into_iter().collect())
+    }
+
+    async fn drop(&mut self, _subproject: Resource<Subproject>) -> Result<()> {
+        // We only ever hand out borrows of subprojects.
+        Ok(())
+    }
+}
+
 impl common::Host for WasmState {}
 
 impl http_client::Host for WasmState {
@@ -883,6 +981,8 @@ impl slash_command::Host for WasmState {}
 #[async_trait]
 impl context_server::Host for WasmState {}
 
+impl toolchain::Host for WasmState {}
+
 impl dap::Host for WasmState {
     async fn resolve_tcp_template(
         &mut self,
@@ -6,8 +6,10 @@ use git::{
     Oid, RunHook,
     blame::Blame,
     repository::{
-        AskPassDelegate, Branch, CommitDetails, CommitOptions, FetchOptions, GitRepository,
-        GitRepositoryCheckpoint, PushOptions, Remote, RepoPath, ResetMode, Worktree,
+        AskPassDelegate, BisectMark, BisectState, Branch, CommitDetails, CommitOptions,
+        FetchOptions, GitRepository, GitRepositoryCheckpoint, PushOptions, RebaseProgress,
+        RebaseTodoEntry, Remote, RepoPath, ResetMode, SequencerKind, SequencerOperation,
+        SequencerState, Submodule, SubmoduleOperation, Tag, Worktree,
     },
     status::{
         DiffTreeType, FileStatus, GitStatus, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
@@ -451,6 +453,32 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        async { Ok(Vec::new()) }.boxed()
+    }
+
+    fn create_tag(
+        &self,
+        _name: String,
+        _target: Option<String>,
+        _message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn delete_tag(&self, _name: String) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn checkout_tag(&self, _name: String) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn tags_containing(&self, _sha: String) -> BoxFuture<'_, Result<Vec<SharedString>>> {
+        async { Ok(Vec::new()) }.boxed()
+    }
+
     fn blame(&self, path: RepoPath, _content: Rope) -> BoxFuture<'_, Result<git::blame::Blame>> {
         self.with_state_async(false, move |state| {
             state
@@ -613,6 +641,110 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn push_tag(
+        &self,
+        _tag_name: String,
+        _remote_name: String,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        unimplemented!()
+    }
+
+    fn commits_since(&self, _base: String) -> BoxFuture<'_, Result<Vec<CommitDetails>>> {
+        unimplemented!()
+    }
+
+    fn rebase_interactive(
+        &self,
+        _base: String,
+        _entries: Vec<RebaseTodoEntry>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn rebase_operation(
+        &self,
+        _operation: SequencerOperation,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        async move { None }.boxed()
+    }
+
+    fn cherry_pick(
+        &self,
+        _sha: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn revert(
+        &self,
+        _sha: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn sequencer_operation(
+        &self,
+        _kind: SequencerKind,
+        _operation: SequencerOperation,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn sequencer_state(&self) -> BoxFuture<'_, Option<SequencerState>> {
+        async move { None }.boxed()
+    }
+
+    fn bisect_start(
+        &self,
+        _bad: String,
+        _good: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn bisect_mark(
+        &self,
+        _mark: BisectMark,
+        _sha: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn bisect_reset(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn bisect_state(&self) -> BoxFuture<'_, Option<BisectState>> {
+        async move { None }.boxed()
+    }
+
+    fn submodules(&self, _cached: Arc<[Submodule]>) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        async { Ok(Vec::new()) }.boxed()
+    }
+
+    fn submodule_operation(
+        &self,
+        _operation: SubmoduleOperation,
+        _paths: Vec<RepoPath>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
     fn get_all_remotes(&self) -> BoxFuture<'_, Result<Vec<Remote>>> {
         self.with_state_async(false, move |state| {
             let remotes = state
@@ -76,6 +76,32 @@ actions!(
         Pull,
         /// Pulls changes from the remote repository with rebase.
         PullRebase,
+        /// Opens an editor to reorder, reword, squash, fix up or drop the commits since a chosen base.
+        InteractiveRebase,
+        /// Continues the rebase in progress once its conflicts are resolved.
+        RebaseContinue,
+        /// Skips the commit the rebase in progress stopped at.
+        RebaseSkip,
+        /// Aborts the rebase in progress, restoring the branch to its state before it.
+        RebaseAbort,
+        /// Creates a lightweight or annotated tag at the current commit.
+        CreateTag,
+        /// Marks the commit being tested by the bisect in progress as good.
+        BisectGood,
+        /// Marks the commit being tested by the bisect in progress as bad.
+        BisectBad,
+        /// Skips the commit being tested by the bisect in progress, e.g. when it can't be built.
+        BisectSkip,
+        /// Ends the bisect in progress, checking out the commit it started from.
+        BisectReset,
+        /// Runs a task on each commit to test, marking it good or bad by the task's exit code.
+        BisectRun,
+        /// Registers the submodules listed in `.gitmodules` in the repository's config.
+        InitSubmodules,
+        /// Checks out the commits recorded for the submodules, cloning the ones not yet initialized.
+        UpdateSubmodules,
+        /// Updates the submodules' remote URLs to the ones listed in `.gitmodules`.
+        SyncSubmodules,
         /// Fetches changes from the remote repository.
         Fetch,
         /// Fetches changes from a specific remote.
@@ -79,6 +79,27 @@ impl Branch {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Tag {
+    pub name: SharedString,
+    /// The commit the tag points to.
+    pub sha: SharedString,
+    /// The subject of the tag message, `None` for lightweight tags.
+    pub message: Option<SharedString>,
+    /// When the tag was created, or when the tagged commit was for lightweight tags.
+    pub timestamp: i64,
+}
+
+impl Tag {
+    pub fn ref_name(&self) -> String {
+        format!("refs/tags/{}", self.name)
+    }
+
+    pub fn is_annotated(&self) -> bool {
+        self.message.is_some()
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Worktree {
     pub path: PathBuf,
@@ -461,6 +482,28 @@ pub trait GitRepository: Send + Sync {
 
     fn delete_branch(&self, name: String) -> BoxFuture<'_, Result<()>>;
 
+    /// Returns the tags of the repository, most recently created first.
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>>;
+
+    /// Creates a tag pointing to `target`, or to HEAD if it's not given.
+    ///
+    /// The tag is annotated if it has a message, and lightweight otherwise.
+    fn create_tag(
+        &self,
+        name: String,
+        target: Option<String>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>>;
+
+    /// Checks out the commit the tag points to, detaching HEAD.
+    fn checkout_tag(&self, name: String) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the names of the tags whose history contains the commit, oldest first.
+    fn tags_containing(&self, sha: String) -> BoxFuture<'_, Result<Vec<SharedString>>>;
+
     fn worktrees(&self) -> BoxFuture<'_, Result<Vec<Worktree>>>;
 
     fn create_worktree(
@@ -592,6 +635,104 @@ pub trait GitRepository: Send + Sync {
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
 
+    fn push_tag(
+        &self,
+        tag_name: String,
+        remote_name: String,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
+    /// Returns the commits reachable from HEAD but not from `base`, oldest first,
+    /// in the order an interactive rebase onto `base` would apply them.
+    fn commits_since(&self, base: String) -> BoxFuture<'_, Result<Vec<CommitDetails>>>;
+
+    /// Rebases HEAD onto `base`, applying the commits as listed in `entries`.
+    ///
+    /// Stopping on conflicts is not an error, the rebase stays in progress
+    /// until it is continued, skipped past or aborted via [`GitRepository::rebase_operation`].
+    fn rebase_interactive(
+        &self,
+        base: String,
+        entries: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn rebase_operation(
+        &self,
+        operation: SequencerOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the progress of the rebase in progress, if any.
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>>;
+
+    /// Applies the changes of the commit on top of HEAD, committing them.
+    ///
+    /// Stopping on conflicts is not an error, the cherry-pick stays in progress
+    /// until it is continued or aborted via [`GitRepository::sequencer_operation`].
+    fn cherry_pick(
+        &self,
+        sha: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Commits the inverse of the commit's changes on top of HEAD.
+    ///
+    /// Stopping on conflicts is not an error, the revert stays in progress
+    /// until it is continued or aborted via [`GitRepository::sequencer_operation`].
+    fn revert(&self, sha: String, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    fn sequencer_operation(
+        &self,
+        kind: SequencerKind,
+        operation: SequencerOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the cherry-pick or revert in progress, if any.
+    fn sequencer_state(&self) -> BoxFuture<'_, Option<SequencerState>>;
+
+    /// Starts bisecting the history between the bad commit and, if given, a good one,
+    /// checking out the next commit to test once both are known.
+    fn bisect_start(
+        &self,
+        bad: String,
+        good: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Marks the commit, or HEAD if not given, checking out the next commit to test.
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        sha: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Ends the bisect, checking out the branch or commit HEAD was at when it started.
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the bisect in progress, if any.
+    fn bisect_state(&self) -> BoxFuture<'_, Option<BisectState>>;
+
+    /// Returns the submodules listed in `.gitmodules`, ordered by path.
+    ///
+    /// Running `git status` in a submodule is expensive, so the worktree state of the submodules
+    /// in `cached` whose gitlinks and initialization haven't changed is reused from there.
+    fn submodules(&self, cached: Arc<[Submodule]>) -> BoxFuture<'_, Result<Vec<Submodule>>>;
+
+    /// Runs `git submodule init`, `update` or `sync` for the given submodules, or all of them if none are given.
+    fn submodule_operation(
+        &self,
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
     fn get_push_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>>;
 
     fn get_branch_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>>;
@@ -642,6 +783,162 @@ pub enum PushOptions {
     Force,
 }
 
+/// What to do with a commit during an interactive rebase.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RebaseAction {
+    #[default]
+    Pick,
+    Reword,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebaseTodoEntry {
+    pub sha: SharedString,
+    pub action: RebaseAction,
+    /// The new message of the commit, used when it is reworded.
+    pub message: Option<SharedString>,
+}
+
+/// A rebase that stopped before applying all of its commits, e.g. on conflicts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebaseProgress {
+    /// The 1-based number of the todo list step the rebase stopped at.
+    pub step: usize,
+    pub total: usize,
+    /// The commit that was being applied when the rebase stopped.
+    pub stopped_sha: Option<SharedString>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequencerOperation {
+    Continue,
+    Skip,
+    Abort,
+}
+
+impl SequencerOperation {
+    pub fn as_flag(&self) -> &'static str {
+        match self {
+            SequencerOperation::Continue => "--continue",
+            SequencerOperation::Skip => "--skip",
+            SequencerOperation::Abort => "--abort",
+        }
+    }
+}
+
+/// A git command applying the changes of existing commits on top of HEAD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequencerKind {
+    CherryPick,
+    Revert,
+}
+
+impl SequencerKind {
+    pub fn as_command(&self) -> &'static str {
+        match self {
+            SequencerKind::CherryPick => "cherry-pick",
+            SequencerKind::Revert => "revert",
+        }
+    }
+}
+
+/// A cherry-pick or revert that stopped before committing, e.g. on conflicts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequencerState {
+    pub kind: SequencerKind,
+    /// The commit being cherry-picked or reverted.
+    pub sha: SharedString,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BisectMark {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl BisectMark {
+    pub fn as_command(&self) -> &'static str {
+        match self {
+            BisectMark::Good => "good",
+            BisectMark::Bad => "bad",
+            BisectMark::Skip => "skip",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BisectState {
+    pub bad: Option<SharedString>,
+    pub good: Vec<SharedString>,
+    pub skipped: Vec<SharedString>,
+    /// The number of revisions left to test after the current one,
+    /// known once both a good and a bad commit are marked.
+    pub remaining_revisions: Option<u32>,
+    /// The estimated number of steps left to find the first bad commit.
+    pub remaining_steps: Option<u32>,
+    /// The commit that introduced the change, once the bisect narrowed it down.
+    pub first_bad: Option<SharedString>,
+}
+
+/// A submodule listed in `.gitmodules`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submodule {
+    pub path: RepoPath,
+    pub url: Option<SharedString>,
+    /// The commit recorded in HEAD.
+    pub head_sha: Option<SharedString>,
+    /// The commit recorded in the index, i.e. the one `git submodule update` checks out.
+    pub recorded_sha: Option<SharedString>,
+    /// The commit checked out in the submodule, or `None` if it's not initialized.
+    pub checked_out_sha: Option<SharedString>,
+    /// Whether the submodule has uncommitted changes or untracked files.
+    pub dirty: bool,
+}
+
+impl Submodule {
+    pub fn is_initialized(&self) -> bool {
+        self.checked_out_sha.is_some()
+    }
+
+    /// Whether the checked-out commit differs from the one recorded in the index.
+    pub fn is_pointer_changed(&self) -> bool {
+        self.is_initialized() && self.checked_out_sha != self.recorded_sha
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmoduleOperation {
+    Init,
+    Update,
+    Sync,
+}
+
+impl SubmoduleOperation {
+    pub fn as_command(&self) -> &'static str {
+        match self {
+            SubmoduleOperation::Init => "init",
+            SubmoduleOperation::Update => "update",
+            SubmoduleOperation::Sync => "sync",
+        }
+    }
+}
+
 impl std::fmt::Debug for dyn GitRepository {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("dyn GitRepository<...>").finish()
@@ -703,6 +1000,42 @@ impl RealGitRepository {
         *self.any_git_binary_help_output.lock() = Some(output.clone());
         output
     }
+
+    fn apply_commit(
+        &self,
+        kind: SequencerKind,
+        sha: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                anyhow::ensure!(
+                    read_sequencer_state(&git_dir).is_none(),
+                    "A cherry-pick or revert is already in progress"
+                );
+                let mut command = new_smol_command(&git_binary_path);
+                command
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .arg(kind.as_command());
+                if kind == SequencerKind::Revert {
+                    command.arg("--no-edit");
+                }
+                let output = command.arg(&sha).output().await?;
+
+                anyhow::ensure!(
+                    output.status.success() || read_sequencer_state(&git_dir).is_some(),
+                    "Failed to {} {sha}:\n{}",
+                    kind.as_command(),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -1512,6 +1845,130 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                let fields = [
+                    "%(refname:strip=2)",
+                    "%(objecttype)",
+                    "%(objectname)",
+                    "%(*objectname)",
+                    "%(creatordate:unix)",
+                    "%(contents:subject)",
+                ]
+                .join("%00");
+                let output = GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&[
+                        "for-each-ref",
+                        "refs/tags",
+                        "--sort=-creatordate",
+                        "--format",
+                        &fields,
+                    ])
+                    .await?;
+                Ok(parse_tags(&output))
+            })
+            .boxed()
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        target: Option<String>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                validate_tag_argument(&name)?;
+                if let Some(target) = &target {
+                    validate_tag_argument(target)?;
+                }
+                let mut args = vec!["tag"];
+                if let Some(message) = &message {
+                    args.extend([
+                        "--annotate",
+                        "--cleanup=strip",
+                        "--message",
+                        message.as_str(),
+                    ]);
+                }
+                args.push(&name);
+                args.extend(target.as_deref());
+
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs(HashMap::clone(&env))
+                    .run(&args)
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                validate_tag_argument(&name)?;
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&["tag", "--delete", &name])
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn checkout_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&["switch", "--detach", &format!("refs/tags/{name}")])
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn tags_containing(&self, sha: String) -> BoxFuture<'_, Result<Vec<SharedString>>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                let output = GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&[
+                        "--no-optional-locks",
+                        "tag",
+                        "--contains",
+                        &sha,
+                        "--sort=creatordate",
+                    ])
+                    .await?;
+                Ok(output
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(|line| SharedString::from(line.to_string()))
+                    .collect())
+            })
+            .boxed()
+    }
+
     fn blame(&self, path: RepoPath, content: Rope) -> BoxFuture<'_, Result<crate::blame::Blame>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.any_git_binary_path.clone();
@@ -1902,6 +2359,37 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
+    fn push_tag(
+        &self,
+        tag_name: String,
+        remote_name: String,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        let git_binary_path = self.system_git_binary_path.clone();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git_binary_path = git_binary_path.context("git not found on $PATH, can't push")?;
+            let mut command = new_smol_command(git_binary_path);
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .arg("push")
+                .arg(remote_name)
+                .arg(format!("refs/tags/{tag_name}"))
+                .stdin(smol::process::Stdio::null())
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
     fn pull(
         &self,
         branch_name: Option<String>,
@@ -1967,16 +2455,420 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
-    fn get_push_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>> {
+    fn commits_since(&self, base: String) -> BoxFuture<'_, Result<Vec<CommitDetails>>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.any_git_binary_path.clone();
         self.executor
             .spawn(async move {
-                let working_directory = working_directory?;
                 let output = new_smol_command(&git_binary_path)
-                    .current_dir(&working_directory)
-                    .args(["rev-parse", "--abbrev-ref"])
-                    .arg(format!("{branch}@{{push}}"))
+                    .current_dir(&working_directory?)
+                    .args([
+                        "--no-optional-locks",
+                        "log",
+                        "-z",
+                        "--reverse",
+                        "--topo-order",
+                        "--no-merges",
+                        "--format=%H%x00%at%x00%ae%x00%an%x00%B",
+                    ])
+                    .arg(format!("{base}..HEAD"))
+                    .arg("--")
+                    .output()
+                    .await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list commits since {base}:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(parse_commit_log(&String::from_utf8_lossy(&output.stdout)))
+            })
+            .boxed()
+    }
+
+    fn rebase_interactive(
+        &self,
+        base: String,
+        entries: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                anyhow::ensure!(
+                    !git_dir.join("rebase-merge").exists()
+                        && !git_dir.join("rebase-apply").exists(),
+                    "A rebase is already in progress"
+                );
+
+                let todo_dir = git_dir.join(REBASE_TODO_DIR);
+                smol::fs::remove_dir_all(&todo_dir).await.ok();
+                smol::fs::create_dir_all(&todo_dir).await?;
+                let (todo, messages) = rebase_todo(&entries, &todo_dir);
+                for (message_path, message) in messages {
+                    smol::fs::write(message_path, message).await?;
+                }
+                let todo_path = todo_dir.join("git-rebase-todo");
+                smol::fs::write(&todo_path, todo).await?;
+
+                // Git hands the path of its todo list to the sequence editor, which replaces it with ours.
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .envs(env.iter())
+                    .env(
+                        "GIT_SEQUENCE_EDITOR",
+                        format!("cp {}", shell_quote(&todo_path)),
+                    )
+                    .env("GIT_EDITOR", ":")
+                    .args(["rebase", "--interactive", "--no-autosquash"])
+                    .arg(&base)
+                    .output()
+                    .await?;
+
+                let progress = read_rebase_progress(&git_dir);
+                if progress.is_none() {
+                    smol::fs::remove_dir_all(&todo_dir).await.ok();
+                }
+                anyhow::ensure!(
+                    output.status.success() || progress.is_some(),
+                    "Failed to rebase onto {base}:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn rebase_operation(
+        &self,
+        operation: SequencerOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                let progress_before = read_rebase_progress(&git_dir);
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .env("GIT_EDITOR", ":")
+                    .args(["rebase", operation.as_flag()])
+                    .output()
+                    .await?;
+
+                let progress = read_rebase_progress(&git_dir);
+                if progress.is_none() {
+                    smol::fs::remove_dir_all(git_dir.join(REBASE_TODO_DIR))
+                        .await
+                        .ok();
+                }
+                // Stopping on the next commit's conflicts is not a failure of the operation itself.
+                anyhow::ensure!(
+                    output.status.success() || (progress.is_some() && progress != progress_before),
+                    "Failed to run git rebase {}:\n{}",
+                    operation.as_flag(),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move { read_rebase_progress(&git_dir) })
+            .boxed()
+    }
+
+    fn cherry_pick(
+        &self,
+        sha: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.apply_commit(SequencerKind::CherryPick, sha, env)
+    }
+
+    fn revert(&self, sha: String, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.apply_commit(SequencerKind::Revert, sha, env)
+    }
+
+    fn sequencer_operation(
+        &self,
+        kind: SequencerKind,
+        operation: SequencerOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .env("GIT_EDITOR", ":")
+                    .args([kind.as_command(), operation.as_flag()])
+                    .output()
+                    .await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to run git {} {}:\n{}",
+                    kind.as_command(),
+                    operation.as_flag(),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn sequencer_state(&self) -> BoxFuture<'_, Option<SequencerState>> {
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move { read_sequencer_state(&git_dir) })
+            .boxed()
+    }
+
+    fn bisect_start(
+        &self,
+        bad: String,
+        good: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                let mut args = vec!["bisect", "start", bad.as_str()];
+                args.extend(good.as_deref());
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs(HashMap::clone(&env))
+                    .run(&args)
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        sha: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                let mut args = vec!["bisect", mark.as_command()];
+                args.extend(sha.as_deref());
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs(HashMap::clone(&env))
+                    .run(&args)
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs(HashMap::clone(&env))
+                    .run(&["bisect", "reset"])
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn bisect_state(&self) -> BoxFuture<'_, Option<BisectState>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+        let git_dir = self.path();
+
+        self.executor
+            .spawn(async move {
+                if !git_dir.join("BISECT_START").exists() {
+                    return None;
+                }
+                let git = GitBinary::new(git_binary_path, working_directory.log_err()?, executor);
+                let refs = git
+                    .run(&[
+                        "for-each-ref",
+                        "refs/bisect",
+                        "--format",
+                        "%(refname:strip=2) %(objectname)",
+                    ])
+                    .await
+                    .log_err()?;
+                let mut state = parse_bisect_refs(&refs);
+
+                if let Some(bad) = &state.bad
+                    && !state.good.is_empty()
+                {
+                    let mut args = vec!["rev-list", "--bisect-vars", bad.as_ref(), "--not"];
+                    args.extend(state.good.iter().map(|sha| sha.as_ref()));
+                    if let Some(vars) = git.run(&args).await.log_err() {
+                        apply_bisect_vars(&vars, &mut state);
+                    }
+                }
+                Some(state)
+            })
+            .boxed()
+    }
+
+    fn submodules(&self, cached: Arc<[Submodule]>) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                if !working_directory.join(".gitmodules").exists() {
+                    return Ok(Vec::new());
+                }
+                let git = GitBinary::new(
+                    git_binary_path.clone(),
+                    working_directory.clone(),
+                    executor.clone(),
+                );
+
+                // `git config` fails when nothing matches, e.g. for an empty `.gitmodules`.
+                let config = git
+                    .run(&[
+                        "config",
+                        "--file",
+                        ".gitmodules",
+                        "--get-regexp",
+                        r"^submodule\..*\.(path|url)$",
+                    ])
+                    .await
+                    .unwrap_or_default();
+                let mut submodules = parse_submodule_config(&config);
+                if submodules.is_empty() {
+                    return Ok(submodules);
+                }
+
+                let mut args = vec![
+                    "ls-files".to_string(),
+                    "--stage".into(),
+                    "-z".into(),
+                    "--".into(),
+                ];
+                args.extend(
+                    submodules
+                        .iter()
+                        .map(|submodule| submodule.path.as_unix_str().to_string()),
+                );
+                let stage = git.run(&args).await?;
+                for (path, sha) in parse_gitlinks(&stage, 1) {
+                    if let Ok(ix) =
+                        submodules.binary_search_by(|submodule| submodule.path.cmp(&path))
+                    {
+                        submodules[ix].recorded_sha = Some(sha);
+                    }
+                }
+
+                // There's no HEAD in a repository without commits yet.
+                args.splice(..3, ["ls-tree".to_string(), "-z".into(), "HEAD".into()]);
+                if let Ok(tree) = git.run(&args).await {
+                    for (path, sha) in parse_gitlinks(&tree, 2) {
+                        if let Ok(ix) =
+                            submodules.binary_search_by(|submodule| submodule.path.cmp(&path))
+                        {
+                            submodules[ix].head_sha = Some(sha);
+                        }
+                    }
+                }
+
+                for submodule in &mut submodules {
+                    let submodule_directory = working_directory.join(submodule.path.as_std_path());
+                    let is_initialized = submodule_directory.join(".git").exists();
+                    if let Some(cached) = cached.iter().find(|cached| {
+                        cached.path == submodule.path
+                            && cached.head_sha == submodule.head_sha
+                            && cached.recorded_sha == submodule.recorded_sha
+                            && cached.is_initialized() == is_initialized
+                    }) {
+                        submodule.checked_out_sha = cached.checked_out_sha.clone();
+                        submodule.dirty = cached.dirty;
+                        continue;
+                    }
+                    if !is_initialized {
+                        continue;
+                    }
+                    let status = GitBinary::new(
+                        git_binary_path.clone(),
+                        submodule_directory,
+                        executor.clone(),
+                    )
+                    .run(&["status", "--porcelain=v2", "--branch", "-z"])
+                    .await;
+                    if let Some(status) = status.log_err() {
+                        (submodule.checked_out_sha, submodule.dirty) =
+                            parse_submodule_worktree_status(&status);
+                    }
+                }
+                Ok(submodules)
+            })
+            .boxed()
+    }
+
+    fn submodule_operation(
+        &self,
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                let mut args = vec!["submodule".to_string(), operation.as_command().to_string()];
+                if operation == SubmoduleOperation::Update {
+                    args.push("--init".into());
+                }
+                args.push("--".into());
+                args.extend(paths.iter().map(|path| path.as_unix_str().to_string()));
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs(HashMap::clone(&env))
+                    .run(&args)
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn get_push_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args(["rev-parse", "--abbrev-ref"])
+                    .arg(format!("{branch}@{{push}}"))
                     .output()
                     .await?;
                 if !output.status.success() {
@@ -2344,17 +3236,272 @@ impl GitRepository for RealGitRepository {
                     }
                 }
 
-                return Ok(());
+                return Ok(());
+            }
+
+            let git = GitBinary::new(git_binary_path, working_directory, executor)
+                .envs(HashMap::clone(&env));
+            git.run(&["hook", "run", "--ignore-missing", hook.as_str()])
+                .await?;
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+/// The directory, inside of the git directory, with the todo list and reworded commit messages
+/// of the interactive rebase in progress.
+const REBASE_TODO_DIR: &str = "zed-rebase";
+
+fn parse_commit_log(output: &str) -> Vec<CommitDetails> {
+    let mut fields = output.split('\0');
+    let mut commits = Vec::new();
+    while let (Some(sha), Some(timestamp), Some(author_email), Some(author_name), Some(message)) = (
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+    ) {
+        commits.push(CommitDetails {
+            sha: sha.trim().to_string().into(),
+            message: message.trim().to_string().into(),
+            commit_timestamp: timestamp.trim().parse().unwrap_or(0),
+            author_email: author_email.trim().to_string().into(),
+            author_name: author_name.trim().to_string().into(),
+        });
+    }
+    commits
+}
+
+/// Generates the todo list of an interactive rebase, along with the message files it refers to.
+///
+/// Git would open an editor for the reworded commits, so those are picked instead
+/// and amended with their new messages right after.
+fn rebase_todo(
+    entries: &[RebaseTodoEntry],
+    messages_dir: &Path,
+) -> (String, Vec<(PathBuf, String)>) {
+    let mut todo = String::new();
+    let mut messages = Vec::new();
+    for (ix, entry) in entries.iter().enumerate() {
+        match (entry.action, &entry.message) {
+            (RebaseAction::Reword, Some(message)) => {
+                let message_path = messages_dir.join(format!("message-{ix}"));
+                todo.push_str(&format!("pick {}\n", entry.sha));
+                todo.push_str(&format!(
+                    "exec git commit --amend --only --allow-empty --no-verify --cleanup=strip --file={}\n",
+                    shell_quote(&message_path)
+                ));
+                messages.push((message_path, message.to_string()));
+            }
+            (action, _) => todo.push_str(&format!("{} {}\n", action.as_str(), entry.sha)),
+        }
+    }
+    (todo, messages)
+}
+
+/// Quotes the path for the shell git runs its editors and `exec` commands with.
+fn shell_quote(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let path = if cfg!(windows) {
+        path.replace('\\', "/")
+    } else {
+        path.into_owned()
+    };
+    format!("'{}'", path.replace('\'', r"'\''"))
+}
+
+fn read_rebase_progress(git_dir: &Path) -> Option<RebaseProgress> {
+    let rebase_dir = git_dir.join("rebase-merge");
+    if !rebase_dir.is_dir() {
+        return None;
+    }
+    let read = |name: &str| {
+        std::fs::read_to_string(rebase_dir.join(name))
+            .ok()
+            .map(|contents| contents.trim().to_string())
+            .filter(|contents| !contents.is_empty())
+    };
+    Some(RebaseProgress {
+        step: read("msgnum")
+            .and_then(|step| step.parse().ok())
+            .unwrap_or(0),
+        total: read("end")
+            .and_then(|total| total.parse().ok())
+            .unwrap_or(0),
+        stopped_sha: read("stopped-sha").map(SharedString::from),
+    })
+}
+
+/// Rejects the tag names and targets that `git tag` would parse as options.
+fn validate_tag_argument(argument: &str) -> Result<()> {
+    anyhow::ensure!(!argument.is_empty(), "tag argument cannot be empty");
+    anyhow::ensure!(
+        !argument.starts_with('-'),
+        "invalid tag argument {argument:?}: cannot start with '-'"
+    );
+    Ok(())
+}
+
+fn parse_tags(output: &str) -> Vec<Tag> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            let name = fields.next()?;
+            let object_type = fields.next()?;
+            let object_sha = fields.next()?;
+            let peeled_sha = fields.next()?;
+            let timestamp = fields.next()?.parse().unwrap_or(0);
+            let subject = fields.next()?;
+            // Annotated tags point to a tag object, which has to be peeled to the tagged commit.
+            let (sha, message) = if object_type == "tag" {
+                (peeled_sha, Some(SharedString::from(subject.to_string())))
+            } else {
+                (object_sha, None)
+            };
+            Some(Tag {
+                name: name.to_string().into(),
+                sha: sha.to_string().into(),
+                message,
+                timestamp,
+            })
+        })
+        .collect()
+}
+
+/// Parses the submodule paths and URLs, listed by `git config --get-regexp` as
+/// `submodule.<name>.path <path>` and `submodule.<name>.url <url>` lines.
+fn parse_submodule_config(output: &str) -> Vec<Submodule> {
+    let mut paths = HashMap::default();
+    let mut urls = HashMap::default();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(' ') else {
+            continue;
+        };
+        let Some(key) = key.strip_prefix("submodule.") else {
+            continue;
+        };
+        if let Some(name) = key.strip_suffix(".path") {
+            paths.insert(name, value);
+        } else if let Some(name) = key.strip_suffix(".url") {
+            urls.insert(name, value);
+        }
+    }
+
+    let mut submodules = paths
+        .into_iter()
+        .filter_map(|(name, path)| {
+            Some(Submodule {
+                path: RepoPath::new(path.trim_end_matches('/')).log_err()?,
+                url: urls
+                    .get(name)
+                    .map(|url| SharedString::from(url.to_string())),
+                head_sha: None,
+                recorded_sha: None,
+                checked_out_sha: None,
+                dirty: false,
+            })
+        })
+        .collect::<Vec<_>>();
+    submodules.sort_by(|a, b| a.path.cmp(&b.path));
+    submodules
+}
+
+/// Parses the submodule commits listed by `git ls-files --stage -z` as `<mode> <sha> <stage>\t<path>`
+/// entries, or by `git ls-tree -z` as `<mode> <type> <sha>\t<path>` ones. Submodules have the `160000` mode.
+fn parse_gitlinks(output: &str, sha_column: usize) -> Vec<(RepoPath, SharedString)> {
+    output
+        .split('\0')
+        .filter_map(|entry| {
+            let (info, path) = entry.split_once('\t')?;
+            let info = info.split(' ').collect::<Vec<_>>();
+            if *info.first()? != "160000" {
+                return None;
+            }
+            let sha = info.get(sha_column)?;
+            Some((
+                RepoPath::new(path).log_err()?,
+                SharedString::from(sha.to_string()),
+            ))
+        })
+        .collect()
+}
+
+/// Parses the checked-out commit and whether there are any changes
+/// from the submodule's `git status --porcelain=v2 --branch -z`.
+fn parse_submodule_worktree_status(output: &str) -> (Option<SharedString>, bool) {
+    let mut sha = None;
+    let mut dirty = false;
+    for entry in output.split('\0').filter(|entry| !entry.is_empty()) {
+        if let Some(oid) = entry.strip_prefix("# branch.oid ") {
+            if oid != "(initial)" {
+                sha = Some(SharedString::from(oid.to_string()));
             }
+        } else if !entry.starts_with('#') {
+            dirty = true;
+        }
+    }
+    (sha, dirty)
+}
 
-            let git = GitBinary::new(git_binary_path, working_directory, executor)
-                .envs(HashMap::clone(&env));
-            git.run(&["hook", "run", "--ignore-missing", hook.as_str()])
-                .await?;
-            Ok(())
+/// Parses the `refs/bisect` refs, listed as `<name> <sha>` lines.
+fn parse_bisect_refs(output: &str) -> BisectState {
+    let mut state = BisectState::default();
+    for line in output.lines() {
+        let Some((name, sha)) = line.split_once(' ') else {
+            continue;
+        };
+        let sha = SharedString::from(sha.to_string());
+        if name == "bad" {
+            state.bad = Some(sha);
+        } else if name.starts_with("good-") {
+            state.good.push(sha);
+        } else if name.starts_with("skip-") {
+            state.skipped.push(sha);
         }
-        .boxed()
     }
+    state
+}
+
+/// Applies the estimates of `git rev-list --bisect-vars` to the bisect state.
+///
+/// Once a single commit is left between the good and bad ones, it's the first bad commit.
+fn apply_bisect_vars(output: &str, state: &mut BisectState) {
+    let mut rev = None;
+    let mut all = None;
+    for line in output.lines() {
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim_matches('\'');
+        match name {
+            "bisect_rev" => rev = Some(SharedString::from(value.to_string())),
+            "bisect_nr" => state.remaining_revisions = value.parse().ok(),
+            "bisect_steps" => state.remaining_steps = value.parse().ok(),
+            "bisect_all" => all = value.parse::<u32>().ok(),
+            _ => {}
+        }
+    }
+    if all == Some(1) {
+        state.first_bad = rev;
+    }
+}
+
+fn read_sequencer_state(git_dir: &Path) -> Option<SequencerState> {
+    [
+        (SequencerKind::CherryPick, "CHERRY_PICK_HEAD"),
+        (SequencerKind::Revert, "REVERT_HEAD"),
+    ]
+    .into_iter()
+    .find_map(|(kind, head)| {
+        let sha = std::fs::read_to_string(git_dir.join(head)).ok()?;
+        Some(SequencerState {
+            kind,
+            sha: sha.trim().to_string().into(),
+        })
+    })
 }
 
 fn git_status_args(path_prefixes: &[RepoPath]) -> Vec<OsString> {
@@ -3115,6 +4262,542 @@ mod tests {
         )
     }
 
+    #[gpui::test]
+    async fn test_interactive_rebase(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let commit_file = async |path: &str, contents: &str, message: &str| {
+            smol::fs::write(repo_dir.path().join(path), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(path)], env.clone())
+                .await
+                .unwrap();
+            repo.commit(
+                message.to_string().into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            repo.head_sha().await.unwrap()
+        };
+
+        let base = commit_file("conflict", "0", "Base").await;
+        commit_file("a", "a", "Add a").await;
+        commit_file("b", "b", "Add b").await;
+        commit_file("c", "c", "Add c").await;
+        commit_file("d", "d", "Add d").await;
+
+        let commits = repo.commits_since(base.clone()).await.unwrap();
+        assert_eq!(
+            commits
+                .iter()
+                .map(|commit| commit.message.as_ref())
+                .collect::<Vec<_>>(),
+            vec!["Add a", "Add b", "Add c", "Add d"]
+        );
+
+        let entry =
+            |commit: &CommitDetails, action: RebaseAction, message: Option<&str>| RebaseTodoEntry {
+                sha: commit.sha.clone(),
+                action,
+                message: message.map(|message| message.to_string().into()),
+            };
+        repo.rebase_interactive(
+            base.clone(),
+            vec![
+                entry(&commits[2], RebaseAction::Reword, Some("Add c\n\nReworded")),
+                entry(&commits[0], RebaseAction::Pick, None),
+                entry(&commits[1], RebaseAction::Fixup, None),
+                entry(&commits[3], RebaseAction::Drop, None),
+            ],
+            env.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(repo.rebase_progress().await, None);
+        let commits = repo.commits_since(base.clone()).await.unwrap();
+        assert_eq!(
+            commits
+                .iter()
+                .map(|commit| commit.message.as_ref())
+                .collect::<Vec<_>>(),
+            vec!["Add c\n\nReworded", "Add a"]
+        );
+        assert!(repo_dir.path().join("b").exists());
+        assert!(!repo_dir.path().join("d").exists());
+        assert!(!repo_dir.path().join(".git").join(REBASE_TODO_DIR).exists());
+
+        commit_file("conflict", "1", "Change conflict").await;
+        commit_file("conflict", "2", "Change conflict again").await;
+        let head_before_rebase = repo.head_sha().await.unwrap();
+        let commits = repo.commits_since(base.clone()).await.unwrap();
+
+        // Applying the second change without the first one conflicts, stopping the rebase.
+        repo.rebase_interactive(
+            base.clone(),
+            vec![
+                entry(&commits[3], RebaseAction::Pick, None),
+                entry(&commits[2], RebaseAction::Pick, None),
+            ],
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        let progress = repo.rebase_progress().await.unwrap();
+        assert_eq!(progress.step, 1);
+        assert_eq!(progress.total, 2);
+        assert!(
+            commits[3]
+                .sha
+                .starts_with(progress.stopped_sha.unwrap().as_ref())
+        );
+
+        repo.rebase_interactive(base.clone(), Vec::new(), env.clone())
+            .await
+            .unwrap_err();
+        repo.rebase_operation(SequencerOperation::Continue, env.clone())
+            .await
+            .unwrap_err();
+
+        repo.rebase_operation(SequencerOperation::Abort, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+        assert_eq!(repo.head_sha().await.unwrap(), head_before_rebase);
+        assert_eq!(
+            smol::fs::read_to_string(repo_dir.path().join("conflict"))
+                .await
+                .unwrap(),
+            "2"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_and_revert(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let commit_file = async |path: &str, contents: &str, message: &str| {
+            smol::fs::write(repo_dir.path().join(path), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(path)], env.clone())
+                .await
+                .unwrap();
+            repo.commit(
+                message.to_string().into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            repo.head_sha().await.unwrap()
+        };
+        let head_message = async || repo.show("HEAD".into()).await.unwrap().message;
+
+        commit_file("conflict", "0", "Base").await;
+        let add_a = commit_file("a", "a", "Add a").await;
+        commit_file("b", "b", "Add b").await;
+
+        repo.revert(add_a.clone(), env.clone()).await.unwrap();
+        assert_eq!(repo.sequencer_state().await, None);
+        assert!(!repo_dir.path().join("a").exists());
+        assert!(head_message().await.starts_with("Revert \"Add a\""));
+
+        repo.cherry_pick(add_a.clone(), env.clone()).await.unwrap();
+        assert_eq!(repo.sequencer_state().await, None);
+        assert!(repo_dir.path().join("a").exists());
+        assert_eq!(head_message().await.trim(), "Add a");
+
+        let change_conflict = commit_file("conflict", "1", "Change conflict").await;
+        let change_conflict_again = commit_file("conflict", "2", "Change conflict again").await;
+
+        // Reverting the first change conflicts with the second one, stopping the revert.
+        repo.revert(change_conflict.clone(), env.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.sequencer_state().await,
+            Some(SequencerState {
+                kind: SequencerKind::Revert,
+                sha: change_conflict.clone().into(),
+            })
+        );
+        repo.cherry_pick(add_a.clone(), env.clone())
+            .await
+            .unwrap_err();
+        repo.sequencer_operation(
+            SequencerKind::Revert,
+            SequencerOperation::Continue,
+            env.clone(),
+        )
+        .await
+        .unwrap_err();
+
+        smol::fs::write(repo_dir.path().join("conflict"), "0")
+            .await
+            .unwrap();
+        repo.stage_paths(vec![repo_path("conflict")], env.clone())
+            .await
+            .unwrap();
+        repo.sequencer_operation(
+            SequencerKind::Revert,
+            SequencerOperation::Continue,
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(repo.sequencer_state().await, None);
+        assert!(
+            head_message()
+                .await
+                .starts_with("Revert \"Change conflict\"")
+        );
+
+        // Applying the second change without the first one conflicts too.
+        let head_before_cherry_pick = repo.head_sha().await.unwrap();
+        repo.cherry_pick(change_conflict_again.clone(), env.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.sequencer_state().await,
+            Some(SequencerState {
+                kind: SequencerKind::CherryPick,
+                sha: change_conflict_again.into(),
+            })
+        );
+        repo.sequencer_operation(
+            SequencerKind::CherryPick,
+            SequencerOperation::Abort,
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(repo.sequencer_state().await, None);
+        assert_eq!(repo.head_sha().await.unwrap(), head_before_cherry_pick);
+        assert_eq!(
+            smol::fs::read_to_string(repo_dir.path().join("conflict"))
+                .await
+                .unwrap(),
+            "0"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_tags(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let commit_file = async |path: &str, message: &str| {
+            smol::fs::write(repo_dir.path().join(path), path)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(path)], env.clone())
+                .await
+                .unwrap();
+            repo.commit(
+                message.to_string().into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            repo.head_sha().await.unwrap()
+        };
+
+        let first = commit_file("a", "Add a").await;
+        let second = commit_file("b", "Add b").await;
+
+        repo.create_tag("v1".into(), Some(first.clone()), None, env.clone())
+            .await
+            .unwrap();
+        repo.create_tag(
+            "v2".into(),
+            None,
+            Some("Release 2\n\nWith b".into()),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+
+        let mut tags = repo.tags().await.unwrap();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(
+            tags.iter()
+                .map(|tag| (tag.name.as_ref(), tag.sha.as_ref(), tag.message.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("v1", first.as_str(), None),
+                ("v2", second.as_str(), Some("Release 2")),
+            ]
+        );
+        assert_eq!(tags[0].ref_name(), "refs/tags/v1");
+        assert!(!tags[0].is_annotated());
+        assert!(tags[1].is_annotated());
+
+        assert_eq!(
+            repo.tags_containing(first.clone()).await.unwrap(),
+            vec![SharedString::from("v1"), SharedString::from("v2")]
+        );
+        assert_eq!(
+            repo.tags_containing(second.clone()).await.unwrap(),
+            vec![SharedString::from("v2")]
+        );
+        repo.create_tag("v2".into(), None, None, env.clone())
+            .await
+            .unwrap_err();
+        repo.create_tag("-d".into(), None, None, env.clone())
+            .await
+            .unwrap_err();
+        repo.create_tag("v3".into(), Some("--help".into()), None, env.clone())
+            .await
+            .unwrap_err();
+        repo.delete_tag("--list".into()).await.unwrap_err();
+
+        repo.checkout_tag("v1".into()).await.unwrap();
+        assert_eq!(repo.head_sha().await.unwrap(), first);
+        assert!(!repo_dir.path().join("b").exists());
+
+        repo.delete_tag("v1".into()).await.unwrap();
+        assert_eq!(
+            repo.tags()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|tag| tag.name)
+                .collect::<Vec<_>>(),
+            vec![SharedString::from("v2")]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_bisect(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let mut commits = Vec::new();
+        for ix in 0..8 {
+            smol::fs::write(repo_dir.path().join("file"), ix.to_string())
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path("file")], env.clone())
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Commit {ix}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            commits.push(repo.head_sha().await.unwrap());
+        }
+
+        assert_eq!(repo.bisect_state().await, None);
+        repo.bisect_mark(BisectMark::Good, None, env.clone())
+            .await
+            .unwrap_err();
+
+        // Commit 5 introduced the change.
+        repo.bisect_start("HEAD".into(), Some(commits[0].clone()), env.clone())
+            .await
+            .unwrap();
+        let state = repo.bisect_state().await.unwrap();
+        assert_eq!(state.bad.as_deref(), Some(commits[7].as_str()));
+        assert_eq!(state.good, vec![SharedString::from(commits[0].clone())]);
+        assert!(state.remaining_revisions.is_some());
+        assert!(state.remaining_steps.is_some());
+        assert_eq!(state.first_bad, None);
+
+        while repo.bisect_state().await.unwrap().first_bad.is_none() {
+            let head = repo.head_sha().await.unwrap();
+            let ix = commits.iter().position(|sha| *sha == head).unwrap();
+            let mark = if ix >= 5 {
+                BisectMark::Bad
+            } else {
+                BisectMark::Good
+            };
+            repo.bisect_mark(mark, None, env.clone()).await.unwrap();
+        }
+        assert_eq!(
+            repo.bisect_state().await.unwrap().first_bad.as_deref(),
+            Some(commits[5].as_str())
+        );
+
+        repo.bisect_reset(env.clone()).await.unwrap();
+        assert_eq!(repo.bisect_state().await, None);
+        assert_eq!(repo.head_sha().await.unwrap(), commits[7]);
+    }
+
+    #[gpui::test]
+    async fn test_submodules(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let dir = tempfile::tempdir().unwrap();
+        let git = |dir: &Path, args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .current_dir(dir)
+                .args(["-c", "protocol.file.allow=always"])
+                .args(args)
+                .envs(checkpoint_author_envs())
+                .output()
+                .unwrap();
+            assert!(
+                output.status.success(),
+                "{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        };
+
+        let library_dir = dir.path().join("library");
+        std::fs::create_dir(&library_dir).unwrap();
+        git(&library_dir, &["init"]);
+        std::fs::write(library_dir.join("lib.rs"), "").unwrap();
+        git(&library_dir, &["add", "lib.rs"]);
+        git(&library_dir, &["commit", "-m", "Add lib.rs"]);
+
+        let app_dir = dir.path().join("app");
+        std::fs::create_dir(&app_dir).unwrap();
+        git(&app_dir, &["init"]);
+        git(
+            &app_dir,
+            &["submodule", "add", "../library", "deps/library"],
+        );
+        git(&app_dir, &["commit", "-m", "Add library"]);
+
+        let repo = RealGitRepository::new(
+            &app_dir.join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+
+        let submodules = repo.submodules(Arc::default()).await.unwrap();
+        assert_eq!(submodules.len(), 1);
+        let submodule = &submodules[0];
+        assert_eq!(submodule.path, repo_path("deps/library"));
+        assert_eq!(submodule.url.as_deref(), Some("../library"));
+        assert_eq!(submodule.head_sha, submodule.recorded_sha);
+        assert!(submodule.is_initialized());
+        assert!(!submodule.is_pointer_changed());
+        assert!(!submodule.dirty);
+
+        std::fs::write(app_dir.join("deps/library/lib.rs"), "fn main() {}").unwrap();
+        // The gitlinks haven't changed, so the cached worktree state is reused.
+        let cached_submodule = repo
+            .submodules(submodules.clone().into())
+            .await
+            .unwrap()
+            .remove(0);
+        assert!(!cached_submodule.dirty);
+        let submodule = repo.submodules(Arc::default()).await.unwrap().remove(0);
+        assert!(submodule.dirty);
+        assert!(!submodule.is_pointer_changed());
+
+        git(
+            &app_dir.join("deps/library"),
+            &["commit", "-am", "Add main"],
+        );
+        let submodule = repo.submodules(Arc::default()).await.unwrap().remove(0);
+        assert!(!submodule.dirty);
+        assert!(submodule.is_pointer_changed());
+
+        // Updating the submodule checks out the recorded commit again.
+        repo.submodule_operation(
+            SubmoduleOperation::Update,
+            Vec::new(),
+            Arc::new(HashMap::default()),
+        )
+        .await
+        .unwrap();
+        let submodule = repo.submodules(Arc::default()).await.unwrap().remove(0);
+        assert!(!submodule.is_pointer_changed());
+    }
+
+    #[test]
+    fn test_parse_submodule_config() {
+        let submodules = parse_submodule_config(
+            "submodule.vendor/a.path vendor/a\n\
+             submodule.vendor/a.url https://example.com/a.git\n\
+             submodule.b.c.path b/\n\
+             submodule.b.c.url ../b.git\n\
+             submodule.d.url ../d.git",
+        );
+        assert_eq!(
+            submodules
+                .iter()
+                .map(|submodule| (submodule.path.clone(), submodule.url.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                (repo_path("b"), Some("../b.git")),
+                (repo_path("vendor/a"), Some("https://example.com/a.git")),
+            ]
+        );
+    }
+
     impl RealGitRepository {
         /// Force a Git garbage collection on the repository.
         fn gc(&self) -> BoxFuture<'_, Result<()>> {
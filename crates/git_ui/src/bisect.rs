@@ -0,0 +1,281 @@
+use git::{BisectBad, BisectGood, BisectReset, BisectRun, BisectSkip, repository::BisectMark};
+use gpui::{App, Context, Entity, SharedString, Task, Window};
+use project::git_store::Repository;
+use task::{ShellBuilder, TaskContext, TaskTemplate};
+use workspace::{Workspace, notifications::DetachAndPromptErr};
+
+use crate::picker_prompt;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &BisectGood, window, cx| {
+        mark(workspace, BisectMark::Good, window, cx);
+    });
+    workspace.register_action(|workspace, _: &BisectBad, window, cx| {
+        mark(workspace, BisectMark::Bad, window, cx);
+    });
+    workspace.register_action(|workspace, _: &BisectSkip, window, cx| {
+        mark(workspace, BisectMark::Skip, window, cx);
+    });
+    workspace.register_action(|workspace, _: &BisectReset, window, cx| {
+        reset(workspace, window, cx);
+    });
+    workspace.register_action(|workspace, _: &BisectRun, window, cx| {
+        run_task(workspace, window, cx);
+    });
+}
+
+/// Starts bisecting from a commit known to be good or bad.
+///
+/// A good commit is bisected against HEAD, which is assumed to be bad,
+/// while a bad one waits for a good commit to be marked.
+pub(crate) fn start(
+    repository: &Entity<Repository>,
+    sha: SharedString,
+    mark: BisectMark,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let (bad, good) = match mark {
+        BisectMark::Good => ("HEAD".to_string(), Some(sha.to_string())),
+        BisectMark::Bad => (sha.to_string(), None),
+        BisectMark::Skip => return,
+    };
+    let start_task = repository.update(cx, |repository, cx| repository.bisect_start(bad, good, cx));
+    window
+        .spawn(cx, async move |_| start_task.await?)
+        .detach_and_prompt_err("Failed to start bisect", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+}
+
+fn mark(
+    workspace: &mut Workspace,
+    mark: BisectMark,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+        return;
+    };
+    if repository.read(cx).bisect.is_none() {
+        return;
+    }
+
+    let mark_task = repository.update(cx, |repository, cx| repository.bisect_mark(mark, None, cx));
+    cx.spawn(async move |_, _| mark_task.await?)
+        .detach_and_prompt_err("Failed to bisect", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+}
+
+fn reset(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+    let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+        return;
+    };
+    if repository.read(cx).bisect.is_none() {
+        return;
+    }
+
+    let reset_task = repository.update(cx, |repository, cx| repository.bisect_reset(cx));
+    cx.spawn(async move |_, _| reset_task.await?)
+        .detach_and_prompt_err("Failed to reset bisect", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+}
+
+/// Picks one of the project's tasks and spawns it as the `git bisect run` predicate,
+/// so the bisect marks each commit by the task's exit code until it finds the first bad one.
+fn run_task(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+    let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+        return;
+    };
+    if repository.read(cx).bisect.is_none() {
+        return;
+    }
+    let work_directory = repository.read(cx).work_directory_abs_path.clone();
+    let task_contexts = tasks_ui::task_contexts(workspace, window, cx);
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let task_contexts = task_contexts.await;
+        let tasks = workspace
+            .update(cx, |workspace, cx| {
+                let Some(task_inventory) = workspace
+                    .project()
+                    .read(cx)
+                    .task_store()
+                    .read(cx)
+                    .task_inventory()
+                    .cloned()
+                else {
+                    return Task::ready(Vec::new());
+                };
+                let (file, language) = task_contexts
+                    .location()
+                    .map(|location| {
+                        let buffer = location.buffer.read(cx);
+                        (
+                            buffer.file().cloned(),
+                            buffer.language_at(location.range.start),
+                        )
+                    })
+                    .unwrap_or_default();
+                task_inventory
+                    .read(cx)
+                    .list_tasks(file, language, task_contexts.worktree(), cx)
+            })?
+            .await;
+        if tasks.is_empty() {
+            anyhow::bail!("No tasks to run as the bisect predicate");
+        }
+
+        let labels = tasks
+            .iter()
+            .map(|(_, template)| SharedString::from(template.label.clone()))
+            .collect();
+        let selection = workspace
+            .update_in(cx, |_, window, cx| {
+                picker_prompt::prompt(
+                    "Task to mark commits by, exiting with 0 when good",
+                    labels,
+                    workspace.clone(),
+                    window,
+                    cx,
+                )
+            })?
+            .await;
+        let Some((task_source_kind, template)) = selection.and_then(|ix| tasks.get(ix)) else {
+            return Ok(());
+        };
+
+        let is_windows = workspace.read_with(cx, |workspace, cx| {
+            workspace.project().read(cx).path_style(cx).is_windows()
+        })?;
+        let template =
+            bisect_run_template(template, &work_directory.to_string_lossy(), is_windows)?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            let default_context = TaskContext::default();
+            let active_context = task_contexts.active_context().unwrap_or(&default_context);
+            workspace.schedule_task(
+                task_source_kind.clone(),
+                &template,
+                active_context,
+                false,
+                window,
+                cx,
+            );
+        })
+    })
+    .detach_and_prompt_err("Failed to run bisect", window, cx, |e, _, _| {
+        Some(e.to_string())
+    });
+}
+
+/// Wraps the task's command into `git bisect run`, which runs it on every commit to test.
+///
+/// The command is run through the task's shell, so that it may use shell syntax, as when the task is spawned directly.
+fn bisect_run_template(
+    template: &TaskTemplate,
+    work_directory: &str,
+    is_windows: bool,
+) -> anyhow::Result<TaskTemplate> {
+    anyhow::ensure!(
+        !template.command.trim().is_empty(),
+        "Task \"{}\" has no command to run as the bisect predicate",
+        template.label
+    );
+    let (shell, shell_args) = ShellBuilder::new(&template.shell, is_windows)
+        .non_interactive()
+        .build(Some(template.command.clone()), &template.args);
+    let mut args = vec!["bisect".to_string(), "run".to_string(), shell];
+    args.extend(shell_args);
+    Ok(TaskTemplate {
+        label: format!("git bisect run: {}", template.label),
+        command: "git".to_string(),
+        args,
+        cwd: template
+            .cwd
+            .clone()
+            .or_else(|| Some(work_directory.to_string())),
+        depends_on: Vec::new(),
+        ..template.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use task::Shell;
+
+    #[test]
+    fn test_bisect_run_template() {
+        let template = TaskTemplate {
+            label: "cargo test".to_string(),
+            command: "cargo".to_string(),
+            args: vec![
+                "test".to_string(),
+                "-p".to_string(),
+                "$ZED_CUSTOM_crate".to_string(),
+            ],
+            shell: Shell::Program("sh".to_string()),
+            depends_on: vec!["build".to_string()],
+            ..TaskTemplate::default()
+        };
+        let bisect_template = bisect_run_template(&template, "/repo", false).unwrap();
+        assert_eq!(bisect_template.label, "git bisect run: cargo test");
+        assert_eq!(bisect_template.command, "git");
+        assert_eq!(
+            bisect_template.args,
+            vec![
+                "bisect",
+                "run",
+                "sh",
+                "-c",
+                "cargo test -p '$ZED_CUSTOM_crate'"
+            ]
+        );
+        assert_eq!(bisect_template.cwd.as_deref(), Some("/repo"));
+        assert!(bisect_template.depends_on.is_empty());
+
+        let template = TaskTemplate {
+            cwd: Some("$ZED_WORKTREE_ROOT/crates".to_string()),
+            ..template
+        };
+        assert_eq!(
+            bisect_run_template(&template, "/repo", false)
+                .unwrap()
+                .cwd
+                .as_deref(),
+            Some("$ZED_WORKTREE_ROOT/crates")
+        );
+
+        let composite_template = TaskTemplate {
+            label: "check".to_string(),
+            depends_on: vec!["build".to_string(), "test".to_string()],
+            ..TaskTemplate::default()
+        };
+        bisect_run_template(&composite_template, "/repo", false).unwrap_err();
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_bisect_run_shell_command() {
+        let template = TaskTemplate {
+            label: "check".to_string(),
+            command: "test \"$(echo bad)\" = bad && exit 3 || exit 0".to_string(),
+            shell: Shell::Program("sh".to_string()),
+            ..TaskTemplate::default()
+        };
+        let bisect_template = bisect_run_template(&template, "/repo", false).unwrap();
+        let [bisect, run, shell, shell_args @ ..] = bisect_template.args.as_slice() else {
+            panic!("unexpected bisect args {:?}", bisect_template.args);
+        };
+        assert_eq!((bisect.as_str(), run.as_str()), ("bisect", "run"));
+
+        // This is the command `git bisect run` spawns for every commit it tests.
+        let status = std::process::Command::new(shell)
+            .args(shell_args)
+            .status()
+            .unwrap();
+        assert_eq!(status.code(), Some(3));
+    }
+}
@@ -3,7 +3,8 @@ use editor::Editor;
 use fuzzy::StringMatchCandidate;
 
 use collections::HashSet;
-use git::repository::Branch;
+use git::SHORT_SHA_LENGTH;
+use git::repository::{Branch, Tag};
 use gpui::http_client::Url;
 use gpui::{
     Action, App, AsyncApp, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
@@ -24,15 +25,20 @@ use util::ResultExt;
 use workspace::notifications::DetachAndPromptErr;
 use workspace::{ModalView, Workspace};
 
-use crate::{branch_picker, git_panel::show_error_toast};
+use crate::{
+    branch_picker,
+    git_panel::{GitPanel, show_error_toast},
+};
 
 actions!(
     branch_picker,
     [
-        /// Deletes the selected git branch or remote.
+        /// Deletes the selected git branch, tag or remote.
         DeleteBranch,
         /// Filter the list of remotes
-        FilterRemotes
+        FilterRemotes,
+        /// Pushes the selected tag to a remote.
+        PushTag
     ]
 );
 
@@ -130,6 +136,9 @@ impl BranchList {
         let default_branch_request = repository
             .clone()
             .map(|repository| repository.update(cx, |repository, _| repository.default_branch()));
+        let all_tags_request = repository
+            .clone()
+            .map(|repository| repository.update(cx, |repository, _| repository.tags()));
 
         cx.spawn_in(window, async move |this, cx| {
             let mut all_branches = all_branches_request
@@ -142,6 +151,12 @@ impl BranchList {
                 .ok()
                 .flatten()
                 .flatten();
+            // Tags are optional checkout targets, failing to list them shouldn't hide the branches.
+            let all_tags = match all_tags_request {
+                Some(request) => request.await.ok().and_then(|tags| tags.log_err()),
+                None => None,
+            }
+            .unwrap_or_default();
 
             let all_branches = cx
                 .background_spawn(async move {
@@ -176,6 +191,7 @@ impl BranchList {
                 this.picker.update(cx, |picker, cx| {
                     picker.delegate.default_branch = default_branch;
                     picker.delegate.all_branches = Some(all_branches);
+                    picker.delegate.all_tags = all_tags;
                     picker.refresh(window, cx);
                 })
             });
@@ -226,6 +242,19 @@ impl BranchList {
         })
     }
 
+    fn handle_push_tag(
+        &mut self,
+        _: &branch_picker::PushTag,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .push_tag_at(picker.delegate.selected_index, window, cx)
+        })
+    }
+
     fn handle_filter(
         &mut self,
         _: &branch_picker::FilterRemotes,
@@ -266,6 +295,7 @@ impl Render for BranchList {
             .on_modifiers_changed(cx.listener(Self::handle_modifiers_changed))
             .on_action(cx.listener(Self::handle_delete))
             .on_action(cx.listener(Self::handle_filter))
+            .on_action(cx.listener(Self::handle_push_tag))
             .child(self.picker.clone())
             .on_mouse_down_out({
                 cx.listener(move |this, _, window, cx| {
@@ -283,6 +313,10 @@ enum Entry {
         branch: Branch,
         positions: Vec<usize>,
     },
+    Tag {
+        tag: Tag,
+        positions: Vec<usize>,
+    },
     NewUrl {
         url: String,
     },
@@ -299,9 +333,17 @@ impl Entry {
         }
     }
 
+    fn as_tag(&self) -> Option<&Tag> {
+        match self {
+            Entry::Tag { tag, .. } => Some(tag),
+            _ => None,
+        }
+    }
+
     fn name(&self) -> &str {
         match self {
             Entry::Branch { branch, .. } => branch.name(),
+            Entry::Tag { tag, .. } => tag.name.as_ref(),
             Entry::NewUrl { url, .. } => url.as_str(),
             Entry::NewBranch { name, .. } => name.as_str(),
         }
@@ -322,6 +364,7 @@ pub struct BranchListDelegate {
     workspace: Option<WeakEntity<Workspace>>,
     matches: Vec<Entry>,
     all_branches: Option<Vec<Branch>>,
+    all_tags: Vec<Tag>,
     default_branch: Option<SharedString>,
     repo: Option<Entity<Repository>>,
     style: BranchListStyle,
@@ -359,6 +402,7 @@ impl BranchListDelegate {
             repo,
             style,
             all_branches: None,
+            all_tags: Vec::new(),
             default_branch: None,
             selected_index: 0,
             last_query: Default::default(),
@@ -464,6 +508,10 @@ impl BranchListDelegate {
                             .await?
                     }
                 },
+                Entry::Tag { tag, .. } => {
+                    repo.update(cx, |repo, _| repo.delete_tag(tag.name.to_string()))?
+                        .await?
+                }
                 _ => {
                     log::error!("Failed to delete remote: wrong entry to delete");
                     return Ok(());
@@ -471,30 +519,19 @@ impl BranchListDelegate {
             };
 
             if let Err(e) = result {
-                if is_remote {
+                let action = if is_remote {
                     log::error!("Failed to delete remote: {}", e);
+                    format!("remote remove {}", entry.name())
+                } else if entry.as_tag().is_some() {
+                    log::error!("Failed to delete tag: {}", e);
+                    format!("tag -d {}", entry.name())
                 } else {
                     log::error!("Failed to delete branch: {}", e);
-                }
+                    format!("branch -d {}", entry.name())
+                };
 
                 if let Some(workspace) = workspace.and_then(|w| w.upgrade()) {
-                    cx.update(|_window, cx| {
-                        if is_remote {
-                            show_error_toast(
-                                workspace,
-                                format!("remote remove {}", entry.name()),
-                                e,
-                                cx,
-                            )
-                        } else {
-                            show_error_toast(
-                                workspace,
-                                format!("branch -d {}", entry.name()),
-                                e,
-                                cx,
-                            )
-                        }
-                    })?;
+                    cx.update(|_window, cx| show_error_toast(workspace, action, e, cx))?;
                 }
 
                 return Ok(());
@@ -503,10 +540,16 @@ impl BranchListDelegate {
             picker.update_in(cx, |picker, _, cx| {
                 picker.delegate.matches.retain(|e| e != &entry);
 
-                if let Entry::Branch { branch, .. } = &entry {
-                    if let Some(all_branches) = &mut picker.delegate.all_branches {
-                        all_branches.retain(|e| e.ref_name != branch.ref_name);
+                match &entry {
+                    Entry::Branch { branch, .. } => {
+                        if let Some(all_branches) = &mut picker.delegate.all_branches {
+                            all_branches.retain(|e| e.ref_name != branch.ref_name);
+                        }
+                    }
+                    Entry::Tag { tag, .. } => {
+                        picker.delegate.all_tags.retain(|e| e.name != tag.name);
                     }
+                    _ => {}
                 }
 
                 if picker.delegate.matches.is_empty() {
@@ -522,6 +565,24 @@ impl BranchListDelegate {
         })
         .detach();
     }
+
+    fn push_tag_at(&self, idx: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self.matches.get(idx).and_then(Entry::as_tag) else {
+            return;
+        };
+        let Some(git_panel) = self
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.upgrade())
+            .and_then(|workspace| workspace.read(cx).panel::<GitPanel>(cx))
+        else {
+            return;
+        };
+        git_panel.update(cx, |git_panel, cx| {
+            git_panel.push_tag(tag.name.clone(), window, cx)
+        });
+        cx.emit(DismissEvent);
+    }
 }
 
 impl PickerDelegate for BranchListDelegate {
@@ -635,8 +696,15 @@ impl PickerDelegate for BranchListDelegate {
         let Some(all_branches) = self.all_branches.clone() else {
             return Task::ready(());
         };
+        // Tags are checkout targets alongside the local branches, but not the remote ones.
+        let all_tags = if self.display_remotes {
+            Vec::new()
+        } else {
+            self.all_tags.clone()
+        };
 
         const RECENT_BRANCHES_COUNT: usize = 10;
+        const RECENT_TAGS_COUNT: usize = 5;
         let display_remotes = self.display_remotes;
         cx.spawn_in(window, async move |picker, cx| {
             let mut matches: Vec<Entry> = if query.is_empty() {
@@ -654,6 +722,15 @@ impl PickerDelegate for BranchListDelegate {
                         branch,
                         positions: Vec::new(),
                     })
+                    .chain(
+                        all_tags
+                            .into_iter()
+                            .take(RECENT_TAGS_COUNT)
+                            .map(|tag| Entry::Tag {
+                                tag,
+                                positions: Vec::new(),
+                            }),
+                    )
                     .collect()
             } else {
                 let branches = all_branches
@@ -668,8 +745,10 @@ impl PickerDelegate for BranchListDelegate {
                     .collect::<Vec<_>>();
                 let candidates = branches
                     .iter()
+                    .map(|branch| branch.name())
+                    .chain(all_tags.iter().map(|tag| tag.name.as_ref()))
                     .enumerate()
-                    .map(|(ix, branch)| StringMatchCandidate::new(ix, branch.name()))
+                    .map(|(ix, name)| StringMatchCandidate::new(ix, name))
                     .collect::<Vec<StringMatchCandidate>>();
                 fuzzy::match_strings(
                     &candidates,
@@ -682,9 +761,15 @@ impl PickerDelegate for BranchListDelegate {
                 )
                 .await
                 .into_iter()
-                .map(|candidate| Entry::Branch {
-                    branch: branches[candidate.candidate_id].clone(),
-                    positions: candidate.positions,
+                .map(|candidate| match branches.get(candidate.candidate_id) {
+                    Some(branch) => Entry::Branch {
+                        branch: (*branch).clone(),
+                        positions: candidate.positions,
+                    },
+                    None => Entry::Tag {
+                        tag: all_tags[candidate.candidate_id - branches.len()].clone(),
+                        positions: candidate.positions,
+                    },
                 })
                 .collect()
             };
@@ -783,6 +868,25 @@ impl PickerDelegate for BranchListDelegate {
                     |_, _, _| None,
                 );
             }
+            Entry::Tag { tag, .. } => {
+                let Some(repo) = self.repo.clone() else {
+                    return;
+                };
+
+                let tag_name = tag.name.to_string();
+                cx.spawn(async move |_, cx| {
+                    repo.update(cx, |repo, _| repo.checkout_tag(tag_name))?
+                        .await??;
+
+                    anyhow::Ok(())
+                })
+                .detach_and_prompt_err(
+                    "Failed to checkout tag",
+                    window,
+                    cx,
+                    |e, _, _| Some(e.to_string()),
+                );
+            }
             Entry::NewUrl { url } => {
                 self.state = PickerState::CreateRemote(url.clone().into());
                 self.matches = Vec::new();
@@ -842,6 +946,16 @@ impl PickerDelegate for BranchListDelegate {
                 })
             })
             .unwrap_or_else(|| (None, None, None));
+        let commit_time = commit_time.or_else(|| {
+            let tag = entry.as_tag()?;
+            let tag_time = OffsetDateTime::from_unix_timestamp(tag.timestamp).ok()?;
+            Some(time_format::format_localized_timestamp(
+                tag_time,
+                OffsetDateTime::now_utc(),
+                time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC),
+                time_format::TimestampFormat::Relative,
+            ))
+        });
 
         let entry_icon = match entry {
             Entry::NewUrl { .. } | Entry::NewBranch { .. } => {
@@ -855,6 +969,8 @@ impl PickerDelegate for BranchListDelegate {
                     Icon::new(IconName::GitBranchAlt).color(Color::Muted)
                 }
             }
+
+            Entry::Tag { .. } => Icon::new(IconName::Hash).color(Color::Muted),
         };
 
         let entry_title = match entry {
@@ -872,15 +988,26 @@ impl PickerDelegate for BranchListDelegate {
                     .truncate()
                     .into_any_element()
             }
+            Entry::Tag { tag, positions } => {
+                HighlightedLabel::new(tag.name.clone(), positions.clone())
+                    .single_line()
+                    .truncate()
+                    .into_any_element()
+            }
         };
 
         let focus_handle = self.focus_handle.clone();
         let is_new_items = matches!(entry, Entry::NewUrl { .. } | Entry::NewBranch { .. });
 
+        let delete_tooltip_label = if entry.as_tag().is_some() {
+            "Delete Tag"
+        } else {
+            "Delete Branch"
+        };
         let delete_branch_button = IconButton::new("delete", IconName::Trash)
             .tooltip(move |_, cx| {
                 Tooltip::for_action_in(
-                    "Delete Branch",
+                    delete_tooltip_label,
                     &branch_picker::DeleteBranch,
                     &focus_handle,
                     cx,
@@ -952,6 +1079,14 @@ impl PickerDelegate for BranchListDelegate {
                                                                 .to_string()
                                                         }
                                                     }
+                                                    Entry::Tag { tag, .. } => match &tag.message {
+                                                        Some(message) => message.to_string(),
+                                                        None => format!(
+                                                            "Tag at {}",
+                                                            &tag.sha[..SHORT_SHA_LENGTH
+                                                                .min(tag.sha.len())]
+                                                        ),
+                                                    },
                                                     Entry::Branch { .. } => {
                                                         let show_author_name =
                                                             ProjectSettings::get_global(cx)
@@ -993,8 +1128,11 @@ impl PickerDelegate for BranchListDelegate {
                                         }),
                                 )
                                 .when_some(
-                                    entry.as_branch().map(|b| b.name().to_string()),
-                                    |this, branch_name| this.tooltip(Tooltip::text(branch_name)),
+                                    entry
+                                        .as_branch()
+                                        .map(|b| b.name().to_string())
+                                        .or_else(|| entry.as_tag().map(|t| t.name.to_string())),
+                                    |this, name| this.tooltip(Tooltip::text(name)),
                                 ),
                         ),
                 )
@@ -1088,8 +1226,36 @@ impl PickerDelegate for BranchListDelegate {
                             }))
                     });
 
+                let push_tag_button = selected_entry
+                    .and_then(Entry::as_tag)
+                    .filter(|_| {
+                        self.workspace.as_ref().is_some_and(|workspace| {
+                            workspace
+                                .read_with(cx, |workspace, cx| {
+                                    !workspace.project().read(cx).is_via_collab()
+                                })
+                                .unwrap_or(false)
+                        })
+                    })
+                    .map(|_| {
+                        Button::new("push-tag", "Push")
+                            .disabled(self.loading)
+                            .key_binding(
+                                KeyBinding::for_action_in(
+                                    &branch_picker::PushTag,
+                                    &focus_handle,
+                                    cx,
+                                )
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(branch_picker::PushTag.boxed_clone(), cx);
+                            })
+                    });
+
                 let delete_and_select_btns = h_flex()
                     .gap_1()
+                    .children(push_tag_button)
                     .child(
                         Button::new("delete-branch", "Delete")
                             .disabled(self.loading)
@@ -1387,6 +1553,82 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_update_matches_with_tags(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let branches = create_test_branches();
+        let (mut ctx, branch_list) = init_branch_list_test(cx, None, branches);
+        let cx = &mut ctx;
+
+        let update_matches =
+            async |query: &str, display_remotes: bool, cx: &mut VisualTestContext| {
+                branch_list
+                    .update_in(cx, |branch_list, window, cx| {
+                        branch_list.picker.update(cx, |picker, cx| {
+                            picker.delegate.display_remotes = display_remotes;
+                            picker.delegate.all_tags = vec![
+                                Tag {
+                                    name: "v2.0".into(),
+                                    sha: "abc123".into(),
+                                    message: Some("Release 2.0".into()),
+                                    timestamp: 1100,
+                                },
+                                Tag {
+                                    name: "feature-preview".into(),
+                                    sha: "def456".into(),
+                                    message: None,
+                                    timestamp: 600,
+                                },
+                            ];
+                            picker
+                                .delegate
+                                .update_matches(query.to_string(), window, cx)
+                        })
+                    })
+                    .await;
+                cx.run_until_parked();
+                branch_list.update(cx, |branch_list, cx| {
+                    branch_list.picker.read(cx).delegate.matches.clone()
+                })
+            };
+
+        let matches = update_matches("", false, cx).await;
+        assert_eq!(
+            matches.iter().map(Entry::name).collect::<Vec<_>>(),
+            vec![
+                "main",
+                "feature-auth",
+                "feature-ui",
+                "develop",
+                "v2.0",
+                "feature-preview"
+            ]
+        );
+
+        let matches = update_matches("feature", false, cx).await;
+        let names = matches
+            .iter()
+            .filter(|entry| !entry.is_new_branch())
+            .map(Entry::name)
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            names,
+            HashSet::from_iter(["feature-auth", "feature-ui", "feature-preview"])
+        );
+        assert!(
+            matches
+                .iter()
+                .find(|entry| entry.name() == "feature-preview")
+                .and_then(Entry::as_tag)
+                .is_some()
+        );
+
+        // Tags aren't listed among the remote branches.
+        let matches = update_matches("", true, cx).await;
+        assert!(matches.iter().all(|entry| entry.as_tag().is_none()));
+    }
+
     async fn update_branch_list_matches_with_empty_query(
         branch_list: &Entity<BranchList>,
         cx: &mut VisualTestContext,
@@ -1,4 +1,4 @@
-use crate::commit_view::CommitView;
+use crate::commit_view::{CommitView, format_tags};
 use editor::hover_markdown_style;
 use futures::Future;
 use git::blame::BlameEntry;
@@ -113,6 +113,7 @@ pub struct CommitTooltip {
     markdown: Entity<Markdown>,
     repository: Entity<Repository>,
     workspace: WeakEntity<Workspace>,
+    tags: Vec<SharedString>,
 }
 
 impl CommitTooltip {
@@ -164,12 +165,29 @@ impl CommitTooltip {
                 cx,
             )
         });
+        let tags_containing = repository.update(cx, |repository, _| {
+            repository.tags_containing(commit.sha.to_string())
+        });
+        // Lines that aren't committed yet have no tags, and fail to be looked up.
+        cx.spawn(async move |this, cx| {
+            let Ok(Ok(tags)) = tags_containing.await else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.tags = tags;
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+
         Self {
             commit,
             repository,
             workspace,
             scroll_handle: ScrollHandle::new(),
             markdown,
+            tags: Vec::new(),
         }
     }
 }
@@ -218,6 +236,7 @@ impl Render for CommitTooltip {
         let message_max_height = window.line_height() * 12 + (ui_font_size / 0.4);
         let repo = self.repository.clone();
         let workspace = self.workspace.clone();
+        let tags = (!self.tags.is_empty()).then(|| format_tags(&self.tags));
         let commit_summary = CommitSummary {
             sha: self.commit.sha.clone(),
             subject: self
@@ -273,6 +292,23 @@ impl Render for CommitTooltip {
                                 .overflow_y_scroll()
                                 .track_scroll(&self.scroll_handle),
                         )
+                        .when_some(tags, |this, tags| {
+                            this.child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(
+                                        Icon::new(IconName::Hash)
+                                            .color(Color::Muted)
+                                            .size(IconSize::XSmall),
+                                    )
+                                    .child(
+                                        Label::new(tags)
+                                            .color(Color::Muted)
+                                            .size(LabelSize::Small)
+                                            .truncate(),
+                                    ),
+                            )
+                        })
                         .child(
                             h_flex()
                                 .text_color(cx.theme().colors().text_muted)
@@ -2,8 +2,8 @@ use anyhow::{Context as _, Result};
 use buffer_diff::{BufferDiff, BufferDiffSnapshot};
 use editor::display_map::{BlockPlacement, BlockProperties, BlockStyle};
 use editor::{Editor, EditorEvent, ExcerptRange, MultiBuffer, multibuffer_context_lines};
-use git::repository::{CommitDetails, CommitDiff, RepoPath};
-use git::{GitHostingProviderRegistry, GitRemote, parse_git_remote_url};
+use git::repository::{BisectMark, CommitDetails, CommitDiff, RepoPath, SequencerKind};
+use git::{GitHostingProviderRegistry, GitRemote, SHORT_SHA_LENGTH, parse_git_remote_url};
 use gpui::{
     AnyElement, App, AppContext as _, Asset, AsyncApp, AsyncWindowContext, Context, Element,
     Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement, ParentElement,
@@ -33,7 +33,7 @@ use workspace::{
     searchable::SearchableItemHandle,
 };
 
-use crate::git_panel::GitPanel;
+use crate::{bisect, git_panel::GitPanel, tag_modal::TagModal};
 
 actions!(git, [ApplyCurrentStash, PopCurrentStash, DropCurrentStash,]);
 
@@ -59,6 +59,8 @@ pub struct CommitView {
     multibuffer: Entity<MultiBuffer>,
     repository: Entity<Repository>,
     remote: Option<GitRemote>,
+    /// The tags whose history contains the commit.
+    tags: Vec<SharedString>,
 }
 
 struct GitBlob {
@@ -300,6 +302,20 @@ impl CommitView {
             })
         });
 
+        if stash.is_none() {
+            let tags_containing = repository.update(cx, |repository, _| {
+                repository.tags_containing(commit.sha.to_string())
+            });
+            cx.spawn(async move |this, cx| {
+                let tags = tags_containing.await??;
+                this.update(cx, |this, cx| {
+                    this.tags = tags;
+                    cx.notify();
+                })
+            })
+            .detach_and_log_err(cx);
+        }
+
         Self {
             commit,
             editor,
@@ -307,6 +323,7 @@ impl CommitView {
             stash,
             repository,
             remote,
+            tags: Vec::new(),
         }
     }
 
@@ -474,19 +491,144 @@ impl CommitView {
                                             .size(LabelSize::Small),
                                     )
                                     .children(commit_diff_stat),
-                            ),
+                            )
+                            .when(!self.tags.is_empty(), |this| {
+                                this.child(
+                                    h_flex()
+                                        .gap_1()
+                                        .child(
+                                            Icon::new(IconName::Hash)
+                                                .color(Color::Muted)
+                                                .size(IconSize::XSmall),
+                                        )
+                                        .child(
+                                            Label::new(format_tags(&self.tags))
+                                                .color(Color::Muted)
+                                                .size(LabelSize::Small)
+                                                .truncate(),
+                                        ),
+                                )
+                            }),
                     )
-                    .children(github_url.map(|url| {
-                        Button::new("view_on_github", "View on GitHub")
-                            .icon(IconName::Github)
-                            .icon_color(Color::Muted)
-                            .icon_size(IconSize::Small)
-                            .icon_position(IconPosition::Start)
-                            .on_click(move |_, _, cx| cx.open_url(&url))
-                    })),
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .when(self.stash.is_none(), |this| {
+                                this.child(
+                                    Button::new("cherry_pick", "Cherry-pick")
+                                        .icon(IconName::Copy)
+                                        .icon_color(Color::Muted)
+                                        .icon_size(IconSize::Small)
+                                        .icon_position(IconPosition::Start)
+                                        .tooltip(Tooltip::text(
+                                            "Apply this commit's changes on top of HEAD",
+                                        ))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.apply_commit(SequencerKind::CherryPick, window, cx)
+                                        })),
+                                )
+                                .child(
+                                    Button::new("create_tag", "Tag…")
+                                        .icon(IconName::Hash)
+                                        .icon_color(Color::Muted)
+                                        .icon_size(IconSize::Small)
+                                        .icon_position(IconPosition::Start)
+                                        .tooltip(Tooltip::text("Create a tag at this commit"))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.create_tag(window, cx)
+                                        })),
+                                )
+                                .child(
+                                    Button::new("revert", "Revert")
+                                        .icon(IconName::Undo)
+                                        .icon_color(Color::Muted)
+                                        .icon_size(IconSize::Small)
+                                        .icon_position(IconPosition::Start)
+                                        .tooltip(Tooltip::text(
+                                            "Commit the inverse of this commit's changes",
+                                        ))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.apply_commit(SequencerKind::Revert, window, cx)
+                                        })),
+                                )
+                            })
+                            .when(
+                                self.stash.is_none() && self.repository.read(cx).bisect.is_none(),
+                                |this| {
+                                    this.child(
+                                        Button::new("bisect", "Bisect…")
+                                            .icon(IconName::Crosshair)
+                                            .icon_color(Color::Muted)
+                                            .icon_size(IconSize::Small)
+                                            .icon_position(IconPosition::Start)
+                                            .tooltip(Tooltip::text(
+                                                "Start bisecting from this commit",
+                                            ))
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.start_bisect(window, cx)
+                                            })),
+                                    )
+                                },
+                            )
+                            .children(github_url.map(|url| {
+                                Button::new("view_on_github", "View on GitHub")
+                                    .icon(IconName::Github)
+                                    .icon_color(Color::Muted)
+                                    .icon_size(IconSize::Small)
+                                    .icon_position(IconPosition::Start)
+                                    .on_click(move |_, _, cx| cx.open_url(&url))
+                            })),
+                    ),
             )
     }
 
+    fn apply_commit(&mut self, kind: SequencerKind, window: &mut Window, cx: &mut Context<Self>) {
+        apply_commit(
+            kind,
+            self.commit.sha.clone(),
+            self.repository.clone(),
+            window,
+            cx,
+        );
+    }
+
+    fn start_bisect(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let sha = self.commit.sha.clone();
+        let short_sha = sha.get(..SHORT_SHA_LENGTH).unwrap_or(&sha).to_string();
+        let answer = window.prompt(
+            PromptLevel::Info,
+            &format!("Start bisecting from {short_sha}?"),
+            Some(
+                "A good commit is bisected against HEAD. \
+                A bad one waits for a good commit to be marked.",
+            ),
+            &["Mark Good", "Mark Bad", "Cancel"],
+            cx,
+        );
+        let repository = self.repository.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let mark = match answer.await {
+                Ok(0) => BisectMark::Good,
+                Ok(1) => BisectMark::Bad,
+                _ => return,
+            };
+            cx.update(|window, cx| bisect::start(&repository, sha, mark, window, cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    fn create_tag(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(workspace) = window.root::<Workspace>().flatten() else {
+            return;
+        };
+        let repository = self.repository.clone();
+        let sha = self.commit.sha.clone();
+        workspace.update(cx, |workspace, cx| {
+            TagModal::toggle(workspace, repository, Some(sha), window, cx)
+        });
+    }
+
     fn apply_stash(workspace: &mut Workspace, window: &mut Window, cx: &mut App) {
         Self::stash_action(
             workspace,
@@ -1071,6 +1213,58 @@ impl ToolbarItemView for CommitViewToolbar {
     }
 }
 
+/// Lists the tags containing a commit, the oldest ones first, as they are the ones the commit first shipped in.
+pub(crate) fn format_tags(tags: &[SharedString]) -> String {
+    const MAX_TAGS: usize = 3;
+    let mut text = tags
+        .iter()
+        .take(MAX_TAGS)
+        .map(|tag| tag.as_ref())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if tags.len() > MAX_TAGS {
+        text.push_str(&format!(" (+{})", tags.len() - MAX_TAGS));
+    }
+    text
+}
+
+/// Cherry-picks or reverts the commit after confirming it with the user.
+///
+/// Conflicts leave the operation in progress, to be continued or aborted from the git panel.
+pub(crate) fn apply_commit(
+    kind: SequencerKind,
+    sha: SharedString,
+    repository: Entity<Repository>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let short_sha = sha.get(..SHORT_SHA_LENGTH).unwrap_or(&sha).to_string();
+    let (action, message) = match kind {
+        SequencerKind::CherryPick => (
+            "Cherry-pick",
+            format!("Cherry-pick commit {short_sha} onto the current branch?"),
+        ),
+        SequencerKind::Revert => ("Revert", format!("Revert commit {short_sha}?")),
+    };
+    let answer = window.prompt(PromptLevel::Info, &message, None, &[action, "Cancel"], cx);
+
+    window
+        .spawn(cx, async move |cx| {
+            if answer.await != Ok(0) {
+                return anyhow::Ok(());
+            }
+
+            repository
+                .update(cx, |repository, cx| match kind {
+                    SequencerKind::CherryPick => repository.cherry_pick(sha.to_string(), cx),
+                    SequencerKind::Revert => repository.revert(sha.to_string(), cx),
+                })?
+                .await??;
+            anyhow::Ok(())
+        })
+        .detach_and_notify_err(window, cx);
+}
+
 fn stash_matches_index(sha: &str, stash_index: usize, repo: &Repository) -> bool {
     repo.stash_entries
         .entries
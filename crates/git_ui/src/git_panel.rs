@@ -4,7 +4,7 @@ use crate::commit_tooltip::CommitTooltip;
 use crate::commit_view::CommitView;
 use crate::project_diff::{self, Diff, ProjectDiff};
 use crate::remote_output::{self, RemoteAction, SuccessMessage};
-use crate::{branch_picker, picker_prompt, render_remote_button};
+use crate::{branch_picker, picker_prompt, render_remote_button, submodule_summary};
 use crate::{
     file_history_view::FileHistoryView, git_panel_settings::GitPanelSettings, git_status_icon,
     repository_selector::RepositorySelector,
@@ -22,16 +22,18 @@ use editor::{
 use futures::StreamExt as _;
 use git::blame::ParsedCommitMessage;
 use git::repository::{
-    Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions, GitCommitter,
-    PushOptions, Remote, RemoteCommandOutput, ResetMode, Upstream, UpstreamTracking,
+    BisectState, Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions,
+    GitCommitter, PushOptions, Remote, RemoteCommandOutput, ResetMode, SequencerKind,
+    SequencerOperation, SequencerState, SubmoduleOperation, Upstream, UpstreamTracking,
     UpstreamTrackingStatus, get_git_committer,
 };
 use git::stash::GitStash;
 use git::status::StageStatus;
 use git::{Amend, Signoff, ToggleStaged, repository::RepoPath, status::FileStatus};
 use git::{
-    ExpandCommitEditor, RestoreTrackedFiles, StageAll, StashAll, StashApply, StashPop,
-    TrashUntrackedFiles, UnstageAll,
+    BisectBad, BisectGood, BisectReset, BisectRun, BisectSkip, ExpandCommitEditor, InitSubmodules,
+    RestoreTrackedFiles, SHORT_SHA_LENGTH, StageAll, StashAll, StashApply, StashPop,
+    SyncSubmodules, TrashUntrackedFiles, UnstageAll, UpdateSubmodules,
 };
 use gpui::{
     Action, AsyncApp, AsyncWindowContext, Bounds, ClickEvent, Corner, DismissEvent, Entity,
@@ -127,6 +129,7 @@ struct GitMenuState {
     has_new_changes: bool,
     sort_by_path: bool,
     has_stash_items: bool,
+    has_submodules: bool,
     tree_view: bool,
 }
 
@@ -160,6 +163,12 @@ fn git_panel_context_menu(
             .separator()
             .action("Open Diff", project_diff::Diff.boxed_clone())
             .separator()
+            .when(state.has_submodules, |this| {
+                this.action("Update Submodules", UpdateSubmodules.boxed_clone())
+                    .action("Init Submodules", InitSubmodules.boxed_clone())
+                    .action("Sync Submodules", SyncSubmodules.boxed_clone())
+                    .separator()
+            })
             .action_disabled_when(
                 !state.has_tracked_changes,
                 "Discard Tracked Changes",
@@ -755,6 +764,15 @@ impl GitPanel {
                     | GitStoreEvent::RepositoryRemoved(_) => {
                         this.schedule_update(window, cx);
                     }
+                    GitStoreEvent::RepositoryUpdated(
+                        _,
+                        RepositoryEvent::SequencerStateChanged
+                        | RepositoryEvent::BisectStateChanged
+                        | RepositoryEvent::SubmodulesChanged,
+                        true,
+                    ) => {
+                        cx.notify();
+                    }
                     GitStoreEvent::IndexWriteError(error) => {
                         this.workspace
                             .update(cx, |workspace, cx| {
@@ -1832,6 +1850,38 @@ impl GitPanel {
         .detach();
     }
 
+    fn sequencer_operation(
+        &mut self,
+        kind: SequencerKind,
+        operation: SequencerOperation,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+
+        cx.spawn({
+            async move |this, cx| {
+                let operation_task = active_repository
+                    .update(cx, |repo, cx| repo.sequencer_operation(kind, operation, cx))?
+                    .await;
+                this.update(cx, |this, cx| {
+                    operation_task
+                        .map_err(|e| {
+                            this.show_error_toast(
+                                format!("{} {}", kind.as_command(), operation.as_flag()),
+                                e,
+                                cx,
+                            );
+                        })
+                        .ok();
+                    cx.notify();
+                })
+            }
+        })
+        .detach();
+    }
+
     pub fn stash_apply(&mut self, _: &StashApply, _window: &mut Window, cx: &mut Context<Self>) {
         let Some(active_repository) = self.active_repository.clone() else {
             return;
@@ -1855,6 +1905,46 @@ impl GitPanel {
         .detach();
     }
 
+    fn has_submodules(&self, cx: &App) -> bool {
+        self.active_repository
+            .as_ref()
+            .is_some_and(|repository| !repository.read(cx).submodules.is_empty())
+    }
+
+    pub(crate) fn submodule_operation(
+        &mut self,
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+
+        cx.spawn({
+            async move |this, cx| {
+                let operation_task = active_repository
+                    .update(cx, |repo, cx| {
+                        repo.submodule_operation(operation, paths, cx)
+                    })?
+                    .await;
+                this.update(cx, |this, cx| {
+                    operation_task
+                        .map_err(|e| {
+                            this.show_error_toast(
+                                format!("submodule {}", operation.as_command()),
+                                e,
+                                cx,
+                            );
+                        })
+                        .ok();
+                    cx.notify();
+                })
+            }
+        })
+        .detach();
+    }
+
     pub fn stash_all(&mut self, _: &StashAll, _window: &mut Window, cx: &mut Context<Self>) {
         let Some(active_repository) = self.active_repository.clone() else {
             return;
@@ -2903,6 +2993,59 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
+    pub(crate) fn push_tag(
+        &mut self,
+        tag: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Tag Pushed");
+        let remote = self.get_remote(true, true, window, cx);
+
+        cx.spawn_in(window, async move |this, cx| {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(cx, |this, cx| this.show_error_toast("push", e, cx))
+                        .ok();
+                    return Ok(());
+                }
+            };
+
+            let askpass_delegate = this.update_in(cx, |this, window, cx| {
+                this.askpass_delegate(format!("git push {}", remote.name), window, cx)
+            })?;
+
+            let push = repo.update(cx, |repo, cx| {
+                repo.push_tag(tag.clone(), remote.name.clone(), askpass_delegate, cx)
+            })?;
+
+            let remote_output = push.await?;
+
+            let action = RemoteAction::PushTag(tag, remote);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while pushing tag {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn askpass_delegate(
         &self,
         operation: impl Into<SharedString>,
@@ -3638,6 +3781,7 @@ impl GitPanel {
         let has_unstaged_changes = self.has_unstaged_changes();
         let has_new_changes = self.new_count > 0;
         let has_stash_items = self.stash_entries.entries.len() > 0;
+        let has_submodules = self.has_submodules(cx);
 
         PopoverMenu::new(id.into())
             .trigger(
@@ -3655,6 +3799,7 @@ impl GitPanel {
                         has_new_changes,
                         sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                         has_stash_items,
+                        has_submodules,
                         tree_view: GitPanelSettings::get_global(cx).tree_view,
                     },
                     window,
@@ -4191,6 +4336,168 @@ impl GitPanel {
             )
     }
 
+    fn render_sequencer_state(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let active_repository = self.active_repository.as_ref()?;
+        let SequencerState { kind, sha } = active_repository.read(cx).sequencer.clone()?;
+        let short_sha = sha.get(..SHORT_SHA_LENGTH).unwrap_or(&sha).to_string();
+        let label = match kind {
+            SequencerKind::CherryPick => format!("Cherry-picking {short_sha}"),
+            SequencerKind::Revert => format!("Reverting {short_sha}"),
+        };
+
+        Some(
+            h_flex()
+                .py_1p5()
+                .px_2()
+                .gap_1p5()
+                .justify_between()
+                .border_t_1()
+                .border_color(cx.theme().colors().border.opacity(0.8))
+                .child(
+                    div()
+                        .flex_grow()
+                        .overflow_hidden()
+                        .child(Label::new(label).size(LabelSize::Small).truncate()),
+                )
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            panel_button("Abort")
+                                .tooltip(Tooltip::text(format!(
+                                    "git {} --abort",
+                                    kind.as_command()
+                                )))
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.sequencer_operation(kind, SequencerOperation::Abort, cx)
+                                })),
+                        )
+                        .child(
+                            panel_filled_button("Continue")
+                                .tooltip(Tooltip::text(format!(
+                                    "git {} --continue",
+                                    kind.as_command()
+                                )))
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.sequencer_operation(kind, SequencerOperation::Continue, cx)
+                                })),
+                        ),
+                ),
+        )
+    }
+
+    fn render_bisect_state(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let active_repository = self.active_repository.as_ref()?;
+        let BisectState {
+            bad,
+            good,
+            remaining_revisions,
+            remaining_steps,
+            first_bad,
+            ..
+        } = active_repository.read(cx).bisect.clone()?;
+        let label = if let Some(first_bad) = &first_bad {
+            format!(
+                "{} is the first bad commit",
+                first_bad.get(..SHORT_SHA_LENGTH).unwrap_or(first_bad)
+            )
+        } else if bad.is_none() {
+            "Bisecting: waiting for a bad commit".to_string()
+        } else if good.is_empty() {
+            "Bisecting: waiting for a good commit".to_string()
+        } else {
+            let revisions = remaining_revisions.unwrap_or_default();
+            let steps = remaining_steps.unwrap_or_default();
+            format!(
+                "Bisecting: {revisions} {} left (roughly {steps} {})",
+                if revisions == 1 {
+                    "revision"
+                } else {
+                    "revisions"
+                },
+                if steps == 1 { "step" } else { "steps" }
+            )
+        };
+        let focus_handle = self.focus_handle.clone();
+
+        Some(
+            v_flex()
+                .py_1p5()
+                .px_2()
+                .gap_1()
+                .border_t_1()
+                .border_color(cx.theme().colors().border.opacity(0.8))
+                .child(Label::new(label).size(LabelSize::Small).truncate())
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .justify_end()
+                        .when(first_bad.is_none(), |this| {
+                            this.child(
+                                panel_button("Good")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "Mark HEAD as Good",
+                                        &BisectGood,
+                                        &focus_handle,
+                                    ))
+                                    .on_click(|_, window, cx| {
+                                        window.dispatch_action(BisectGood.boxed_clone(), cx)
+                                    }),
+                            )
+                            .child(
+                                panel_button("Bad")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "Mark HEAD as Bad",
+                                        &BisectBad,
+                                        &focus_handle,
+                                    ))
+                                    .on_click(|_, window, cx| {
+                                        window.dispatch_action(BisectBad.boxed_clone(), cx)
+                                    }),
+                            )
+                            .child(
+                                panel_button("Skip")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "Skip HEAD",
+                                        &BisectSkip,
+                                        &focus_handle,
+                                    ))
+                                    .on_click(|_, window, cx| {
+                                        window.dispatch_action(BisectSkip.boxed_clone(), cx)
+                                    }),
+                            )
+                            .when(
+                                bad.is_some() && !good.is_empty(),
+                                |this| {
+                                    this.child(
+                                        panel_button("Run Task…")
+                                            .tooltip(Tooltip::for_action_title_in(
+                                                "Mark Commits by a Task",
+                                                &BisectRun,
+                                                &focus_handle,
+                                            ))
+                                            .on_click(|_, window, cx| {
+                                                window.dispatch_action(BisectRun.boxed_clone(), cx)
+                                            }),
+                                    )
+                                },
+                            )
+                        })
+                        .child(
+                            panel_filled_button("Reset")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "End Bisect",
+                                    &BisectReset,
+                                    &focus_handle,
+                                ))
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(BisectReset.boxed_clone(), cx)
+                                }),
+                        ),
+                ),
+        )
+    }
+
     fn render_previous_commit(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
         let active_repository = self.active_repository.as_ref()?;
         let branch = active_repository.read(cx).branch.as_ref()?;
@@ -4617,6 +4924,7 @@ impl GitPanel {
                 has_new_changes: self.new_count > 0,
                 sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                 has_stash_items: self.stash_entries.entries.len() > 0,
+                has_submodules: self.has_submodules(cx),
                 tree_view: GitPanelSettings::get_global(cx).tree_view,
             },
             window,
@@ -4778,6 +5086,15 @@ impl GitPanel {
             }))
         };
 
+        if let Some(submodule) = repo.submodule(&entry.repo_path) {
+            name_row = name_row.child(
+                Label::new(submodule_summary(submodule))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .truncate(),
+            );
+        }
+
         h_flex()
             .id(id)
             .h(self.list_item_height())
@@ -5261,6 +5578,8 @@ impl Render for GitPanel {
                         }
                     })
                     .children(self.render_footer(window, cx))
+                    .children(self.render_sequencer_state(cx))
+                    .children(self.render_bisect_state(cx))
                     .when(self.amend_pending, |this| {
                         this.child(self.render_pending_amend(cx))
                     })
@@ -12,7 +12,10 @@ use ui::{
 mod blame_ui;
 
 use git::{
-    repository::{Branch, Upstream, UpstreamTracking, UpstreamTrackingStatus},
+    SHORT_SHA_LENGTH,
+    repository::{
+        Branch, Submodule, SubmoduleOperation, Upstream, UpstreamTracking, UpstreamTrackingStatus,
+    },
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
 };
 use gpui::{
@@ -30,6 +33,7 @@ use zed_actions;
 use crate::{git_panel::GitPanel, text_diff_view::TextDiffView};
 
 mod askpass_modal;
+mod bisect;
 pub mod branch_picker;
 mod commit_modal;
 pub mod commit_tooltip;
@@ -42,9 +46,11 @@ mod git_panel_settings;
 pub mod onboarding;
 pub mod picker_prompt;
 pub mod project_diff;
+pub mod rebase_view;
 pub(crate) mod remote_output;
 pub mod repository_selector;
 pub mod stash_picker;
+mod tag_modal;
 pub mod text_diff_view;
 pub mod worktree_picker;
 
@@ -137,6 +143,9 @@ pub fn init(cx: &mut App) {
                 });
             });
         }
+        rebase_view::register(workspace);
+        tag_modal::register(workspace);
+        bisect::register(workspace);
         workspace.register_action(|workspace, action: &git::StashAll, window, cx| {
             let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                 return;
@@ -177,6 +186,30 @@ pub fn init(cx: &mut App) {
                 panel.unstage_all(action, window, cx);
             });
         });
+        workspace.register_action(|workspace, _: &git::InitSubmodules, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.submodule_operation(SubmoduleOperation::Init, Vec::new(), cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::UpdateSubmodules, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.submodule_operation(SubmoduleOperation::Update, Vec::new(), cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::SyncSubmodules, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.submodule_operation(SubmoduleOperation::Sync, Vec::new(), cx);
+            });
+        });
         workspace.register_action(|workspace, _: &git::Uncommit, window, cx| {
             let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                 return;
@@ -301,6 +334,25 @@ pub fn git_status_icon(status: FileStatus) -> impl IntoElement {
     GitStatusIcon::new(status)
 }
 
+/// Describes the submodule's checked-out commit and changes, the way `git status` does.
+pub fn submodule_summary(submodule: &Submodule) -> String {
+    let Some(sha) = &submodule.checked_out_sha else {
+        return "not initialized".to_string();
+    };
+    let mut summary = sha.get(..SHORT_SHA_LENGTH).unwrap_or(sha).to_string();
+    let changes = [
+        submodule.is_pointer_changed().then_some("new commits"),
+        submodule.dirty.then_some("modified content"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if !changes.is_empty() {
+        summary.push_str(&format!(" ({})", changes.join(", ")));
+    }
+    summary
+}
+
 struct RenameBranchModal {
     current_branch: SharedString,
     editor: Entity<Editor>,
@@ -524,6 +524,8 @@ impl ProjectDiff {
             }
         };
 
+        // Buffers outside of the project, e.g. for submodules, have no uncommitted diff to pick up.
+        let is_project_buffer = project::File::from_dyn(buffer.read(cx).file()).is_some();
         let (was_empty, is_excerpt_newly_added) = self.multibuffer.update(cx, |multibuffer, cx| {
             let was_empty = multibuffer.is_empty();
             let (_, is_newly_added) = multibuffer.set_excerpts_for_path(
@@ -533,7 +535,7 @@ impl ProjectDiff {
                 multibuffer_context_lines(cx),
                 cx,
             );
-            if self.branch_diff.read(cx).diff_base().is_merge_base() {
+            if self.branch_diff.read(cx).diff_base().is_merge_base() || !is_project_buffer {
                 multibuffer.add_diff(diff.clone(), cx);
             }
             (was_empty, is_newly_added)
@@ -0,0 +1,579 @@
+//! An editor for the todo list of an interactive rebase: the commits since a chosen base
+//! can be reordered, reworded, squashed, fixed up or dropped before the rebase is started.
+//!
+//! When the rebase stops on conflicts, those are shown in the project diff,
+//! and the rebase is resumed with the continue, skip and abort actions.
+
+use editor::Editor;
+use git::{
+    RebaseAbort, RebaseContinue, RebaseSkip, SHORT_SHA_LENGTH,
+    repository::{
+        CommitDetails, RebaseAction, RebaseProgress, RebaseTodoEntry, SequencerOperation,
+    },
+};
+use gpui::{
+    Action, AnyElement, App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
+    Render, Subscription, Task, WeakEntity, Window,
+};
+use menu::Confirm;
+use project::git_store::{Repository, RepositoryEvent};
+use ui::{Divider, Tooltip, prelude::*};
+use workspace::{Item, Workspace, item::ItemEvent, notifications::DetachAndPromptErr};
+
+use crate::{git_panel::show_error_toast, project_diff::ProjectDiff};
+
+const REBASE_ACTIONS: [RebaseAction; 5] = [
+    RebaseAction::Pick,
+    RebaseAction::Reword,
+    RebaseAction::Squash,
+    RebaseAction::Fixup,
+    RebaseAction::Drop,
+];
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::InteractiveRebase, window, cx| {
+        RebaseView::open(workspace, window, cx);
+    });
+    workspace.register_action(|workspace, _: &RebaseContinue, window, cx| {
+        rebase_operation(workspace, SequencerOperation::Continue, window, cx);
+    });
+    workspace.register_action(|workspace, _: &RebaseSkip, window, cx| {
+        rebase_operation(workspace, SequencerOperation::Skip, window, cx);
+    });
+    workspace.register_action(|workspace, _: &RebaseAbort, window, cx| {
+        rebase_operation(workspace, SequencerOperation::Abort, window, cx);
+    });
+}
+
+fn rebase_operation(
+    workspace: &mut Workspace,
+    operation: SequencerOperation,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+        return;
+    };
+    if repository.read(cx).rebase.is_none() {
+        return;
+    }
+
+    let operation_task = repository.update(cx, |repository, cx| {
+        repository.rebase_operation(operation, cx)
+    });
+    cx.spawn(async move |_, _| operation_task.await?)
+        .detach_and_prompt_err("Failed to rebase", window, cx, |_, _, _| None);
+}
+
+struct RebaseEntry {
+    commit: CommitDetails,
+    action: RebaseAction,
+    message_editor: Option<Entity<Editor>>,
+}
+
+impl RebaseEntry {
+    fn subject(&self) -> &str {
+        self.commit.message.lines().next().unwrap_or_default()
+    }
+}
+
+pub struct RebaseView {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    base_editor: Entity<Editor>,
+    /// The base the entries were listed for.
+    base: Option<String>,
+    entries: Vec<RebaseEntry>,
+    /// Whether a rebase was seen in progress, to list the rebased commits once it is done.
+    rebase_in_progress: bool,
+    /// The stop of the rebase the conflicts were last shown for.
+    shown_conflicts: Option<RebaseProgress>,
+    pending_task: Option<Task<()>>,
+    focus_handle: FocusHandle,
+    _subscription: Subscription,
+}
+
+impl RebaseView {
+    fn open(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .find_map(|item| item.downcast::<RebaseView>())
+            .filter(|view| view.read(cx).repository == repository);
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let workspace_handle = workspace.weak_handle();
+        let view = cx.new(|cx| Self::new(repository, workspace_handle, window, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let upstream = repository
+            .read(cx)
+            .branch
+            .as_ref()
+            .and_then(|branch| branch.upstream.as_ref())
+            .and_then(|upstream| upstream.stripped_ref_name())
+            .map(|upstream| upstream.to_string());
+        let base_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Base branch or commit", window, cx);
+            if let Some(upstream) = &upstream {
+                editor.set_text(upstream.as_str(), window, cx);
+            }
+            editor
+        });
+
+        let subscription = cx.subscribe_in(
+            &repository,
+            window,
+            |this, _, event: &RepositoryEvent, window, cx| match event {
+                RepositoryEvent::RebaseProgressChanged | RepositoryEvent::MergeHeadsChanged => {
+                    this.rebase_progress_changed(window, cx)
+                }
+                _ => {}
+            },
+        );
+
+        let mut this = Self {
+            repository,
+            workspace,
+            base_editor,
+            base: None,
+            entries: Vec::new(),
+            rebase_in_progress: false,
+            shown_conflicts: None,
+            pending_task: None,
+            focus_handle: cx.focus_handle(),
+            _subscription: subscription,
+        };
+        if upstream.is_some() {
+            this.load_commits(window, cx);
+        }
+        this
+    }
+
+    fn load_commits(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let base = self.base_editor.read(cx).text(cx).trim().to_string();
+        if base.is_empty() {
+            return;
+        }
+
+        let commits = self
+            .repository
+            .update(cx, |repository, _| repository.commits_since(base.clone()));
+        self.pending_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let commits = commits.await.map_err(anyhow::Error::from).and_then(|c| c);
+            this.update(cx, |this, cx| {
+                this.pending_task = None;
+                match commits {
+                    Ok(commits) => {
+                        this.base = Some(base);
+                        this.entries = commits
+                            .into_iter()
+                            .map(|commit| RebaseEntry {
+                                commit,
+                                action: RebaseAction::Pick,
+                                message_editor: None,
+                            })
+                            .collect();
+                    }
+                    Err(error) => {
+                        this.base = None;
+                        this.entries.clear();
+                        this.show_error("log", error, cx);
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn show_error(&self, action: &'static str, error: anyhow::Error, cx: &mut App) {
+        if let Some(workspace) = self.workspace.upgrade() {
+            show_error_toast(workspace, action, error, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self
+            .base_editor
+            .focus_handle(cx)
+            .contains_focused(window, cx)
+        {
+            self.load_commits(window, cx);
+        }
+    }
+
+    fn set_action(
+        &mut self,
+        ix: usize,
+        action: RebaseAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.entries.get_mut(ix) else {
+            return;
+        };
+        entry.action = action;
+        if action == RebaseAction::Reword && entry.message_editor.is_none() {
+            let message = entry.commit.message.clone();
+            entry.message_editor = Some(cx.new(|cx| {
+                let mut editor = Editor::auto_height(1, 10, window, cx);
+                editor.set_text(message.as_ref(), window, cx);
+                editor
+            }));
+        }
+        cx.notify();
+    }
+
+    fn move_entry(&mut self, ix: usize, up: bool, cx: &mut Context<Self>) {
+        let target = if up { ix.checked_sub(1) } else { Some(ix + 1) };
+        if let Some(target) = target.filter(|target| *target < self.entries.len()) {
+            self.entries.swap(ix, target);
+            cx.notify();
+        }
+    }
+
+    fn start_rebase(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(base) = self.base.clone() else {
+            return;
+        };
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| RebaseTodoEntry {
+                sha: entry.commit.sha.clone(),
+                action: entry.action,
+                message: entry
+                    .message_editor
+                    .as_ref()
+                    .filter(|_| entry.action == RebaseAction::Reword)
+                    .map(|editor| editor.read(cx).text(cx).into()),
+            })
+            .collect();
+
+        self.shown_conflicts = None;
+        let rebase = self.repository.update(cx, |repository, cx| {
+            repository.rebase_interactive(base, entries, cx)
+        });
+        self.pending_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let result = rebase.await.map_err(anyhow::Error::from).and_then(|r| r);
+            this.update_in(cx, |this, window, cx| {
+                this.pending_task = None;
+                match result {
+                    Ok(()) => this.load_commits(window, cx),
+                    Err(error) => this.show_error("rebase", error, cx),
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    /// Shows the conflicts the rebase stopped on, or the rebased commits once it is done.
+    fn rebase_progress_changed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let repository = self.repository.read(cx);
+        match repository.rebase.clone() {
+            Some(progress) => {
+                self.rebase_in_progress = true;
+                let has_conflicts = !repository.merge.conflicted_paths.is_empty();
+                if has_conflicts && self.shown_conflicts.as_ref() != Some(&progress) {
+                    self.shown_conflicts = Some(progress);
+                    self.workspace
+                        .update(cx, |workspace, cx| {
+                            ProjectDiff::deploy_at(workspace, None, window, cx)
+                        })
+                        .ok();
+                }
+            }
+            None => {
+                self.shown_conflicts = None;
+                if std::mem::take(&mut self.rebase_in_progress) {
+                    self.load_commits(window, cx);
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    fn render_rebase_progress(
+        &self,
+        progress: &RebaseProgress,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let conflict_count = self.repository.read(cx).merge.conflicted_paths.len();
+        let mut status = format!(
+            "Rebase stopped at step {} of {}",
+            progress.step, progress.total
+        );
+        if let Some(sha) = &progress.stopped_sha {
+            status.push_str(&format!(" ({})", &sha[..sha.len().min(SHORT_SHA_LENGTH)]));
+        }
+        if conflict_count > 0 {
+            status.push_str(&format!(
+                ", {conflict_count} conflicted {}",
+                if conflict_count == 1 { "file" } else { "files" }
+            ));
+        }
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new(status).color(if conflict_count > 0 {
+                Color::Warning
+            } else {
+                Color::Muted
+            }))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new("rebase-continue", "Continue")
+                            .label_size(LabelSize::Small)
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(RebaseContinue.boxed_clone(), cx)
+                            }),
+                    )
+                    .child(
+                        Button::new("rebase-skip", "Skip")
+                            .label_size(LabelSize::Small)
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(RebaseSkip.boxed_clone(), cx)
+                            }),
+                    )
+                    .child(
+                        Button::new("rebase-abort", "Abort")
+                            .label_size(LabelSize::Small)
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(RebaseAbort.boxed_clone(), cx)
+                            }),
+                    ),
+            )
+    }
+
+    fn render_entry(&self, ix: usize, entry: &RebaseEntry, cx: &Context<Self>) -> AnyElement {
+        let sha = &entry.commit.sha;
+        let is_dropped = entry.action == RebaseAction::Drop;
+        let last_ix = self.entries.len().saturating_sub(1);
+
+        v_flex()
+            .id(("rebase-entry", ix))
+            .w_full()
+            .px_3()
+            .py_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(
+                        h_flex()
+                            .flex_none()
+                            .gap_0p5()
+                            .children(REBASE_ACTIONS.into_iter().map(|action| {
+                                Button::new(
+                                    SharedString::from(format!("{}-{ix}", action.as_str())),
+                                    action.as_str(),
+                                )
+                                .label_size(LabelSize::Small)
+                                .toggle_state(entry.action == action)
+                                .on_click(cx.listener(
+                                    move |this, _, window, cx| {
+                                        this.set_action(ix, action, window, cx)
+                                    },
+                                ))
+                            })),
+                    )
+                    .child(
+                        Label::new(sha[..sha.len().min(SHORT_SHA_LENGTH)].to_string())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        div().min_w_0().flex_1().child(
+                            Label::new(entry.subject().to_string())
+                                .size(LabelSize::Small)
+                                .when(is_dropped, |label| label.strikethrough())
+                                .color(if is_dropped {
+                                    Color::Disabled
+                                } else {
+                                    Color::Default
+                                })
+                                .truncate(),
+                        ),
+                    )
+                    .child(
+                        h_flex()
+                            .flex_none()
+                            .child(
+                                IconButton::new(("move-up", ix), IconName::ArrowUp)
+                                    .icon_size(IconSize::Small)
+                                    .disabled(ix == 0)
+                                    .tooltip(Tooltip::text("Move Up"))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.move_entry(ix, true, cx)
+                                    })),
+                            )
+                            .child(
+                                IconButton::new(("move-down", ix), IconName::ArrowDown)
+                                    .icon_size(IconSize::Small)
+                                    .disabled(ix == last_ix)
+                                    .tooltip(Tooltip::text("Move Down"))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.move_entry(ix, false, cx)
+                                    })),
+                            ),
+                    ),
+            )
+            .when_some(
+                entry
+                    .message_editor
+                    .clone()
+                    .filter(|_| entry.action == RebaseAction::Reword),
+                |this, editor| {
+                    this.child(
+                        div()
+                            .w_full()
+                            .p_1()
+                            .border_1()
+                            .rounded_sm()
+                            .border_color(cx.theme().colors().border)
+                            .child(editor),
+                    )
+                },
+            )
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<ItemEvent> for RebaseView {}
+
+impl Focusable for RebaseView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RebaseView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let progress = self.repository.read(cx).rebase.clone();
+        let is_pending = self.pending_task.is_some();
+        let can_start = progress.is_none() && !is_pending && !self.entries.is_empty();
+
+        v_flex()
+            .key_context("RebaseView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .h(rems_from_px(41.))
+                    .pl_3()
+                    .pr_2()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new("Rebase onto").color(Color::Muted))
+                    .child(div().w(rems(16.)).child(self.base_editor.clone()))
+                    .child(
+                        Button::new("load-commits", "Load Commits")
+                            .disabled(is_pending)
+                            .label_size(LabelSize::Small)
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.load_commits(window, cx)),
+                            ),
+                    )
+                    .child(div().flex_1())
+                    .child(
+                        Label::new(format!("{} commits", self.entries.len()))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(Divider::vertical())
+                    .child(
+                        Button::new("start-rebase", "Start Rebase")
+                            .disabled(!can_start)
+                            .label_size(LabelSize::Small)
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.start_rebase(window, cx)),
+                            ),
+                    ),
+            )
+            .when_some(progress.as_ref(), |this, progress| {
+                this.child(self.render_rebase_progress(progress, cx))
+            })
+            .child(
+                v_flex()
+                    .id("rebase-entries")
+                    .flex_1()
+                    .w_full()
+                    .overflow_y_scroll()
+                    .when(progress.is_none(), |this| {
+                        this.children(
+                            self.entries
+                                .iter()
+                                .enumerate()
+                                .map(|(ix, entry)| self.render_entry(ix, entry, cx)),
+                        )
+                    }),
+            )
+    }
+}
+
+impl Item for RebaseView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        match &self.base {
+            Some(base) => format!("Rebase onto {base}").into(),
+            None => "Interactive Rebase".into(),
+        }
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("interactive rebase")
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.base_editor.focus_handle(cx));
+    }
+}
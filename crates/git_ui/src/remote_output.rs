@@ -10,6 +10,7 @@ pub enum RemoteAction {
     Fetch(Option<Remote>),
     Pull(Remote),
     Push(SharedString, Remote),
+    PushTag(SharedString, Remote),
 }
 
 impl RemoteAction {
@@ -17,7 +18,7 @@ impl RemoteAction {
         match self {
             RemoteAction::Fetch(_) => "fetch",
             RemoteAction::Pull(_) => "pull",
-            RemoteAction::Push(_, _) => "push",
+            RemoteAction::Push(_, _) | RemoteAction::PushTag(_, _) => "push",
         }
     }
 }
@@ -157,6 +158,19 @@ pub fn format_output(action: &RemoteAction, output: RemoteCommandOutput) -> Succ
                 style: style.unwrap_or(SuccessStyle::ToastWithLog { output }),
             }
         }
+        RemoteAction::PushTag(tag_name, remote_ref) => {
+            if output.stderr.ends_with("Everything up-to-date\n") {
+                SuccessMessage {
+                    message: format!("Tag {} is already on {}", tag_name, remote_ref.name),
+                    style: SuccessStyle::Toast,
+                }
+            } else {
+                SuccessMessage {
+                    message: format!("Pushed tag {} to {}", tag_name, remote_ref.name),
+                    style: SuccessStyle::ToastWithLog { output },
+                }
+            }
+        }
     }
 }
 
@@ -0,0 +1,207 @@
+use editor::Editor;
+use git::{CreateTag, SHORT_SHA_LENGTH};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString,
+    WeakEntity, Window,
+};
+use menu::{Cancel, Confirm, SecondaryConfirm};
+use project::git_store::Repository;
+use ui::{KeyBinding, prelude::*};
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
+
+use crate::git_panel::GitPanel;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &CreateTag, window, cx| {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        TagModal::toggle(workspace, repository, None, window, cx);
+    });
+}
+
+/// Creates a tag at HEAD, or at the given commit.
+///
+/// The tag is annotated when a message is given, and lightweight otherwise.
+pub struct TagModal {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    target: Option<SharedString>,
+    name_editor: Entity<Editor>,
+    message_editor: Entity<Editor>,
+    can_push: bool,
+}
+
+impl TagModal {
+    pub fn toggle(
+        workspace: &mut Workspace,
+        repository: Entity<Repository>,
+        target: Option<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let workspace_handle = workspace.weak_handle();
+        let can_push = !workspace.project().read(cx).is_via_collab();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(repository, workspace_handle, target, can_push, window, cx)
+        });
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        target: Option<SharedString>,
+        can_push: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Tag name, e.g. v1.0.0", window, cx);
+            editor
+        });
+        let message_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Message (optional, creates an annotated tag)", window, cx);
+            editor
+        });
+        Self {
+            repository,
+            workspace,
+            target,
+            name_editor,
+            message_editor,
+            can_push,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.create_tag(false, window, cx);
+    }
+
+    fn secondary_confirm(
+        &mut self,
+        _: &SecondaryConfirm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.can_push {
+            self.create_tag(true, window, cx);
+        }
+    }
+
+    fn create_tag(&mut self, push: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_editor.read(cx).text(cx).trim().replace(' ', "-");
+        if name.is_empty() {
+            return;
+        }
+        let message = self.message_editor.read(cx).text(cx).trim().to_string();
+        let message = (!message.is_empty()).then_some(message);
+        let target = self.target.as_ref().map(|target| target.to_string());
+
+        let create_tag = self.repository.update(cx, |repository, _| {
+            repository.create_tag(name.clone(), target, message)
+        });
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            create_tag.await??;
+            if push {
+                workspace.update_in(cx, |workspace, window, cx| {
+                    if let Some(git_panel) = workspace.panel::<GitPanel>(cx) {
+                        git_panel.update(cx, |git_panel, cx| {
+                            git_panel.push_tag(name.into(), window, cx)
+                        });
+                    }
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to create tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for TagModal {}
+impl ModalView for TagModal {}
+impl Focusable for TagModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.name_editor.focus_handle(cx)
+    }
+}
+
+impl Render for TagModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let title = match &self.target {
+            Some(target) => format!(
+                "Create Tag at {}",
+                &target[..SHORT_SHA_LENGTH.min(target.len())]
+            ),
+            None => "Create Tag".to_string(),
+        };
+        let focus_handle = self.focus_handle(cx);
+
+        v_flex()
+            .key_context("GitTagModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::secondary_confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::Hash).size(IconSize::XSmall))
+                    .child(Headline::new(title).size(HeadlineSize::XSmall)),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_2()
+                    .w_full()
+                    .gap_2()
+                    .child(self.name_editor.clone())
+                    .child(self.message_editor.clone()),
+            )
+            .child(
+                h_flex()
+                    .w_full()
+                    .p_1p5()
+                    .gap_1()
+                    .justify_end()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .when(self.can_push, |this| {
+                        this.child(
+                            Button::new("create-and-push-tag", "Create & Push")
+                                .key_binding(
+                                    KeyBinding::for_action_in(&SecondaryConfirm, &focus_handle, cx)
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.create_tag(true, window, cx)
+                                })),
+                        )
+                    })
+                    .child(
+                        Button::new("create-tag", "Create")
+                            .key_binding(
+                                KeyBinding::for_action_in(&Confirm, &focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.create_tag(false, window, cx)
+                            })),
+                    ),
+            )
+    }
+}
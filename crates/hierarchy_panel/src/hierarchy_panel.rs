@@ -0,0 +1,870 @@
+mod hierarchy_panel_settings;
+
+use std::{ops::Range, sync::Arc};
+
+use anyhow::Context as _;
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, SelectionEffects, scroll::Autoscroll};
+use gpui::{
+    Action, App, AppContext as _, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, KeyContext, Pixels, Render, ScrollStrategy, SharedString, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, px, uniform_list,
+};
+use hierarchy_panel_settings::{DockSide, HierarchyPanelSettings};
+use language::{Anchor, Buffer, Point, ToPoint as _};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{CallHierarchyCall, CallHierarchyItem, Fs, Location, Project, TypeHierarchyItem};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::{ListItem, Tab, Tooltip, prelude::*};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+actions!(
+    hierarchy_panel,
+    [
+        /// Collapses the currently selected entry.
+        CollapseSelectedEntry,
+        /// Expands the currently selected entry, fetching its children if needed.
+        ExpandSelectedEntry,
+        /// Shows the callers of the symbol under the cursor.
+        ShowIncomingCalls,
+        /// Shows the callees of the symbol under the cursor.
+        ShowOutgoingCalls,
+        /// Shows the types the type under the cursor derives from.
+        ShowSupertypes,
+        /// Shows the types deriving from the type under the cursor.
+        ShowSubtypes,
+        /// Toggles focus on the hierarchy panel.
+        ToggleFocus,
+    ]
+);
+
+const HIERARCHY_PANEL_KEY: &str = "HierarchyPanel";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyKind {
+    IncomingCalls,
+    OutgoingCalls,
+    Supertypes,
+    Subtypes,
+}
+
+impl HierarchyKind {
+    fn label(&self) -> &'static str {
+        match self {
+            HierarchyKind::IncomingCalls => "Incoming Calls",
+            HierarchyKind::OutgoingCalls => "Outgoing Calls",
+            HierarchyKind::Supertypes => "Supertypes",
+            HierarchyKind::Subtypes => "Subtypes",
+        }
+    }
+
+    fn hierarchy_name(&self) -> &'static str {
+        match self {
+            HierarchyKind::IncomingCalls | HierarchyKind::OutgoingCalls => "call hierarchy",
+            HierarchyKind::Supertypes | HierarchyKind::Subtypes => "type hierarchy",
+        }
+    }
+
+    /// The kind showing the same hierarchy in the opposite direction.
+    fn toggled(&self) -> Self {
+        match self {
+            HierarchyKind::IncomingCalls => HierarchyKind::OutgoingCalls,
+            HierarchyKind::OutgoingCalls => HierarchyKind::IncomingCalls,
+            HierarchyKind::Supertypes => HierarchyKind::Subtypes,
+            HierarchyKind::Subtypes => HierarchyKind::Supertypes,
+        }
+    }
+}
+
+#[derive(Clone)]
+enum HierarchyItem {
+    Call(CallHierarchyItem),
+    Type(TypeHierarchyItem),
+}
+
+impl HierarchyItem {
+    fn name(&self) -> &str {
+        match self {
+            HierarchyItem::Call(item) => &item.name,
+            HierarchyItem::Type(item) => &item.name,
+        }
+    }
+
+    fn detail(&self) -> Option<&str> {
+        match self {
+            HierarchyItem::Call(item) => item.detail.as_deref(),
+            HierarchyItem::Type(item) => item.detail.as_deref(),
+        }
+    }
+
+    fn location(&self) -> &Location {
+        match self {
+            HierarchyItem::Call(item) => &item.location,
+            HierarchyItem::Type(item) => &item.location,
+        }
+    }
+
+    fn selection_range(&self) -> &Range<Anchor> {
+        match self {
+            HierarchyItem::Call(item) => &item.selection_range,
+            HierarchyItem::Type(item) => &item.selection_range,
+        }
+    }
+}
+
+type NodeId = usize;
+
+struct HierarchyNode {
+    item: HierarchyItem,
+    /// Locations of the calls that connect this node to its parent, empty for type hierarchies.
+    call_sites: Vec<Location>,
+    depth: usize,
+    expanded: bool,
+    /// `None` until the children are fetched from the language server.
+    children: Option<Vec<NodeId>>,
+}
+
+pub struct HierarchyPanel {
+    fs: Arc<dyn Fs>,
+    width: Option<Pixels>,
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    active: bool,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    pending_serialization: Task<Option<()>>,
+    kind: HierarchyKind,
+    /// The buffer position the hierarchy was requested for, used to re-query when switching the direction.
+    origin: Option<(Entity<Buffer>, Anchor)>,
+    nodes: Vec<HierarchyNode>,
+    roots: Vec<NodeId>,
+    visible_entries: Vec<NodeId>,
+    selected_entry: Option<usize>,
+    prepare_task: Option<Task<()>>,
+    fetch_tasks: HashMap<NodeId, Task<()>>,
+    status: Option<SharedString>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedHierarchyPanel {
+    width: Option<Pixels>,
+    active: Option<bool>,
+}
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<HierarchyPanel>(window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowIncomingCalls, window, cx| {
+            show_hierarchy(workspace, HierarchyKind::IncomingCalls, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowOutgoingCalls, window, cx| {
+            show_hierarchy(workspace, HierarchyKind::OutgoingCalls, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowSupertypes, window, cx| {
+            show_hierarchy(workspace, HierarchyKind::Supertypes, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowSubtypes, window, cx| {
+            show_hierarchy(workspace, HierarchyKind::Subtypes, window, cx);
+        });
+    })
+    .detach();
+}
+
+fn show_hierarchy(
+    workspace: &mut Workspace,
+    kind: HierarchyKind,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace
+        .active_item(cx)
+        .and_then(|item| item.act_as::<Editor>(cx))
+    else {
+        return;
+    };
+    let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+    let Some(panel) = workspace.focus_panel::<HierarchyPanel>(window, cx) else {
+        return;
+    };
+    panel.update(cx, |panel, cx| {
+        panel.show_hierarchy(kind, buffer, position, window, cx);
+    });
+}
+
+impl HierarchyPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = match workspace
+            .read_with(&cx, |workspace, _| {
+                HierarchyPanel::serialization_key(workspace)
+            })
+            .ok()
+            .flatten()
+        {
+            Some(serialization_key) => cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&serialization_key) })
+                .await
+                .context("loading hierarchy panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedHierarchyPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten(),
+            None => None,
+        };
+
+        workspace.update_in(&mut cx, |workspace, _, cx| {
+            let panel = Self::new(workspace, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    panel.active = serialized_panel.active.unwrap_or(false);
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let project = workspace.project().clone();
+        let fs = workspace.app_state().fs.clone();
+        let workspace_handle = cx.entity().downgrade();
+        cx.new(|cx| Self {
+            fs,
+            width: None,
+            project,
+            workspace: workspace_handle,
+            active: false,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            pending_serialization: Task::ready(None),
+            kind: HierarchyKind::IncomingCalls,
+            origin: None,
+            nodes: Vec::new(),
+            roots: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_entry: None,
+            prepare_task: None,
+            fetch_tasks: HashMap::default(),
+            status: None,
+        })
+    }
+
+    fn serialization_key(workspace: &Workspace) -> Option<String> {
+        workspace
+            .database_id()
+            .map(|id| i64::from(id).to_string())
+            .or(workspace.session_id())
+            .map(|id| format!("{}-{:?}", HIERARCHY_PANEL_KEY, id))
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let Some(serialization_key) = self
+            .workspace
+            .read_with(cx, |workspace, _| {
+                HierarchyPanel::serialization_key(workspace)
+            })
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        let width = self.width;
+        let active = Some(self.active);
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        serialization_key,
+                        serde_json::to_string(&SerializedHierarchyPanel { width, active })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    /// Replaces the panel contents with the hierarchy of the symbol at the given position.
+    pub fn show_hierarchy(
+        &mut self,
+        kind: HierarchyKind,
+        buffer: Entity<Buffer>,
+        position: Anchor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.kind = kind;
+        self.origin = Some((buffer.clone(), position));
+        self.nodes.clear();
+        self.roots.clear();
+        self.fetch_tasks.clear();
+        self.selected_entry = None;
+        self.status = Some("Loading…".into());
+        self.update_visible_entries(cx);
+
+        let prepare = self.project.update(cx, |project, cx| match kind {
+            HierarchyKind::IncomingCalls | HierarchyKind::OutgoingCalls => {
+                let task = project.prepare_call_hierarchy(&buffer, position, cx);
+                cx.spawn(async move |_, _| {
+                    anyhow::Ok(
+                        task.await?
+                            .into_iter()
+                            .map(HierarchyItem::Call)
+                            .collect::<Vec<_>>(),
+                    )
+                })
+            }
+            HierarchyKind::Supertypes | HierarchyKind::Subtypes => {
+                let task = project.prepare_type_hierarchy(&buffer, position, cx);
+                cx.spawn(async move |_, _| {
+                    anyhow::Ok(
+                        task.await?
+                            .into_iter()
+                            .map(HierarchyItem::Type)
+                            .collect::<Vec<_>>(),
+                    )
+                })
+            }
+        });
+        self.prepare_task = Some(cx.spawn_in(window, async move |panel, cx| {
+            let items = prepare.await;
+            panel
+                .update(cx, |panel, cx| {
+                    panel.prepare_task = None;
+                    let hierarchy_name = kind.hierarchy_name();
+                    match items {
+                        Ok(items) if items.is_empty() => {
+                            panel.status =
+                                Some(format!("No {hierarchy_name} at the cursor").into());
+                        }
+                        Ok(items) => {
+                            panel.status = None;
+                            panel.roots = items
+                                .into_iter()
+                                .map(|item| panel.push_node(item, Vec::new(), 0))
+                                .collect();
+                            panel.selected_entry = Some(0);
+                            if let [root] = panel.roots.as_slice() {
+                                panel.expand_node(*root, cx);
+                            }
+                        }
+                        Err(error) => {
+                            panel.status =
+                                Some(format!("Failed to fetch {hierarchy_name}: {error:#}").into());
+                        }
+                    }
+                    panel.update_visible_entries(cx);
+                })
+                .ok();
+        }));
+    }
+
+    fn toggle_kind(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let kind = self.kind.toggled();
+        if let Some((buffer, position)) = self.origin.clone() {
+            self.show_hierarchy(kind, buffer, position, window, cx);
+        } else {
+            self.kind = kind;
+            cx.notify();
+        }
+    }
+
+    fn push_node(
+        &mut self,
+        item: HierarchyItem,
+        call_sites: Vec<Location>,
+        depth: usize,
+    ) -> NodeId {
+        self.nodes.push(HierarchyNode {
+            item,
+            call_sites,
+            depth,
+            expanded: false,
+            children: None,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn expand_node(&mut self, node_id: NodeId, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get_mut(node_id) else {
+            return;
+        };
+        node.expanded = true;
+        if node.children.is_none() && !self.fetch_tasks.contains_key(&node_id) {
+            let item = node.item.clone();
+            let children = self.fetch_children(&item, cx);
+            let task = cx.spawn(async move |panel, cx| {
+                let children = children.await.log_err().unwrap_or_default();
+                panel
+                    .update(cx, |panel, cx| {
+                        panel.fetch_tasks.remove(&node_id);
+                        panel.set_children(node_id, children);
+                        panel.update_visible_entries(cx);
+                    })
+                    .ok();
+            });
+            self.fetch_tasks.insert(node_id, task);
+        }
+        self.update_visible_entries(cx);
+    }
+
+    fn fetch_children(
+        &self,
+        item: &HierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<Vec<(HierarchyItem, Vec<Location>)>>> {
+        let kind = self.kind;
+        self.project.update(cx, |project, cx| match (kind, item) {
+            (HierarchyKind::IncomingCalls, HierarchyItem::Call(item)) => {
+                let task = project.incoming_calls(item, cx);
+                cx.spawn(async move |_, _| Ok(calls_to_children(task.await?)))
+            }
+            (HierarchyKind::OutgoingCalls, HierarchyItem::Call(item)) => {
+                let task = project.outgoing_calls(item, cx);
+                cx.spawn(async move |_, _| Ok(calls_to_children(task.await?)))
+            }
+            (HierarchyKind::Supertypes, HierarchyItem::Type(item)) => {
+                let task = project.supertypes(item, cx);
+                cx.spawn(async move |_, _| Ok(types_to_children(task.await?)))
+            }
+            (HierarchyKind::Subtypes, HierarchyItem::Type(item)) => {
+                let task = project.subtypes(item, cx);
+                cx.spawn(async move |_, _| Ok(types_to_children(task.await?)))
+            }
+            _ => Task::ready(Ok(Vec::new())),
+        })
+    }
+
+    fn set_children(&mut self, node_id: NodeId, children: Vec<(HierarchyItem, Vec<Location>)>) {
+        let depth = self.nodes[node_id].depth + 1;
+        let children = children
+            .into_iter()
+            .map(|(item, call_sites)| self.push_node(item, call_sites, depth))
+            .collect();
+        self.nodes[node_id].children = Some(children);
+    }
+
+    fn collapse_node(&mut self, node_id: NodeId, cx: &mut Context<Self>) {
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            node.expanded = false;
+            self.update_visible_entries(cx);
+        }
+    }
+
+    fn toggle_node(&mut self, node_id: NodeId, cx: &mut Context<Self>) {
+        if self.nodes[node_id].expanded {
+            self.collapse_node(node_id, cx);
+        } else {
+            self.expand_node(node_id, cx);
+        }
+    }
+
+    fn update_visible_entries(&mut self, cx: &mut Context<Self>) {
+        let selected_node = self.selected_node();
+        self.visible_entries.clear();
+        let mut stack = self.roots.iter().rev().copied().collect::<Vec<_>>();
+        while let Some(node_id) = stack.pop() {
+            self.visible_entries.push(node_id);
+            let node = &self.nodes[node_id];
+            if node.expanded
+                && let Some(children) = &node.children
+            {
+                stack.extend(children.iter().rev().copied());
+            }
+        }
+        self.selected_entry = selected_node
+            .and_then(|node_id| self.visible_entries.iter().position(|id| *id == node_id))
+            .or_else(|| (!self.visible_entries.is_empty()).then_some(0));
+        cx.notify();
+    }
+
+    fn selected_node(&self) -> Option<NodeId> {
+        self.selected_entry
+            .and_then(|ix| self.visible_entries.get(ix))
+            .copied()
+    }
+
+    fn select_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix < self.visible_entries.len() {
+            self.selected_entry = Some(ix);
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_entry.map_or(0, |ix| ix + 1);
+        self.select_entry(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_entry.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_entry(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_entry(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_entry(self.visible_entries.len().saturating_sub(1), cx);
+    }
+
+    fn expand_selected_entry(
+        &mut self,
+        _: &ExpandSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(node_id) = self.selected_node() {
+            self.expand_node(node_id, cx);
+        }
+    }
+
+    fn collapse_selected_entry(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(node_id) = self.selected_node() else {
+            return;
+        };
+        if self.nodes[node_id].expanded {
+            self.collapse_node(node_id, cx);
+        } else if let Some(parent_ix) = self.selected_entry.and_then(|ix| {
+            let depth = self.nodes[node_id].depth;
+            self.visible_entries[..ix]
+                .iter()
+                .rposition(|id| self.nodes[*id].depth < depth)
+        }) {
+            self.select_entry(parent_ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(node_id) = self.selected_node() {
+            self.open_node(node_id, true, window, cx);
+        }
+    }
+
+    fn open_node(
+        &mut self,
+        node_id: NodeId,
+        focus: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let item = &self.nodes[node_id].item;
+        let buffer = item.location().buffer.clone();
+        let range = item.selection_range().clone();
+        let range = {
+            let snapshot = buffer.read(cx).snapshot();
+            range.start.to_point(&snapshot)..range.end.to_point(&snapshot)
+        };
+        let editor = workspace.update(cx, |workspace, cx| {
+            let pane = workspace.active_pane().clone();
+            workspace.open_project_item::<Editor>(pane, buffer, true, focus, true, true, window, cx)
+        });
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(
+                SelectionEffects::scroll(Autoscroll::center()),
+                window,
+                cx,
+                |selections| selections.select_ranges([range]),
+            );
+        });
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("HierarchyPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let title = match self.roots.as_slice() {
+            [root] => format!("{}: {}", self.kind.label(), self.nodes[*root].item.name()),
+            _ => self.kind.label().to_string(),
+        };
+        let (icon, tooltip) = match self.kind {
+            HierarchyKind::IncomingCalls => (IconName::ArrowUpRight, "Show Outgoing Calls"),
+            HierarchyKind::OutgoingCalls => (IconName::ArrowDownRight, "Show Incoming Calls"),
+            HierarchyKind::Supertypes => (IconName::ArrowDown, "Show Subtypes"),
+            HierarchyKind::Subtypes => (IconName::ArrowUp, "Show Supertypes"),
+        };
+
+        h_flex()
+            .p_2()
+            .h(Tab::container_height(cx))
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new(title).truncate())
+            .child(
+                IconButton::new("toggle-hierarchy-kind", icon)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text(tooltip))
+                    .on_click(cx.listener(|panel, _, window, cx| {
+                        panel.toggle_kind(window, cx);
+                    })),
+            )
+    }
+
+    fn render_entry(&self, ix: usize, node_id: NodeId, cx: &mut Context<Self>) -> ListItem {
+        let node = &self.nodes[node_id];
+        let indent_size = HierarchyPanelSettings::get_global(cx).indent_size;
+        let is_loading = self.fetch_tasks.contains_key(&node_id);
+        let has_children = node
+            .children
+            .as_ref()
+            .is_none_or(|children| !children.is_empty());
+        let location = location_label(
+            &node.item.location().buffer,
+            node.item.selection_range(),
+            cx,
+        );
+        let call_count = node.call_sites.len();
+
+        ListItem::new(ix)
+            .indent_level(node.depth)
+            .indent_step_size(px(indent_size))
+            .toggle_state(self.selected_entry == Some(ix))
+            .toggle(has_children.then_some(node.expanded))
+            .on_toggle(cx.listener(move |panel, _, _, cx| {
+                panel.toggle_node(node_id, cx);
+            }))
+            .child(
+                h_flex()
+                    .gap_1p5()
+                    .child(Label::new(node.item.name().to_string()).single_line())
+                    .when_some(node.item.detail().map(str::to_string), |this, detail| {
+                        this.child(
+                            Label::new(detail)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .single_line(),
+                        )
+                    })
+                    .child(
+                        Label::new(location)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .single_line(),
+                    ),
+            )
+            .end_slot(if is_loading {
+                Some(
+                    Label::new("Loading…")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .into_any_element(),
+                )
+            } else if call_count > 1 {
+                Some(
+                    Label::new(format!("{call_count} calls"))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .into_any_element(),
+                )
+            } else {
+                None
+            })
+            .on_click(
+                cx.listener(move |panel, event: &gpui::ClickEvent, window, cx| {
+                    panel.selected_entry = Some(ix);
+                    let focus = event.click_count() > 1;
+                    panel.open_node(node_id, focus, window, cx);
+                    cx.notify();
+                }),
+            )
+    }
+
+    fn render_empty_state(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let header = self
+            .status
+            .clone()
+            .unwrap_or_else(|| "No hierarchy to show".into());
+        let keystroke = window.keystroke_text_for(&ShowIncomingCalls);
+
+        v_flex()
+            .id("empty-hierarchy-state")
+            .gap_0p5()
+            .flex_1()
+            .justify_center()
+            .size_full()
+            .child(h_flex().justify_center().child(Label::new(header)))
+            .when(self.status.is_none(), |this| {
+                this.child(h_flex().justify_center().child(
+                    Label::new(format!("Show Incoming Calls With {keystroke}")).color(Color::Muted),
+                ))
+            })
+    }
+}
+
+fn calls_to_children(calls: Vec<CallHierarchyCall>) -> Vec<(HierarchyItem, Vec<Location>)> {
+    calls
+        .into_iter()
+        .map(|call| (HierarchyItem::Call(call.item), call.call_sites))
+        .collect()
+}
+
+fn types_to_children(items: Vec<TypeHierarchyItem>) -> Vec<(HierarchyItem, Vec<Location>)> {
+    items
+        .into_iter()
+        .map(|item| (HierarchyItem::Type(item), Vec::new()))
+        .collect()
+}
+
+fn location_label(buffer: &Entity<Buffer>, range: &Range<Anchor>, cx: &App) -> String {
+    let buffer = buffer.read(cx);
+    let Point { row, .. } = range.start.to_point(&buffer.snapshot());
+    match buffer.file() {
+        Some(file) => format!("{}:{}", file.file_name(cx), row + 1),
+        None => format!("line {}", row + 1),
+    }
+}
+
+impl Render for HierarchyPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let contents = if self.visible_entries.is_empty() {
+            self.render_empty_state(window, cx).into_any_element()
+        } else {
+            uniform_list(
+                "hierarchy-entries",
+                self.visible_entries.len(),
+                cx.processor(|panel, range: Range<usize>, _, cx| {
+                    range
+                        .filter_map(|ix| {
+                            let node_id = *panel.visible_entries.get(ix)?;
+                            Some(panel.render_entry(ix, node_id, cx))
+                        })
+                        .collect()
+                }),
+            )
+            .size_full()
+            .track_scroll(&self.scroll_handle)
+            .into_any_element()
+        };
+
+        v_flex()
+            .id("hierarchy-panel")
+            .size_full()
+            .overflow_hidden()
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::expand_selected_entry))
+            .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::confirm))
+            .child(self.render_header(cx))
+            .child(contents)
+    }
+}
+
+impl Panel for HierarchyPanel {
+    fn persistent_name() -> &'static str {
+        "Hierarchy Panel"
+    }
+
+    fn panel_key() -> &'static str {
+        HIERARCHY_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match HierarchyPanelSettings::get_global(cx).dock {
+            DockSide::Left => DockPosition::Left,
+            DockSide::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            let dock = match position {
+                DockPosition::Left | DockPosition::Bottom => DockSide::Left,
+                DockPosition::Right => DockSide::Right,
+            };
+            settings.hierarchy_panel.get_or_insert_default().dock = Some(dock);
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| HierarchyPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, window: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        cx.notify();
+        cx.defer_in(window, |this, _, cx| {
+            this.serialize(cx);
+        });
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        HierarchyPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ListCollapse)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _: &App) -> Option<&'static str> {
+        Some("Hierarchy Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn starts_open(&self, _window: &Window, _: &App) -> bool {
+        self.active
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut Window, cx: &mut Context<Self>) {
+        if self.active != active {
+            self.active = active;
+            self.serialize(cx);
+        }
+    }
+
+    fn activation_priority(&self) -> u32 {
+        4
+    }
+}
+
+impl Focusable for HierarchyPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for HierarchyPanel {}
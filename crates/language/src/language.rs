@@ -1215,6 +1215,8 @@ pub struct Language {
     pub(crate) grammar: Option<Arc<Grammar>>,
     pub(crate) context_provider: Option<Arc<dyn ContextProvider>>,
     pub(crate) toolchain: Option<Arc<dyn ToolchainLister>>,
+    /// A toolchain lister registered after this language was loaded, e.g. by an extension.
+    pub(crate) registered_toolchain: Mutex<Option<Arc<dyn ToolchainLister>>>,
     pub(crate) manifest_name: Option<ManifestName>,
 }
 
@@ -1446,6 +1448,7 @@ impl Language {
             }),
             context_provider: None,
             toolchain: None,
+            registered_toolchain: Mutex::new(None),
             manifest_name: None,
         }
     }
@@ -2009,7 +2012,13 @@ impl Language {
     }
 
     pub fn toolchain_lister(&self) -> Option<Arc<dyn ToolchainLister>> {
-        self.toolchain.clone()
+        self.toolchain
+            .clone()
+            .or_else(|| self.registered_toolchain.lock().clone())
+    }
+
+    pub(crate) fn set_registered_toolchain_lister(&self, lister: Option<Arc<dyn ToolchainLister>>) {
+        *self.registered_toolchain.lock() = lister;
     }
 
     pub fn highlight_text<'a>(
@@ -2764,8 +2773,10 @@ pub fn markdown_lang() -> Arc<Language> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fs::Fs;
     use gpui::TestAppContext;
     use pretty_assertions::assert_matches;
+    use task::ShellKind;
 
     #[gpui::test(iterations = 10)]
     async fn test_language_loading(cx: &mut TestAppContext) {
@@ -2832,6 +2843,70 @@ mod tests {
         assert!(languages.language_for_name("Unknown").await.is_err());
     }
 
+    #[gpui::test]
+    async fn test_registered_toolchain_lister(cx: &mut TestAppContext) {
+        struct TestToolchainLister;
+
+        #[async_trait]
+        impl ToolchainLister for TestToolchainLister {
+            async fn list(
+                &self,
+                _: PathBuf,
+                _: Arc<RelPath>,
+                _: Option<HashMap<String, String>>,
+                _: &dyn Fs,
+            ) -> ToolchainList {
+                ToolchainList::default()
+            }
+
+            async fn resolve(
+                &self,
+                _: PathBuf,
+                _: Option<HashMap<String, String>>,
+                _: &dyn Fs,
+            ) -> Result<Toolchain> {
+                anyhow::bail!("not supported")
+            }
+
+            fn activation_script(&self, _: &Toolchain, _: ShellKind, _: &App) -> Vec<String> {
+                Vec::new()
+            }
+
+            fn meta(&self) -> ToolchainMetadata {
+                ToolchainMetadata {
+                    term: "Test Toolchain".into(),
+                    new_toolchain_placeholder: "".into(),
+                    manifest_name: SharedString::new_static("test.toml").into(),
+                }
+            }
+        }
+
+        let languages = Arc::new(LanguageRegistry::test(cx.executor()));
+        languages.register_test_language(LanguageConfig {
+            name: "Ruby".into(),
+            matcher: LanguageMatcher {
+                path_suffixes: vec!["rb".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let ruby = languages.language_for_name("Ruby").await.unwrap();
+        assert!(ruby.toolchain_lister().is_none());
+
+        // Listers apply to languages that are already loaded...
+        languages.register_toolchain_lister("Ruby".into(), Arc::new(TestToolchainLister));
+        assert!(ruby.toolchain_lister().is_some());
+
+        // ...and to languages that are loaded afterwards.
+        languages.reload();
+        let ruby = languages.language_for_name("Ruby").await.unwrap();
+        assert!(ruby.toolchain_lister().is_some());
+
+        languages.remove_toolchain_lister(&"Ruby".into());
+        assert!(ruby.toolchain_lister().is_none());
+    }
+
     #[gpui::test]
     async fn test_completion_label_omits_duplicate_data() {
         let regular_completion_item_1 = lsp::CompletionItem {
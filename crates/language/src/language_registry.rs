@@ -121,6 +121,7 @@ struct LanguageRegistryState {
     grammars: HashMap<Arc<str>, AvailableGrammar>,
     lsp_adapters: HashMap<LanguageName, Vec<Arc<CachedLspAdapter>>>,
     all_lsp_adapters: HashMap<LanguageServerName, Arc<CachedLspAdapter>>,
+    toolchain_listers: HashMap<LanguageName, Arc<dyn ToolchainLister>>,
     available_lsp_adapters:
         HashMap<LanguageServerName, Arc<dyn Fn() -> Arc<CachedLspAdapter> + 'static + Send + Sync>>,
     loading_languages: HashMap<LanguageId, Vec<oneshot::Sender<Result<Arc<Language>>>>>,
@@ -280,6 +281,7 @@ impl LanguageRegistry {
                 loading_languages: Default::default(),
                 lsp_adapters: Default::default(),
                 all_lsp_adapters: Default::default(),
+                toolchain_listers: Default::default(),
                 available_lsp_adapters: HashMap::default(),
                 subscription: watch::channel(),
                 theme: Default::default(),
@@ -435,6 +437,34 @@ impl LanguageRegistry {
             .insert(cached.name.clone(), cached.clone());
     }
 
+    /// Registers a toolchain lister for the given language.
+    ///
+    /// Languages that come with their own toolchain lister keep using it.
+    pub fn register_toolchain_lister(
+        &self,
+        language_name: LanguageName,
+        lister: Arc<dyn ToolchainLister>,
+    ) {
+        let mut state = self.state.write();
+        for language in &state.languages {
+            if language.name() == language_name {
+                language.set_registered_toolchain_lister(Some(lister.clone()));
+            }
+        }
+        state.toolchain_listers.insert(language_name, lister);
+    }
+
+    /// Removes the toolchain lister registered for the given language.
+    pub fn remove_toolchain_lister(&self, language_name: &LanguageName) {
+        let mut state = self.state.write();
+        for language in &state.languages {
+            if &language.name() == language_name {
+                language.set_registered_toolchain_lister(None);
+            }
+        }
+        state.toolchain_listers.remove(language_name);
+    }
+
     /// Register a fake language server and adapter
     /// The returned channel receives a new instance of the language server every time it is started
     #[cfg(any(feature = "test-support", test))]
@@ -1189,6 +1219,9 @@ impl LanguageRegistryState {
         if let Some(theme) = self.theme.as_ref() {
             language.set_theme(theme.syntax());
         }
+        if let Some(lister) = self.toolchain_listers.get(&language.name()) {
+            language.set_registered_toolchain_lister(Some(lister.clone()));
+        }
         self.language_settings.languages.0.insert(
             language.name().0,
             LanguageSettingsContent {
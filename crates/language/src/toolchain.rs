@@ -112,6 +112,10 @@ pub trait ToolchainLister: Send + Sync + 'static {
 
     fn activation_script(&self, toolchain: &Toolchain, shell: ShellKind, cx: &App) -> Vec<String>;
 
+    /// Adds the environment variables that make the tools of this language (like language servers or tasks)
+    /// use the given toolchain.
+    fn activate_environment(&self, _toolchain: &Toolchain, _env: &mut HashMap<String, String>) {}
+
     /// Returns various "static" bits of information about this toolchain lister. This function should be pure.
     fn meta(&self) -> ToolchainMetadata;
 }
@@ -0,0 +1,226 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use collections::HashMap;
+use extension::{
+    Extension, ExtensionToolchainProxy, SubprojectDelegate, ToolchainProviderManifestEntry,
+};
+use fs::Fs;
+use gpui::{App, SharedString};
+use language::{
+    LanguageName, ManifestName, ManifestProvider, ManifestQuery, Toolchain, ToolchainList,
+    ToolchainLister, ToolchainMetadata,
+};
+use project::ManifestProvidersStore;
+use util::{ResultExt, rel_path::RelPath, shell::ShellKind};
+
+use crate::LanguageServerRegistryProxy;
+
+impl ExtensionToolchainProxy for LanguageServerRegistryProxy {
+    fn register_toolchain_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        provider: ToolchainProviderManifestEntry,
+        fs: Arc<dyn Fs>,
+        cx: &mut App,
+    ) {
+        let manifest_name = ManifestName::from(SharedString::from(
+            provider.manifest_name.unwrap_or_default(),
+        ));
+        let manifest_providers = ManifestProvidersStore::global(cx);
+        // Builtin manifest providers know their manifests best, so we only fill in the gaps.
+        // The providers we add are not removed with the extension, as they only look for files.
+        if !manifest_name.as_ref().is_empty()
+            && !manifest_providers.contains(manifest_name.as_ref())
+        {
+            manifest_providers.register(Arc::new(ExtensionManifestProvider {
+                name: manifest_name.clone(),
+            }));
+        }
+
+        self.language_registry.register_toolchain_lister(
+            provider.language.clone(),
+            Arc::new(ExtensionToolchainLister {
+                extension,
+                provider_id,
+                language_name: provider.language,
+                meta: ToolchainMetadata {
+                    term: provider.term.into(),
+                    new_toolchain_placeholder: provider
+                        .new_toolchain_placeholder
+                        .unwrap_or_default()
+                        .into(),
+                    manifest_name,
+                },
+                fs,
+            }),
+        );
+    }
+
+    fn unregister_toolchain_provider(&self, language: &LanguageName) {
+        self.language_registry.remove_toolchain_lister(language);
+    }
+}
+
+/// Finds subprojects by the nearest ancestor directory containing a given manifest file.
+struct ExtensionManifestProvider {
+    name: ManifestName,
+}
+
+impl ManifestProvider for ExtensionManifestProvider {
+    fn name(&self) -> ManifestName {
+        self.name.clone()
+    }
+
+    fn search(
+        &self,
+        ManifestQuery {
+            path,
+            depth,
+            delegate,
+        }: ManifestQuery,
+    ) -> Option<Arc<RelPath>> {
+        let file_name = RelPath::unix(self.name.as_ref().as_str()).log_err()?;
+        path.ancestors()
+            .take(depth)
+            .find(|ancestor| delegate.exists(&ancestor.join(file_name), Some(false)))
+            .map(Into::into)
+    }
+}
+
+/// A subproject that an extension lists toolchains for.
+struct ExtensionSubproject {
+    worktree_root: PathBuf,
+    path: Arc<RelPath>,
+    env: Option<HashMap<String, String>>,
+    fs: Arc<dyn Fs>,
+}
+
+#[async_trait]
+impl SubprojectDelegate for ExtensionSubproject {
+    fn worktree_root_path(&self) -> String {
+        self.worktree_root.to_string_lossy().into_owned()
+    }
+
+    fn path(&self) -> String {
+        self.path.as_unix_str().to_string()
+    }
+
+    async fn read_text_file(&self, path: &RelPath) -> Result<String> {
+        self.fs
+            .load(&self.worktree_root.join(path.as_std_path()))
+            .await
+    }
+
+    async fn shell_env(&self) -> Vec<(String, String)> {
+        self.env.clone().unwrap_or_default().into_iter().collect()
+    }
+}
+
+/// A [`ToolchainLister`] that lists toolchains using an extension.
+///
+/// The toolchain data returned by the extension is kept in [`Toolchain::as_json`],
+/// so that activating a toolchain doesn't need to call into the extension.
+struct ExtensionToolchainLister {
+    extension: Arc<dyn Extension>,
+    provider_id: Arc<str>,
+    language_name: LanguageName,
+    meta: ToolchainMetadata,
+    fs: Arc<dyn Fs>,
+}
+
+impl ExtensionToolchainLister {
+    fn toolchain(&self, toolchain: extension::Toolchain) -> Toolchain {
+        Toolchain {
+            name: toolchain.name.clone().into(),
+            path: toolchain.path.clone().into(),
+            language_name: self.language_name.clone(),
+            as_json: serde_json::to_value(toolchain).unwrap_or_default(),
+        }
+    }
+}
+
+fn toolchain_data(toolchain: &Toolchain) -> Option<extension::Toolchain> {
+    serde_json::from_value(toolchain.as_json.clone()).ok()
+}
+
+#[async_trait]
+impl ToolchainLister for ExtensionToolchainLister {
+    async fn list(
+        &self,
+        worktree_root: PathBuf,
+        subroot_relative_path: Arc<RelPath>,
+        project_env: Option<HashMap<String, String>>,
+        _: &dyn Fs,
+    ) -> ToolchainList {
+        let subproject = Arc::new(ExtensionSubproject {
+            worktree_root,
+            path: subroot_relative_path,
+            env: project_env,
+            fs: self.fs.clone(),
+        });
+        let Some(list) = self
+            .extension
+            .list_toolchains(self.provider_id.clone(), subproject)
+            .await
+            .log_err()
+        else {
+            return ToolchainList::default();
+        };
+
+        let toolchains = list
+            .toolchains
+            .into_iter()
+            .map(|toolchain| self.toolchain(toolchain))
+            .collect::<Vec<_>>();
+        ToolchainList {
+            default: list.default.filter(|ix| *ix < toolchains.len()),
+            toolchains,
+            groups: Box::default(),
+        }
+    }
+
+    async fn resolve(
+        &self,
+        path: PathBuf,
+        project_env: Option<HashMap<String, String>>,
+        _: &dyn Fs,
+    ) -> Result<Toolchain> {
+        let env = project_env.unwrap_or_default().into_iter().collect();
+        let toolchain = self
+            .extension
+            .resolve_toolchain(self.provider_id.clone(), path, env)
+            .await?;
+        Ok(self.toolchain(toolchain))
+    }
+
+    fn activation_script(&self, toolchain: &Toolchain, shell: ShellKind, _: &App) -> Vec<String> {
+        toolchain_data(toolchain)
+            .and_then(|toolchain| {
+                toolchain
+                    .activation_scripts
+                    .into_iter()
+                    .find(|script| script.shell == shell)
+            })
+            .map(|script| script.commands)
+            .unwrap_or_default()
+    }
+
+    fn activate_environment(&self, toolchain: &Toolchain, env: &mut HashMap<String, String>) {
+        let Some(toolchain) = toolchain_data(toolchain) else {
+            return;
+        };
+        env.extend(toolchain.env);
+
+        if !toolchain.bin_paths.is_empty() {
+            util::shell_env::prepend_to_path(env, toolchain.bin_paths);
+        }
+    }
+
+    fn meta(&self) -> ToolchainMetadata {
+        self.meta.clone()
+    }
+}
@@ -1,4 +1,5 @@
 mod extension_lsp_adapter;
+mod extension_toolchain_lister;
 
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -27,7 +28,8 @@ pub fn init(
     };
     extension_host_proxy.register_grammar_proxy(language_server_registry_proxy.clone());
     extension_host_proxy.register_language_proxy(language_server_registry_proxy.clone());
-    extension_host_proxy.register_language_server_proxy(language_server_registry_proxy);
+    extension_host_proxy.register_language_server_proxy(language_server_registry_proxy.clone());
+    extension_host_proxy.register_toolchain_proxy(language_server_registry_proxy);
 }
 
 #[derive(Clone)]
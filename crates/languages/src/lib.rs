@@ -1,5 +1,7 @@
 use anyhow::Context as _;
+use collections::HashMap;
 use gpui::{App, SharedString, UpdateGlobal};
+use node::PackageJsonManifestProvider;
 use node_runtime::NodeRuntime;
 use project::Fs;
 use python::PyprojectTomlManifestProvider;
@@ -7,7 +9,7 @@ use rust::CargoManifestProvider;
 use rust_embed::RustEmbed;
 use settings::SettingsStore;
 use smol::stream::StreamExt;
-use std::{str, sync::Arc};
+use std::{path::PathBuf, str, sync::Arc};
 use util::{ResultExt, asset_str};
 
 pub use language::*;
@@ -24,6 +26,7 @@ mod css;
 mod eslint;
 mod go;
 mod json;
+mod node;
 mod package_json;
 mod python;
 mod rust;
@@ -186,6 +189,7 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
             name: "rust",
             adapters: vec![rust_lsp_adapter],
             context: Some(rust_context_provider),
+            toolchain: Some(Arc::new(rust::RustToolchainProvider)),
             manifest_name: Some(SharedString::new_static("Cargo.toml").into()),
             ..Default::default()
         },
@@ -193,18 +197,27 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
             name: "tsx",
             adapters: vec![typescript_lsp_adapter.clone(), vtsls_adapter.clone()],
             context: Some(typescript_context.clone()),
+            toolchain: Some(Arc::new(node::NodeToolchainProvider::new(
+                LanguageName::new_static("TSX"),
+            ))),
             ..Default::default()
         },
         LanguageInfo {
             name: "typescript",
             adapters: vec![typescript_lsp_adapter.clone(), vtsls_adapter.clone()],
             context: Some(typescript_context.clone()),
+            toolchain: Some(Arc::new(node::NodeToolchainProvider::new(
+                LanguageName::new_static("TypeScript"),
+            ))),
             ..Default::default()
         },
         LanguageInfo {
             name: "javascript",
             adapters: vec![typescript_lsp_adapter.clone(), vtsls_adapter.clone()],
             context: Some(typescript_context),
+            toolchain: Some(Arc::new(node::NodeToolchainProvider::new(
+                LanguageName::new_static("JavaScript"),
+            ))),
             ..Default::default()
         },
         LanguageInfo {
@@ -327,8 +340,9 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
         anyhow::Ok(())
     })
     .detach();
-    let manifest_providers: [Arc<dyn ManifestProvider>; 2] = [
+    let manifest_providers: [Arc<dyn ManifestProvider>; 3] = [
         Arc::from(CargoManifestProvider),
+        Arc::from(PackageJsonManifestProvider),
         Arc::from(PyprojectTomlManifestProvider),
     ];
     for provider in manifest_providers {
@@ -431,3 +445,11 @@ fn load_queries(name: &str) -> LanguageQueries {
     }
     result
 }
+
+/// Returns the user's home directory as seen by the project environment.
+fn home_dir(env: &HashMap<String, String>) -> PathBuf {
+    env.get("HOME")
+        .or_else(|| env.get("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| util::paths::home_dir().clone())
+}
@@ -0,0 +1,486 @@
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use collections::HashMap;
+use futures::StreamExt;
+use gpui::{App, SharedString};
+use language::{
+    LanguageName, ManifestName, ManifestProvider, ManifestQuery, Toolchain, ToolchainList,
+    ToolchainLister, ToolchainMetadata,
+};
+use project::Fs;
+use serde::{Deserialize, Serialize};
+use serde_json_lenient::Value;
+use std::{
+    cmp::Reverse,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use task::ShellKind;
+use util::{ResultExt, rel_path::RelPath};
+
+pub(crate) struct PackageJsonManifestProvider;
+
+impl ManifestProvider for PackageJsonManifestProvider {
+    fn name(&self) -> ManifestName {
+        SharedString::new_static("package.json").into()
+    }
+
+    fn search(
+        &self,
+        ManifestQuery {
+            path,
+            depth,
+            delegate,
+        }: ManifestQuery,
+    ) -> Option<Arc<RelPath>> {
+        // Use the root of a monorepo, so that its packages share the same Node.js version.
+        let mut outermost_package_json = None;
+        for path in path.ancestors().take(depth) {
+            let p = path.join(RelPath::unix("package.json").unwrap());
+            if delegate.exists(&p, Some(false)) {
+                outermost_package_json = Some(Arc::from(path));
+            }
+        }
+
+        outermost_package_json
+    }
+}
+
+/// Lists the Node.js versions installed with nvm, fnm and Volta.
+///
+/// JavaScript, TypeScript and TSX each get their own lister, as the selected toolchain is stored per language.
+pub(crate) struct NodeToolchainProvider {
+    language_name: LanguageName,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NodeToolchainData {
+    version: String,
+    bin_dir: PathBuf,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum VersionManager {
+    Nvm,
+    Fnm,
+    Volta,
+}
+
+impl VersionManager {
+    fn label(&self) -> &'static str {
+        match self {
+            VersionManager::Nvm => "nvm",
+            VersionManager::Fnm => "fnm",
+            VersionManager::Volta => "Volta",
+        }
+    }
+
+    /// The directories in which the version manager keeps one directory per installed version.
+    fn versions_dirs(&self, env: &HashMap<String, String>) -> Vec<PathBuf> {
+        let home = crate::home_dir(env);
+        match self {
+            VersionManager::Nvm => vec![
+                env.get("NVM_DIR")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| home.join(".nvm"))
+                    .join("versions")
+                    .join("node"),
+            ],
+            VersionManager::Fnm => {
+                let fnm_dirs = if let Some(fnm_dir) = env.get("FNM_DIR") {
+                    vec![PathBuf::from(fnm_dir)]
+                } else {
+                    let data_dir = if cfg!(target_os = "macos") {
+                        home.join("Library").join("Application Support")
+                    } else if cfg!(windows) {
+                        env.get("APPDATA")
+                            .map(PathBuf::from)
+                            .unwrap_or_else(|| home.join("AppData").join("Roaming"))
+                    } else {
+                        env.get("XDG_DATA_HOME")
+                            .map(PathBuf::from)
+                            .unwrap_or_else(|| home.join(".local").join("share"))
+                    };
+                    // Older releases of fnm used `~/.fnm`.
+                    vec![data_dir.join("fnm"), home.join(".fnm")]
+                };
+                fnm_dirs
+                    .into_iter()
+                    .map(|fnm_dir| fnm_dir.join("node-versions"))
+                    .collect()
+            }
+            VersionManager::Volta => {
+                let volta_home = env.get("VOLTA_HOME").map(PathBuf::from).unwrap_or_else(|| {
+                    if cfg!(windows) {
+                        env.get("LOCALAPPDATA")
+                            .map(PathBuf::from)
+                            .unwrap_or_else(|| home.join("AppData").join("Local"))
+                            .join("Volta")
+                    } else {
+                        home.join(".volta")
+                    }
+                });
+                vec![volta_home.join("tools").join("image").join("node")]
+            }
+        }
+    }
+
+    /// Returns the directory with the `node` binary of a version directory.
+    fn bin_dir(&self, version_dir: &Path) -> PathBuf {
+        let installation_dir = match self {
+            VersionManager::Fnm => version_dir.join("installation"),
+            VersionManager::Nvm | VersionManager::Volta => version_dir.to_path_buf(),
+        };
+        node_bin_dir(&installation_dir)
+    }
+}
+
+/// Node.js distributions keep their binaries in `bin`, except for the Windows ones.
+fn node_bin_dir(installation_dir: &Path) -> PathBuf {
+    if cfg!(windows) {
+        installation_dir.to_path_buf()
+    } else {
+        installation_dir.join("bin")
+    }
+}
+
+fn node_file_name() -> &'static str {
+    if cfg!(windows) { "node.exe" } else { "node" }
+}
+
+/// Parses versions like `v20.11.0` or `20.11.0`.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut components = version.trim().trim_start_matches('v').splitn(3, '.');
+    Some((
+        components.next()?.parse().ok()?,
+        components.next()?.parse().ok()?,
+        components.next()?.parse().ok()?,
+    ))
+}
+
+/// Whether the version matches a pinned version like `20`, `v20.11` or `20.11.0`.
+///
+/// Aliases like `lts/*` or `node` are not supported and match no version.
+fn version_matches_pin(version: (u64, u64, u64), pin: &str) -> bool {
+    let pin = pin.trim().trim_start_matches('v');
+    if pin.is_empty() {
+        return false;
+    }
+    let version = [version.0, version.1, version.2];
+    let mut pin_components = pin.split('.');
+    for (version_component, pin_component) in version.iter().zip(pin_components.by_ref()) {
+        if pin_component.parse::<u64>().ok() != Some(*version_component) {
+            return false;
+        }
+    }
+    pin_components.next().is_none()
+}
+
+impl NodeToolchainProvider {
+    pub(crate) fn new(language_name: LanguageName) -> Self {
+        Self { language_name }
+    }
+
+    fn toolchain(
+        &self,
+        version: String,
+        manager: Option<VersionManager>,
+        bin_dir: PathBuf,
+    ) -> Option<Toolchain> {
+        let name = match manager {
+            Some(manager) => format!("Node.js {version} ({})", manager.label()),
+            None => format!("Node.js {version}"),
+        };
+        Some(Toolchain {
+            name: name.into(),
+            path: bin_dir
+                .join(node_file_name())
+                .to_string_lossy()
+                .into_owned()
+                .into(),
+            language_name: self.language_name.clone(),
+            as_json: serde_json::to_value(NodeToolchainData { version, bin_dir }).log_err()?,
+        })
+    }
+
+    /// Finds the innermost version pin of the subproject, from `.nvmrc`, `.node-version` or the `volta` field
+    /// of `package.json`.
+    async fn version_pin(
+        worktree_root: &Path,
+        subroot_relative_path: &RelPath,
+        fs: &dyn Fs,
+    ) -> Option<String> {
+        for directory in subroot_relative_path.ancestors() {
+            let directory = worktree_root.join(directory.as_std_path());
+            for file_name in [".nvmrc", ".node-version"] {
+                if let Ok(contents) = fs.load(&directory.join(file_name)).await
+                    && let Some(pin) = contents.lines().next().map(str::trim)
+                    && !pin.is_empty()
+                {
+                    return Some(pin.to_owned());
+                }
+            }
+            if let Ok(contents) = fs.load(&directory.join("package.json")).await
+                && let Ok(package_json) = serde_json_lenient::from_str::<Value>(&contents)
+                && let Some(pin) = package_json
+                    .get("volta")
+                    .and_then(|volta| volta.get("node"))
+                    .and_then(Value::as_str)
+            {
+                return Some(pin.to_owned());
+            }
+        }
+        None
+    }
+}
+
+#[async_trait]
+impl ToolchainLister for NodeToolchainProvider {
+    async fn list(
+        &self,
+        worktree_root: PathBuf,
+        subroot_relative_path: Arc<RelPath>,
+        project_env: Option<HashMap<String, String>>,
+        fs: &dyn Fs,
+    ) -> ToolchainList {
+        let env = project_env.unwrap_or_default();
+
+        let mut installed = Vec::new();
+        for manager in [
+            VersionManager::Nvm,
+            VersionManager::Fnm,
+            VersionManager::Volta,
+        ] {
+            for versions_dir in manager.versions_dirs(&env) {
+                let Ok(mut entries) = fs.read_dir(&versions_dir).await else {
+                    continue;
+                };
+                while let Some(entry) = entries.next().await {
+                    let Ok(version_dir) = entry else {
+                        continue;
+                    };
+                    let Some(version) = version_dir
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .and_then(parse_version)
+                    else {
+                        continue;
+                    };
+                    let bin_dir = manager.bin_dir(&version_dir);
+                    if fs.is_file(&bin_dir.join(node_file_name())).await {
+                        installed.push((version, manager, bin_dir));
+                    }
+                }
+            }
+        }
+        installed.sort_by_key(|(version, manager, _)| (Reverse(*version), *manager));
+        installed.dedup_by(|(_, _, a), (_, _, b)| a == b);
+
+        let default = match Self::version_pin(&worktree_root, &subroot_relative_path, fs).await {
+            Some(pin) => installed
+                .iter()
+                .position(|(version, _, _)| version_matches_pin(*version, &pin)),
+            None => None,
+        };
+
+        ToolchainList {
+            toolchains: installed
+                .into_iter()
+                .filter_map(|((major, minor, patch), manager, bin_dir)| {
+                    self.toolchain(format!("v{major}.{minor}.{patch}"), Some(manager), bin_dir)
+                })
+                .collect(),
+            default,
+            groups: Default::default(),
+        }
+    }
+
+    async fn resolve(
+        &self,
+        path: PathBuf,
+        _: Option<HashMap<String, String>>,
+        fs: &dyn Fs,
+    ) -> Result<Toolchain> {
+        let node = if fs.is_file(&path).await {
+            path
+        } else if fs
+            .is_file(&node_bin_dir(&path).join(node_file_name()))
+            .await
+        {
+            node_bin_dir(&path).join(node_file_name())
+        } else {
+            path.join(node_file_name())
+        };
+        anyhow::ensure!(
+            fs.is_file(&node).await,
+            "{} does not contain a Node.js installation",
+            path.display()
+        );
+
+        let output = util::command::new_smol_command(&node)
+            .arg("--version")
+            .output()
+            .await
+            .with_context(|| format!("failed to run {}", node.display()))?;
+        anyhow::ensure!(
+            output.status.success(),
+            "`node --version` failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let version = String::from_utf8(output.stdout)
+            .context("`node --version` printed invalid UTF-8")?
+            .trim()
+            .to_owned();
+        let bin_dir = node
+            .parent()
+            .context("node binary has no parent directory")?
+            .to_path_buf();
+
+        self.toolchain(version, None, bin_dir)
+            .context("failed to serialize toolchain")
+    }
+
+    fn activation_script(&self, _: &Toolchain, _: ShellKind, _: &App) -> Vec<String> {
+        // Node.js toolchains are activated through the environment instead.
+        Vec::new()
+    }
+
+    fn activate_environment(&self, toolchain: &Toolchain, env: &mut HashMap<String, String>) {
+        if let Ok(toolchain) =
+            serde_json::from_value::<NodeToolchainData>(toolchain.as_json.clone())
+        {
+            util::shell_env::prepend_to_path(env, [toolchain.bin_dir]);
+        }
+    }
+
+    fn meta(&self) -> ToolchainMetadata {
+        ToolchainMetadata {
+            term: SharedString::new_static("Node.js Version"),
+            new_toolchain_placeholder: SharedString::new_static(
+                "A path to a node executable, or to the Node.js installation containing it",
+            ),
+            manifest_name: ManifestName::from(SharedString::new_static("package.json")),
+        }
+    }
+}
+
+/// Returns the `node` binary of a toolchain listed by [`NodeToolchainProvider`].
+pub(crate) fn node_binary(toolchain: &Toolchain) -> Option<PathBuf> {
+    let toolchain = serde_json::from_value::<NodeToolchainData>(toolchain.as_json.clone()).ok()?;
+    Some(toolchain.bin_dir.join(node_file_name()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use util::{path, rel_path::rel_path};
+
+    #[test]
+    fn test_version_matches_pin() {
+        let version = parse_version("v20.11.1").unwrap();
+        assert_eq!(version, (20, 11, 1));
+        assert!(version_matches_pin(version, "20"));
+        assert!(version_matches_pin(version, "v20.11"));
+        assert!(version_matches_pin(version, "20.11.1\n"));
+        assert!(!version_matches_pin(version, "2"));
+        assert!(!version_matches_pin(version, "20.1"));
+        assert!(!version_matches_pin(version, "lts/iron"));
+        assert!(!version_matches_pin(version, ""));
+    }
+
+    #[gpui::test]
+    async fn test_node_toolchain_listing(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        let node = node_file_name();
+        let installation = || {
+            if cfg!(windows) {
+                json!({ node: "" })
+            } else {
+                json!({ "bin": { node: "" } })
+            }
+        };
+        fs.insert_tree(
+            path!("/nvm/versions/node"),
+            json!({
+                "v18.19.0": installation(),
+                "v20.11.1": installation(),
+                "v20.9.0": installation(),
+            }),
+        )
+        .await;
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "package.json": "{}",
+                "legacy": {
+                    "package.json": r#"{ "volta": { "node": "18.19.0" } }"#,
+                },
+                "modern": {
+                    ".nvmrc": "v20\n",
+                    "package.json": "{}",
+                },
+            }),
+        )
+        .await;
+
+        let provider = NodeToolchainProvider::new(LanguageName::new_static("TypeScript"));
+        let env = HashMap::from_iter([("NVM_DIR".to_owned(), path!("/nvm").to_owned())]);
+
+        let toolchains = provider
+            .list(
+                PathBuf::from(path!("/project")),
+                rel_path("").into(),
+                Some(env.clone()),
+                fs.as_ref(),
+            )
+            .await;
+        assert_eq!(
+            toolchains
+                .toolchains()
+                .iter()
+                .map(|toolchain| toolchain.name.as_ref())
+                .collect::<Vec<_>>(),
+            [
+                "Node.js v20.11.1 (nvm)",
+                "Node.js v20.9.0 (nvm)",
+                "Node.js v18.19.0 (nvm)",
+            ]
+        );
+        assert_eq!(toolchains.default_toolchain(), None);
+
+        let toolchains = provider
+            .list(
+                PathBuf::from(path!("/project")),
+                rel_path("modern").into(),
+                Some(env.clone()),
+                fs.as_ref(),
+            )
+            .await;
+        let default = toolchains.default_toolchain().unwrap();
+        assert_eq!(default.name, "Node.js v20.11.1 (nvm)");
+        let mut project_env = HashMap::default();
+        provider.activate_environment(&default, &mut project_env);
+        assert_eq!(
+            project_env.get("PATH").map(PathBuf::from),
+            Some(node_bin_dir(Path::new(path!(
+                "/nvm/versions/node/v20.11.1"
+            ))))
+        );
+
+        let toolchains = provider
+            .list(
+                PathBuf::from(path!("/project")),
+                rel_path("legacy").into(),
+                Some(env),
+                fs.as_ref(),
+            )
+            .await;
+        assert_eq!(
+            toolchains.default_toolchain().unwrap().name,
+            "Node.js v18.19.0 (nvm)"
+        );
+    }
+}
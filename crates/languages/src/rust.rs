@@ -8,9 +8,11 @@ use http_client::github::{GitHubLspBinaryVersion, latest_github_release};
 use http_client::github_download::{GithubBinaryMetadata, download_server_binary};
 pub use language::*;
 use lsp::{InitializeParams, LanguageServerBinary};
+use project::Fs;
 use project::lsp_store::rust_analyzer_ext::CARGO_DIAGNOSTICS_SOURCE_NAME;
 use project::project_settings::ProjectSettings;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use settings::Settings as _;
 use smallvec::SmallVec;
@@ -23,7 +25,7 @@ use std::{
     path::{Path, PathBuf},
     sync::{Arc, LazyLock},
 };
-use task::{TaskTemplate, TaskTemplates, TaskVariables, VariableName};
+use task::{ShellKind, TaskTemplate, TaskTemplates, TaskVariables, VariableName};
 use util::fs::{make_file_executable, remove_matching};
 use util::merge_json_value_into;
 use util::rel_path::RelPath;
@@ -499,15 +501,35 @@ impl LspInstaller for RustLspAdapter {
     async fn check_if_user_installed(
         &self,
         delegate: &dyn LspAdapterDelegate,
-        _: Option<Toolchain>,
+        toolchain: Option<Toolchain>,
         _: &AsyncApp,
     ) -> Option<LanguageServerBinary> {
-        let path = delegate.which("rust-analyzer".as_ref()).await?;
-        let env = delegate.shell_env().await;
+        let mut env = delegate.shell_env().await;
+        if let Some(toolchain) = &toolchain {
+            RustToolchainProvider.activate_environment(toolchain, &mut env);
+        }
+        // Prefer the rust-analyzer component of the selected toolchain over the one in PATH.
+        let toolchain_binary = toolchain
+            .and_then(|toolchain| {
+                serde_json::from_value::<RustToolchainData>(toolchain.as_json).ok()
+            })
+            .and_then(|toolchain| toolchain.bin_dir)
+            .map(|bin_dir| {
+                bin_dir
+                    .join("rust-analyzer")
+                    .with_extension(std::env::consts::EXE_EXTENSION)
+            });
+        let path = if let Some(path) = toolchain_binary
+            && fs::metadata(&path).await.is_ok()
+        {
+            path
+        } else {
+            delegate.which("rust-analyzer".as_ref()).await?
+        };
 
         // It is surprisingly common for ~/.cargo/bin/rust-analyzer to be a symlink to
         // /usr/bin/rust-analyzer that fails when you run it; so we need to test it.
-        log::debug!("found rust-analyzer in PATH. trying to run `rust-analyzer --help`");
+        log::debug!("found rust-analyzer at {path:?}. trying to run `rust-analyzer --help`");
         let result = delegate
             .try_exec(LanguageServerBinary {
                 path: path.clone(),
@@ -517,7 +539,7 @@ impl LspInstaller for RustLspAdapter {
             .await;
         if let Err(err) = result {
             log::debug!(
-                "failed to run rust-analyzer after detecting it in PATH: binary: {:?}: {}",
+                "failed to run rust-analyzer after detecting it: binary: {:?}: {}",
                 path,
                 err
             );
@@ -1176,6 +1198,276 @@ fn test_fragment(variables: &TaskVariables, path: &Path, stem: &str) -> String {
     fragment.unwrap_or_else(|| "--".to_owned())
 }
 
+pub(crate) struct RustToolchainProvider;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RustToolchainData {
+    /// The value of `RUSTUP_TOOLCHAIN`: the name of an installed toolchain, a channel or a path to a toolchain.
+    toolchain: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bin_dir: Option<PathBuf>,
+    /// Whether rustup already picks this toolchain for the project, because the project pins it or it's
+    /// the default toolchain, so that activating it doesn't need to override rustup's choice.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    selected_by_rustup: bool,
+}
+
+/// The toolchain override of a directory, from its `rust-toolchain.toml` or `rust-toolchain` file.
+#[derive(Debug, PartialEq)]
+struct ToolchainOverride {
+    channel: String,
+    file_path: PathBuf,
+}
+
+impl RustToolchainProvider {
+    fn rustup_home(env: &HashMap<String, String>) -> PathBuf {
+        env.get("RUSTUP_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| crate::home_dir(env).join(".rustup"))
+    }
+
+    fn rustc_file_name() -> &'static str {
+        if cfg!(windows) { "rustc.exe" } else { "rustc" }
+    }
+
+    /// Finds the innermost toolchain override that applies to the subproject, like rustup does.
+    async fn toolchain_override(
+        worktree_root: &Path,
+        subroot_relative_path: &RelPath,
+        fs: &dyn Fs,
+    ) -> Option<ToolchainOverride> {
+        for directory in subroot_relative_path.ancestors() {
+            let directory = worktree_root.join(directory.as_std_path());
+            // rustup prefers the legacy file when both of them are present.
+            for file_name in ["rust-toolchain", "rust-toolchain.toml"] {
+                let file_path = directory.join(file_name);
+                let Ok(contents) = fs.load(&file_path).await else {
+                    continue;
+                };
+                if let Some(channel) = parse_toolchain_file(&contents) {
+                    return Some(ToolchainOverride { channel, file_path });
+                }
+            }
+        }
+        None
+    }
+
+    async fn default_toolchain_name(rustup_home: &Path, fs: &dyn Fs) -> Option<String> {
+        #[derive(Deserialize)]
+        struct RustupSettings {
+            default_toolchain: Option<String>,
+        }
+
+        let contents = fs.load(&rustup_home.join("settings.toml")).await.ok()?;
+        toml::from_str::<RustupSettings>(&contents)
+            .log_err()?
+            .default_toolchain
+    }
+}
+
+/// Reads the channel from the contents of a `rust-toolchain.toml` file, or from the legacy `rust-toolchain`
+/// file that may also contain just the channel name.
+fn parse_toolchain_file(contents: &str) -> Option<String> {
+    #[derive(Deserialize)]
+    struct ToolchainFile {
+        toolchain: ToolchainSection,
+    }
+
+    #[derive(Deserialize)]
+    struct ToolchainSection {
+        channel: Option<String>,
+    }
+
+    let contents = contents.trim();
+    if !contents.is_empty() && !contents.contains(['\n', '=', '[']) {
+        return Some(contents.to_owned());
+    }
+    toml::from_str::<ToolchainFile>(contents)
+        .ok()?
+        .toolchain
+        .channel
+        .filter(|channel| !channel.is_empty())
+}
+
+/// Whether an installed toolchain, like `stable-aarch64-apple-darwin`, is the one rustup picks for a channel.
+fn toolchain_matches_channel(toolchain_name: &str, channel: &str) -> bool {
+    toolchain_name
+        .strip_prefix(channel)
+        .is_some_and(|host| host.is_empty() || host.starts_with('-'))
+}
+
+fn rust_toolchain(name: String, path: PathBuf, data: RustToolchainData) -> Option<Toolchain> {
+    Some(Toolchain {
+        name: name.into(),
+        path: path.to_string_lossy().into_owned().into(),
+        language_name: LanguageName::new_static("Rust"),
+        as_json: serde_json::to_value(data).log_err()?,
+    })
+}
+
+#[async_trait]
+impl ToolchainLister for RustToolchainProvider {
+    async fn list(
+        &self,
+        worktree_root: PathBuf,
+        subroot_relative_path: Arc<RelPath>,
+        project_env: Option<HashMap<String, String>>,
+        fs: &dyn Fs,
+    ) -> ToolchainList {
+        let env = project_env.unwrap_or_default();
+        let rustup_home = Self::rustup_home(&env);
+
+        let mut installed = Vec::new();
+        if let Ok(mut entries) = fs.read_dir(&rustup_home.join("toolchains")).await {
+            while let Some(entry) = entries.next().await {
+                let Ok(path) = entry else {
+                    continue;
+                };
+                if !fs.is_dir(&path).await {
+                    continue;
+                }
+                if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+                    installed.push((name.to_owned(), path.clone()));
+                }
+            }
+        }
+        installed.sort();
+
+        let find_installed = |channel: &str| {
+            installed
+                .iter()
+                .enumerate()
+                .filter(|(_, (name, _))| toolchain_matches_channel(name, channel))
+                // Prefer `nightly-<host>` over dated toolchains like `nightly-2025-01-01-<host>`.
+                .min_by_key(|(_, (name, _))| name.len())
+                .map(|(ix, _)| ix)
+        };
+
+        let mut missing_override = None;
+        let mut default = None;
+        if let Some(toolchain_override) =
+            Self::toolchain_override(&worktree_root, &subroot_relative_path, fs).await
+        {
+            default = find_installed(&toolchain_override.channel);
+            if default.is_none() {
+                missing_override = Some(toolchain_override);
+            }
+        } else if let Some(default_toolchain) = Self::default_toolchain_name(&rustup_home, fs).await
+        {
+            default = find_installed(&default_toolchain);
+        }
+
+        let mut toolchains = installed
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, (name, path))| {
+                rust_toolchain(
+                    name.clone(),
+                    path.clone(),
+                    RustToolchainData {
+                        toolchain: name.clone(),
+                        bin_dir: Some(path.join("bin")),
+                        selected_by_rustup: default == Some(ix),
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(toolchain_override) = missing_override {
+            // rustup installs the toolchain once it's used, so offer it anyway.
+            let channel = toolchain_override.channel;
+            default = rust_toolchain(
+                format!("{channel} (not installed)"),
+                toolchain_override.file_path,
+                RustToolchainData {
+                    toolchain: channel,
+                    bin_dir: None,
+                    selected_by_rustup: true,
+                },
+            )
+            .map(|toolchain| {
+                toolchains.insert(0, toolchain);
+                0
+            });
+        }
+
+        ToolchainList {
+            toolchains,
+            default,
+            groups: Default::default(),
+        }
+    }
+
+    async fn resolve(
+        &self,
+        path: PathBuf,
+        _: Option<HashMap<String, String>>,
+        fs: &dyn Fs,
+    ) -> anyhow::Result<Toolchain> {
+        let toolchain_dir = if fs.is_file(&path).await {
+            path.parent()
+                .and_then(Path::parent)
+                .context("rustc is not located in a toolchain's bin directory")?
+                .to_path_buf()
+        } else {
+            path
+        };
+        let bin_dir = toolchain_dir.join("bin");
+        anyhow::ensure!(
+            fs.is_file(&bin_dir.join(Self::rustc_file_name())).await,
+            "{} does not contain a Rust toolchain",
+            toolchain_dir.display()
+        );
+        let name = toolchain_dir
+            .file_name()
+            .context("toolchain directory has no name")?
+            .to_string_lossy()
+            .into_owned();
+
+        rust_toolchain(
+            name,
+            toolchain_dir.clone(),
+            RustToolchainData {
+                toolchain: toolchain_dir.to_string_lossy().into_owned(),
+                bin_dir: Some(bin_dir),
+                selected_by_rustup: false,
+            },
+        )
+        .context("failed to serialize toolchain")
+    }
+
+    fn activation_script(&self, _: &Toolchain, _: ShellKind, _: &App) -> Vec<String> {
+        // Rust toolchains are activated through the environment instead.
+        Vec::new()
+    }
+
+    fn activate_environment(&self, toolchain: &Toolchain, env: &mut HashMap<String, String>) {
+        let Ok(toolchain) = serde_json::from_value::<RustToolchainData>(toolchain.as_json.clone())
+        else {
+            return;
+        };
+        // Leave it to rustup to pick the toolchain it already uses, so that changes to the project's
+        // `rust-toolchain.toml` still apply, and don't override a toolchain set in the environment.
+        if toolchain.selected_by_rustup || env.contains_key("RUSTUP_TOOLCHAIN") {
+            return;
+        }
+        env.insert("RUSTUP_TOOLCHAIN".to_owned(), toolchain.toolchain);
+        if let Some(bin_dir) = &toolchain.bin_dir {
+            util::shell_env::prepend_to_path(env, [bin_dir.clone()]);
+        }
+    }
+
+    fn meta(&self) -> ToolchainMetadata {
+        ToolchainMetadata {
+            term: SharedString::new_static("Rust Toolchain"),
+            new_toolchain_placeholder: SharedString::new_static(
+                "A path to a Rust toolchain directory, or to the rustc executable within it",
+            ),
+            manifest_name: ManifestName::from(SharedString::new_static("Cargo.toml")),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::num::NonZeroU32;
@@ -1818,4 +2110,123 @@ mod tests {
         );
         check([], "/project/src/main.rs", "--");
     }
+
+    #[test]
+    fn test_parse_toolchain_file() {
+        assert_eq!(
+            parse_toolchain_file("nightly\n"),
+            Some("nightly".to_owned())
+        );
+        assert_eq!(
+            parse_toolchain_file("[toolchain]\nchannel = \"1.80.0\"\ncomponents = [\"clippy\"]\n"),
+            Some("1.80.0".to_owned())
+        );
+        assert_eq!(
+            parse_toolchain_file("[toolchain]\ncomponents = [\"clippy\"]\n"),
+            None
+        );
+        assert_eq!(parse_toolchain_file(""), None);
+
+        assert!(toolchain_matches_channel(
+            "stable-x86_64-unknown-linux-gnu",
+            "stable"
+        ));
+        assert!(toolchain_matches_channel("1.80.0", "1.80.0"));
+        assert!(!toolchain_matches_channel(
+            "1.80.0-x86_64-unknown-linux-gnu",
+            "1.8"
+        ));
+    }
+
+    #[gpui::test]
+    async fn test_rust_toolchain_listing(cx: &mut TestAppContext) {
+        let fs = project::FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/rustup"),
+            json!({
+                "settings.toml": "default_toolchain = \"stable-x86_64-unknown-linux-gnu\"\n",
+                "toolchains": {
+                    "nightly-2025-01-01-x86_64-unknown-linux-gnu": { "bin": { "rustc": "" } },
+                    "nightly-x86_64-unknown-linux-gnu": { "bin": { "rustc": "" } },
+                    "stable-x86_64-unknown-linux-gnu": { "bin": { "rustc": "" } },
+                },
+            }),
+        )
+        .await;
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "Cargo.toml": "",
+                "nightly": {
+                    "Cargo.toml": "",
+                    "rust-toolchain.toml": "[toolchain]\nchannel = \"nightly\"\n",
+                },
+                "pinned": {
+                    "Cargo.toml": "",
+                    "rust-toolchain": "1.80.0\n",
+                },
+            }),
+        )
+        .await;
+
+        let env = HashMap::from_iter([("RUSTUP_HOME".to_owned(), path!("/rustup").to_owned())]);
+        let list = |subroot: &'static str| {
+            RustToolchainProvider.list(
+                PathBuf::from(path!("/project")),
+                RelPath::unix(subroot).unwrap().into(),
+                Some(env.clone()),
+                fs.as_ref(),
+            )
+        };
+
+        let toolchains = list("").await;
+        assert_eq!(
+            toolchains
+                .toolchains()
+                .iter()
+                .map(|toolchain| toolchain.name.as_ref())
+                .collect::<Vec<_>>(),
+            [
+                "nightly-2025-01-01-x86_64-unknown-linux-gnu",
+                "nightly-x86_64-unknown-linux-gnu",
+                "stable-x86_64-unknown-linux-gnu",
+            ]
+        );
+        assert_eq!(
+            toolchains.default_toolchain().unwrap().name,
+            "stable-x86_64-unknown-linux-gnu"
+        );
+
+        let toolchains = list("nightly").await;
+        let default = toolchains.default_toolchain().unwrap();
+        assert_eq!(default.name, "nightly-x86_64-unknown-linux-gnu");
+        let mut env = HashMap::default();
+        RustToolchainProvider.activate_environment(&default, &mut env);
+        assert!(
+            env.is_empty(),
+            "rustup already picks the pinned toolchain, so it shouldn't be forced"
+        );
+
+        let stable = &toolchains.toolchains()[2];
+        assert_eq!(stable.name, "stable-x86_64-unknown-linux-gnu");
+        RustToolchainProvider.activate_environment(stable, &mut env);
+        assert_eq!(
+            env.get("RUSTUP_TOOLCHAIN").map(String::as_str),
+            Some("stable-x86_64-unknown-linux-gnu")
+        );
+
+        let mut env = HashMap::from_iter([("RUSTUP_TOOLCHAIN".to_owned(), "beta".to_owned())]);
+        RustToolchainProvider.activate_environment(stable, &mut env);
+        assert_eq!(
+            env,
+            HashMap::from_iter([("RUSTUP_TOOLCHAIN".to_owned(), "beta".to_owned())]),
+            "The toolchain set in the environment takes precedence"
+        );
+
+        let toolchains = list("pinned").await;
+        assert_eq!(toolchains.toolchains().len(), 4);
+        let default = toolchains.default_toolchain().unwrap();
+        assert_eq!(default.name, "1.80.0 (not installed)");
+        assert_eq!(default.path, path!("/project/pinned/rust-toolchain"));
+    }
 }
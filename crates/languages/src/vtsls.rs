@@ -15,6 +15,8 @@ use std::{
 };
 use util::{ResultExt, maybe, merge_json_value_into};
 
+use crate::node::node_binary;
+
 fn typescript_server_binary_arguments(server_path: &Path) -> Vec<OsString> {
     vec![server_path.into(), "--stdio".into()]
 }
@@ -227,12 +229,12 @@ impl LspAdapter for VtslsLspAdapter {
     async fn workspace_configuration(
         self: Arc<Self>,
         delegate: &Arc<dyn LspAdapterDelegate>,
-        _: Option<Toolchain>,
+        toolchain: Option<Toolchain>,
         _: Option<Uri>,
         cx: &mut AsyncApp,
     ) -> Result<Value> {
         let tsdk_path = self.tsdk_path(delegate).await;
-        let config = serde_json::json!({
+        let mut config = serde_json::json!({
             "tsdk": tsdk_path,
             "suggest": {
                 "completeFunctionCalls": true
@@ -263,6 +265,10 @@ impl LspAdapter for VtslsLspAdapter {
                 "maxTsServerMemory": 8092
             },
         });
+        // Run tsserver with the selected Node.js version rather than the one Zed runs vtsls with.
+        if let Some(node_path) = toolchain.as_ref().and_then(node_binary) {
+            config["tsserver"]["nodePath"] = node_path.to_string_lossy().into();
+        }
 
         let mut default_workspace_configuration = serde_json::json!({
             "typescript": config,
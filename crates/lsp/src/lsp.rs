@@ -92,6 +92,9 @@ pub struct LanguageServer {
     process_name: Arc<str>,
     binary: LanguageServerBinary,
     capabilities: RwLock<ServerCapabilities>,
+    /// Whether the server reported `typeHierarchyProvider`, which [`ServerCapabilities`] has no
+    /// field for.
+    supports_type_hierarchy: bool,
     /// Configuration sent to the server, stored for display in the language server logs
     /// buffer. This is represented as the message sent to the LSP in order to avoid cloning it (can
     /// be large in cases like sending schemas to the json server).
@@ -310,6 +313,18 @@ pub struct AdapterServerCapabilities {
     pub server_capabilities: ServerCapabilities,
     // List of code actions supported by the LspAdapter matching the server
     pub code_action_kinds: Option<Vec<CodeActionKind>>,
+    // Whether the server supports type hierarchies, see [`LanguageServer::supports_type_hierarchy`]
+    pub supports_type_hierarchy: bool,
+}
+
+/// The `initialize` request, with its result left as JSON so that capabilities that
+/// [`ServerCapabilities`] has no fields for can be read from it.
+enum RawInitialize {}
+
+impl request::Request for RawInitialize {
+    type Params = InitializeParams;
+    type Result = Value;
+    const METHOD: &'static str = request::Initialize::METHOD;
 }
 
 impl LanguageServer {
@@ -508,6 +523,7 @@ impl LanguageServer {
                 .unwrap_or_default(),
             binary,
             capabilities: Default::default(),
+            supports_type_hierarchy: false,
             configuration,
             code_action_kinds,
             next_id: Default::default(),
@@ -701,6 +717,9 @@ impl LanguageServer {
                     code_lens: Some(CodeLensWorkspaceClientCapabilities {
                         refresh_support: Some(true),
                     }),
+                    semantic_tokens: Some(SemanticTokensWorkspaceClientCapabilities {
+                        refresh_support: Some(true),
+                    }),
                     workspace_edit: Some(WorkspaceEditClientCapabilities {
                         resource_operations: Some(vec![
                             ResourceOperationKind::Create,
@@ -873,6 +892,61 @@ impl LanguageServer {
                     color_provider: Some(DocumentColorClientCapabilities {
                         dynamic_registration: Some(true),
                     }),
+                    call_hierarchy: Some(CallHierarchyClientCapabilities {
+                        dynamic_registration: Some(false),
+                    }),
+                    type_hierarchy: Some(TypeHierarchyClientCapabilities {
+                        dynamic_registration: Some(false),
+                    }),
+                    semantic_tokens: Some(SemanticTokensClientCapabilities {
+                        dynamic_registration: Some(false),
+                        requests: SemanticTokensClientCapabilitiesRequests {
+                            range: Some(false),
+                            full: Some(SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                        },
+                        token_types: vec![
+                            SemanticTokenType::NAMESPACE,
+                            SemanticTokenType::TYPE,
+                            SemanticTokenType::CLASS,
+                            SemanticTokenType::ENUM,
+                            SemanticTokenType::INTERFACE,
+                            SemanticTokenType::STRUCT,
+                            SemanticTokenType::TYPE_PARAMETER,
+                            SemanticTokenType::PARAMETER,
+                            SemanticTokenType::VARIABLE,
+                            SemanticTokenType::PROPERTY,
+                            SemanticTokenType::ENUM_MEMBER,
+                            SemanticTokenType::EVENT,
+                            SemanticTokenType::FUNCTION,
+                            SemanticTokenType::METHOD,
+                            SemanticTokenType::MACRO,
+                            SemanticTokenType::KEYWORD,
+                            SemanticTokenType::MODIFIER,
+                            SemanticTokenType::COMMENT,
+                            SemanticTokenType::STRING,
+                            SemanticTokenType::NUMBER,
+                            SemanticTokenType::REGEXP,
+                            SemanticTokenType::OPERATOR,
+                            SemanticTokenType::DECORATOR,
+                        ],
+                        token_modifiers: vec![
+                            SemanticTokenModifier::DECLARATION,
+                            SemanticTokenModifier::DEFINITION,
+                            SemanticTokenModifier::READONLY,
+                            SemanticTokenModifier::STATIC,
+                            SemanticTokenModifier::DEPRECATED,
+                            SemanticTokenModifier::ABSTRACT,
+                            SemanticTokenModifier::ASYNC,
+                            SemanticTokenModifier::MODIFICATION,
+                            SemanticTokenModifier::DOCUMENTATION,
+                            SemanticTokenModifier::DEFAULT_LIBRARY,
+                        ],
+                        formats: vec![TokenFormat::RELATIVE],
+                        overlapping_token_support: Some(false),
+                        multiline_token_support: Some(false),
+                        server_cancel_support: Some(false),
+                        augments_syntax_tokens: Some(true),
+                    }),
                     ..TextDocumentClientCapabilities::default()
                 }),
                 experimental: Some(json!({
@@ -912,7 +986,7 @@ impl LanguageServer {
     ) -> Task<Result<Arc<Self>>> {
         cx.background_spawn(async move {
             let response = self
-                .request::<request::Initialize>(params)
+                .request::<RawInitialize>(params)
                 .await
                 .into_response()
                 .with_context(|| {
@@ -922,6 +996,11 @@ impl LanguageServer {
                         self.server_id()
                     )
                 })?;
+            self.supports_type_hierarchy = response
+                .pointer("/capabilities/typeHierarchyProvider")
+                .is_some_and(|provider| !provider.is_null() && provider != &Value::Bool(false));
+            let response = serde_json::from_value::<InitializeResult>(response)
+                .context("parsing the initialize result")?;
             if let Some(info) = response.server_info {
                 self.process_name = info.name.into();
             }
@@ -1162,12 +1241,18 @@ impl LanguageServer {
         self.capabilities.read().clone()
     }
 
+    /// Whether the server reported the `typeHierarchyProvider` capability when it was initialized.
+    pub fn supports_type_hierarchy(&self) -> bool {
+        self.supports_type_hierarchy
+    }
+
     /// Get the reported capabilities of the running language server and
     /// what we know on the client/adapter-side of its capabilities.
     pub fn adapter_server_capabilities(&self) -> AdapterServerCapabilities {
         AdapterServerCapabilities {
             server_capabilities: self.capabilities(),
             code_action_kinds: self.code_action_kinds(),
+            supports_type_hierarchy: self.supports_type_hierarchy(),
         }
     }
 
@@ -1759,6 +1844,15 @@ impl FakeLanguageServer {
         }
     }
 
+    /// Replies to the `initialize` request with the given raw capabilities, which can include
+    /// ones that [`ServerCapabilities`] has no fields for, like `typeHierarchyProvider`.
+    pub fn set_raw_capabilities(&self, capabilities: Value) {
+        self.set_request_handler::<RawInitialize, _, _>(move |_, _| {
+            let capabilities = capabilities.clone();
+            async move { Ok(json!({ "capabilities": capabilities })) }
+        });
+    }
+
     /// Registers a handler for a specific kind of request. Removes any existing handler for specified request type.
     pub fn set_request_handler<T, F, Fut>(
         &self,
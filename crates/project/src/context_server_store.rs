@@ -5,8 +5,18 @@ use std::sync::Arc;
 
 use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet};
-use context_server::{ContextServer, ContextServerCommand, ContextServerId};
-use futures::{FutureExt as _, future::join_all};
+use context_server::{
+    ContextServer, ContextServerCommand, ContextServerId,
+    types::{
+        Notification as _, Resource, ResourceTemplate, ResourcesUpdatedParams,
+        notifications::{ResourcesListChanged, ResourcesUpdated},
+        requests::{ListResourceTemplates, ResourcesList},
+    },
+};
+use futures::{
+    FutureExt as _,
+    future::{Shared, join_all},
+};
 use gpui::{App, AsyncApp, Context, Entity, EventEmitter, Subscription, Task, WeakEntity, actions};
 use registry::ContextServerDescriptorRegistry;
 use settings::{Settings as _, SettingsStore};
@@ -171,14 +181,27 @@ pub struct ContextServerStore {
     update_servers_task: Option<Task<Result<()>>>,
     context_server_factory: Option<ContextServerFactory>,
     needs_server_update: bool,
+    resource_lists: HashMap<ContextServerId, Shared<Task<Arc<ContextServerResources>>>>,
     _subscriptions: Vec<Subscription>,
 }
 
+/// The resources and resource templates listed by a running server.
+#[derive(Debug, Default)]
+pub struct ContextServerResources {
+    pub resources: Vec<Resource>,
+    pub templates: Vec<ResourceTemplate>,
+}
+
 pub enum Event {
     ServerStatusChanged {
         server_id: ContextServerId,
         status: ContextServerStatus,
     },
+    /// A server notified that a resource it was subscribed to changed.
+    ResourceUpdated {
+        server_id: ContextServerId,
+        uri: String,
+    },
 }
 
 impl EventEmitter<Event> for ContextServerStore {}
@@ -271,6 +294,7 @@ impl ContextServerStore {
             registry,
             needs_server_update: false,
             servers: HashMap::default(),
+            resource_lists: HashMap::default(),
             update_servers_task: None,
             context_server_factory,
         };
@@ -330,6 +354,53 @@ impl ContextServerStore {
             .collect()
     }
 
+    /// Lists the resources and resource templates of a running server. The listing
+    /// is reused until the server notifies that its resources changed or restarts.
+    pub fn resources_for_server(
+        &mut self,
+        server: &Arc<ContextServer>,
+        cx: &mut Context<Self>,
+    ) -> Shared<Task<Arc<ContextServerResources>>> {
+        let id = server.id();
+        if let Some(task) = self.resource_lists.get(&id) {
+            return task.clone();
+        }
+
+        let server = server.clone();
+        let task = cx
+            .spawn({
+                let id = id.clone();
+                async move |this, cx| {
+                    let Some(client) = server.client() else {
+                        return Arc::default();
+                    };
+                    let resources = match client.request::<ResourcesList>(()).await {
+                        Ok(response) => response.resources,
+                        Err(error) => {
+                            log::error!("failed to list resources of {id}: {error}");
+                            // Don't keep the failed listing around, so the next search retries.
+                            this.update(cx, |this, _| this.resource_lists.remove(&id))
+                                .ok();
+                            Vec::new()
+                        }
+                    };
+                    // Not every server implements templates, so failing to list them is expected.
+                    let templates = client
+                        .request::<ListResourceTemplates>(())
+                        .await
+                        .map(|response| response.resource_templates)
+                        .unwrap_or_default();
+                    Arc::new(ContextServerResources {
+                        resources,
+                        templates,
+                    })
+                }
+            })
+            .shared();
+        self.resource_lists.insert(id, task.clone());
+        task
+    }
+
     pub fn start_server(&mut self, server: Arc<ContextServer>, cx: &mut Context<Self>) {
         cx.spawn(async move |this, cx| {
             let this = this.upgrade().context("Context server store dropped")?;
@@ -417,7 +488,15 @@ impl ContextServerStore {
             let configuration = configuration.clone();
 
             async move |this, cx| {
-                match server.clone().start(cx).await {
+                let notification_handlers = vec![
+                    Self::resource_updated_handler(this.clone(), id.clone()),
+                    Self::resource_list_changed_handler(this.clone(), id.clone()),
+                ];
+                match server
+                    .clone()
+                    .start_with_handlers(notification_handlers, cx)
+                    .await
+                {
                     Ok(_) => {
                         debug_assert!(server.client().is_some());
 
@@ -463,12 +542,57 @@ impl ContextServerStore {
         );
     }
 
+    fn resource_updated_handler(
+        this: WeakEntity<Self>,
+        server_id: ContextServerId,
+    ) -> (
+        &'static str,
+        Box<dyn 'static + Send + FnMut(serde_json::Value, AsyncApp)>,
+    ) {
+        (
+            ResourcesUpdated::METHOD,
+            Box::new(move |params, mut cx| {
+                let Some(params) =
+                    serde_json::from_value::<ResourcesUpdatedParams>(params).log_err()
+                else {
+                    return;
+                };
+                this.update(&mut cx, |_, cx| {
+                    cx.emit(Event::ResourceUpdated {
+                        server_id: server_id.clone(),
+                        uri: params.uri,
+                    })
+                })
+                .ok();
+            }),
+        )
+    }
+
+    fn resource_list_changed_handler(
+        this: WeakEntity<Self>,
+        server_id: ContextServerId,
+    ) -> (
+        &'static str,
+        Box<dyn 'static + Send + FnMut(serde_json::Value, AsyncApp)>,
+    ) {
+        (
+            ResourcesListChanged::METHOD,
+            Box::new(move |_, mut cx| {
+                this.update(&mut cx, |this, _| {
+                    this.resource_lists.remove(&server_id);
+                })
+                .ok();
+            }),
+        )
+    }
+
     fn remove_server(&mut self, id: &ContextServerId, cx: &mut Context<Self>) -> Result<()> {
         let state = self
             .servers
             .remove(id)
             .context("Context server not found")?;
         drop(state);
+        self.resource_lists.remove(id);
         cx.emit(Event::ServerStatusChanged {
             server_id: id.clone(),
             status: ContextServerStatus::Stopped,
@@ -492,7 +616,7 @@ impl ContextServerStore {
                 url,
                 headers.clone(),
                 cx.http_client(),
-                cx.background_executor().clone(),
+                cx,
             )?)),
             _ => {
                 let root_path = self
@@ -543,6 +667,7 @@ impl ContextServerStore {
     ) {
         let status = ContextServerStatus::from_state(&state);
         self.servers.insert(id.clone(), state);
+        self.resource_lists.remove(&id);
         cx.emit(Event::ServerStatusChanged {
             server_id: id,
             status,
@@ -669,7 +794,12 @@ mod tests {
     use gpui::{AppContext, TestAppContext, UpdateGlobal as _};
     use http_client::{FakeHttpClient, Response};
     use serde_json::json;
-    use std::{cell::RefCell, path::PathBuf, rc::Rc};
+    use std::{
+        cell::RefCell,
+        path::PathBuf,
+        rc::Rc,
+        sync::atomic::{AtomicUsize, Ordering::SeqCst},
+    };
     use util::path;
 
     #[gpui::test]
@@ -814,6 +944,75 @@ mod tests {
             .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_context_server_resources_cache(cx: &mut TestAppContext) {
+        const SERVER_1_ID: &str = "mcp-1";
+
+        let (_fs, project) = setup_context_server_test(
+            cx,
+            json!({"code.rs": ""}),
+            vec![(SERVER_1_ID.into(), dummy_server_settings())],
+        )
+        .await;
+
+        let registry = cx.new(|_| ContextServerDescriptorRegistry::new());
+        let store = cx.new(|cx| {
+            ContextServerStore::test(
+                registry.clone(),
+                project.read(cx).worktree_store(),
+                project.downgrade(),
+                cx,
+            )
+        });
+
+        let list_count = Arc::new(AtomicUsize::new(0));
+        let transport = create_fake_transport(SERVER_1_ID, cx.executor())
+            .on_request::<context_server::types::requests::ResourcesList, _>({
+                let list_count = list_count.clone();
+                move |_| {
+                    list_count.fetch_add(1, SeqCst);
+                    async move {
+                        serde_json::from_value(json!({
+                            "resources": [{ "uri": "file:///notes.md", "name": "notes" }]
+                        }))
+                        .unwrap()
+                    }
+                }
+            })
+            .on_request::<context_server::types::requests::ListResourceTemplates, _>(|_| async {
+                serde_json::from_value(json!({ "resourceTemplates": [] })).unwrap()
+            });
+        let server_1_id = ContextServerId(SERVER_1_ID.into());
+        let server_1 = Arc::new(ContextServer::new(server_1_id.clone(), Arc::new(transport)));
+
+        store.update(cx, |store, cx| store.start_server(server_1.clone(), cx));
+        cx.run_until_parked();
+
+        let first = store
+            .update(cx, |store, cx| store.resources_for_server(&server_1, cx))
+            .await;
+        let second = store
+            .update(cx, |store, cx| store.resources_for_server(&server_1, cx))
+            .await;
+        assert_eq!(first.resources.len(), 1);
+        assert_eq!(first.resources[0].name, "notes");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(list_count.load(SeqCst), 1);
+
+        // Restarting the server lists its resources again.
+        store
+            .update(cx, |store, cx| store.stop_server(&server_1_id, cx))
+            .unwrap();
+        store.update(cx, |store, cx| store.start_server(server_1.clone(), cx));
+        cx.run_until_parked();
+
+        let third = store
+            .update(cx, |store, cx| store.resources_for_server(&server_1, cx))
+            .await;
+        assert_eq!(third.resources.len(), 1);
+        assert_eq!(list_count.load(SeqCst), 2);
+    }
+
     #[gpui::test(iterations = 25)]
     async fn test_context_server_concurrent_starts(cx: &mut TestAppContext) {
         const SERVER_1_ID: &str = "mcp-1";
@@ -1370,6 +1569,7 @@ mod tests {
                         ix += 1;
                         *received_event_count.borrow_mut() += 1;
                     }
+                    Event::ResourceUpdated { .. } => {}
                 }
             });
             ServerEvents {
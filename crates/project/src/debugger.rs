@@ -20,4 +20,4 @@ pub mod session;
 
 #[cfg(any(feature = "test-support", test))]
 pub mod test;
-pub use memory::MemoryCell;
+pub use memory::{MemoryCell, parse_memory_address};
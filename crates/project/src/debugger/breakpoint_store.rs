@@ -14,7 +14,7 @@ use rpc::{
     proto::{self},
 };
 use std::{hash::Hash, ops::Range, path::Path, sync::Arc, u32};
-use text::{Point, PointUtf16};
+use text::{Bias, Point, PointUtf16, Unclipped};
 use util::maybe;
 
 use crate::{Project, ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
@@ -156,6 +156,7 @@ pub struct ActiveStackFrame {
 
 pub struct BreakpointStore {
     breakpoints: BTreeMap<Arc<Path>, BreakpointsInFile>,
+    function_breakpoints: Vec<FunctionBreakpoint>,
     downstream_client: Option<(AnyProtoClient, u64)>,
     active_stack_frame: Option<ActiveStackFrame>,
     // E.g ssh
@@ -170,6 +171,7 @@ impl BreakpointStore {
     pub fn local(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            function_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Local(LocalBreakpointStore {
                 worktree_store,
                 buffer_store,
@@ -182,6 +184,7 @@ impl BreakpointStore {
     pub(crate) fn remote(upstream_project_id: u64, upstream_client: AnyProtoClient) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            function_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Remote(RemoteBreakpointStore {
                 upstream_client,
                 _upstream_project_id: upstream_project_id,
@@ -601,6 +604,81 @@ impl BreakpointStore {
         let breakpoint_paths = self.breakpoints.keys().cloned().collect();
         self.breakpoints.clear();
         cx.emit(BreakpointStoreEvent::BreakpointsCleared(breakpoint_paths));
+        if !self.function_breakpoints.is_empty() {
+            self.function_breakpoints.clear();
+            cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        }
+    }
+
+    pub fn function_breakpoints(&self) -> &[FunctionBreakpoint] {
+        &self.function_breakpoints
+    }
+
+    /// Adds an enabled breakpoint on the function with the given name, unless there's one already.
+    pub fn add_function_breakpoint(&mut self, name: &str, cx: &mut Context<Self>) {
+        let name = name.trim();
+        if name.is_empty()
+            || self
+                .function_breakpoints
+                .iter()
+                .any(|breakpoint| breakpoint.name.as_ref() == name)
+        {
+            return;
+        }
+
+        self.function_breakpoints
+            .push(FunctionBreakpoint::new(name.into()));
+        cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        cx.notify();
+    }
+
+    pub fn edit_function_breakpoint(
+        &mut self,
+        name: Arc<str>,
+        edit_action: BreakpointEditAction,
+        cx: &mut Context<Self>,
+    ) {
+        let existing = self
+            .function_breakpoints
+            .iter()
+            .position(|breakpoint| breakpoint.name == name);
+
+        match (edit_action, existing) {
+            (BreakpointEditAction::Toggle, Some(ix)) => {
+                self.function_breakpoints.remove(ix);
+            }
+            (BreakpointEditAction::Toggle, None) => {
+                self.function_breakpoints
+                    .push(FunctionBreakpoint::new(name));
+            }
+            (BreakpointEditAction::InvertState, Some(ix)) => {
+                let breakpoint = &mut self.function_breakpoints[ix];
+                breakpoint.state = if breakpoint.state.is_enabled() {
+                    BreakpointState::Disabled
+                } else {
+                    BreakpointState::Enabled
+                };
+            }
+            (BreakpointEditAction::EditCondition(condition), Some(ix)) => {
+                self.function_breakpoints[ix].condition =
+                    (!condition.is_empty()).then_some(condition);
+            }
+            (BreakpointEditAction::EditHitCondition(hit_condition), Some(ix)) => {
+                self.function_breakpoints[ix].hit_condition =
+                    (!hit_condition.is_empty()).then_some(hit_condition);
+            }
+            // Function breakpoints can't log messages, and the other edits need an existing breakpoint.
+            (BreakpointEditAction::EditLogMessage(_), _)
+            | (
+                BreakpointEditAction::InvertState
+                | BreakpointEditAction::EditCondition(_)
+                | BreakpointEditAction::EditHitCondition(_),
+                None,
+            ) => return,
+        }
+
+        cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        cx.notify();
     }
 
     pub fn breakpoints<'a>(
@@ -695,6 +773,23 @@ impl BreakpointStore {
         })
     }
 
+    pub fn breakpoint_at_position(
+        &self,
+        path: &Path,
+        position: PointUtf16,
+        cx: &App,
+    ) -> Option<(Entity<Buffer>, BreakpointWithPosition)> {
+        self.breakpoints.get(path).and_then(|breakpoints| {
+            let snapshot = breakpoints.buffer.read(cx).text_snapshot();
+
+            breakpoints
+                .breakpoints
+                .iter()
+                .find(|bp| bp.position().summary::<PointUtf16>(&snapshot) == position)
+                .map(|breakpoint| (breakpoints.buffer.clone(), breakpoint.bp.clone()))
+        })
+    }
+
     pub fn breakpoints_from_path(&self, path: &Arc<Path>) -> Vec<BreakpointWithPosition> {
         self.breakpoints
             .get(path)
@@ -714,10 +809,11 @@ impl BreakpointStore {
                 bp.breakpoints
                     .iter()
                     .map(|bp| {
-                        let position = snapshot.summary_for_anchor::<PointUtf16>(bp.position()).row;
+                        let position = snapshot.summary_for_anchor::<PointUtf16>(bp.position());
                         let bp = &bp.bp;
                         SourceBreakpoint {
-                            row: position,
+                            row: position.row,
+                            column: (position.column > 0).then_some(position.column),
                             path: path.clone(),
                             state: bp.bp.state,
                             message: bp.bp.message.clone(),
@@ -751,12 +847,12 @@ impl BreakpointStore {
                     bp.breakpoints
                         .iter()
                         .map(|breakpoint| {
-                            let position = snapshot
-                                .summary_for_anchor::<PointUtf16>(breakpoint.position())
-                                .row;
+                            let position =
+                                snapshot.summary_for_anchor::<PointUtf16>(breakpoint.position());
                             let breakpoint = &breakpoint.bp;
                             SourceBreakpoint {
-                                row: position,
+                                row: position.row,
+                                column: (position.column > 0).then_some(position.column),
                                 path: path.clone(),
                                 message: breakpoint.bp.message.clone(),
                                 state: breakpoint.bp.state,
@@ -773,6 +869,7 @@ impl BreakpointStore {
     pub fn with_serialized_breakpoints(
         &self,
         breakpoints: BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>,
+        function_breakpoints: Vec<FunctionBreakpoint>,
         cx: &mut Context<BreakpointStore>,
     ) -> Task<Result<()>> {
         if let BreakpointStoreMode::Local(mode) = &self.mode {
@@ -810,12 +907,13 @@ impl BreakpointStore {
 
                     for bp in bps {
                         let max_point = snapshot.max_point_utf16();
-                        let point = PointUtf16::new(bp.row, 0);
+                        let point = PointUtf16::new(bp.row, bp.column.unwrap_or(0));
                         if point > max_point {
                             log::error!("skipping a deserialized breakpoint that's out of range");
                             continue;
                         }
-                        let position = snapshot.anchor_after(point);
+                        let point = snapshot.clip_point_utf16(Unclipped(point), Bias::Left);
+                        let position = snapshot.anchor_before(point);
                         breakpoints_for_file
                             .breakpoints
                             .push(StatefulBreakpoint::new(BreakpointWithPosition {
@@ -843,6 +941,7 @@ impl BreakpointStore {
                     }
 
                     this.breakpoints = new_breakpoints;
+                    this.function_breakpoints = function_breakpoints;
 
                     cx.notify();
                 })?;
@@ -871,6 +970,7 @@ pub enum BreakpointStoreEvent {
     ClearDebugLines,
     BreakpointsUpdated(Arc<Path>, BreakpointUpdatedReason),
     BreakpointsCleared(Vec<Arc<Path>>),
+    FunctionBreakpointsUpdated,
 }
 
 impl EventEmitter<BreakpointStoreEvent> for BreakpointStore {}
@@ -1009,6 +1109,8 @@ impl Breakpoint {
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct SourceBreakpoint {
     pub row: u32,
+    /// The UTF-16 column of an inline breakpoint, or `None` when the breakpoint is set on the whole line.
+    pub column: Option<u32>,
     pub path: Arc<Path>,
     pub message: Option<Arc<str>>,
     pub condition: Option<Arc<str>>,
@@ -1020,7 +1122,7 @@ impl From<SourceBreakpoint> for dap::SourceBreakpoint {
     fn from(bp: SourceBreakpoint) -> Self {
         Self {
             line: bp.row as u64 + 1,
-            column: None,
+            column: bp.column.map(|column| column as u64 + 1),
             condition: bp
                 .condition
                 .map(|condition| String::from(condition.as_ref())),
@@ -1032,3 +1134,37 @@ impl From<SourceBreakpoint> for dap::SourceBreakpoint {
         }
     }
 }
+
+/// Breakpoint that stops when a function with the given name is entered, regardless of where it's defined.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FunctionBreakpoint {
+    pub name: Arc<str>,
+    pub condition: Option<Arc<str>>,
+    pub hit_condition: Option<Arc<str>>,
+    pub state: BreakpointState,
+}
+
+impl FunctionBreakpoint {
+    pub fn new(name: Arc<str>) -> Self {
+        Self {
+            name,
+            condition: None,
+            hit_condition: None,
+            state: BreakpointState::Enabled,
+        }
+    }
+}
+
+impl From<FunctionBreakpoint> for dap::FunctionBreakpoint {
+    fn from(bp: FunctionBreakpoint) -> Self {
+        Self {
+            name: String::from(bp.name.as_ref()),
+            condition: bp
+                .condition
+                .map(|condition| String::from(condition.as_ref())),
+            hit_condition: bp
+                .hit_condition
+                .map(|hit_condition| String::from(hit_condition.as_ref())),
+        }
+    }
+}
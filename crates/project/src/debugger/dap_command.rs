@@ -973,6 +973,38 @@ impl DapCommand for SetVariableValueCommand {
     }
 }
 
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub(crate) struct SetExpressionCommand {
+    pub expression: String,
+    pub value: String,
+    pub frame_id: Option<u64>,
+}
+
+impl LocalDapCommand for SetExpressionCommand {
+    type Response = dap::SetExpressionResponse;
+    type DapRequest = dap::requests::SetExpression;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities.supports_set_expression.unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::SetExpressionArguments {
+            expression: self.expression.clone(),
+            value: self.value.clone(),
+            frame_id: self.frame_id,
+            format: None,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message)
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub(crate) struct RestartStackFrameCommand {
     pub stack_frame_id: u64,
@@ -1669,6 +1701,68 @@ impl LocalDapCommand for SetBreakpoints {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(super) struct SetFunctionBreakpoints {
+    pub(super) breakpoints: Vec<dap::FunctionBreakpoint>,
+}
+
+impl LocalDapCommand for SetFunctionBreakpoints {
+    type Response = Vec<dap::Breakpoint>;
+    type DapRequest = dap::requests::SetFunctionBreakpoints;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities.supports_function_breakpoints.unwrap_or(false)
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::SetFunctionBreakpointsArguments {
+            breakpoints: self.breakpoints.clone(),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.breakpoints)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub(super) struct BreakpointLocations {
+    pub(super) source: dap::Source,
+    /// 1-based line to find the breakpoint locations on.
+    pub(super) line: u64,
+}
+
+impl LocalDapCommand for BreakpointLocations {
+    type Response = Vec<dap::BreakpointLocation>;
+    type DapRequest = dap::requests::BreakpointLocations;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_breakpoint_locations_request
+            .unwrap_or(false)
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::BreakpointLocationsArguments {
+            source: self.source.clone(),
+            line: self.line,
+            column: None,
+            end_line: None,
+            end_column: None,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.breakpoints)
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum DataBreakpointContext {
     Variable {
@@ -1792,6 +1886,35 @@ impl LocalDapCommand for SetDataBreakpointsCommand {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct SetInstructionBreakpointsCommand {
+    pub breakpoints: Vec<dap::InstructionBreakpoint>,
+}
+
+impl LocalDapCommand for SetInstructionBreakpointsCommand {
+    type Response = Vec<dap::Breakpoint>;
+    type DapRequest = dap::requests::SetInstructionBreakpoints;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_instruction_breakpoints
+            .unwrap_or(false)
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::SetInstructionBreakpointsArguments {
+            breakpoints: self.breakpoints.clone(),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.breakpoints)
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub(super) enum SetExceptionBreakpoints {
     Plain {
@@ -1973,3 +2096,38 @@ impl LocalDapCommand for dap::WriteMemoryArguments {
         Ok(message)
     }
 }
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct DisassembleCommand {
+    pub(crate) memory_reference: String,
+    pub(crate) instruction_offset: i64,
+    pub(crate) instruction_count: u64,
+}
+
+impl LocalDapCommand for DisassembleCommand {
+    type Response = Vec<dap::DisassembledInstruction>;
+    type DapRequest = dap::requests::Disassemble;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_disassemble_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::DisassembleArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: None,
+            instruction_offset: Some(self.instruction_offset),
+            instruction_count: self.instruction_count,
+            resolve_symbols: Some(true),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.instructions)
+    }
+}
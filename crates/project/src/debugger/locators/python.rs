@@ -119,6 +119,9 @@ mod test {
             shell: task::Shell::System,
             show_summary: false,
             show_command: false,
+            depends_on: Vec::new(),
+            depends_order: task::DependsOrder::default(),
+            problem_matchers: Vec::new(),
         };
 
         let expected_scenario = DebugScenario {
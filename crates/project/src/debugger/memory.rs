@@ -337,13 +337,37 @@ impl Iterator for MemoryIterator {
     }
 }
 
+/// Parses a memory address like `0x7ff6a1b2`, as debug adapters format them, so that addresses
+/// formatted differently still compare equal.
+pub fn parse_memory_address(address: &str) -> Option<u64> {
+    let address = address.trim();
+    match address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => address.parse().ok(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::debugger::{
         MemoryCell,
-        memory::{MemoryIterator, PageAddress, PageContents},
+        memory::{MemoryIterator, PageAddress, PageContents, parse_memory_address},
     };
 
+    #[test]
+    fn parses_memory_addresses() {
+        assert_eq!(parse_memory_address("0x7ff6a1b2"), Some(0x7ff6a1b2));
+        assert_eq!(parse_memory_address("0X00007FF6A1B2"), Some(0x7ff6a1b2));
+        assert_eq!(parse_memory_address(" 0x10 "), Some(16));
+        assert_eq!(parse_memory_address("4096"), Some(4096));
+        assert_eq!(parse_memory_address("0x"), None);
+        assert_eq!(parse_memory_address("0xfoo"), None);
+        assert_eq!(parse_memory_address("main+4"), None);
+    }
+
     #[test]
     fn iterate_over_unmapped_memory() {
         let empty_iterator = MemoryIterator::new(0..=127, Default::default());
@@ -2,17 +2,21 @@ use super::breakpoint_store::{
     BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetDataBreakpointsCommand, SetExceptionBreakpoints, SetVariableValueCommand,
+    self, Attach, BreakpointLocations, ConfigurationDone, ContinueCommand,
+    DataBreakpointInfoCommand, DisconnectCommand, EvaluateCommand, Initialize, Launch,
+    LoadedSourcesCommand, LocalDapCommand, LocationsCommand, ModulesCommand, NextCommand,
+    PauseCommand, RestartCommand, RestartStackFrameCommand, ScopesCommand,
+    SetDataBreakpointsCommand, SetExceptionBreakpoints, SetExpressionCommand,
+    SetFunctionBreakpoints, SetInstructionBreakpointsCommand, SetVariableValueCommand,
     StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
     TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
 };
 use super::dap_store::DapStore;
 use crate::debugger::breakpoint_store::BreakpointSessionState;
-use crate::debugger::dap_command::{DataBreakpointContext, ReadMemory};
-use crate::debugger::memory::{self, Memory, MemoryIterator, MemoryPageBuilder, PageAddress};
+use crate::debugger::dap_command::{DataBreakpointContext, DisassembleCommand, ReadMemory};
+use crate::debugger::memory::{
+    self, Memory, MemoryIterator, MemoryPageBuilder, PageAddress, parse_memory_address,
+};
 use anyhow::{Context as _, Result, anyhow, bail};
 use base64::Engine;
 use collections::{HashMap, HashSet, IndexMap};
@@ -47,7 +51,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use smol::net::{TcpListener, TcpStream};
 use std::any::TypeId;
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::net::Ipv4Addr;
 use std::ops::RangeInclusive;
 use std::path::PathBuf;
@@ -193,6 +197,15 @@ fn client_source(abs_path: &Path) -> dap::Source {
     }
 }
 
+/// Normalizes instruction references that are memory addresses, so that an instruction doesn't
+/// get a second breakpoint when its address is formatted differently.
+fn instruction_breakpoint_key(instruction_reference: &str) -> String {
+    match parse_memory_address(instruction_reference) {
+        Some(address) => format!("{address:#x}"),
+        None => instruction_reference.trim().to_string(),
+    }
+}
+
 impl RunningMode {
     async fn new(
         session_id: SessionId,
@@ -416,6 +429,27 @@ impl RunningMode {
         })
     }
 
+    fn send_function_breakpoints(
+        &self,
+        ignore_breakpoints: bool,
+        breakpoint_store: &Entity<BreakpointStore>,
+        cx: &App,
+    ) -> Task<Result<Vec<dap::Breakpoint>>> {
+        let breakpoints = if ignore_breakpoints {
+            vec![]
+        } else {
+            breakpoint_store
+                .read(cx)
+                .function_breakpoints()
+                .iter()
+                .filter(|bp| bp.state.is_enabled())
+                .cloned()
+                .map(Into::into)
+                .collect()
+        };
+        self.request(SetFunctionBreakpoints { breakpoints })
+    }
+
     fn initialize_sequence(
         &self,
         capabilities: &Capabilities,
@@ -448,6 +482,9 @@ impl RunningMode {
         let supports_exception_filters = capabilities
             .supports_exception_filter_options
             .unwrap_or_default();
+        let supports_function_breakpoints = SetFunctionBreakpoints::is_supported(capabilities);
+        let supports_instruction_breakpoints =
+            SetInstructionBreakpointsCommand::is_supported(capabilities);
         let this = self.clone();
         let worktree = self.worktree().clone();
         let mut filters = capabilities
@@ -496,6 +533,22 @@ impl RunningMode {
                     }
                 })?;
 
+                if supports_function_breakpoints {
+                    cx.update(|cx| this.send_function_breakpoints(false, &breakpoint_store, cx))?
+                        .await
+                        .log_err();
+                }
+
+                if supports_instruction_breakpoints {
+                    let breakpoints = session
+                        .read_with(cx, |session, _| session.dap_instruction_breakpoints())?;
+                    if !breakpoints.is_empty() {
+                        this.request(SetInstructionBreakpointsCommand { breakpoints })
+                            .await
+                            .log_err();
+                    }
+                }
+
                 if should_send_exception_breakpoints {
                     _ = session.update(cx, |this, _| {
                         filters.retain(|filter| {
@@ -710,6 +763,7 @@ pub struct Session {
     ignore_breakpoints: bool,
     exception_breakpoints: BTreeMap<String, (ExceptionBreakpointsFilter, IsEnabled)>,
     data_breakpoints: BTreeMap<String, DataBreakpointState>,
+    instruction_breakpoints: BTreeSet<String>,
     background_tasks: Vec<Task<()>>,
     restart_task: Option<Task<()>>,
     task_context: TaskContext,
@@ -861,6 +915,15 @@ impl Session {
                         local.unset_breakpoints_from_paths(paths, cx).detach();
                     }
                 }
+                BreakpointStoreEvent::FunctionBreakpointsUpdated => {
+                    if SetFunctionBreakpoints::is_supported(&this.capabilities)
+                        && let Some(local) = this.as_running()
+                    {
+                        local
+                            .send_function_breakpoints(this.ignore_breakpoints, &store, cx)
+                            .detach_and_log_err(cx);
+                    }
+                }
                 BreakpointStoreEvent::SetDebugLine | BreakpointStoreEvent::ClearDebugLines => {}
             })
             .detach();
@@ -884,6 +947,7 @@ impl Session {
                 ignore_breakpoints: false,
                 breakpoint_store,
                 data_breakpoints: Default::default(),
+                instruction_breakpoints: Default::default(),
                 exception_breakpoints: Default::default(),
                 label,
                 adapter,
@@ -1899,7 +1963,12 @@ impl Session {
         })
     }
 
-    pub fn write_memory(&mut self, address: u64, data: &[u8], cx: &mut Context<Self>) {
+    pub fn write_memory(
+        &mut self,
+        address: u64,
+        data: &[u8],
+        cx: &mut Context<Self>,
+    ) -> Task<Option<dap::WriteMemoryResponse>> {
         let data = base64::engine::general_purpose::STANDARD.encode(data);
         self.request(
             WriteMemoryArguments {
@@ -1913,12 +1982,32 @@ impl Session {
                 this.invalidate_command_type::<ReadMemory>();
                 this.invalidate_command_type::<VariablesCommand>();
                 cx.emit(SessionEvent::Variables);
-                response.ok()
+                response.log_err()
             },
             cx,
         )
-        .detach();
     }
+
+    /// Disassembles `instruction_count` instructions, starting `instruction_offset` instructions
+    /// away from the one at `memory_reference`.
+    pub fn disassemble(
+        &mut self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Vec<dap::DisassembledInstruction>>> {
+        self.request(
+            DisassembleCommand {
+                memory_reference,
+                instruction_offset,
+                instruction_count,
+            },
+            |_, response, _| response.log_err(),
+            cx,
+        )
+    }
+
     pub fn read_memory(
         &mut self,
         range: RangeInclusive<u64>,
@@ -2004,6 +2093,11 @@ impl Session {
         self.ignore_breakpoints = ignore;
 
         if let Some(local) = self.as_running() {
+            if SetFunctionBreakpoints::is_supported(&self.capabilities) {
+                local
+                    .send_function_breakpoints(ignore, &self.breakpoint_store, cx)
+                    .detach_and_log_err(cx);
+            }
             local.send_source_breakpoints(ignore, &self.breakpoint_store, cx)
         } else {
             // todo(debugger): We need to propagate this change to downstream sessions and send a message to upstream sessions
@@ -2087,6 +2181,103 @@ impl Session {
         self.send_data_breakpoints(cx);
     }
 
+    /// Returns the UTF-16 columns on the given row where the debug adapter can set an inline
+    /// breakpoint, or an empty list if the adapter can't list them.
+    pub fn breakpoint_locations(
+        &self,
+        abs_path: &Path,
+        row: u32,
+        cx: &App,
+    ) -> Task<Result<Vec<u32>>> {
+        let Some(mode) = self
+            .as_running()
+            .filter(|_| BreakpointLocations::is_supported(&self.capabilities))
+        else {
+            return Task::ready(Ok(Vec::new()));
+        };
+
+        let line = row as u64 + 1;
+        let locations = mode.request(BreakpointLocations {
+            source: client_source(abs_path),
+            line,
+        });
+        cx.background_spawn(async move {
+            let mut columns = locations
+                .await?
+                .into_iter()
+                .filter(|location| location.line == line)
+                .map(|location| {
+                    location
+                        .column
+                        .map_or(0, |column| column.saturating_sub(1) as u32)
+                })
+                .collect::<Vec<_>>();
+            columns.sort_unstable();
+            columns.dedup();
+            Ok(columns)
+        })
+    }
+
+    /// Returns the memory references of the instructions that have a breakpoint.
+    pub fn instruction_breakpoints(&self) -> &BTreeSet<String> {
+        &self.instruction_breakpoints
+    }
+
+    pub fn has_instruction_breakpoint(&self, instruction_reference: &str) -> bool {
+        self.instruction_breakpoints
+            .contains(&instruction_breakpoint_key(instruction_reference))
+    }
+
+    pub fn toggle_instruction_breakpoint(
+        &mut self,
+        instruction_reference: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let key = instruction_breakpoint_key(instruction_reference);
+        if !self.instruction_breakpoints.remove(&key) {
+            self.instruction_breakpoints.insert(key);
+        }
+        self.send_instruction_breakpoints(cx);
+        cx.notify();
+    }
+
+    /// Replaces the instruction breakpoints, like when restarting a session with the breakpoints
+    /// of the previous one. They're sent to the adapter once it's initialized.
+    pub fn set_instruction_breakpoints(
+        &mut self,
+        instruction_references: impl IntoIterator<Item = String>,
+        cx: &mut Context<Self>,
+    ) {
+        self.instruction_breakpoints = instruction_references
+            .into_iter()
+            .map(|reference| instruction_breakpoint_key(&reference))
+            .collect();
+        self.send_instruction_breakpoints(cx);
+        cx.notify();
+    }
+
+    fn send_instruction_breakpoints(&mut self, cx: &mut Context<Self>) {
+        if let Some(mode) = self.as_running() {
+            let command = SetInstructionBreakpointsCommand {
+                breakpoints: self.dap_instruction_breakpoints(),
+            };
+            mode.request(command).detach_and_log_err(cx);
+        }
+    }
+
+    fn dap_instruction_breakpoints(&self) -> Vec<dap::InstructionBreakpoint> {
+        self.instruction_breakpoints
+            .iter()
+            .map(|instruction_reference| dap::InstructionBreakpoint {
+                instruction_reference: instruction_reference.clone(),
+                offset: None,
+                condition: None,
+                hit_condition: None,
+                mode: None,
+            })
+            .collect()
+    }
+
     pub fn breakpoints_enabled(&self) -> bool {
         self.ignore_breakpoints
     }
@@ -2708,6 +2899,35 @@ impl Session {
         }
     }
 
+    /// Assigns `value` to an assignable expression, like a watched expression or a variable's
+    /// `evaluateName`, in the given stack frame.
+    pub fn set_expression(
+        &mut self,
+        stack_frame_id: u64,
+        expression: String,
+        value: String,
+        cx: &mut Context<Self>,
+    ) {
+        self.request(
+            SetExpressionCommand {
+                expression,
+                value,
+                frame_id: Some(stack_frame_id),
+            },
+            move |this, response, cx| {
+                let response = response.log_err()?;
+                this.invalidate_command_type::<VariablesCommand>();
+                this.invalidate_command_type::<ReadMemory>();
+                this.memory.clear(cx.background_executor());
+                this.refresh_watchers(stack_frame_id, cx);
+                cx.emit(SessionEvent::Variables);
+                Some(response)
+            },
+            cx,
+        )
+        .detach();
+    }
+
     pub fn evaluate(
         &mut self,
         expression: String,
@@ -29,9 +29,11 @@ use git::{
     blame::Blame,
     parse_git_remote_url,
     repository::{
-        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
-        GitRepository, GitRepositoryCheckpoint, PushOptions, Remote, RemoteCommandOutput, RepoPath,
-        ResetMode, UpstreamTrackingStatus, Worktree as GitWorktree,
+        BisectMark, BisectState, Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions,
+        DiffType, FetchOptions, GitRepository, GitRepositoryCheckpoint, PushOptions, RebaseAction,
+        RebaseProgress, RebaseTodoEntry, Remote, RemoteCommandOutput, RepoPath, ResetMode,
+        SequencerKind, SequencerOperation, SequencerState, Submodule, SubmoduleOperation, Tag,
+        UpstreamTrackingStatus, Worktree as GitWorktree,
     },
     stash::{GitStash, StashEntry},
     status::{
@@ -265,6 +267,10 @@ pub struct RepositorySnapshot {
     pub remote_origin_url: Option<String>,
     pub remote_upstream_url: Option<String>,
     pub stash_entries: GitStash,
+    pub rebase: Option<RebaseProgress>,
+    pub sequencer: Option<SequencerState>,
+    pub bisect: Option<BisectState>,
+    pub submodules: Arc<[Submodule]>,
 }
 
 type JobId = u64;
@@ -290,6 +296,8 @@ pub struct Repository {
     askpass_delegates: Arc<Mutex<HashMap<u64, AskPassDelegate>>>,
     latest_askpass_id: u64,
     repository_state: Shared<Task<Result<RepositoryState, String>>>,
+    /// Tags containing each of the commits looked up, cleared whenever the repository state is reloaded.
+    tags_containing_cache: HashMap<String, Vec<SharedString>>,
 }
 
 impl std::ops::Deref for Repository {
@@ -366,6 +374,10 @@ pub enum RepositoryEvent {
     MergeHeadsChanged,
     BranchChanged,
     StashEntriesChanged,
+    RebaseProgressChanged,
+    SequencerStateChanged,
+    BisectStateChanged,
+    SubmodulesChanged,
     PendingOpsChanged { pending_ops: SumTree<PendingOps> },
 }
 
@@ -491,6 +503,22 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_show);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_file_history);
+        client.add_entity_request_handler(Self::handle_commits_since);
+        client.add_entity_request_handler(Self::handle_rebase_interactive);
+        client.add_entity_request_handler(Self::handle_rebase_operation);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_sequencer_operation);
+        client.add_entity_request_handler(Self::handle_get_tags);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_checkout_tag);
+        client.add_entity_request_handler(Self::handle_tags_containing);
+        client.add_entity_request_handler(Self::handle_push_tag);
+        client.add_entity_request_handler(Self::handle_bisect_start);
+        client.add_entity_request_handler(Self::handle_bisect_mark);
+        client.add_entity_request_handler(Self::handle_bisect_reset);
+        client.add_entity_request_handler(Self::handle_submodule_operation);
         client.add_entity_request_handler(Self::handle_checkout_files);
         client.add_entity_request_handler(Self::handle_open_commit_message_buffer);
         client.add_entity_request_handler(Self::handle_set_index_text);
@@ -1638,6 +1666,41 @@ impl GitStore {
             .max_by_key(|(repo, _)| repo.read(cx).work_directory_abs_path.clone())
     }
 
+    /// Returns the submodules listed in the `.gitmodules` of every repository, by the project
+    /// path they're checked out at.
+    pub fn submodules_by_project_path(&self, cx: &App) -> HashMap<ProjectPath, Submodule> {
+        let worktree_store = self.worktree_store.read(cx);
+        let mut submodules = HashMap::default();
+        for repo in self.repositories.values() {
+            let repo = repo.read(cx);
+            for submodule in repo.submodules.iter() {
+                let abs_path = repo.repo_path_to_abs_path(&submodule.path);
+                if let Some((worktree, path)) = worktree_store.find_worktree(&abs_path, cx) {
+                    let project_path = ProjectPath {
+                        worktree_id: worktree.read(cx).id(),
+                        path,
+                    };
+                    submodules.insert(project_path, submodule.clone());
+                }
+            }
+        }
+        submodules
+    }
+
+    /// Returns the repository checked out in the submodule, if it's initialized.
+    pub fn submodule_repository(
+        &self,
+        repository: &Entity<Repository>,
+        submodule: &Submodule,
+        cx: &App,
+    ) -> Option<Entity<Repository>> {
+        let abs_path = repository.read(cx).repo_path_to_abs_path(&submodule.path);
+        self.repositories
+            .values()
+            .find(|repo| *repo.read(cx).work_directory_abs_path == *abs_path)
+            .cloned()
+    }
+
     pub fn git_init(
         &self,
         path: Arc<Path>,
@@ -2313,6 +2376,130 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_get_tags(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetTags>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitTagsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())?
+            .await??;
+
+        Ok(proto::GitTagsResponse {
+            tags: tags.iter().map(tag_to_proto).collect(),
+        })
+    }
+
+    async fn handle_create_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let proto::GitCreateTag {
+            name,
+            target,
+            message,
+            ..
+        } = envelope.payload;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_tag(name, target, message)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_tag(envelope.payload.name)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_checkout_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.checkout_tag(envelope.payload.name)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_tags_containing(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitTagsContaining>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitTagsContainingResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.tags_containing(envelope.payload.sha)
+            })?
+            .await??;
+
+        Ok(proto::GitTagsContainingResponse {
+            tags: tags.into_iter().map(|tag| tag.to_string()).collect(),
+        })
+    }
+
+    async fn handle_push_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitPushTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let askpass_id = envelope.payload.askpass_id;
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let tag_name = envelope.payload.tag_name.into();
+        let remote_name = envelope.payload.remote_name.into();
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.push_tag(tag_name, remote_name, askpass, cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
     async fn handle_remove_remote(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GitRemoveRemote>,
@@ -2413,6 +2600,212 @@ impl GitStore {
         })
     }
 
+    async fn handle_commits_since(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCommitsSince>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitCommitsSinceResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let commits = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.commits_since(envelope.payload.base)
+            })?
+            .await??;
+
+        Ok(proto::GitCommitsSinceResponse {
+            commits: commits.iter().map(commit_details_to_proto).collect(),
+        })
+    }
+
+    async fn handle_rebase_interactive(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseInteractive>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let entries = envelope
+            .payload
+            .entries
+            .into_iter()
+            .map(|entry| RebaseTodoEntry {
+                action: match entry.action() {
+                    proto::git_rebase_todo_entry::Action::Pick => RebaseAction::Pick,
+                    proto::git_rebase_todo_entry::Action::Reword => RebaseAction::Reword,
+                    proto::git_rebase_todo_entry::Action::Squash => RebaseAction::Squash,
+                    proto::git_rebase_todo_entry::Action::Fixup => RebaseAction::Fixup,
+                    proto::git_rebase_todo_entry::Action::Drop => RebaseAction::Drop,
+                },
+                sha: entry.sha.into(),
+                message: entry.message.map(SharedString::from),
+            })
+            .collect();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_interactive(envelope.payload.base, entries, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_rebase_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let operation = match envelope.payload.operation() {
+            proto::git_rebase_operation::Operation::Continue => SequencerOperation::Continue,
+            proto::git_rebase_operation::Operation::Skip => SequencerOperation::Skip,
+            proto::git_rebase_operation::Operation::Abort => SequencerOperation::Abort,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_operation(operation, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.cherry_pick(envelope.payload.sha, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.revert(envelope.payload.sha, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_sequencer_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSequencerOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let kind = proto_to_sequencer_kind(envelope.payload.kind());
+        let operation = match envelope.payload.operation() {
+            proto::git_rebase_operation::Operation::Continue => SequencerOperation::Continue,
+            proto::git_rebase_operation::Operation::Skip => SequencerOperation::Skip,
+            proto::git_rebase_operation::Operation::Abort => SequencerOperation::Abort,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.sequencer_operation(kind, operation, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_start(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectStart>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_start(envelope.payload.bad, envelope.payload.good, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_mark(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectMark>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let mark = match envelope.payload.mark() {
+            proto::git_bisect_mark::Mark::Good => BisectMark::Good,
+            proto::git_bisect_mark::Mark::Bad => BisectMark::Bad,
+            proto::git_bisect_mark::Mark::Skip => BisectMark::Skip,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_mark(mark, envelope.payload.sha, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_reset(cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_submodule_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let operation = match envelope.payload.operation() {
+            proto::git_submodule_operation::Operation::Init => SubmoduleOperation::Init,
+            proto::git_submodule_operation::Operation::Update => SubmoduleOperation::Update,
+            proto::git_submodule_operation::Operation::Sync => SubmoduleOperation::Sync,
+        };
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|path| RepoPath::from_proto(path))
+            .collect::<Result<Vec<_>>>()?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.submodule_operation(operation, paths, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
     async fn handle_reset(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GitReset>,
@@ -3263,6 +3656,10 @@ impl RepositorySnapshot {
             remote_origin_url: None,
             remote_upstream_url: None,
             stash_entries: Default::default(),
+            rebase: None,
+            sequencer: None,
+            bisect: None,
+            submodules: Arc::default(),
             path_style,
         }
     }
@@ -3298,6 +3695,10 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            rebase_progress: self.rebase.as_ref().map(rebase_progress_to_proto),
+            sequencer_state: self.sequencer.as_ref().map(sequencer_state_to_proto),
+            bisect_state: self.bisect.as_ref().map(bisect_state_to_proto),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
         }
     }
 
@@ -3369,6 +3770,10 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            rebase_progress: self.rebase.as_ref().map(rebase_progress_to_proto),
+            sequencer_state: self.sequencer.as_ref().map(sequencer_state_to_proto),
+            bisect_state: self.bisect.as_ref().map(bisect_state_to_proto),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
         }
     }
 
@@ -3407,6 +3812,13 @@ impl RepositorySnapshot {
         Some(RepoPath::from_rel_path(&rel_path))
     }
 
+    pub fn submodule(&self, repo_path: &RepoPath) -> Option<&Submodule> {
+        self.submodules
+            .binary_search_by(|submodule| submodule.path.cmp(repo_path))
+            .ok()
+            .map(|ix| &self.submodules[ix])
+    }
+
     pub fn had_conflict_on_last_merge_head_change(&self, repo_path: &RepoPath) -> bool {
         self.merge.conflicted_paths.contains(repo_path)
     }
@@ -3578,6 +3990,7 @@ impl Repository {
             job_sender,
             job_id: 0,
             active_jobs: Default::default(),
+            tags_containing_cache: Default::default(),
         }
     }
 
@@ -3607,6 +4020,7 @@ impl Repository {
             latest_askpass_id: 0,
             active_jobs: Default::default(),
             job_id: 0,
+            tags_containing_cache: Default::default(),
         }
     }
 
@@ -4056,16 +4470,384 @@ impl Repository {
         })
     }
 
-    pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
+    pub fn commits_since(&mut self, base: String) -> oneshot::Receiver<Result<Vec<CommitDetails>>> {
         let id = self.id;
         self.send_job(None, move |git_repo, _cx| async move {
             match git_repo {
                 RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.show(commit).await
+                    backend.commits_since(base).await
                 }
                 RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                    let resp = client
-                        .request(proto::GitShow {
+                    let response = client
+                        .request(proto::GitCommitsSince {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                        })
+                        .await?;
+
+                    Ok(response
+                        .commits
+                        .iter()
+                        .map(proto_to_commit_details)
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn rebase_interactive(
+        &mut self,
+        base: String,
+        entries: Vec<RebaseTodoEntry>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = format!("git rebase --interactive {base}");
+
+        self.send_job(Some(status.into()), move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.rebase_interactive(base, entries, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitRebaseInteractive {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                            entries: entries
+                                .into_iter()
+                                .map(|entry| proto::GitRebaseTodoEntry {
+                                    sha: entry.sha.to_string(),
+                                    action: match entry.action {
+                                        RebaseAction::Pick => {
+                                            proto::git_rebase_todo_entry::Action::Pick
+                                        }
+                                        RebaseAction::Reword => {
+                                            proto::git_rebase_todo_entry::Action::Reword
+                                        }
+                                        RebaseAction::Squash => {
+                                            proto::git_rebase_todo_entry::Action::Squash
+                                        }
+                                        RebaseAction::Fixup => {
+                                            proto::git_rebase_todo_entry::Action::Fixup
+                                        }
+                                        RebaseAction::Drop => {
+                                            proto::git_rebase_todo_entry::Action::Drop
+                                        }
+                                    }
+                                    .into(),
+                                    message: entry.message.map(|message| message.to_string()),
+                                })
+                                .collect(),
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn rebase_operation(
+        &mut self,
+        operation: SequencerOperation,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = format!("git rebase {}", operation.as_flag());
+
+        self.send_job(Some(status.into()), move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.rebase_operation(operation, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitRebaseOperation {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            operation: match operation {
+                                SequencerOperation::Continue => {
+                                    proto::git_rebase_operation::Operation::Continue
+                                }
+                                SequencerOperation::Skip => {
+                                    proto::git_rebase_operation::Operation::Skip
+                                }
+                                SequencerOperation::Abort => {
+                                    proto::git_rebase_operation::Operation::Abort
+                                }
+                            }
+                            .into(),
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn cherry_pick(&mut self, sha: String, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = format!("git cherry-pick {sha}");
+
+        self.send_job(Some(status.into()), move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.cherry_pick(sha, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitCherryPick {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            sha,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn revert(&mut self, sha: String, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = format!("git revert {sha}");
+
+        self.send_job(Some(status.into()), move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.revert(sha, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitRevert {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            sha,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn sequencer_operation(
+        &mut self,
+        kind: SequencerKind,
+        operation: SequencerOperation,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = format!("git {} {}", kind.as_command(), operation.as_flag());
+
+        self.send_job(Some(status.into()), move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => {
+                    backend
+                        .sequencer_operation(kind, operation, environment)
+                        .await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitSequencerOperation {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            kind: sequencer_kind_to_proto(kind).into(),
+                            operation: match operation {
+                                SequencerOperation::Continue => {
+                                    proto::git_rebase_operation::Operation::Continue
+                                }
+                                SequencerOperation::Skip => {
+                                    proto::git_rebase_operation::Operation::Skip
+                                }
+                                SequencerOperation::Abort => {
+                                    proto::git_rebase_operation::Operation::Abort
+                                }
+                            }
+                            .into(),
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn submodule_operation(
+        &mut self,
+        operation: SubmoduleOperation,
+        paths: Vec<RepoPath>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = format!("git submodule {}", operation.as_command());
+
+        self.send_job(Some(status.into()), move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => {
+                    backend
+                        .submodule_operation(operation, paths, environment)
+                        .await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitSubmoduleOperation {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            operation: match operation {
+                                SubmoduleOperation::Init => {
+                                    proto::git_submodule_operation::Operation::Init
+                                }
+                                SubmoduleOperation::Update => {
+                                    proto::git_submodule_operation::Operation::Update
+                                }
+                                SubmoduleOperation::Sync => {
+                                    proto::git_submodule_operation::Operation::Sync
+                                }
+                            }
+                            .into(),
+                            paths: paths.iter().map(|path| path.to_proto()).collect(),
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn bisect_start(
+        &mut self,
+        bad: String,
+        good: Option<String>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = match &good {
+            Some(good) => format!("git bisect start {bad} {good}"),
+            None => format!("git bisect start {bad}"),
+        };
+
+        self.send_job(Some(status.into()), move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.bisect_start(bad, good, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitBisectStart {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            bad,
+                            good,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn bisect_mark(
+        &mut self,
+        mark: BisectMark,
+        sha: Option<String>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = format!("git bisect {}", mark.as_command());
+
+        self.send_job(Some(status.into()), move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.bisect_mark(mark, sha, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitBisectMark {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            mark: match mark {
+                                BisectMark::Good => proto::git_bisect_mark::Mark::Good,
+                                BisectMark::Bad => proto::git_bisect_mark::Mark::Bad,
+                                BisectMark::Skip => proto::git_bisect_mark::Mark::Skip,
+                            }
+                            .into(),
+                            sha,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn bisect_reset(&mut self, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some("git bisect reset".into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_reset(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectReset {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.show(commit).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let resp = client
+                        .request(proto::GitShow {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
                             commit,
@@ -4763,6 +5545,63 @@ impl Repository {
         )
     }
 
+    pub fn push_tag(
+        &mut self,
+        tag: SharedString,
+        remote: SharedString,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git push {remote} refs/tags/{tag}").into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .push_tag(
+                                tag.to_string(),
+                                remote.to_string(),
+                                askpass,
+                                environment,
+                                cx,
+                            )
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::GitPushTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                askpass_id,
+                                tag_name: tag.to_string(),
+                                remote_name: remote.to_string(),
+                            })
+                            .await
+                            .context("sending push tag request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
     pub fn pull(
         &mut self,
         branch: Option<SharedString>,
@@ -5284,6 +6123,142 @@ impl Repository {
         )
     }
 
+    pub fn tags(&mut self) -> oneshot::Receiver<Result<Vec<Tag>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.tags().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetTags {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.tags.iter().map(proto_to_tag).collect())
+                }
+            }
+        })
+    }
+
+    pub fn create_tag(
+        &mut self,
+        name: String,
+        target: Option<String>,
+        message: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let status = match &target {
+            Some(target) => format!("git tag {name} {target}"),
+            None => format!("git tag {name}"),
+        };
+        self.send_job(Some(status.into()), move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.create_tag(name, target, message, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitCreateTag {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            name,
+                            target,
+                            message,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn delete_tag(&mut self, name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag -d {name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(state) => state.backend.delete_tag(name).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitDeleteTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn checkout_tag(&mut self, name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git switch --detach {name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(state) => state.backend.checkout_tag(name).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCheckoutTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn tags_containing(&mut self, sha: String) -> oneshot::Receiver<Result<Vec<SharedString>>> {
+        if let Some(tags) = self.tags_containing_cache.get(&sha) {
+            let (tx, rx) = oneshot::channel();
+            tx.send(Ok(tags.clone())).ok();
+            return rx;
+        }
+
+        let id = self.id;
+        let this = self.this.clone();
+        self.send_job(None, move |repo, mut cx| async move {
+            let tags = match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.tags_containing(sha.clone()).await?
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitTagsContaining {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            sha: sha.clone(),
+                        })
+                        .await?;
+
+                    response.tags.into_iter().map(SharedString::from).collect()
+                }
+            };
+            this.update(&mut cx, |this, _| {
+                this.tags_containing_cache.insert(sha, tags.clone());
+            })
+            .ok();
+            Ok(tags)
+        })
+    }
+
     pub fn rename_branch(
         &mut self,
         branch: String,
@@ -5397,6 +6372,36 @@ impl Repository {
             cx.emit(RepositoryEvent::StashEntriesChanged)
         }
         self.snapshot.stash_entries = new_stash_entries;
+        let new_rebase = update
+            .rebase_progress
+            .as_ref()
+            .map(proto_to_rebase_progress);
+        if self.snapshot.rebase != new_rebase {
+            cx.emit(RepositoryEvent::RebaseProgressChanged)
+        }
+        self.snapshot.rebase = new_rebase;
+        let new_sequencer = update
+            .sequencer_state
+            .as_ref()
+            .map(proto_to_sequencer_state);
+        if self.snapshot.sequencer != new_sequencer {
+            cx.emit(RepositoryEvent::SequencerStateChanged)
+        }
+        self.snapshot.sequencer = new_sequencer;
+        let new_bisect = update.bisect_state.as_ref().map(proto_to_bisect_state);
+        if self.snapshot.bisect != new_bisect {
+            cx.emit(RepositoryEvent::BisectStateChanged)
+        }
+        self.snapshot.bisect = new_bisect;
+        let new_submodules = update
+            .submodules
+            .iter()
+            .filter_map(|submodule| proto_to_submodule(submodule).log_err())
+            .collect::<Arc<[_]>>();
+        if self.snapshot.submodules != new_submodules {
+            cx.emit(RepositoryEvent::SubmodulesChanged)
+        }
+        self.snapshot.submodules = new_submodules;
         self.snapshot.remote_upstream_url = update.remote_upstream_url;
         self.snapshot.remote_origin_url = update.remote_origin_url;
 
@@ -5424,6 +6429,7 @@ impl Repository {
         if update.is_last_update {
             self.snapshot.scan_id = update.scan_id;
         }
+        self.tags_containing_cache.clear();
         self.clear_pending_ops(cx);
         Ok(())
     }
@@ -5517,6 +6523,7 @@ impl Repository {
                     .await?;
                 this.update(&mut cx, |this, cx| {
                     this.snapshot = snapshot.clone();
+                    this.tags_containing_cache.clear();
                     this.clear_pending_ops(cx);
                     for event in events {
                         cx.emit(event);
@@ -6096,6 +7103,120 @@ fn proto_to_commit_details(proto: &proto::GitCommitDetails) -> CommitDetails {
     }
 }
 
+fn rebase_progress_to_proto(progress: &RebaseProgress) -> proto::GitRebaseProgress {
+    proto::GitRebaseProgress {
+        step: progress.step as u64,
+        total: progress.total as u64,
+        stopped_sha: progress.stopped_sha.as_ref().map(|sha| sha.to_string()),
+    }
+}
+
+fn proto_to_rebase_progress(proto: &proto::GitRebaseProgress) -> RebaseProgress {
+    RebaseProgress {
+        step: proto.step as usize,
+        total: proto.total as usize,
+        stopped_sha: proto.stopped_sha.clone().map(SharedString::from),
+    }
+}
+
+fn sequencer_kind_to_proto(kind: SequencerKind) -> proto::GitSequencerKind {
+    match kind {
+        SequencerKind::CherryPick => proto::GitSequencerKind::CherryPick,
+        SequencerKind::Revert => proto::GitSequencerKind::Revert,
+    }
+}
+
+fn proto_to_sequencer_kind(kind: proto::GitSequencerKind) -> SequencerKind {
+    match kind {
+        proto::GitSequencerKind::CherryPick => SequencerKind::CherryPick,
+        proto::GitSequencerKind::Revert => SequencerKind::Revert,
+    }
+}
+
+fn sequencer_state_to_proto(state: &SequencerState) -> proto::GitSequencerState {
+    proto::GitSequencerState {
+        kind: sequencer_kind_to_proto(state.kind).into(),
+        sha: state.sha.to_string(),
+    }
+}
+
+fn proto_to_sequencer_state(proto: &proto::GitSequencerState) -> SequencerState {
+    SequencerState {
+        kind: proto_to_sequencer_kind(proto.kind()),
+        sha: proto.sha.clone().into(),
+    }
+}
+
+fn bisect_state_to_proto(state: &BisectState) -> proto::GitBisectState {
+    proto::GitBisectState {
+        bad: state.bad.as_ref().map(|sha| sha.to_string()),
+        good: state.good.iter().map(|sha| sha.to_string()).collect(),
+        skipped: state.skipped.iter().map(|sha| sha.to_string()).collect(),
+        remaining_revisions: state.remaining_revisions,
+        remaining_steps: state.remaining_steps,
+        first_bad: state.first_bad.as_ref().map(|sha| sha.to_string()),
+    }
+}
+
+fn submodule_to_proto(submodule: &Submodule) -> proto::GitSubmodule {
+    proto::GitSubmodule {
+        path: submodule.path.to_proto(),
+        url: submodule.url.as_ref().map(|url| url.to_string()),
+        head_sha: submodule.head_sha.as_ref().map(|sha| sha.to_string()),
+        recorded_sha: submodule.recorded_sha.as_ref().map(|sha| sha.to_string()),
+        checked_out_sha: submodule
+            .checked_out_sha
+            .as_ref()
+            .map(|sha| sha.to_string()),
+        dirty: submodule.dirty,
+    }
+}
+
+fn proto_to_submodule(proto: &proto::GitSubmodule) -> Result<Submodule> {
+    Ok(Submodule {
+        path: RepoPath::from_proto(&proto.path)?,
+        url: proto.url.clone().map(SharedString::from),
+        head_sha: proto.head_sha.clone().map(SharedString::from),
+        recorded_sha: proto.recorded_sha.clone().map(SharedString::from),
+        checked_out_sha: proto.checked_out_sha.clone().map(SharedString::from),
+        dirty: proto.dirty,
+    })
+}
+
+fn proto_to_bisect_state(proto: &proto::GitBisectState) -> BisectState {
+    BisectState {
+        bad: proto.bad.clone().map(SharedString::from),
+        good: proto.good.iter().cloned().map(SharedString::from).collect(),
+        skipped: proto
+            .skipped
+            .iter()
+            .cloned()
+            .map(SharedString::from)
+            .collect(),
+        remaining_revisions: proto.remaining_revisions,
+        remaining_steps: proto.remaining_steps,
+        first_bad: proto.first_bad.clone().map(SharedString::from),
+    }
+}
+
+fn tag_to_proto(tag: &Tag) -> proto::GitTag {
+    proto::GitTag {
+        name: tag.name.to_string(),
+        sha: tag.sha.to_string(),
+        message: tag.message.as_ref().map(|message| message.to_string()),
+        timestamp: tag.timestamp,
+    }
+}
+
+fn proto_to_tag(proto: &proto::GitTag) -> Tag {
+    Tag {
+        name: proto.name.clone().into(),
+        sha: proto.sha.clone().into(),
+        message: proto.message.clone().map(SharedString::from),
+        timestamp: proto.timestamp,
+    }
+}
+
 async fn compute_snapshot(
     id: RepositoryId,
     work_directory_abs_path: Arc<Path>,
@@ -6143,6 +7264,41 @@ async fn compute_snapshot(
         events.push(RepositoryEvent::BranchChanged);
     }
 
+    let rebase = backend.rebase_progress().await;
+    if rebase != prev_snapshot.rebase {
+        events.push(RepositoryEvent::RebaseProgressChanged);
+    }
+
+    let sequencer = backend.sequencer_state().await;
+    if sequencer != prev_snapshot.sequencer {
+        events.push(RepositoryEvent::SequencerStateChanged);
+    }
+
+    let bisect = backend.bisect_state().await;
+    if bisect != prev_snapshot.bisect {
+        events.push(RepositoryEvent::BisectStateChanged);
+    }
+
+    // A submodule whose status changed may have a different commit checked out, or changes in its worktree.
+    let cached_submodules = prev_snapshot
+        .submodules
+        .iter()
+        .filter(|submodule| {
+            let key = PathKey(submodule.path.as_ref().clone());
+            statuses_by_path.get(&key, ()) == prev_snapshot.statuses_by_path.get(&key, ())
+        })
+        .cloned()
+        .collect();
+    let submodules: Arc<[Submodule]> = backend
+        .submodules(cached_submodules)
+        .await
+        .log_err()
+        .unwrap_or_default()
+        .into();
+    if submodules != prev_snapshot.submodules {
+        events.push(RepositoryEvent::SubmodulesChanged);
+    }
+
     let remote_origin_url = backend.remote_url("origin").await;
     let remote_upstream_url = backend.remote_url("upstream").await;
 
@@ -6158,6 +7314,10 @@ async fn compute_snapshot(
         remote_origin_url,
         remote_upstream_url,
         stash_entries,
+        rebase,
+        sequencer,
+        bisect,
+        submodules,
     };
 
     Ok((snapshot, events))
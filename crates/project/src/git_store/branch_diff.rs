@@ -1,24 +1,28 @@
-use anyhow::Result;
-use buffer_diff::BufferDiff;
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use buffer_diff::{BufferDiff, BufferDiffSnapshot};
 use collections::HashSet;
 use futures::StreamExt;
 use git::{
-    repository::RepoPath,
+    SHORT_SHA_LENGTH,
+    repository::{RepoPath, Submodule},
     status::{DiffTreeType, FileStatus, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus},
 };
 use gpui::{
-    App, AsyncWindowContext, Context, Entity, EventEmitter, SharedString, Subscription, Task,
-    WeakEntity, Window,
+    App, AppContext as _, AsyncWindowContext, Context, Entity, EventEmitter, SharedString,
+    Subscription, Task, WeakEntity, Window,
 };
 
-use language::Buffer;
-use text::BufferId;
-use util::ResultExt;
+use language::{Buffer, Capability, DiskState};
+use text::{BufferId, ReplicaId};
+use util::{ResultExt, paths::PathStyle, rel_path::RelPath};
+use worktree::WorktreeId;
 use ztracing::instrument;
 
 use crate::{
-    Project,
-    git_store::{GitStoreEvent, Repository, RepositoryEvent},
+    Project, ProjectPath,
+    git_store::{GitStore, GitStoreEvent, Repository, RepositoryEvent},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -262,7 +266,8 @@ impl BranchDiff {
             return output;
         };
 
-        self.project.update(cx, |_project, cx| {
+        self.project.update(cx, |project, cx| {
+            let git_store = project.git_store().clone();
             let mut seen = HashSet::default();
 
             for item in repo.read(cx).cached_status() {
@@ -285,7 +290,18 @@ impl BranchDiff {
                 else {
                     continue;
                 };
-                let task = Self::load_buffer(branch_diff, project_path, repo.clone(), cx);
+                let task = if let Some(submodule) = repo.read(cx).submodule(&item.repo_path) {
+                    Self::load_submodule_buffer(
+                        submodule.clone(),
+                        branch_diff,
+                        project_path,
+                        &repo,
+                        &git_store,
+                        cx,
+                    )
+                } else {
+                    Self::load_buffer(branch_diff, project_path, repo.clone(), cx)
+                };
 
                 output.push(DiffBuffer {
                     repo_path: item.repo_path.clone(),
@@ -305,8 +321,18 @@ impl BranchDiff {
                 let Some(project_path) = repo.read(cx).repo_path_to_project_path(&path, cx) else {
                     continue;
                 };
-                let task =
-                    Self::load_buffer(Some(branch_diff.clone()), project_path, repo.clone(), cx);
+                let task = if let Some(submodule) = repo.read(cx).submodule(path) {
+                    Self::load_submodule_buffer(
+                        submodule.clone(),
+                        Some(branch_diff.clone()),
+                        project_path,
+                        &repo,
+                        &git_store,
+                        cx,
+                    )
+                } else {
+                    Self::load_buffer(Some(branch_diff.clone()), project_path, repo.clone(), cx)
+                };
 
                 let file_status = diff_status_to_file_status(branch_diff);
 
@@ -358,6 +384,151 @@ impl BranchDiff {
         });
         task
     }
+
+    /// Builds a read-only buffer describing a submodule's pointer change, diffed against
+    /// the commit it pointed to before and listing the commits it moved forward by.
+    fn load_submodule_buffer(
+        submodule: Submodule,
+        branch_diff: Option<TreeDiffStatus>,
+        project_path: ProjectPath,
+        repo: &Entity<Repository>,
+        git_store: &Entity<GitStore>,
+        cx: &Context<Project>,
+    ) -> Task<Result<(Entity<Buffer>, Entity<BufferDiff>)>> {
+        let old_sha = match branch_diff {
+            Some(TreeDiffStatus::Added) => None,
+            Some(TreeDiffStatus::Modified { old } | TreeDiffStatus::Deleted { old }) => {
+                Some(SharedString::from(old.to_string()))
+            }
+            None => submodule.head_sha.clone(),
+        };
+        let new_sha = submodule
+            .checked_out_sha
+            .clone()
+            .or_else(|| submodule.recorded_sha.clone());
+        let commits = old_sha.clone().and_then(|old_sha| {
+            let submodule_repo = git_store
+                .read(cx)
+                .submodule_repository(repo, &submodule, cx)?;
+            Some(submodule_repo.update(cx, |submodule_repo, _| {
+                submodule_repo.commits_since(old_sha.to_string())
+            }))
+        });
+        let file = Arc::new(SubmoduleFile {
+            worktree_id: project_path.worktree_id,
+            path: project_path.path,
+        });
+
+        cx.spawn(async move |_, cx| {
+            let old_text = old_sha.map(|sha| format!("Subproject commit {sha}\n"));
+            let mut new_text = String::new();
+            if let Some(new_sha) = new_sha {
+                let dirty = if submodule.dirty { "-dirty" } else { "" };
+                new_text.push_str(&format!("Subproject commit {new_sha}{dirty}\n"));
+            }
+            // Commits the submodule moved back by are not listed, being no ancestors of its HEAD.
+            // Neither are any when the old commit isn't available locally, leaving just the pointers.
+            let commits = match commits {
+                Some(commits) => commits
+                    .await?
+                    .context("listing submodule commits")
+                    .log_err()
+                    .unwrap_or_default(),
+                None => Vec::new(),
+            };
+            for commit in commits {
+                let subject = commit.message.lines().next().unwrap_or_default();
+                let short_sha = &commit.sha[..SHORT_SHA_LENGTH.min(commit.sha.len())];
+                new_text.push_str(&format!("{short_sha} {subject}\n"));
+            }
+
+            let buffer = cx.new(|cx| {
+                let text_buffer = text::Buffer::new(
+                    ReplicaId::LOCAL,
+                    cx.entity_id().as_non_zero_u64().into(),
+                    new_text,
+                );
+                Buffer::build(text_buffer, Some(file), Capability::ReadOnly)
+            })?;
+            let buffer_snapshot = buffer.read_with(cx, |buffer, _| buffer.text_snapshot())?;
+            let base_buffer = cx
+                .update(|cx| {
+                    Buffer::build_snapshot(
+                        old_text.as_deref().unwrap_or_default().into(),
+                        None,
+                        None,
+                        cx,
+                    )
+                })?
+                .await;
+            let diff_snapshot = cx
+                .update(|cx| {
+                    BufferDiffSnapshot::new_with_base_buffer(
+                        buffer_snapshot.clone(),
+                        old_text.map(Arc::new),
+                        base_buffer,
+                        cx,
+                    )
+                })?
+                .await;
+            let diff = cx.new(|cx| {
+                let mut diff = BufferDiff::new(&buffer_snapshot, cx);
+                diff.set_snapshot(diff_snapshot, &buffer_snapshot, cx);
+                diff
+            })?;
+            Ok((buffer, diff))
+        })
+    }
+}
+
+/// The file of a submodule's pointer change buffer, which only exists in the diff.
+struct SubmoduleFile {
+    worktree_id: WorktreeId,
+    path: Arc<RelPath>,
+}
+
+impl language::File for SubmoduleFile {
+    fn as_local(&self) -> Option<&dyn language::LocalFile> {
+        None
+    }
+
+    fn disk_state(&self) -> DiskState {
+        DiskState::New
+    }
+
+    fn path_style(&self, _: &App) -> PathStyle {
+        PathStyle::Posix
+    }
+
+    fn path(&self) -> &Arc<RelPath> {
+        &self.path
+    }
+
+    fn full_path(&self, _: &App) -> PathBuf {
+        self.path.as_std_path().to_path_buf()
+    }
+
+    fn file_name<'a>(&'a self, _: &'a App) -> &'a str {
+        self.path.file_name().unwrap_or_default()
+    }
+
+    fn worktree_id(&self, _: &App) -> WorktreeId {
+        self.worktree_id
+    }
+
+    fn to_proto(&self, _cx: &App) -> language::proto::File {
+        language::proto::File {
+            worktree_id: self.worktree_id.to_proto(),
+            entry_id: None,
+            path: self.path.as_ref().to_proto(),
+            mtime: None,
+            is_deleted: false,
+        }
+    }
+
+    fn is_private(&self) -> bool {
+        false
+    }
 }
 
 fn diff_status_to_file_status(branch_diff: &git::status::TreeDiffStatus) -> FileStatus {
@@ -17,8 +17,12 @@ pub mod rust_analyzer_ext;
 pub mod vue_language_server_ext;
 
 mod inlay_hint_cache;
+mod semantic_tokens;
 
-use self::inlay_hint_cache::BufferInlayHints;
+use self::{
+    inlay_hint_cache::BufferInlayHints,
+    semantic_tokens::{RawSemanticTokens, SemanticTokensData},
+};
 use crate::{
     CodeAction, ColorPresentation, Completion, CompletionDisplayOptions, CompletionResponse,
     CompletionSource, CoreCompletion, DocumentColor, Hover, InlayHint, InlayId, LocationLink,
@@ -119,6 +123,7 @@ use std::{
     vec,
 };
 use sum_tree::Dimensions;
+use task::{ProblemSeverity, TaskProblem};
 use text::{Anchor, BufferId, LineEnding, OffsetRangeExt, ToPoint as _};
 
 use util::{
@@ -131,6 +136,9 @@ use util::{
 pub use fs::*;
 pub use language::Location;
 pub use lsp_store::inlay_hint_cache::{CacheInlayHints, InvalidationStrategy};
+pub use lsp_store::semantic_tokens::{
+    BufferSemanticTokens, SemanticToken, SemanticTokensTask, ServerSemanticTokens,
+};
 #[cfg(any(test, feature = "test-support"))]
 pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use worktree::{
@@ -293,6 +301,11 @@ pub struct LocalLspStore {
         LanguageServerId,
         HashMap<Option<SharedString>, HashMap<PathBuf, Option<SharedString>>>,
     >,
+    buffer_semantic_tokens: HashMap<BufferId, HashMap<LanguageServerId, RawSemanticTokens>>,
+    /// Diagnostics reported by the task problem matchers are stored under an ID of a language server
+    /// that does not exist, one per task label, along with the paths the task reported problems for.
+    /// The label stays the same between the runs of a task, unlike its ID.
+    task_diagnostics: HashMap<String, (LanguageServerId, HashSet<PathBuf>)>,
 }
 
 impl LocalLspStore {
@@ -579,9 +592,11 @@ impl LocalLspStore {
             && let Some(path) = settings.path.as_ref().map(PathBuf::from)
         {
             let settings = settings.clone();
+            let languages = self.languages.clone();
 
             return cx.background_spawn(async move {
                 let mut env = delegate.shell_env().await;
+                activate_toolchain_environment(&languages, toolchain.as_ref(), &mut env).await;
                 env.extend(settings.env.unwrap_or_default());
 
                 Ok(LanguageServerBinary {
@@ -610,10 +625,16 @@ impl LocalLspStore {
                 .unwrap_or(false),
         };
 
+        let languages = self.languages.clone();
         cx.spawn(async move |cx| {
             let (existing_binary, maybe_download_binary) = adapter
                 .clone()
-                .get_language_server_command(delegate.clone(), toolchain, lsp_binary_options, cx)
+                .get_language_server_command(
+                    delegate.clone(),
+                    toolchain.clone(),
+                    lsp_binary_options,
+                    cx,
+                )
                 .await
                 .await;
 
@@ -644,6 +665,7 @@ impl LocalLspStore {
             let mut shell_env = delegate.shell_env().await;
 
             shell_env.extend(binary.env.unwrap_or_default());
+            activate_toolchain_environment(&languages, toolchain.as_ref(), &mut shell_env).await;
 
             if let Some(settings) = settings.binary.as_ref() {
                 if let Some(arguments) = &settings.arguments {
@@ -959,6 +981,29 @@ impl LocalLspStore {
             })
             .detach();
 
+        language_server
+            .on_request::<lsp::request::SemanticTokensRefresh, _, _>({
+                let this = lsp_store.clone();
+                move |(), cx| {
+                    let this = this.clone();
+                    let mut cx = cx.clone();
+                    async move {
+                        this.update(&mut cx, |this, cx| {
+                            this.invalidate_semantic_tokens();
+                            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+                            this.downstream_client.as_ref().map(|(client, project_id)| {
+                                client.send(proto::RefreshSemanticTokens {
+                                    project_id: *project_id,
+                                })
+                            })
+                        })?
+                        .transpose()?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
+
         language_server
             .on_request::<lsp::request::WorkspaceDiagnosticRefresh, _, _>({
                 let this = lsp_store.clone();
@@ -3287,6 +3332,9 @@ impl LocalLspStore {
             for buffer_servers in self.buffers_opened_in_servers.values_mut() {
                 buffer_servers.remove(server_id_to_remove);
             }
+            for buffer_tokens in self.buffer_semantic_tokens.values_mut() {
+                buffer_tokens.remove(server_id_to_remove);
+            }
             cx.emit(LspStoreEvent::LanguageServerRemoved(*server_id_to_remove));
         }
         servers_to_remove.into_iter().collect()
@@ -3675,6 +3723,7 @@ pub struct BufferLspData {
     buffer_version: Global,
     document_colors: Option<DocumentColorData>,
     code_lens: Option<CodeLensData>,
+    semantic_tokens: Option<SemanticTokensData>,
     inlay_hints: BufferInlayHints,
     lsp_requests: HashMap<LspKey, HashMap<LspRequestId, Task<()>>>,
     chunk_lsp_requests: HashMap<LspKey, HashMap<RowChunk, LspRequestId>>,
@@ -3692,6 +3741,7 @@ impl BufferLspData {
             buffer_version: buffer.read(cx).version(),
             document_colors: None,
             code_lens: None,
+            semantic_tokens: None,
             inlay_hints: BufferInlayHints::new(buffer, cx),
             lsp_requests: HashMap::default(),
             chunk_lsp_requests: HashMap::default(),
@@ -3708,6 +3758,11 @@ impl BufferLspData {
             code_lens.lens.remove(&for_server);
         }
 
+        if let Some(semantic_tokens) = &mut self.semantic_tokens {
+            semantic_tokens.servers.remove(&for_server);
+            semantic_tokens.cache_version += 1;
+        }
+
         self.inlay_hints.remove_server_data(for_server);
     }
 
@@ -3760,6 +3815,7 @@ pub enum LspStoreEvent {
         request_id: Option<usize>,
     },
     RefreshCodeLens,
+    RefreshSemanticTokens,
     DiagnosticsUpdated {
         server_id: LanguageServerId,
         paths: Vec<ProjectPath>,
@@ -3839,6 +3895,8 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_open_buffer_for_symbol);
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
         client.add_entity_request_handler(Self::handle_refresh_code_lens);
+        client.add_entity_request_handler(Self::handle_refresh_semantic_tokens);
+        client.add_entity_request_handler(Self::handle_get_semantic_tokens);
         client.add_entity_request_handler(Self::handle_on_type_formatting);
         client.add_entity_request_handler(Self::handle_apply_additional_edits_for_completion);
         client.add_entity_request_handler(Self::handle_register_buffer_with_language_servers);
@@ -3847,6 +3905,12 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_get_completions);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentHighlights>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentSymbols>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareCallHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetIncomingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetOutgoingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareTypeHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSupertypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSubtypes>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
@@ -3971,6 +4035,8 @@ impl LspStore {
                 buffers_opened_in_servers: HashMap::default(),
                 buffer_pull_diagnostics_result_ids: HashMap::default(),
                 workspace_pull_diagnostics_result_ids: HashMap::default(),
+                buffer_semantic_tokens: HashMap::default(),
+                task_diagnostics: HashMap::default(),
                 watched_manifest_filenames: ManifestProvidersStore::global(cx)
                     .manifest_file_names(),
             }),
@@ -4246,6 +4312,7 @@ impl LspStore {
                         local.registered_buffers.remove(&buffer_id);
 
                         local.buffers_opened_in_servers.remove(&buffer_id);
+                        local.buffer_semantic_tokens.remove(&buffer_id);
                         if let Some(file) = File::from_dyn(buffer.read(cx).file()).cloned() {
                             local.unregister_old_buffer_from_language_servers(buffer, &file, cx);
 
@@ -4561,6 +4628,9 @@ impl LspStore {
                 request.check_capabilities(AdapterServerCapabilities {
                     server_capabilities: capabilities.clone(),
                     code_action_kinds: None,
+                    // Capabilities that `lsp::ServerCapabilities` can't represent aren't sent
+                    // to remote clients, so let the host check them instead.
+                    supports_type_hierarchy: true,
                 })
             },
             cx,
@@ -7476,6 +7546,302 @@ impl LspStore {
         }
     }
 
+    pub fn semantic_tokens(
+        &mut self,
+        known_cache_version: Option<usize>,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Option<SemanticTokensTask> {
+        let version_queried_for = buffer.read(cx).version();
+        let buffer_id = buffer.read(cx).remote_id();
+
+        let current_language_servers = self.as_local().map(|local| {
+            local
+                .buffers_opened_in_servers
+                .get(&buffer_id)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|server_id| {
+                    self.lsp_server_capabilities
+                        .get(server_id)
+                        .is_some_and(|capabilities| capabilities.semantic_tokens_provider.is_some())
+                })
+                .collect::<HashSet<_>>()
+        });
+
+        if let Some(lsp_data) = self.current_lsp_data(buffer_id)
+            && let Some(cached_tokens) = &lsp_data.semantic_tokens
+            && !version_queried_for.changed_since(&lsp_data.buffer_version)
+        {
+            let has_different_servers =
+                current_language_servers.is_some_and(|current_language_servers| {
+                    current_language_servers != cached_tokens.servers.keys().copied().collect()
+                });
+            if !has_different_servers {
+                let cache_version = cached_tokens.cache_version;
+                if Some(cache_version) == known_cache_version {
+                    return None;
+                } else {
+                    return Some(
+                        Task::ready(Ok(BufferSemanticTokens {
+                            servers: cached_tokens.servers.clone(),
+                            cache_version: Some(cache_version),
+                        }))
+                        .shared(),
+                    );
+                }
+            }
+        }
+
+        let semantic_tokens_data = self
+            .latest_lsp_data(&buffer, cx)
+            .semantic_tokens
+            .get_or_insert_default();
+        if let Some((updating_for, running_update)) = &semantic_tokens_data.update
+            && !version_queried_for.changed_since(updating_for)
+        {
+            return Some(running_update.clone());
+        }
+        let buffer_version_queried_for = version_queried_for.clone();
+        let new_task = cx
+            .spawn(async move |lsp_store, cx| {
+                cx.background_executor()
+                    .timer(Duration::from_millis(30))
+                    .await;
+                let fetched_tokens = lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        lsp_store.fetch_semantic_tokens_for_buffer(&buffer, cx)
+                    })?
+                    .await
+                    .context("fetching semantic tokens")
+                    .map_err(Arc::new);
+                let fetched_tokens = match fetched_tokens {
+                    Ok(fetched_tokens) => fetched_tokens,
+                    Err(e) => {
+                        lsp_store
+                            .update(cx, |lsp_store, _| {
+                                if let Some(lsp_data) = lsp_store.lsp_data.get_mut(&buffer_id) {
+                                    if let Some(semantic_tokens) = &mut lsp_data.semantic_tokens {
+                                        semantic_tokens.update = None;
+                                    }
+                                }
+                            })
+                            .ok();
+                        return Err(e);
+                    }
+                };
+
+                lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        let lsp_data = lsp_store.latest_lsp_data(&buffer, cx);
+                        let semantic_tokens = lsp_data.semantic_tokens.get_or_insert_default();
+
+                        if let Some(fetched_tokens) = fetched_tokens {
+                            if lsp_data.buffer_version == buffer_version_queried_for {
+                                semantic_tokens.servers.extend(fetched_tokens);
+                                semantic_tokens.cache_version += 1;
+                            } else if !lsp_data
+                                .buffer_version
+                                .changed_since(&buffer_version_queried_for)
+                            {
+                                lsp_data.buffer_version = buffer_version_queried_for;
+                                semantic_tokens.servers = fetched_tokens;
+                                semantic_tokens.cache_version += 1;
+                            }
+                        }
+                        semantic_tokens.update = None;
+                        BufferSemanticTokens {
+                            servers: semantic_tokens.servers.clone(),
+                            cache_version: Some(semantic_tokens.cache_version),
+                        }
+                    })
+                    .map_err(Arc::new)
+            })
+            .shared();
+        semantic_tokens_data.update = Some((version_queried_for, new_task.clone()));
+        Some(new_task)
+    }
+
+    fn invalidate_semantic_tokens(&mut self) {
+        for lsp_data in self.lsp_data.values_mut() {
+            lsp_data.semantic_tokens = None;
+        }
+    }
+
+    fn fetch_semantic_tokens_for_buffer(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<Option<HashMap<LanguageServerId, Arc<ServerSemanticTokens>>>>> {
+        if let Some((client, project_id)) = self.upstream_client() {
+            if !self.check_if_capable_for_proto_request(
+                buffer,
+                |capabilities| capabilities.semantic_tokens_provider.is_some(),
+                cx,
+            ) {
+                return Task::ready(Ok(None));
+            }
+
+            let request_task = client.request(proto::GetSemanticTokens {
+                project_id,
+                buffer_id: buffer.read(cx).remote_id().to_proto(),
+                version: serialize_version(&buffer.read(cx).version()),
+            });
+            let buffer = buffer.clone();
+            cx.spawn(async move |_, cx| {
+                let response = request_task
+                    .await
+                    .context("semantic tokens proto request")?;
+                let version = deserialize_version(&response.version);
+                buffer
+                    .update(cx, |buffer, _| buffer.wait_for_version(version.clone()))?
+                    .await?;
+                let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+                // The buffer got edited after the host responded, the tokens would be misplaced.
+                if snapshot.version() != &version {
+                    return Ok(None);
+                }
+                Ok(Some(
+                    response
+                        .servers
+                        .into_iter()
+                        .map(|server_tokens| {
+                            semantic_tokens::tokens_from_proto(server_tokens, &snapshot)
+                        })
+                        .map(|(server_id, server_tokens)| (server_id, Arc::new(server_tokens)))
+                        .collect(),
+                ))
+            })
+        } else {
+            let Some(local) = self.as_local() else {
+                return Task::ready(Ok(None));
+            };
+            let Some(abs_path) = File::from_dyn(buffer.read(cx).file()).map(|f| f.abs_path(cx))
+            else {
+                return Task::ready(Ok(None));
+            };
+            let text_document = match make_text_document_identifier(&abs_path) {
+                Ok(text_document) => text_document,
+                Err(e) => return Task::ready(Err(e)),
+            };
+            let snapshot = buffer.read(cx).snapshot();
+            let buffer_id = snapshot.remote_id();
+            let opened_in_servers = local
+                .buffers_opened_in_servers
+                .get(&buffer_id)
+                .cloned()
+                .unwrap_or_default();
+            let servers = buffer.update(cx, |buffer, cx| {
+                local
+                    .language_servers_for_buffer(buffer, cx)
+                    .filter(|(_, server)| opened_in_servers.contains(&server.server_id()))
+                    .filter_map(|(_, server)| {
+                        let options = match self
+                            .lsp_server_capabilities
+                            .get(&server.server_id())?
+                            .semantic_tokens_provider
+                            .clone()?
+                        {
+                            lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                                options,
+                            ) => options,
+                            lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(
+                                options,
+                            ) => options.semantic_tokens_options,
+                        };
+                        let supports_delta = match options.full? {
+                            lsp::SemanticTokensFullOptions::Bool(full) => {
+                                if !full {
+                                    return None;
+                                }
+                                false
+                            }
+                            lsp::SemanticTokensFullOptions::Delta { delta } => {
+                                delta.unwrap_or(false)
+                            }
+                        };
+                        let previous_tokens = supports_delta
+                            .then(|| {
+                                local
+                                    .buffer_semantic_tokens
+                                    .get(&buffer_id)?
+                                    .get(&server.server_id())
+                                    .cloned()
+                            })
+                            .flatten();
+                        Some((server.clone(), Arc::new(options.legend), previous_tokens))
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+            let fetch_task = cx.background_spawn(async move {
+                join_all(
+                    servers
+                        .into_iter()
+                        .map(|(server, legend, previous_tokens)| {
+                            let text_document = text_document.clone();
+                            let snapshot = snapshot.clone();
+                            async move {
+                                let server_id = server.server_id();
+                                let raw_tokens = semantic_tokens::request_semantic_tokens(
+                                    &server,
+                                    text_document,
+                                    previous_tokens,
+                                )
+                                .await;
+                                let tokens = raw_tokens.as_ref().ok().and_then(|raw_tokens| {
+                                    let tokens = semantic_tokens::decode_tokens(
+                                        &raw_tokens.as_ref()?.data,
+                                        &snapshot,
+                                    );
+                                    Some(ServerSemanticTokens { legend, tokens })
+                                });
+                                (server_id, raw_tokens, tokens)
+                            }
+                        }),
+                )
+                .await
+            });
+            cx.spawn(async move |lsp_store, cx| {
+                let responses = fetch_task.await;
+                lsp_store.update(cx, |lsp_store, _| {
+                    let local = lsp_store.as_local_mut()?;
+                    let mut fetched_tokens = HashMap::default();
+                    for (server_id, raw_tokens, tokens) in responses {
+                        match raw_tokens {
+                            Ok(Some(raw_tokens)) => {
+                                local
+                                    .buffer_semantic_tokens
+                                    .entry(buffer_id)
+                                    .or_default()
+                                    .insert(server_id, raw_tokens);
+                            }
+                            Ok(None) => {
+                                if let Some(buffer_tokens) =
+                                    local.buffer_semantic_tokens.get_mut(&buffer_id)
+                                {
+                                    buffer_tokens.remove(&server_id);
+                                }
+                            }
+                            // rust-analyzer likes to error with this when its still loading up
+                            Err(e) if format!("{e:#}").ends_with("content modified") => {}
+                            Err(e) => {
+                                log::error!(
+                                    "Error fetching semantic tokens from server {server_id}: {e:#}"
+                                );
+                            }
+                        }
+                        if let Some(tokens) = tokens {
+                            fetched_tokens.insert(server_id, Arc::new(tokens));
+                        }
+                    }
+                    Some(fetched_tokens)
+                })
+            })
+        }
+    }
+
     pub fn signature_help<T: ToPointUtf16>(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -8425,6 +8791,93 @@ impl LspStore {
         Ok(())
     }
 
+    /// Replaces the diagnostics reported by the previous run of the task with the problems its problem matchers found in the output.
+    /// Problems in files outside of the project worktrees are ignored, and problems without a source
+    /// are attributed to the task.
+    pub fn update_task_diagnostics(
+        &mut self,
+        task_label: String,
+        problems: Vec<TaskProblem>,
+        cx: &mut Context<Self>,
+    ) -> anyhow::Result<()> {
+        let worktree_store = self.worktree_store.clone();
+        let local = self
+            .as_local_mut()
+            .context("cannot update task diagnostics on a remote LspStore")?;
+        let languages = local.languages.clone();
+        let (server_id, previous_paths) = local
+            .task_diagnostics
+            .entry(task_label.clone())
+            .or_insert_with(|| (languages.next_language_server_id(), HashSet::default()));
+        let server_id = *server_id;
+
+        let mut diagnostics_by_path = previous_paths
+            .drain()
+            .map(|path| (path, Vec::new()))
+            .collect::<HashMap<_, _>>();
+        for problem in problems {
+            let start = PointUtf16::new(
+                problem.line.saturating_sub(1),
+                problem.column.unwrap_or(1).saturating_sub(1),
+            );
+            let end = match (problem.end_line, problem.end_column) {
+                (Some(end_line), Some(end_column)) => {
+                    PointUtf16::new(end_line.saturating_sub(1), end_column.saturating_sub(1))
+                }
+                (None, Some(end_column)) => {
+                    PointUtf16::new(start.row, end_column.saturating_sub(1))
+                }
+                (Some(_), None) | (None, None) => start,
+            };
+            let group_id = post_inc(&mut local.next_diagnostic_group_id);
+            diagnostics_by_path
+                .entry(problem.path)
+                .or_default()
+                .push(DiagnosticEntry {
+                    range: Unclipped(start)..Unclipped(end.max(start)),
+                    diagnostic: Diagnostic {
+                        source: Some(problem.source.unwrap_or_else(|| task_label.clone())),
+                        code: problem.code.map(lsp::NumberOrString::String),
+                        severity: match problem.severity {
+                            ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+                            ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+                            ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+                            ProblemSeverity::Hint => DiagnosticSeverity::HINT,
+                        },
+                        message: problem.message,
+                        group_id,
+                        is_primary: true,
+                        source_kind: DiagnosticSourceKind::Other,
+                        ..Diagnostic::default()
+                    },
+                });
+        }
+        diagnostics_by_path
+            .retain(|path, _| worktree_store.read(cx).find_worktree(path, cx).is_some());
+        previous_paths.extend(
+            diagnostics_by_path
+                .iter()
+                .filter(|(_, diagnostics)| !diagnostics.is_empty())
+                .map(|(path, _)| path.clone()),
+        );
+
+        let updates = diagnostics_by_path
+            .into_iter()
+            .map(|(path, diagnostics)| DocumentDiagnosticsUpdate {
+                diagnostics: DocumentDiagnostics {
+                    diagnostics,
+                    document_abs_path: path,
+                    version: None,
+                },
+                result_id: None,
+                registration_id: None,
+                server_id,
+                disk_based_sources: Cow::Borrowed(&[]),
+            })
+            .collect();
+        self.merge_diagnostic_entries(updates, |_, _, _| false, cx)
+    }
+
     fn update_worktree_diagnostics(
         &mut self,
         worktree_id: WorktreeId,
@@ -10213,6 +10666,58 @@ impl LspStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_refresh_semantic_tokens(
+        this: Entity<Self>,
+        _: TypedEnvelope<proto::RefreshSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        this.update(&mut cx, |this, cx| {
+            this.invalidate_semantic_tokens();
+            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+        })?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_semantic_tokens(
+        lsp_store: Entity<Self>,
+        envelope: TypedEnvelope<proto::GetSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GetSemanticTokensResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let buffer = lsp_store.update(&mut cx, |lsp_store, cx| {
+            lsp_store.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&envelope.payload.version))
+            })?
+            .await?;
+        let semantic_tokens = lsp_store.update(&mut cx, |lsp_store, cx| {
+            lsp_store.semantic_tokens(None, buffer.clone(), cx)
+        })?;
+        let semantic_tokens = match semantic_tokens {
+            Some(task) => task
+                .await
+                .map_err(|e| anyhow!("{e:#}"))
+                .context("fetching semantic tokens")?,
+            None => BufferSemanticTokens::default(),
+        };
+
+        buffer.read_with(&cx, |buffer, _| {
+            let snapshot = buffer.snapshot();
+            proto::GetSemanticTokensResponse {
+                servers: semantic_tokens
+                    .servers
+                    .iter()
+                    .map(|(server_id, server_tokens)| {
+                        semantic_tokens::tokens_to_proto(*server_id, server_tokens, &snapshot)
+                    })
+                    .collect(),
+                version: serialize_version(snapshot.version()),
+            }
+        })
+    }
+
     async fn handle_open_buffer_for_symbol(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::OpenBufferForSymbol>,
@@ -11956,6 +12461,9 @@ impl LspStore {
             for buffer_servers in local.buffers_opened_in_servers.values_mut() {
                 buffer_servers.remove(&for_server);
             }
+            for buffer_tokens in local.buffer_semantic_tokens.values_mut() {
+                buffer_tokens.remove(&for_server);
+            }
         }
     }
 
@@ -12915,6 +13423,26 @@ fn parse_register_capabilities<T: serde::de::DeserializeOwned>(
     })
 }
 
+/// Makes the language server use the toolchain that is active for its root.
+async fn activate_toolchain_environment(
+    languages: &Arc<LanguageRegistry>,
+    toolchain: Option<&Toolchain>,
+    env: &mut HashMap<String, String>,
+) {
+    let Some(toolchain) = toolchain else {
+        return;
+    };
+    let Some(lister) = languages
+        .language_for_name(&toolchain.language_name.0)
+        .await
+        .ok()
+        .and_then(|language| language.toolchain_lister())
+    else {
+        return;
+    };
+    lister.activate_environment(toolchain, env);
+}
+
 fn subscribe_to_binary_statuses(
     languages: &Arc<LanguageRegistry>,
     cx: &mut Context<'_, LspStore>,
@@ -0,0 +1,305 @@
+use std::{ops::Range, sync::Arc};
+
+use anyhow::Context as _;
+use client::proto;
+use collections::HashMap;
+use futures::future::Shared;
+use gpui::Task;
+use language::{BufferSnapshot, PointUtf16, ToPointUtf16 as _, Unclipped};
+use lsp::{LanguageServer, LanguageServerId};
+use text::{Anchor, Bias};
+
+pub type SemanticTokensTask = Shared<Task<Result<BufferSemanticTokens, Arc<anyhow::Error>>>>;
+
+/// Semantic tokens of a single buffer, as reported by all language servers capable of providing them.
+#[derive(Debug, Default, Clone)]
+pub struct BufferSemanticTokens {
+    pub servers: HashMap<LanguageServerId, Arc<ServerSemanticTokens>>,
+    pub cache_version: Option<usize>,
+}
+
+/// Semantic tokens reported by a single language server, along with the legend to interpret them.
+#[derive(Debug)]
+pub struct ServerSemanticTokens {
+    pub legend: Arc<lsp::SemanticTokensLegend>,
+    pub tokens: Vec<SemanticToken>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SemanticToken {
+    pub range: Range<Anchor>,
+    pub token_type: u32,
+    pub token_modifiers: u32,
+}
+
+impl ServerSemanticTokens {
+    pub fn token_type(&self, token: &SemanticToken) -> Option<&str> {
+        self.legend
+            .token_types
+            .get(token.token_type as usize)
+            .map(|token_type| token_type.as_str())
+    }
+
+    pub fn token_modifiers<'a>(
+        &'a self,
+        token: &SemanticToken,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        let modifiers = token.token_modifiers;
+        self.legend
+            .token_modifiers
+            .iter()
+            .enumerate()
+            .filter(move |(ix, _)| *ix < u32::BITS as usize && modifiers & (1 << ix) != 0)
+            .map(|(_, modifier)| modifier.as_str())
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct SemanticTokensData {
+    pub(super) servers: HashMap<LanguageServerId, Arc<ServerSemanticTokens>>,
+    pub(super) cache_version: usize,
+    pub(super) update: Option<(clock::Global, SemanticTokensTask)>,
+}
+
+/// The last token data a language server has reported for a buffer, in the LSP encoding.
+/// Kept across buffer edits, so that `textDocument/semanticTokens/full/delta` responses can be applied on top of it.
+#[derive(Debug, Clone, Default)]
+pub(super) struct RawSemanticTokens {
+    pub(super) result_id: Option<String>,
+    pub(super) data: Vec<lsp::SemanticToken>,
+}
+
+impl RawSemanticTokens {
+    /// Applies the delta edits to the token data.
+    ///
+    /// Edit offsets are expressed in the flat integer array of the protocol, five integers per token.
+    /// Returns `false` if the edits cannot be applied, in which case the full token set has to be re-requested.
+    pub(super) fn apply_edits(&mut self, mut edits: Vec<lsp::SemanticTokensEdit>) -> bool {
+        const TOKEN_LEN: u32 = 5;
+
+        edits.sort_by_key(|edit| edit.start);
+        let mut previous_end = 0;
+        for edit in &edits {
+            if edit.start % TOKEN_LEN != 0
+                || edit.delete_count % TOKEN_LEN != 0
+                || edit.start < previous_end
+            {
+                return false;
+            }
+            previous_end = edit.start + edit.delete_count;
+        }
+        if previous_end as usize > self.data.len() * TOKEN_LEN as usize {
+            return false;
+        }
+
+        for edit in edits.into_iter().rev() {
+            let start = (edit.start / TOKEN_LEN) as usize;
+            let end = start + (edit.delete_count / TOKEN_LEN) as usize;
+            self.data.splice(start..end, edit.data.unwrap_or_default());
+        }
+        true
+    }
+}
+
+/// Converts the relative LSP token encoding into anchored ranges in the buffer snapshot the tokens were computed for.
+pub(super) fn decode_tokens(
+    data: &[lsp::SemanticToken],
+    snapshot: &BufferSnapshot,
+) -> Vec<SemanticToken> {
+    let mut tokens = Vec::with_capacity(data.len());
+    let mut line = 0;
+    let mut start = 0;
+    for token in data {
+        if token.delta_line == 0 {
+            start += token.delta_start;
+        } else {
+            line += token.delta_line;
+            start = token.delta_start;
+        }
+        if token.length == 0 {
+            continue;
+        }
+
+        let range_start =
+            snapshot.clip_point_utf16(Unclipped(PointUtf16::new(line, start)), Bias::Left);
+        let range_end = snapshot.clip_point_utf16(
+            Unclipped(PointUtf16::new(line, start + token.length)),
+            Bias::Right,
+        );
+        if range_start >= range_end {
+            continue;
+        }
+        tokens.push(SemanticToken {
+            range: snapshot.anchor_after(range_start)..snapshot.anchor_before(range_end),
+            token_type: token.token_type,
+            token_modifiers: token.token_modifiers_bitset,
+        });
+    }
+    tokens
+}
+
+/// Converts anchored tokens back into the relative LSP token encoding, to be sent to remote peers.
+fn encode_tokens(tokens: &[SemanticToken], snapshot: &BufferSnapshot) -> Vec<lsp::SemanticToken> {
+    let mut data = Vec::with_capacity(tokens.len());
+    let mut previous = PointUtf16::zero();
+    for token in tokens {
+        let start = token.range.start.to_point_utf16(snapshot);
+        let end = token.range.end.to_point_utf16(snapshot);
+        // Tokens spanning multiple lines are not requested from the language servers,
+        // and ones that became such after the edits are not worth preserving.
+        if end.row != start.row || end.column <= start.column || start < previous {
+            continue;
+        }
+        data.push(lsp::SemanticToken {
+            delta_line: start.row - previous.row,
+            delta_start: if start.row == previous.row {
+                start.column - previous.column
+            } else {
+                start.column
+            },
+            length: end.column - start.column,
+            token_type: token.token_type,
+            token_modifiers_bitset: token.token_modifiers,
+        });
+        previous = start;
+    }
+    data
+}
+
+/// Requests the full token set of the document from the server.
+///
+/// If the previous tokens are given, a delta is requested instead and applied on top of them,
+/// falling back to the full request if the delta cannot be applied.
+pub(super) async fn request_semantic_tokens(
+    server: &LanguageServer,
+    text_document: lsp::TextDocumentIdentifier,
+    previous_tokens: Option<RawSemanticTokens>,
+) -> anyhow::Result<Option<RawSemanticTokens>> {
+    if let Some(mut previous_tokens) = previous_tokens
+        && let Some(previous_result_id) = previous_tokens.result_id.clone()
+    {
+        let response = server
+            .request::<lsp::request::SemanticTokensFullDeltaRequest>(
+                lsp::SemanticTokensDeltaParams {
+                    text_document: text_document.clone(),
+                    previous_result_id,
+                    work_done_progress_params: Default::default(),
+                    partial_result_params: Default::default(),
+                },
+            )
+            .await
+            .into_response()
+            .context("semantic tokens delta LSP request")?;
+        match response {
+            Some(lsp::SemanticTokensFullDeltaResult::Tokens(tokens)) => {
+                return Ok(Some(RawSemanticTokens {
+                    result_id: tokens.result_id,
+                    data: tokens.data,
+                }));
+            }
+            Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(delta)) => {
+                if previous_tokens.apply_edits(delta.edits) {
+                    previous_tokens.result_id = delta.result_id;
+                    return Ok(Some(previous_tokens));
+                }
+            }
+            Some(lsp::SemanticTokensFullDeltaResult::PartialTokensDelta { edits }) => {
+                if previous_tokens.apply_edits(edits) {
+                    previous_tokens.result_id = None;
+                    return Ok(Some(previous_tokens));
+                }
+            }
+            None => {}
+        }
+    }
+
+    let response = server
+        .request::<lsp::request::SemanticTokensFullRequest>(lsp::SemanticTokensParams {
+            text_document,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+        .await
+        .into_response()
+        .context("semantic tokens LSP request")?;
+    Ok(response.map(|response| match response {
+        lsp::SemanticTokensResult::Tokens(tokens) => RawSemanticTokens {
+            result_id: tokens.result_id,
+            data: tokens.data,
+        },
+        lsp::SemanticTokensResult::Partial(partial) => RawSemanticTokens {
+            result_id: None,
+            data: partial.data,
+        },
+    }))
+}
+
+pub(super) fn tokens_to_proto(
+    server_id: LanguageServerId,
+    server_tokens: &ServerSemanticTokens,
+    snapshot: &BufferSnapshot,
+) -> proto::SemanticTokensForServer {
+    proto::SemanticTokensForServer {
+        server_id: server_id.to_proto(),
+        token_types: server_tokens
+            .legend
+            .token_types
+            .iter()
+            .map(|token_type| token_type.as_str().to_owned())
+            .collect(),
+        token_modifiers: server_tokens
+            .legend
+            .token_modifiers
+            .iter()
+            .map(|modifier| modifier.as_str().to_owned())
+            .collect(),
+        data: encode_tokens(&server_tokens.tokens, snapshot)
+            .into_iter()
+            .flat_map(|token| {
+                [
+                    token.delta_line,
+                    token.delta_start,
+                    token.length,
+                    token.token_type,
+                    token.token_modifiers_bitset,
+                ]
+            })
+            .collect(),
+    }
+}
+
+pub(super) fn tokens_from_proto(
+    server_tokens: proto::SemanticTokensForServer,
+    snapshot: &BufferSnapshot,
+) -> (LanguageServerId, ServerSemanticTokens) {
+    let legend = lsp::SemanticTokensLegend {
+        token_types: server_tokens
+            .token_types
+            .into_iter()
+            .map(lsp::SemanticTokenType::from)
+            .collect(),
+        token_modifiers: server_tokens
+            .token_modifiers
+            .into_iter()
+            .map(lsp::SemanticTokenModifier::from)
+            .collect(),
+    };
+    let data = server_tokens
+        .data
+        .chunks_exact(5)
+        .map(|chunk| lsp::SemanticToken {
+            delta_line: chunk[0],
+            delta_start: chunk[1],
+            length: chunk[2],
+            token_type: chunk[3],
+            token_modifiers_bitset: chunk[4],
+        })
+        .collect::<Vec<_>>();
+    (
+        LanguageServerId::from_proto(server_tokens.server_id),
+        ServerSemanticTokens {
+            legend: Arc::new(legend),
+            tokens: decode_tokens(&data, snapshot),
+        },
+    )
+}
@@ -42,6 +42,10 @@ impl ManifestProvidersStore {
         self.0.write().providers.remove(name);
     }
 
+    pub fn contains(&self, name: &SharedString) -> bool {
+        self.0.read().providers.contains_key(name)
+    }
+
     pub(super) fn get(&self, name: &SharedString) -> Option<Arc<dyn ManifestProvider>> {
         self.0.read().providers.get(name).cloned()
     }
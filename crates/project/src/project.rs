@@ -143,14 +143,15 @@ pub use language::Location;
 pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use task_inventory::{
     BasicContextProvider, ContextProviderWithTasks, DebugScenarioContext, Inventory, TaskContexts,
-    TaskSourceKind,
+    TaskDependencies, TaskDependency, TaskSourceKind,
 };
 
 pub use buffer_store::ProjectTransaction;
 pub use lsp_store::{
-    DiagnosticSummary, InvalidationStrategy, LanguageServerLogType, LanguageServerProgress,
-    LanguageServerPromptRequest, LanguageServerStatus, LanguageServerToQuery, LspStore,
-    LspStoreEvent, ProgressToken, SERVER_PROGRESS_THROTTLE_TIMEOUT,
+    BufferSemanticTokens, DiagnosticSummary, InvalidationStrategy, LanguageServerLogType,
+    LanguageServerProgress, LanguageServerPromptRequest, LanguageServerStatus,
+    LanguageServerToQuery, LspStore, LspStoreEvent, ProgressToken,
+    SERVER_PROGRESS_THROTTLE_TIMEOUT, SemanticToken, ServerSemanticTokens,
 };
 pub use toolchain_store::{ToolchainStore, Toolchains};
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
@@ -344,6 +345,7 @@ pub enum Event {
         request_id: Option<usize>,
     },
     RefreshCodeLens,
+    RefreshSemanticTokens,
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
@@ -793,6 +795,47 @@ pub struct DocumentSymbol {
     pub children: Vec<DocumentSymbol>,
 }
 
+/// A symbol that can be a node in a call hierarchy, e.g. a function or a method.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The range enclosing the whole symbol, including its body.
+    pub location: Location,
+    /// The range to reveal when the item is picked, e.g. the name of a function.
+    pub selection_range: Range<language::Anchor>,
+    pub language_server_id: LanguageServerId,
+    /// The item as reported by the language server, which needs to be sent back
+    /// as is when querying for its incoming or outgoing calls.
+    pub lsp_item: lsp::CallHierarchyItem,
+}
+
+#[derive(Clone, Debug)]
+pub struct CallHierarchyCall {
+    /// The caller for incoming calls, the callee for outgoing calls.
+    pub item: CallHierarchyItem,
+    /// Where the calls happen: inside the caller for incoming calls,
+    /// inside the item the calls were requested for in case of outgoing calls.
+    pub call_sites: Vec<Location>,
+}
+
+/// A type that can be a node in a type hierarchy, e.g. a class, an interface or a trait.
+#[derive(Clone, Debug)]
+pub struct TypeHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The range enclosing the whole type definition.
+    pub location: Location,
+    /// The range to reveal when the item is picked, e.g. the name of a class.
+    pub selection_range: Range<language::Anchor>,
+    pub language_server_id: LanguageServerId,
+    /// The item as reported by the language server, which needs to be sent back
+    /// as is when querying for its supertypes or subtypes.
+    pub lsp_item: lsp::TypeHierarchyItem,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HoverBlock {
     pub text: String,
@@ -3082,6 +3125,7 @@ impl Project {
                 request_id: *request_id,
             }),
             LspStoreEvent::RefreshCodeLens => cx.emit(Event::RefreshCodeLens),
+            LspStoreEvent::RefreshSemanticTokens => cx.emit(Event::RefreshSemanticTokens),
             LspStoreEvent::LanguageServerPrompt(prompt) => {
                 cx.emit(Event::LanguageServerPrompt(prompt.clone()))
             }
@@ -3776,6 +3820,100 @@ impl Project {
         )
     }
 
+    /// Resolves the call hierarchy items at the given position, which can then be used
+    /// to query for [`Project::incoming_calls`] and [`Project::outgoing_calls`].
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareCallHierarchy { position },
+            cx,
+        )
+    }
+
+    pub fn incoming_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetIncomingCalls {
+                lsp_item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    pub fn outgoing_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetOutgoingCalls {
+                lsp_item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    /// Resolves the type hierarchy items at the given position, which can then be used
+    /// to query for [`Project::supertypes`] and [`Project::subtypes`].
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareTypeHierarchy { position },
+            cx,
+        )
+    }
+
+    pub fn supertypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetSupertypes {
+                lsp_item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    pub fn subtypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetSubtypes {
+                lsp_item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
     pub fn symbols(&self, query: &str, cx: &mut Context<Self>) -> Task<Result<Vec<Symbol>>> {
         self.lsp_store
             .update(cx, |lsp_store, cx| lsp_store.symbols(query, cx))
@@ -4389,6 +4527,26 @@ impl Project {
             .diagnostic_summaries(include_ignored, cx)
     }
 
+    /// Reports the problems the task's problem matchers found in its output as project diagnostics,
+    /// replacing the ones reported by the previous run of the task with the same label.
+    ///
+    /// Task diagnostics are not supported in remote projects, where nothing is reported.
+    pub fn update_task_diagnostics(
+        &mut self,
+        task_label: String,
+        problems: Vec<task::TaskProblem>,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.is_local() {
+            return;
+        }
+        self.lsp_store
+            .update(cx, |lsp_store, cx| {
+                lsp_store.update_task_diagnostics(task_label, problems, cx)
+            })
+            .log_err();
+    }
+
     pub fn active_entry(&self) -> Option<ProjectEntryId> {
         self.active_entry
     }
@@ -805,6 +805,113 @@ async fn test_running_multiple_instances_of_a_single_server_in_one_worktree(
     assert_eq!(server.server_id(), LanguageServerId(1));
 }
 
+#[gpui::test]
+async fn test_default_toolchain_choice(cx: &mut gpui::TestAppContext) {
+    struct PinningToolchainLister;
+    #[async_trait]
+    impl ToolchainLister for PinningToolchainLister {
+        async fn list(
+            &self,
+            _: PathBuf,
+            _: Arc<RelPath>,
+            _: Option<HashMap<String, String>>,
+            _: &dyn Fs,
+        ) -> ToolchainList {
+            ToolchainList {
+                toolchains: ["1.80", "1.85", "1.90"].map(moot_toolchain).into(),
+                // The project pins the middle one.
+                default: Some(1),
+                groups: Default::default(),
+            }
+        }
+        async fn resolve(
+            &self,
+            _: PathBuf,
+            _: Option<HashMap<String, String>>,
+            _: &dyn Fs,
+        ) -> anyhow::Result<Toolchain> {
+            Err(anyhow::anyhow!("Not implemented"))
+        }
+        fn meta(&self) -> ToolchainMetadata {
+            ToolchainMetadata {
+                term: SharedString::new_static("Toolchain"),
+                new_toolchain_placeholder: SharedString::new_static("A path to a toolchain"),
+                manifest_name: ManifestName::from(SharedString::new_static("Moot.toml")),
+            }
+        }
+        fn activation_script(&self, _: &Toolchain, _: ShellKind, _: &gpui::App) -> Vec<String> {
+            vec![]
+        }
+    }
+
+    fn moot_toolchain(name: &'static str) -> Toolchain {
+        Toolchain {
+            name: SharedString::new_static(name),
+            path: format!("/toolchains/{name}").into(),
+            language_name: LanguageName::new_static("Moot"),
+            as_json: serde_json::Value::Null,
+        }
+    }
+
+    init_test(cx);
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/the-root"),
+        json!({ "Moot.toml": "", "main.moot": "" }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), [path!("/the-root").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(Arc::new(
+        Language::new(
+            LanguageConfig {
+                name: "Moot".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["moot".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )
+        .with_toolchain_lister(Some(Arc::new(PinningToolchainLister))),
+    ));
+    let worktree_id = project.read_with(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let default_choice = async |cx: &mut gpui::TestAppContext| {
+        project
+            .update(cx, |project, cx| {
+                project.available_toolchains(
+                    ProjectPath {
+                        worktree_id,
+                        path: rel_path("main.moot").into(),
+                    },
+                    LanguageName::new_static("Moot"),
+                    cx,
+                )
+            })
+            .await
+            .expect("Toolchains to be listed")
+            .default_choice()
+            .map(|toolchain| toolchain.name)
+    };
+
+    assert_eq!(default_choice(cx).await.as_deref(), Some("1.85"));
+
+    // The lister's default still applies when a global toolchain is hidden from its list.
+    project.update(cx, |project, cx| {
+        project.add_toolchain(moot_toolchain("1.80"), ToolchainScope::Global, cx);
+    });
+    assert_eq!(default_choice(cx).await.as_deref(), Some("1.85"));
+
+    // Toolchains that the user added for the project take precedence.
+    project.update(cx, |project, cx| {
+        project.add_toolchain(moot_toolchain("1.90"), ToolchainScope::Project, cx);
+    });
+    assert_eq!(default_choice(cx).await.as_deref(), Some("1.90"));
+}
+
 #[gpui::test]
 async fn test_managing_language_servers(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -2878,6 +2985,121 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_task_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({ "a.rs": "let one = 1;\nlet two = 2;\n", "b.rs": "" }),
+    )
+    .await;
+
+    let project = Project::test(fs, [Path::new(path!("/dir"))], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store.clone());
+    let task_label = "cargo build".to_string();
+    let problem = |path: &str, line: u32, severity: task::ProblemSeverity| task::TaskProblem {
+        path: PathBuf::from(path),
+        line,
+        column: Some(5),
+        end_line: None,
+        end_column: None,
+        severity,
+        message: format!("problem at line {line}"),
+        code: None,
+        source: None,
+    };
+
+    project.update(cx, |project, cx| {
+        project.update_task_diagnostics(
+            task_label.clone(),
+            vec![
+                problem(path!("/dir/a.rs"), 1, task::ProblemSeverity::Error),
+                problem(path!("/dir/a.rs"), 2, task::ProblemSeverity::Warning),
+                problem(path!("/dir/b.rs"), 1, task::ProblemSeverity::Error),
+                problem(path!("/outside/c.rs"), 1, task::ProblemSeverity::Error),
+            ],
+            cx,
+        );
+    });
+    lsp_store.read_with(cx, |lsp_store, cx| {
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 2,
+                warning_count: 1,
+            }
+        );
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, _| {
+        let snapshot = buffer.snapshot();
+        assert_eq!(
+            snapshot
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .map(|entry| (
+                    entry.range,
+                    entry.diagnostic.severity,
+                    entry.diagnostic.source,
+                    entry.diagnostic.message
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    Point::new(0, 4)..Point::new(0, 5),
+                    DiagnosticSeverity::ERROR,
+                    Some("cargo build".to_string()),
+                    "problem at line 1".to_string()
+                ),
+                (
+                    Point::new(1, 4)..Point::new(1, 5),
+                    DiagnosticSeverity::WARNING,
+                    Some("cargo build".to_string()),
+                    "problem at line 2".to_string()
+                ),
+            ]
+        );
+    });
+
+    // The next run of the same task replaces all diagnostics it has reported before.
+    project.update(cx, |project, cx| {
+        project.update_task_diagnostics(
+            task_label,
+            vec![problem(
+                path!("/dir/b.rs"),
+                1,
+                task::ProblemSeverity::Warning,
+            )],
+            cx,
+        );
+    });
+    lsp_store.read_with(cx, |lsp_store, cx| {
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 1,
+            }
+        );
+    });
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .count(),
+            0
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -3424,6 +3646,493 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() { b(); }",
+            "b.rs": "fn b() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/b.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let lsp_item = |name: &str, path: &str, range: lsp::Range| lsp::CallHierarchyItem {
+        name: name.to_string(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path).unwrap(),
+        range,
+        selection_range: range,
+        data: None,
+    };
+    let item_b = lsp_item(
+        "b",
+        path!("/dir/b.rs"),
+        lsp::Range::new(lsp::Position::new(0, 3), lsp::Position::new(0, 4)),
+    );
+    let item_a = lsp_item(
+        "a",
+        path!("/dir/a.rs"),
+        lsp::Range::new(lsp::Position::new(0, 3), lsp::Position::new(0, 4)),
+    );
+    fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>({
+        let item_b = item_b.clone();
+        move |params, _| {
+            let item_b = item_b.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 3)
+                );
+                Ok(Some(vec![item_b]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>({
+        let item_a = item_a.clone();
+        move |params, _| {
+            let item_a = item_a.clone();
+            async move {
+                assert_eq!(params.item.name, "b");
+                Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                    from: item_a,
+                    from_ranges: vec![lsp::Range::new(
+                        lsp::Position::new(0, 9),
+                        lsp::Position::new(0, 10),
+                    )],
+                }]))
+            }
+        }
+    });
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_call_hierarchy(&buffer, 3, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "b");
+    cx.update(|cx| {
+        assert_eq!(
+            items[0]
+                .selection_range
+                .to_offset(items[0].location.buffer.read(cx)),
+            3..4
+        );
+    });
+
+    let calls = project
+        .update(cx, |project, cx| project.incoming_calls(&items[0], cx))
+        .await
+        .unwrap();
+    assert_eq!(calls.len(), 1);
+    let call = &calls[0];
+    assert_eq!(call.item.name, "a");
+    cx.update(|cx| {
+        let caller_buffer = call.item.location.buffer.read(cx);
+        assert_eq!(
+            caller_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/a.rs")),
+        );
+        assert_eq!(
+            call.call_sites
+                .iter()
+                .map(|call_site| call_site.range.to_offset(call_site.buffer.read(cx)))
+                .collect::<Vec<_>>(),
+            vec![9..10]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_type_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "shape.rs": "trait Shape {}",
+            "circle.rs": "struct Circle; impl Shape for Circle {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            initializer: Some(Box::new(|server| {
+                server.set_raw_capabilities(json!({ "typeHierarchyProvider": true }));
+            })),
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/shape.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let lsp_item = |name: &str, kind, path: &str, range: lsp::Range| lsp::TypeHierarchyItem {
+        name: name.to_string(),
+        kind,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path).unwrap(),
+        range,
+        selection_range: range,
+        data: None,
+    };
+    let shape = lsp_item(
+        "Shape",
+        lsp::SymbolKind::INTERFACE,
+        path!("/dir/shape.rs"),
+        lsp::Range::new(lsp::Position::new(0, 6), lsp::Position::new(0, 11)),
+    );
+    let circle = lsp_item(
+        "Circle",
+        lsp::SymbolKind::STRUCT,
+        path!("/dir/circle.rs"),
+        lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 13)),
+    );
+    fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>({
+        let shape = shape.clone();
+        move |params, _| {
+            let shape = shape.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 7)
+                );
+                Ok(Some(vec![shape]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>({
+        let circle = circle.clone();
+        move |params, _| {
+            let circle = circle.clone();
+            async move {
+                assert_eq!(params.item.name, "Shape");
+                Ok(Some(vec![circle]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.item.name, "Shape");
+            Ok(Some(Vec::new()))
+        },
+    );
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, 7, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "Shape");
+    assert_eq!(items[0].kind, lsp::SymbolKind::INTERFACE);
+
+    let supertypes = project
+        .update(cx, |project, cx| project.supertypes(&items[0], cx))
+        .await
+        .unwrap();
+    assert!(supertypes.is_empty());
+
+    let subtypes = project
+        .update(cx, |project, cx| project.subtypes(&items[0], cx))
+        .await
+        .unwrap();
+    assert_eq!(subtypes.len(), 1);
+    assert_eq!(subtypes[0].name, "Circle");
+    cx.update(|cx| {
+        let subtype_buffer = subtypes[0].location.buffer.read(cx);
+        assert_eq!(
+            subtype_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/circle.rs")),
+        );
+        assert_eq!(subtypes[0].selection_range.to_offset(subtype_buffer), 7..13);
+    });
+}
+
+#[gpui::test]
+async fn test_type_hierarchy_with_multiple_servers(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({ "shape.rs": "trait Shape {}" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut servers_without_type_hierarchy = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            name: "NoTypeHierarchyServer",
+            ..FakeLspAdapter::default()
+        },
+    );
+    let mut servers_with_type_hierarchy = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            name: "TypeHierarchyServer",
+            initializer: Some(Box::new(|server| {
+                server.set_raw_capabilities(json!({ "typeHierarchyProvider": {} }));
+            })),
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/shape.rs"), cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+
+    let server_without_type_hierarchy = servers_without_type_hierarchy.next().await.unwrap();
+    assert!(
+        !server_without_type_hierarchy
+            .server
+            .supports_type_hierarchy()
+    );
+    let _never_handled = server_without_type_hierarchy
+        .set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(|_, _| async move {
+            panic!("Should not prepare type hierarchies with a server that doesn't support them")
+        });
+    let server_with_type_hierarchy = servers_with_type_hierarchy.next().await.unwrap();
+    assert!(server_with_type_hierarchy.server.supports_type_hierarchy());
+    server_with_type_hierarchy.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(
+        |_, _| async move {
+            let range = lsp::Range::new(lsp::Position::new(0, 6), lsp::Position::new(0, 11));
+            Ok(Some(vec![lsp::TypeHierarchyItem {
+                name: "Shape".to_string(),
+                kind: lsp::SymbolKind::INTERFACE,
+                tags: None,
+                detail: None,
+                uri: lsp::Uri::from_file_path(path!("/dir/shape.rs")).unwrap(),
+                range,
+                selection_range: range,
+                data: None,
+            }]))
+        },
+    );
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, 7, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "Shape");
+    assert_eq!(
+        items[0].language_server_id,
+        server_with_type_hierarchy.server.server_id()
+    );
+}
+
+#[gpui::test]
+async fn test_semantic_tokens(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn main() { let mut x = 1; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                semantic_tokens_provider: Some(
+                    lsp::SemanticTokensOptions {
+                        legend: lsp::SemanticTokensLegend {
+                            token_types: vec![
+                                lsp::SemanticTokenType::FUNCTION,
+                                lsp::SemanticTokenType::VARIABLE,
+                            ],
+                            token_modifiers: vec![lsp::SemanticTokenModifier::new("mutable")],
+                        },
+                        full: Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                        ..lsp::SemanticTokensOptions::default()
+                    }
+                    .into(),
+                ),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    fake_server.set_request_handler::<lsp::request::SemanticTokensFullRequest, _, _>(
+        move |_, _| async move {
+            Ok(Some(lsp::SemanticTokensResult::Tokens(
+                lsp::SemanticTokens {
+                    result_id: Some("1".to_string()),
+                    data: vec![
+                        lsp::SemanticToken {
+                            delta_line: 0,
+                            delta_start: 3,
+                            length: 4,
+                            token_type: 0,
+                            token_modifiers_bitset: 0,
+                        },
+                        lsp::SemanticToken {
+                            delta_line: 0,
+                            delta_start: 17,
+                            length: 1,
+                            token_type: 1,
+                            token_modifiers_bitset: 1,
+                        },
+                    ],
+                },
+            )))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::SemanticTokensFullDeltaRequest, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.previous_result_id, "1");
+            Ok(Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(
+                lsp::SemanticTokensDelta {
+                    result_id: Some("2".to_string()),
+                    edits: vec![lsp::SemanticTokensEdit {
+                        start: 0,
+                        delete_count: 5,
+                        data: Some(vec![lsp::SemanticToken {
+                            delta_line: 1,
+                            delta_start: 3,
+                            length: 4,
+                            token_type: 0,
+                            token_modifiers_bitset: 0,
+                        }]),
+                    }],
+                },
+            )))
+        },
+    );
+
+    let fetch_tokens = |cx: &mut gpui::TestAppContext| {
+        let tokens_task = project.update(cx, |project, cx| {
+            project.lsp_store().update(cx, |lsp_store, cx| {
+                lsp_store.semantic_tokens(None, buffer.clone(), cx)
+            })
+        });
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.executor().run_until_parked();
+        tokens_task
+    };
+    let token_offsets = |tokens: &BufferSemanticTokens, cx: &mut gpui::TestAppContext| {
+        buffer.read_with(cx, |buffer, _| {
+            tokens
+                .servers
+                .values()
+                .flat_map(|server_tokens| {
+                    server_tokens.tokens.iter().map(|token| {
+                        (
+                            server_tokens.token_type(token).unwrap().to_string(),
+                            server_tokens
+                                .token_modifiers(token)
+                                .map(ToString::to_string)
+                                .collect::<Vec<_>>(),
+                            token.range.to_offset(buffer),
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let tokens = fetch_tokens(cx).unwrap().await.unwrap();
+    assert_eq!(
+        token_offsets(&tokens, cx),
+        vec![
+            ("function".to_string(), Vec::new(), 3..7),
+            ("variable".to_string(), vec!["mutable".to_string()], 20..21),
+        ]
+    );
+
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "\n")], None, cx));
+    let tokens = fetch_tokens(cx).unwrap().await.unwrap();
+    assert_eq!(
+        token_offsets(&tokens, cx),
+        vec![
+            ("function".to_string(), Vec::new(), 4..8),
+            ("variable".to_string(), vec!["mutable".to_string()], 21..22),
+        ],
+        "Delta edits should be applied on top of the previously reported tokens"
+    );
+}
+
 #[gpui::test]
 async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     init_test(cx);
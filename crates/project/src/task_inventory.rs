@@ -8,7 +8,7 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet, VecDeque};
 use dap::DapRegistry;
 use gpui::{App, AppContext as _, Context, Entity, SharedString, Task, WeakEntity};
@@ -21,8 +21,8 @@ use lsp::{LanguageServerId, LanguageServerName};
 use paths::{debug_task_file_name, task_file_name};
 use settings::{InvalidSettingsError, parse_json_with_comments};
 use task::{
-    DebugScenario, ResolvedTask, TaskContext, TaskId, TaskTemplate, TaskTemplates, TaskVariables,
-    VariableName,
+    DebugScenario, DependsOrder, ResolvedTask, TaskContext, TaskId, TaskTemplate, TaskTemplates,
+    TaskVariables, VariableName,
 };
 use text::{BufferId, Point, ToPoint};
 use util::{NumericPrefixWithSuffix, ResultExt as _, post_inc, rel_path::RelPath};
@@ -373,6 +373,35 @@ impl Inventory {
         })
     }
 
+    /// Resolves the tasks the given task depends on, recursively, with the same [`TaskContext`] the task was resolved with.
+    /// Dependencies are looked up by their labels among the worktree tasks of the task's worktree first, and global tasks second.
+    ///
+    /// Fails if any of the dependencies cannot be found or resolved, or if the dependencies form a cycle.
+    pub fn resolve_task_dependencies(
+        &self,
+        task_source_kind: &TaskSourceKind,
+        resolved_task: &ResolvedTask,
+        cx: &App,
+    ) -> Task<Result<TaskDependencies>> {
+        let template = resolved_task.original_task().clone();
+        let Some(task_context) = resolved_task.dependencies_context().cloned() else {
+            return Task::ready(Ok(TaskDependencies {
+                order: template.depends_order,
+                tasks: Vec::new(),
+            }));
+        };
+        let worktree = match task_source_kind {
+            TaskSourceKind::Worktree { id, .. } => Some(*id),
+            _ => None,
+        };
+        let templates = self.list_tasks(None, None, worktree, cx);
+        cx.background_spawn(async move {
+            let templates = templates.await;
+            let mut dependency_chain = vec![template.label.clone()];
+            resolve_dependencies(&template, &templates, &task_context, &mut dependency_chain)
+        })
+    }
+
     /// Pulls its task sources relevant to the worktree and the language given,
     /// returns all task templates with their source kinds, worktree tasks first, language tasks second
     /// and global tasks last. No specific order inside source kinds groups.
@@ -802,6 +831,69 @@ impl Inventory {
     }
 }
 
+/// Tasks a [`ResolvedTask`] depends on, ready to be spawned before it.
+#[derive(Debug, Clone)]
+pub struct TaskDependencies {
+    pub order: DependsOrder,
+    pub tasks: Vec<TaskDependency>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskDependency {
+    pub source_kind: TaskSourceKind,
+    pub task: ResolvedTask,
+    /// Tasks to finish before this dependency is spawned.
+    pub dependencies: TaskDependencies,
+}
+
+fn resolve_dependencies(
+    template: &TaskTemplate,
+    templates: &[(TaskSourceKind, TaskTemplate)],
+    task_context: &TaskContext,
+    dependency_chain: &mut Vec<String>,
+) -> Result<TaskDependencies> {
+    let mut tasks = Vec::with_capacity(template.depends_on.len());
+    for label in &template.depends_on {
+        if dependency_chain.contains(label) {
+            anyhow::bail!(
+                "Task dependency cycle detected: {} -> {label}",
+                dependency_chain.join(" -> ")
+            );
+        }
+        let (source_kind, dependency_template) = templates
+            .iter()
+            .find(|(_, template)| &template.label == label)
+            .with_context(|| {
+                format!(
+                    "Task `{}` depends on an unknown task `{label}`",
+                    template.label
+                )
+            })?;
+        let task = dependency_template
+            .resolve_task(&source_kind.to_id_base(), task_context)
+            .with_context(|| format!("Failed to resolve task `{label}`"))?;
+
+        dependency_chain.push(label.clone());
+        let dependencies = resolve_dependencies(
+            dependency_template,
+            templates,
+            task_context,
+            dependency_chain,
+        )?;
+        dependency_chain.pop();
+
+        tasks.push(TaskDependency {
+            source_kind: source_kind.clone(),
+            task,
+            dependencies,
+        });
+    }
+    Ok(TaskDependencies {
+        order: template.depends_order,
+        tasks,
+    })
+}
+
 fn task_lru_comparator(
     (kind_a, task_a, lru_score_a): &(TaskSourceKind, ResolvedTask, u32),
     (kind_b, task_b, lru_score_b): &(TaskSourceKind, ResolvedTask, u32),
@@ -1392,6 +1484,73 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_task_dependencies_resolution(cx: &mut TestAppContext) {
+        init_test(cx);
+        let inventory = cx.update(|cx| Inventory::new(cx));
+        inventory.update(cx, |inventory, _| {
+            inventory
+                .update_file_based_tasks(
+                    TaskSettingsLocation::Global(tasks_file()),
+                    Some(
+                        &json!([
+                            {
+                                "label": "build all",
+                                "depends_on": ["build server", "build client"],
+                                "depends_order": "sequence"
+                            },
+                            { "label": "build server", "command": "cargo build" },
+                            {
+                                "label": "build client",
+                                "command": "npm run build",
+                                "depends_on": ["codegen"]
+                            },
+                            { "label": "codegen", "command": "make codegen" },
+                            { "label": "cycle start", "depends_on": ["cycle end"] },
+                            { "label": "cycle end", "command": "true", "depends_on": ["cycle start"] },
+                            { "label": "broken", "depends_on": ["missing"] }
+                        ])
+                        .to_string(),
+                    ),
+                )
+                .unwrap();
+        });
+
+        let dependencies = resolve_dependencies_by_label(&inventory, "build all", cx)
+            .await
+            .unwrap();
+        assert_eq!(dependencies.order, DependsOrder::Sequence);
+        assert_eq!(
+            dependencies
+                .tasks
+                .iter()
+                .map(|dependency| dependency.task.resolved_label.as_str())
+                .collect::<Vec<_>>(),
+            vec!["build server", "build client"]
+        );
+        assert!(dependencies.tasks[0].dependencies.tasks.is_empty());
+        let client_dependencies = &dependencies.tasks[1].dependencies;
+        assert_eq!(client_dependencies.order, DependsOrder::Parallel);
+        assert_eq!(client_dependencies.tasks.len(), 1);
+        assert_eq!(client_dependencies.tasks[0].task.resolved_label, "codegen");
+
+        let cycle_error = resolve_dependencies_by_label(&inventory, "cycle start", cx)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            cycle_error.to_string(),
+            "Task dependency cycle detected: cycle start -> cycle end -> cycle start"
+        );
+
+        let missing_error = resolve_dependencies_by_label(&inventory, "broken", cx)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            missing_error.to_string(),
+            "Task `broken` depends on an unknown task `missing`"
+        );
+    }
+
     #[gpui::test]
     async fn test_inventory_static_task_filters(cx: &mut TestAppContext) {
         init_test(cx);
@@ -1570,6 +1729,29 @@ mod tests {
         })
     }
 
+    async fn resolve_dependencies_by_label(
+        inventory: &Entity<Inventory>,
+        label: &str,
+        cx: &mut TestAppContext,
+    ) -> Result<TaskDependencies> {
+        let (source_kind, template) = inventory
+            .update(cx, |inventory, cx| {
+                inventory.list_tasks(None, None, None, cx)
+            })
+            .await
+            .into_iter()
+            .find(|(_, template)| template.label == label)
+            .unwrap();
+        let resolved_task = template
+            .resolve_task(&source_kind.to_id_base(), &TaskContext::default())
+            .unwrap();
+        inventory
+            .update(cx, |inventory, cx| {
+                inventory.resolve_task_dependencies(&source_kind, &resolved_task, cx)
+            })
+            .await
+    }
+
     fn mock_tasks_from_names<'a>(task_names: impl IntoIterator<Item = &'a str> + 'a) -> String {
         serde_json::to_string(&serde_json::Value::Array(
             task_names
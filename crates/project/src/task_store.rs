@@ -15,7 +15,7 @@ use rpc::{AnyProtoClient, TypedEnvelope, proto};
 use settings::{InvalidSettingsError, SettingsLocation};
 use task::{TaskContext, TaskVariables, VariableName};
 use text::{BufferId, OffsetRangeExt};
-use util::ResultExt;
+use util::{ResultExt, maybe, rel_path::RelPath};
 
 use crate::{
     BasicContextProvider, Inventory, ProjectEnvironment, buffer_store::BufferStore,
@@ -313,15 +313,35 @@ fn local_task_context_for_location(
         .and_then(|worktree_id| worktree_store.read(cx).worktree_for_id(worktree_id, cx))
         .and_then(|worktree| worktree.read(cx).root_dir());
     let fs = worktree_store.read(cx).fs();
+    let toolchain_location = maybe!({
+        let buffer = location.buffer.read(cx);
+        let language = buffer.language()?;
+        let lister = language.toolchain_lister()?;
+        let file = buffer.file()?;
+        let directory: Arc<RelPath> = file
+            .path()
+            .parent()
+            .map(Arc::from)
+            .unwrap_or_else(|| RelPath::empty().into());
+        Some((file.worktree_id(cx), directory, language.name(), lister))
+    });
 
     cx.spawn(async move |cx| {
-        let project_env = environment
+        let mut project_env = environment
             .update(cx, |environment, cx| {
                 environment.buffer_environment(&location.buffer, &worktree_store, cx)
             })
             .ok()?
             .await;
 
+        if let Some((worktree_id, directory, language_name, lister)) = toolchain_location
+            && let Some(toolchain) = toolchain_store
+                .active_toolchain(worktree_id, directory, language_name, cx)
+                .await
+        {
+            lister.activate_environment(&toolchain, project_env.get_or_insert_default());
+        }
+
         let mut task_variables = cx
             .update(|cx| {
                 combine_task_variables(
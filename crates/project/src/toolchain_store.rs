@@ -47,6 +47,26 @@ pub struct Toolchains {
     pub root_path: Arc<RelPath>,
     pub user_toolchains: BTreeMap<ToolchainScope, IndexSet<Toolchain>>,
 }
+
+impl Toolchains {
+    /// Returns the toolchain to activate when the user hasn't selected one: the first one they added
+    /// for the project, or else the lister's default, like the toolchain pinned by the project.
+    pub fn default_choice(&self) -> Option<Toolchain> {
+        self.user_toolchains
+            .iter()
+            .find_map(|(scope, toolchains)| {
+                // Global toolchains are unlikely to be the right choice for this project.
+                if scope == &ToolchainScope::Global {
+                    None
+                } else {
+                    toolchains.first()
+                }
+            })
+            .cloned()
+            .or_else(|| self.toolchains.default_toolchain())
+            .or_else(|| self.toolchains.toolchains.first().cloned())
+    }
+}
 impl EventEmitter<ToolchainStoreEvent> for ToolchainStore {}
 impl ToolchainStore {
     pub fn init(client: &AnyProtoClient) {
@@ -196,11 +216,18 @@ impl ToolchainStore {
         };
         cx.spawn(async move |_, _| {
             let (mut toolchains, root_path) = task.await?;
+            let default = toolchains.default_toolchain();
             toolchains.toolchains.retain(|toolchain| {
                 !user_toolchains
                     .values()
                     .any(|toolchains| toolchains.contains(toolchain))
             });
+            toolchains.default = default.and_then(|default| {
+                toolchains
+                    .toolchains
+                    .iter()
+                    .position(|toolchain| toolchain == &default)
+            });
 
             Some(Toolchains {
                 toolchains,
@@ -307,6 +334,10 @@ impl ToolchainStore {
             })??
             .await;
         let has_values = toolchains.is_some();
+        let default_index = toolchains
+            .as_ref()
+            .and_then(|toolchains| toolchains.toolchains.default)
+            .and_then(|ix| u64::try_from(ix).ok());
         let groups = if let Some(Toolchains { toolchains, .. }) = &toolchains {
             toolchains
                 .groups
@@ -349,6 +380,7 @@ impl ToolchainStore {
             toolchains,
             groups,
             relative_worktree_path: Some(relative_path.to_proto()),
+            default_index,
         })
     }
 
@@ -673,6 +705,9 @@ impl RemoteToolchainStore {
             if !response.has_values {
                 return None;
             }
+            let default = response
+                .default_index
+                .and_then(|ix| usize::try_from(ix).ok());
             let toolchains = response
                 .toolchains
                 .into_iter()
@@ -702,7 +737,7 @@ impl RemoteToolchainStore {
             Some((
                 ToolchainList {
                     toolchains,
-                    default: None,
+                    default,
                     groups,
                 },
                 relative_path,
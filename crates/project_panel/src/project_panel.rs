@@ -15,7 +15,7 @@ use editor::{
 };
 use file_icons::FileIcons;
 use git;
-use git::status::GitSummary;
+use git::{repository::Submodule, status::GitSummary};
 use git_ui;
 use git_ui::file_diff_view::FileDiffView;
 use gpui::{
@@ -131,6 +131,8 @@ pub struct ProjectPanel {
     pending_serialization: Task<Option<()>>,
     diagnostics: HashMap<(WorktreeId, Arc<RelPath>), DiagnosticSeverity>,
     diagnostic_summary_update: Task<()>,
+    /// The submodules in the project, kept up to date as the repositories change.
+    submodules: HashMap<ProjectPath, Submodule>,
     // We keep track of the mouse down state on entries so we don't flash the UI
     // in case a user clicks to open a file.
     mouse_down: bool,
@@ -227,6 +229,7 @@ struct EntryDetails {
     filename_text_color: Color,
     diagnostic_severity: Option<DiagnosticSeverity>,
     git_status: GitSummary,
+    submodule: Option<Submodule>,
     is_private: bool,
     worktree_id: WorktreeId,
     canonical_path: Option<Arc<Path>>,
@@ -590,9 +593,14 @@ impl ProjectPanel {
                     GitStoreEvent::RepositoryUpdated(_, RepositoryEvent::StatusesChanged, _)
                     | GitStoreEvent::RepositoryAdded
                     | GitStoreEvent::RepositoryRemoved(_) => {
+                        this.update_submodules(cx);
                         this.update_visible_entries(None, false, false, window, cx);
                         cx.notify();
                     }
+                    GitStoreEvent::RepositoryUpdated(_, RepositoryEvent::SubmodulesChanged, _) => {
+                        this.update_submodules(cx);
+                        cx.notify();
+                    }
                     _ => {}
                 },
             )
@@ -802,6 +810,7 @@ impl ProjectPanel {
                 pending_serialization: Task::ready(None),
                 diagnostics: Default::default(),
                 diagnostic_summary_update: Task::ready(()),
+                submodules: HashMap::default(),
                 scroll_handle,
                 mouse_down: false,
                 hover_expand_task: None,
@@ -820,6 +829,7 @@ impl ProjectPanel {
                 },
                 update_visible_entries_task: Default::default(),
             };
+            this.update_submodules(cx);
             this.update_visible_entries(None, false, false, window, cx);
 
             this
@@ -950,6 +960,15 @@ impl ProjectPanel {
         })
     }
 
+    fn update_submodules(&mut self, cx: &App) {
+        self.submodules = self
+            .project
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .submodules_by_project_path(cx);
+    }
+
     fn update_diagnostics(&mut self, cx: &mut Context<Self>) {
         let mut diagnostics: HashMap<(WorktreeId, Arc<RelPath>), DiagnosticSeverity> =
             Default::default();
@@ -4460,6 +4479,7 @@ impl ProjectPanel {
 
         let filename_text_color = details.filename_text_color;
         let diagnostic_severity = details.diagnostic_severity;
+        let submodule = details.submodule.clone();
         let item_colors = get_item_color(is_sticky, cx);
 
         let canonical_path = details
@@ -4847,24 +4867,49 @@ impl ProjectPanel {
                         }
                     })
                     .selectable(false)
-                    .when_some(canonical_path, |this, path| {
+                    .when(canonical_path.is_some() || submodule.is_some(), |this| {
                         this.end_slot::<AnyElement>(
-                            div()
-                                .id("symlink_icon")
+                            h_flex()
                                 .pr_3()
-                                .tooltip(move |_window, cx| {
-                                    Tooltip::with_meta(
-                                        path.to_string(),
-                                        None,
-                                        "Symbolic Link",
-                                        cx,
+                                .gap_1()
+                                .when_some(submodule, |this, submodule| {
+                                    this.child(
+                                        div()
+                                            .id("submodule_state")
+                                            .tooltip(move |_window, cx| {
+                                                Tooltip::with_meta(
+                                                    "Submodule",
+                                                    None,
+                                                    submodule.url.clone().unwrap_or_default(),
+                                                    cx,
+                                                )
+                                            })
+                                            .child(
+                                                Label::new(git_ui::submodule_summary(&submodule))
+                                                    .size(LabelSize::XSmall)
+                                                    .color(Color::Muted),
+                                            ),
+                                    )
+                                })
+                                .when_some(canonical_path, |this, path| {
+                                    this.child(
+                                        div()
+                                            .id("symlink_icon")
+                                            .tooltip(move |_window, cx| {
+                                                Tooltip::with_meta(
+                                                    path.to_string(),
+                                                    None,
+                                                    "Symbolic Link",
+                                                    cx,
+                                                )
+                                            })
+                                            .child(
+                                                Icon::new(IconName::ArrowUpRight)
+                                                    .size(IconSize::Indicator)
+                                                    .color(filename_text_color),
+                                            ),
                                     )
                                 })
-                                .child(
-                                    Icon::new(IconName::ArrowUpRight)
-                                        .size(IconSize::Indicator)
-                                        .color(filename_text_color),
-                                )
                                 .into_any_element(),
                         )
                     })
@@ -5191,6 +5236,17 @@ impl ProjectPanel {
         let filename_text_color =
             entry_git_aware_label_color(git_status, entry.is_ignored, is_marked);
 
+        let submodule = if entry.is_dir() {
+            self.submodules
+                .get(&ProjectPath {
+                    worktree_id,
+                    path: entry.path.clone(),
+                })
+                .cloned()
+        } else {
+            None
+        };
+
         let is_cut = self
             .clipboard
             .as_ref()
@@ -5213,6 +5269,7 @@ impl ProjectPanel {
             filename_text_color,
             diagnostic_severity,
             git_status,
+            submodule,
             is_private: entry.is_private,
             worktree_id,
             canonical_path: entry.canonical_path.clone(),
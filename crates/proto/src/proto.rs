@@ -121,6 +121,18 @@ messages!(
     (GetTypeDefinitionResponse, Background),
     (GetImplementation, Background),
     (GetImplementationResponse, Background),
+    (PrepareCallHierarchy, Background),
+    (PrepareCallHierarchyResponse, Background),
+    (GetIncomingCalls, Background),
+    (GetIncomingCallsResponse, Background),
+    (GetOutgoingCalls, Background),
+    (GetOutgoingCallsResponse, Background),
+    (PrepareTypeHierarchy, Background),
+    (PrepareTypeHierarchyResponse, Background),
+    (GetSupertypes, Background),
+    (GetSupertypesResponse, Background),
+    (GetSubtypes, Background),
+    (GetSubtypesResponse, Background),
     (OpenUnstagedDiff, Foreground),
     (OpenUnstagedDiffResponse, Foreground),
     (OpenUncommittedDiff, Foreground),
@@ -227,6 +239,9 @@ messages!(
     (RefreshCodeLens, Background),
     (GetCodeLens, Background),
     (GetCodeLensResponse, Background),
+    (GetSemanticTokens, Background),
+    (GetSemanticTokensResponse, Background),
+    (RefreshSemanticTokens, Background),
     (RespondToChannelInvite, Foreground),
     (RespondToContactRequest, Foreground),
     (RestartLanguageServers, Foreground),
@@ -296,6 +311,25 @@ messages!(
     (GitCommitDetails, Background),
     (GitFileHistory, Background),
     (GitFileHistoryResponse, Background),
+    (GitCommitsSince, Background),
+    (GitCommitsSinceResponse, Background),
+    (GitRebaseInteractive, Background),
+    (GitRebaseOperation, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitSequencerOperation, Background),
+    (GitGetTags, Background),
+    (GitTagsResponse, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitCheckoutTag, Background),
+    (GitTagsContaining, Background),
+    (GitTagsContainingResponse, Background),
+    (GitPushTag, Background),
+    (GitBisectStart, Background),
+    (GitBisectMark, Background),
+    (GitBisectReset, Background),
+    (GitSubmoduleOperation, Background),
     (SetIndexText, Background),
     (Push, Background),
     (Fetch, Background),
@@ -379,6 +413,13 @@ request_messages!(
     (GetImplementation, GetImplementationResponse),
     (GetDocumentHighlights, GetDocumentHighlightsResponse),
     (GetDocumentSymbols, GetDocumentSymbolsResponse),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse),
+    (GetIncomingCalls, GetIncomingCallsResponse),
+    (GetOutgoingCalls, GetOutgoingCallsResponse),
+    (PrepareTypeHierarchy, PrepareTypeHierarchyResponse),
+    (GetSupertypes, GetSupertypesResponse),
+    (GetSubtypes, GetSubtypesResponse),
+    (GetSemanticTokens, GetSemanticTokensResponse),
     (GetHover, GetHoverResponse),
     (GetNotifications, GetNotificationsResponse),
     (GetProjectSymbols, GetProjectSymbolsResponse),
@@ -417,6 +458,7 @@ request_messages!(
     (PrepareRename, PrepareRenameResponse),
     (RefreshInlayHints, Ack),
     (RefreshCodeLens, Ack),
+    (RefreshSemanticTokens, Ack),
     (RejoinChannelBuffers, RejoinChannelBuffersResponse),
     (RejoinRoom, RejoinRoomResponse),
     (ReloadBuffers, ReloadBuffersResponse),
@@ -497,6 +539,22 @@ request_messages!(
     (RegisterBufferWithLanguageServers, Ack),
     (GitShow, GitCommitDetails),
     (GitFileHistory, GitFileHistoryResponse),
+    (GitCommitsSince, GitCommitsSinceResponse),
+    (GitRebaseInteractive, Ack),
+    (GitRebaseOperation, Ack),
+    (GitCherryPick, Ack),
+    (GitRevert, Ack),
+    (GitSequencerOperation, Ack),
+    (GitGetTags, GitTagsResponse),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitCheckoutTag, Ack),
+    (GitTagsContaining, GitTagsContainingResponse),
+    (GitPushTag, RemoteMessageResponse),
+    (GitBisectStart, Ack),
+    (GitBisectMark, Ack),
+    (GitBisectReset, Ack),
+    (GitSubmoduleOperation, Ack),
     (GitReset, Ack),
     (GitDeleteBranch, Ack),
     (GitCheckoutFiles, Ack),
@@ -579,6 +637,13 @@ entity_messages!(
     GetImplementation,
     GetDocumentHighlights,
     GetDocumentSymbols,
+    PrepareCallHierarchy,
+    GetIncomingCalls,
+    GetOutgoingCalls,
+    PrepareTypeHierarchy,
+    GetSupertypes,
+    GetSubtypes,
+    GetSemanticTokens,
     GetHover,
     GetProjectSymbols,
     GetReferences,
@@ -606,6 +671,7 @@ entity_messages!(
     PrepareRename,
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     ReloadBuffers,
     RemoveProjectCollaborator,
     RenameProjectEntry,
@@ -665,6 +731,22 @@ entity_messages!(
     RegisterBufferWithLanguageServers,
     GitShow,
     GitFileHistory,
+    GitCommitsSince,
+    GitRebaseInteractive,
+    GitRebaseOperation,
+    GitCherryPick,
+    GitRevert,
+    GitSequencerOperation,
+    GitGetTags,
+    GitCreateTag,
+    GitDeleteTag,
+    GitCheckoutTag,
+    GitTagsContaining,
+    GitPushTag,
+    GitBisectStart,
+    GitBisectMark,
+    GitBisectReset,
+    GitSubmoduleOperation,
     GitReset,
     GitDeleteBranch,
     GitCheckoutFiles,
@@ -4,19 +4,24 @@ use std::sync::Arc;
 use editor::{Editor, EditorMode, MultiBuffer};
 use futures::future::Shared;
 use gpui::{
-    App, Entity, Hsla, RetainAllImageCache, Task, TextStyleRefinement, image_cache, prelude::*,
+    App, Entity, EventEmitter, Hsla, RetainAllImageCache, Task, TextStyleRefinement, WeakEntity,
+    image_cache, prelude::*,
 };
 use language::{Buffer, Language, LanguageRegistry};
 use markdown_preview::{markdown_parser::parse_markdown, markdown_renderer::render_markdown_block};
 use nbformat::v4::{CellId, CellMetadata, CellType};
+use runtimelib::{JupyterMessage, JupyterMessageContent, MimeBundle};
 use settings::Settings as _;
 use theme::ThemeSettings;
 use ui::{IconButtonShape, prelude::*};
 use util::ResultExt;
+use workspace::Workspace;
 
 use crate::{
     notebook::{CODE_BLOCK_INSET, GUTTER_WIDTH},
-    outputs::{Output, plain::TerminalOutput, user_error::ErrorView},
+    outputs::{
+        ExecutionStatus, ExecutionView, Output, plain::TerminalOutput, user_error::ErrorView,
+    },
 };
 
 #[derive(Copy, Clone, PartialEq, PartialOrd)]
@@ -117,6 +122,7 @@ impl Cell {
         cell: &nbformat::v4::Cell,
         languages: &Arc<LanguageRegistry>,
         notebook_language: Shared<Task<Option<Arc<Language>>>>,
+        workspace: WeakEntity<Workspace>,
         window: &mut Window,
         cx: &mut App,
     ) -> Self {
@@ -125,7 +131,7 @@ impl Cell {
                 id,
                 metadata,
                 source,
-                ..
+                attachments,
             } => {
                 let source = source.join("");
 
@@ -155,6 +161,7 @@ impl Cell {
                         id: id.clone(),
                         metadata: metadata.clone(),
                         source: source.clone(),
+                        attachments: attachments.clone(),
                         parsed_markdown: None,
                         selected: false,
                         cell_position: None,
@@ -197,7 +204,6 @@ impl Cell {
                         ..Default::default()
                     };
 
-                    editor.set_text(text, window, cx);
                     editor.set_show_gutter(false, cx);
                     editor.set_text_style_refinement(refinement);
 
@@ -214,13 +220,22 @@ impl Cell {
                     });
                 });
 
+                let execution_view = cx.new(|cx| {
+                    let mut execution_view =
+                        ExecutionView::new(ExecutionStatus::Finished, workspace, cx);
+                    execution_view.outputs = convert_outputs(outputs, window, cx);
+                    execution_view
+                });
+
                 CodeCell {
                     id: id.clone(),
                     metadata: metadata.clone(),
                     execution_count: *execution_count,
                     source: source.join(""),
                     editor: editor_view,
-                    outputs: convert_outputs(outputs, window, cx),
+                    execution_view,
+                    nbformat_outputs: outputs.clone(),
+                    pending_clear_output: false,
                     selected: false,
                     language_task,
                     cell_position: None,
@@ -239,6 +254,70 @@ impl Cell {
             })),
         }
     }
+
+    pub fn id(&self, cx: &App) -> CellId {
+        match self {
+            Cell::Code(cell) => cell.read(cx).id().clone(),
+            Cell::Markdown(cell) => cell.read(cx).id().clone(),
+            Cell::Raw(cell) => cell.read(cx).id().clone(),
+        }
+    }
+
+    /// Converts the cell back to nbformat, with the source as currently edited.
+    pub fn to_nbformat(&self, cx: &App) -> nbformat::v4::Cell {
+        match self {
+            Cell::Markdown(cell) => {
+                let cell = cell.read(cx);
+                nbformat::v4::Cell::Markdown {
+                    id: cell.id.clone(),
+                    metadata: cell.metadata.clone(),
+                    source: source_lines(&cell.source),
+                    attachments: cell.attachments.clone(),
+                }
+            }
+            Cell::Code(cell) => {
+                let cell = cell.read(cx);
+                nbformat::v4::Cell::Code {
+                    id: cell.id.clone(),
+                    metadata: cell.metadata.clone(),
+                    execution_count: cell.execution_count,
+                    source: source_lines(&cell.editor.read(cx).text(cx)),
+                    outputs: cell.nbformat_outputs.clone(),
+                }
+            }
+            Cell::Raw(cell) => {
+                let cell = cell.read(cx);
+                nbformat::v4::Cell::Raw {
+                    id: cell.id.clone(),
+                    metadata: cell.metadata.clone(),
+                    source: source_lines(&cell.source),
+                }
+            }
+        }
+    }
+}
+
+/// Converts an output of a kernel message to nbformat, through the JSON they share.
+fn nbformat_output(
+    output_type: &str,
+    output: serde_json::Result<serde_json::Value>,
+) -> anyhow::Result<nbformat::v4::Output> {
+    let mut output = output?;
+    if let Some(output) = output.as_object_mut() {
+        // Transient data, like display ids, isn't saved in the notebook.
+        output.remove("transient");
+        output.insert("output_type".to_string(), output_type.into());
+    }
+    Ok(serde_json::from_value(output)?)
+}
+
+/// Splits a cell's source into lines the way nbformat stores them,
+/// each one keeping its trailing newline.
+pub(crate) fn source_lines(source: &str) -> Vec<String> {
+    source
+        .split_inclusive('\n')
+        .map(ToString::to_string)
+        .collect()
 }
 
 pub trait RenderableCell: Render {
@@ -337,6 +416,7 @@ pub struct MarkdownCell {
     metadata: CellMetadata,
     image_cache: Entity<RetainAllImageCache>,
     source: String,
+    attachments: Option<serde_json::Value>,
     parsed_markdown: Option<markdown_preview::markdown_elements::ParsedMarkdown>,
     markdown_parsing_task: Task<()>,
     selected: bool,
@@ -434,7 +514,11 @@ pub struct CodeCell {
     execution_count: Option<i32>,
     source: String,
     editor: Entity<editor::Editor>,
-    outputs: Vec<Output>,
+    execution_view: Entity<ExecutionView>,
+    /// The outputs as stored in the notebook, written back when saving.
+    nbformat_outputs: Vec<nbformat::v4::Output>,
+    /// Whether the kernel asked to clear the outputs once the next one arrives.
+    pending_clear_output: bool,
     selected: bool,
     cell_position: Option<CellPosition>,
     language_task: Task<()>,
@@ -444,16 +528,159 @@ impl CodeCell {
     pub fn is_dirty(&self, cx: &App) -> bool {
         self.editor.read(cx).buffer().read(cx).is_dirty(cx)
     }
-    pub fn has_outputs(&self) -> bool {
-        !self.outputs.is_empty()
+
+    pub fn buffer(&self, cx: &App) -> Option<Entity<Buffer>> {
+        self.editor.read(cx).buffer().read(cx).as_singleton()
+    }
+
+    /// Marks the cell's source as saved, so it's no longer dirty.
+    pub fn did_save(&mut self, cx: &mut App) {
+        if let Some(buffer) = self.buffer(cx) {
+            buffer.update(cx, |buffer, cx| buffer.did_save(buffer.version(), None, cx));
+        }
+    }
+
+    pub fn source_text(&self, cx: &App) -> String {
+        self.editor.read(cx).text(cx)
+    }
+
+    pub(crate) fn execution_view(&self) -> &Entity<ExecutionView> {
+        &self.execution_view
+    }
+
+    pub fn has_outputs(&self, cx: &App) -> bool {
+        !self.execution_view.read(cx).outputs.is_empty()
+    }
+
+    pub fn clear_outputs(&mut self, cx: &mut App) {
+        self.execution_view.update(cx, |execution_view, cx| {
+            execution_view.outputs.clear();
+            cx.notify();
+        });
+        self.nbformat_outputs.clear();
+    }
+
+    /// Whether there are outputs or an execution status to show below the cell.
+    fn shows_execution(&self, cx: &App) -> bool {
+        let execution_view = self.execution_view.read(cx);
+        !execution_view.outputs.is_empty()
+            || !matches!(
+                execution_view.status,
+                ExecutionStatus::Finished | ExecutionStatus::Unknown
+            )
+    }
+
+    /// Clears the cell's outputs and execution count for a new execution.
+    pub(crate) fn start_execution(&mut self, status: ExecutionStatus, cx: &mut Context<Self>) {
+        self.execution_count = None;
+        self.nbformat_outputs.clear();
+        self.pending_clear_output = false;
+        self.execution_view.update(cx, |execution_view, cx| {
+            execution_view.outputs.clear();
+            execution_view.status = status;
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    /// Stops showing the cell as queued or executing, when its execution won't finish.
+    pub(crate) fn abandon_execution(&mut self, cx: &mut Context<Self>) {
+        self.execution_view.update(cx, |execution_view, cx| {
+            if matches!(
+                execution_view.status,
+                ExecutionStatus::ConnectingToKernel
+                    | ExecutionStatus::Queued
+                    | ExecutionStatus::Executing
+                    | ExecutionStatus::Restarting
+            ) {
+                execution_view.status = ExecutionStatus::Unknown;
+                cx.notify();
+            }
+        });
+        cx.notify();
     }
 
-    pub fn clear_outputs(&mut self) {
-        self.outputs.clear();
+    /// Accepts a message from the kernel replying to the cell's execution.
+    pub(crate) fn handle_message(
+        &mut self,
+        message: &JupyterMessage,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match &message.content {
+            JupyterMessageContent::ExecuteInput(input) => {
+                self.execution_count = Some(input.execution_count.0 as i32);
+            }
+            JupyterMessageContent::ExecuteReply(reply) => {
+                self.execution_count = Some(reply.execution_count.0 as i32);
+            }
+            _ => {}
+        }
+        self.record_output(&message.content);
+        self.execution_view.update(cx, |execution_view, cx| {
+            execution_view.push_message(&message.content, window, cx);
+        });
+        cx.notify();
     }
 
-    fn output_control(&self) -> Option<CellControlType> {
-        if self.has_outputs() {
+    pub(crate) fn update_display_data(
+        &mut self,
+        data: &MimeBundle,
+        display_id: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.execution_view.update(cx, |execution_view, cx| {
+            execution_view.update_display_data(data, display_id, window, cx);
+        });
+    }
+
+    /// Records an output the way it's saved in the notebook.
+    fn record_output(&mut self, content: &JupyterMessageContent) {
+        let (output_type, output) = match content {
+            JupyterMessageContent::StreamContent(stream) => {
+                ("stream", serde_json::to_value(stream))
+            }
+            JupyterMessageContent::DisplayData(display_data) => {
+                ("display_data", serde_json::to_value(display_data))
+            }
+            JupyterMessageContent::ExecuteResult(result) => {
+                ("execute_result", serde_json::to_value(result))
+            }
+            JupyterMessageContent::ErrorOutput(error) => ("error", serde_json::to_value(error)),
+            JupyterMessageContent::ClearOutput(options) => {
+                if options.wait {
+                    self.pending_clear_output = true;
+                } else {
+                    self.nbformat_outputs.clear();
+                }
+                return;
+            }
+            _ => return,
+        };
+        let Some(output) = nbformat_output(output_type, output).log_err() else {
+            return;
+        };
+
+        if std::mem::take(&mut self.pending_clear_output) {
+            self.nbformat_outputs.clear();
+        }
+        // Consecutive stream outputs are saved as one, like Jupyter does.
+        if let nbformat::v4::Output::Stream { name, text } = &output
+            && let Some(nbformat::v4::Output::Stream {
+                name: last_name,
+                text: last_text,
+            }) = self.nbformat_outputs.last_mut()
+            && last_name == name
+        {
+            last_text.0.push_str(&text.0);
+            return;
+        }
+        self.nbformat_outputs.push(output);
+    }
+
+    fn output_control(&self, cx: &App) -> Option<CellControlType> {
+        if self.has_outputs(cx) {
             Some(CellControlType::ClearCell)
         } else {
             None
@@ -483,7 +710,7 @@ impl CodeCell {
                             .when(!is_selected, |this| this.bg(cx.theme().colors().border)),
                     ),
             )
-            .when(self.has_outputs(), |this| {
+            .when(self.has_outputs(cx), |this| {
                 this.child(
                     div()
                         .absolute()
@@ -522,14 +749,13 @@ impl RenderableCell for CodeCell {
     }
 
     fn control(&self, window: &mut Window, cx: &mut Context<Self>) -> Option<CellControl> {
-        let cell_control = if self.has_outputs() {
+        let cell_control = if self.has_outputs(cx) {
             CellControl::new("rerun-cell", CellControlType::RerunCell)
         } else {
             CellControl::new("run-cell", CellControlType::RunCell)
-                .on_click(cx.listener(move |this, _, window, cx| this.run(window, cx)))
         };
 
-        Some(cell_control)
+        Some(cell_control.on_click(cx.listener(move |this, _, window, cx| this.run(window, cx))))
     }
 
     fn selected(&self) -> bool {
@@ -551,9 +777,16 @@ impl RenderableCell for CodeCell {
     }
 }
 
+pub enum CodeCellEvent {
+    /// The cell's run control was clicked.
+    Run,
+}
+
+impl EventEmitter<CodeCellEvent> for CodeCell {}
+
 impl RunnableCell for CodeCell {
-    fn run(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Running code cell: {}", self.id);
+    fn run(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(CodeCellEvent::Run);
     }
 
     fn execution_count(&self) -> Option<i32> {
@@ -586,6 +819,7 @@ impl Render for CodeCell {
                     .child(
                         div().py_1p5().w_full().child(
                             div()
+                                .relative()
                                 .flex()
                                 .size_full()
                                 .flex_1()
@@ -595,74 +829,45 @@ impl Render for CodeCell {
                                 .border_1()
                                 .border_color(cx.theme().colors().border)
                                 .bg(cx.theme().colors().editor_background)
-                                .child(div().w_full().child(self.editor.clone())),
+                                .child(div().w_full().child(self.editor.clone()))
+                                .when_some(self.execution_count(), |this, execution_count| {
+                                    this.child(
+                                        div().absolute().top_1().right_2().child(
+                                            Label::new(format!("[{execution_count}]"))
+                                                .size(LabelSize::XSmall)
+                                                .color(Color::Muted),
+                                        ),
+                                    )
+                                }),
                         ),
                     ),
             )
             // Output portion
-            .child(
-                h_flex()
-                    .w_full()
-                    .pr_6()
-                    .rounded_xs()
-                    .items_start()
-                    .gap(DynamicSpacing::Base08.rems(cx))
-                    .bg(self.selected_bg_color(window, cx))
-                    .child(self.gutter_output(window, cx))
-                    .child(
-                        div().py_1p5().w_full().child(
-                            div()
-                                .flex()
-                                .size_full()
-                                .flex_1()
-                                .py_3()
-                                .px_5()
-                                .rounded_lg()
-                                .border_1()
-                                // .border_color(cx.theme().colors().border)
-                                // .bg(cx.theme().colors().editor_background)
-                                .child(div().w_full().children(self.outputs.iter().map(
-                                    |output| {
-                                        let content = match output {
-                                            Output::Plain { content, .. } => {
-                                                Some(content.clone().into_any_element())
-                                            }
-                                            Output::Markdown { content, .. } => {
-                                                Some(content.clone().into_any_element())
-                                            }
-                                            Output::Stream { content, .. } => {
-                                                Some(content.clone().into_any_element())
-                                            }
-                                            Output::Image { content, .. } => {
-                                                Some(content.clone().into_any_element())
-                                            }
-                                            Output::Message(message) => Some(
-                                                div().child(message.clone()).into_any_element(),
-                                            ),
-                                            Output::Table { content, .. } => {
-                                                Some(content.clone().into_any_element())
-                                            }
-                                            Output::ErrorOutput(error_view) => {
-                                                error_view.render(window, cx)
-                                            }
-                                            Output::ClearOutputWaitMarker => None,
-                                        };
-
-                                        div()
-                                            // .w_full()
-                                            // .mt_3()
-                                            // .p_3()
-                                            // .rounded_sm()
-                                            // .bg(cx.theme().colors().editor_background)
-                                            // .border(px(1.))
-                                            // .border_color(cx.theme().colors().border)
-                                            // .shadow_xs()
-                                            .children(content)
-                                    },
-                                ))),
+            .when(self.shows_execution(cx), |this| {
+                this.child(
+                    h_flex()
+                        .w_full()
+                        .pr_6()
+                        .rounded_xs()
+                        .items_start()
+                        .gap(DynamicSpacing::Base08.rems(cx))
+                        .bg(self.selected_bg_color(window, cx))
+                        .child(self.gutter_output(window, cx))
+                        .child(
+                            div().py_1p5().w_full().child(
+                                div()
+                                    .flex()
+                                    .size_full()
+                                    .flex_1()
+                                    .py_3()
+                                    .px_5()
+                                    .rounded_lg()
+                                    .border_1()
+                                    .child(div().w_full().child(self.execution_view.clone())),
+                            ),
                         ),
-                    ),
-            )
+                )
+            })
             // TODO: Move base cell render into trait impl so we don't have to repeat this
             .children(self.cell_position_spacer(false, window, cx))
     }
@@ -1,37 +1,55 @@
 #![allow(unused, dead_code)]
 use std::future::Future;
+use std::ops::Range;
 use std::{path::PathBuf, sync::Arc};
 
-use anyhow::{Context as _, Result};
+use anyhow::{Context as _, Result, anyhow};
 use client::proto::ViewId;
-use collections::HashMap;
+use collections::{HashMap, HashSet};
 use feature_flags::{FeatureFlagAppExt as _, NotebookFeatureFlag};
 use futures::FutureExt;
 use futures::future::Shared;
 use gpui::{
-    AnyElement, App, Entity, EventEmitter, FocusHandle, Focusable, ListScrollEvent, ListState,
-    Point, Task, actions, list, prelude::*,
+    AnyElement, App, Entity, EntityId, EventEmitter, FocusHandle, Focusable, ListScrollEvent,
+    ListState, MouseButton, Point, Subscription, Task, WeakEntity, actions, list, prelude::*,
 };
-use language::{Language, LanguageRegistry};
-use project::{Project, ProjectEntryId, ProjectPath};
+use language::{BufferEvent, Language, LanguageRegistry, LineEnding};
+use project::{Fs, MTime, Project, ProjectEntryId, ProjectPath};
+use serde_json::{Value, json};
 use ui::{Tooltip, prelude::*};
+use util::ResultExt as _;
+use uuid::Uuid;
 use workspace::item::{ItemEvent, SaveOptions, TabContentParams};
 use workspace::searchable::SearchableItemHandle;
-use workspace::{Item, ItemHandle, Pane, ProjectItem, ToolbarItemLocation};
+use workspace::{Item, ItemHandle, Pane, ProjectItem, ToolbarItemLocation, Workspace};
 use workspace::{ToolbarItemEvent, ToolbarItemView};
 
-use super::{Cell, CellPosition, RenderableCell};
+use super::{Cell, CellPosition, CodeCell, CodeCellEvent, RenderableCell};
+use crate::components::KernelSelector;
+use crate::repl_store::ReplStore;
+use crate::session::SessionEvent;
+use crate::{KernelSpecification, Session};
 
-use nbformat::v4::CellId;
 use nbformat::v4::Metadata as NotebookMetadata;
+use nbformat::v4::{CellId, CellType};
 
 actions!(
     notebook,
     [
         /// Opens a Jupyter notebook file.
         OpenNotebook,
+        /// Runs the current cell.
+        RunCell,
         /// Runs all cells in the notebook.
         RunAll,
+        /// Runs all cells above the current cell.
+        RunCellsAbove,
+        /// Runs the current cell and all cells below it.
+        RunCellsBelow,
+        /// Interrupts the notebook's kernel.
+        InterruptKernel,
+        /// Restarts the notebook's kernel.
+        RestartKernel,
         /// Clears all cell outputs.
         ClearOutputs,
         /// Moves the current cell up.
@@ -42,6 +60,8 @@ actions!(
         AddMarkdownBlock,
         /// Adds a new code cell.
         AddCodeBlock,
+        /// Deletes the current cell.
+        DeleteCell,
     ]
 );
 
@@ -74,6 +94,7 @@ pub fn init(cx: &mut App) {
 pub struct NotebookEditor {
     languages: Arc<LanguageRegistry>,
     project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
 
     focus_handle: FocusHandle,
     notebook_item: Entity<NotebookItem>,
@@ -84,6 +105,73 @@ pub struct NotebookEditor {
     selected_cell_index: usize,
     cell_order: Vec<CellId>,
     cell_map: HashMap<CellId, Cell>,
+
+    /// Whether the cells changed in ways their buffers don't track, e.g. by clearing outputs.
+    edited: bool,
+    has_conflict: bool,
+    undo_stack: Vec<CellOperation>,
+    redo_stack: Vec<CellOperation>,
+    _subscriptions: Vec<Subscription>,
+    /// Subscriptions to the cells in the notebook, or in its undo and redo stacks.
+    cell_subscriptions: HashMap<CellId, Vec<Subscription>>,
+}
+
+pub enum NotebookEditorEvent {
+    DirtyChanged,
+}
+
+/// A change to the notebook's cells, as opposed to their contents.
+///
+/// Applying an operation yields its inverse, which is how operations are undone and redone.
+enum CellOperation<C = Cell> {
+    Insert { ix: usize, cell_id: CellId, cell: C },
+    Remove { ix: usize },
+    Move { from: usize, to: usize },
+}
+
+impl<C> CellOperation<C> {
+    /// Applies the operation to the cells, returning the operation undoing it and the index of the cell to select.
+    fn apply(
+        self,
+        cell_order: &mut Vec<CellId>,
+        cell_map: &mut HashMap<CellId, C>,
+    ) -> (CellOperation<C>, usize) {
+        match self {
+            CellOperation::Insert { ix, cell_id, cell } => {
+                cell_order.insert(ix, cell_id.clone());
+                cell_map.insert(cell_id, cell);
+                (CellOperation::Remove { ix }, ix)
+            }
+            CellOperation::Remove { ix } => {
+                let cell_id = cell_order.remove(ix);
+                let cell = cell_map
+                    .remove(&cell_id)
+                    .expect("cells in the order are in the map");
+                (
+                    CellOperation::Insert { ix, cell_id, cell },
+                    ix.min(cell_order.len().saturating_sub(1)),
+                )
+            }
+            CellOperation::Move { from, to } => {
+                let cell_id = cell_order.remove(from);
+                cell_order.insert(to, cell_id);
+                (CellOperation::Move { from: to, to: from }, to)
+            }
+        }
+    }
+
+    /// The range of the cells list replaced by the operation, and the number of cells replacing it.
+    fn list_splice(&self) -> (Range<usize>, usize) {
+        match self {
+            CellOperation::Insert { ix, .. } => (*ix..*ix, 1),
+            CellOperation::Remove { ix } => (*ix..*ix + 1, 0),
+            CellOperation::Move { from, to } => {
+                let range = *from.min(to)..*from.max(to) + 1;
+                let count = range.len();
+                (range, count)
+            }
+        }
+    }
 }
 
 impl NotebookEditor {
@@ -94,57 +182,196 @@ impl NotebookEditor {
         cx: &mut Context<Self>,
     ) -> Self {
         let focus_handle = cx.focus_handle();
+        let workspace = window
+            .root::<Workspace>()
+            .flatten()
+            .map_or_else(WeakEntity::new_invalid, |workspace| workspace.downgrade());
 
         let languages = project.read(cx).languages().clone();
-        let language_name = notebook_item.read(cx).language_name();
+        let cell_list = ListState::new(0, gpui::ListAlignment::Top, px(1000.));
+        let editor_id = cx.entity_id();
+        let subscriptions = vec![
+            cx.subscribe_in(&notebook_item, window, Self::handle_notebook_item_event),
+            // The project reads whether the notebook is dirty from the notebook item.
+            cx.subscribe_self(move |this, event, cx| match event {
+                NotebookEditorEvent::DirtyChanged => {
+                    let dirty = this.is_dirty(cx);
+                    this.notebook_item.update(cx, |notebook_item, _| {
+                        notebook_item.set_editor_dirty(editor_id, dirty)
+                    });
+                }
+            }),
+            cx.on_release({
+                let notebook_item = notebook_item.downgrade();
+                move |_, cx| {
+                    notebook_item
+                        .update(cx, |notebook_item, _| {
+                            notebook_item.set_editor_dirty(editor_id, false)
+                        })
+                        .ok();
+                }
+            }),
+        ];
+
+        if project.read(cx).is_local() {
+            let worktree_id = notebook_item.read(cx).project_path.worktree_id;
+            ReplStore::global(cx).update(cx, |store, cx| {
+                store
+                    .refresh_python_kernelspecs(worktree_id, &project, cx)
+                    .detach_and_log_err(cx);
+            });
+        }
 
-        let notebook_language = notebook_item.read(cx).notebook_language();
-        let notebook_language = cx
-            .spawn_in(window, async move |_, _| notebook_language.await)
-            .shared();
+        let mut this = Self {
+            project,
+            languages,
+            workspace,
+            focus_handle,
+            notebook_item,
+            remote_id: None,
+            cell_list,
+            selected_cell_index: 0,
+            cell_order: Vec::new(),
+            cell_map: HashMap::default(),
+            edited: false,
+            has_conflict: false,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            _subscriptions: subscriptions,
+            cell_subscriptions: HashMap::default(),
+        };
+        this.load_cells(window, cx);
+        this
+    }
 
-        let mut cell_order = vec![]; // Vec<CellId>
-        let mut cell_map = HashMap::default(); // HashMap<CellId, Cell>
+    /// Replaces the cells with the ones of the notebook as it was last read or written.
+    fn load_cells(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let notebook_language = self.notebook_language(window, cx);
 
-        for (index, cell) in notebook_item
-            .read(cx)
-            .notebook
-            .clone()
-            .cells
-            .iter()
-            .enumerate()
-        {
+        self.cell_order.clear();
+        self.cell_map.clear();
+        self.cell_subscriptions.clear();
+        for cell in self.notebook_item.read(cx).notebook.cells.clone().iter() {
             let cell_id = cell.id();
-            cell_order.push(cell_id.clone());
-            cell_map.insert(
-                cell_id.clone(),
-                Cell::load(cell, &languages, notebook_language.clone(), window, cx),
+            let cell = Cell::load(
+                cell,
+                &self.languages,
+                notebook_language.clone(),
+                self.workspace.clone(),
+                window,
+                cx,
             );
+            self.subscribe_to_cell(&cell, window, cx);
+            self.cell_order.push(cell_id.clone());
+            self.cell_map.insert(cell_id.clone(), cell);
         }
 
-        let notebook_handle = cx.entity().downgrade();
-        let cell_count = cell_order.len();
+        self.cell_list.reset(self.cell_order.len());
+        self.selected_cell_index = self
+            .selected_cell_index
+            .min(self.cell_order.len().saturating_sub(1));
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.edited = false;
+        self.has_conflict = false;
+        cx.emit(NotebookEditorEvent::DirtyChanged);
+        cx.notify();
+    }
 
-        let this = cx.entity();
-        let cell_list = ListState::new(cell_count, gpui::ListAlignment::Top, px(1000.));
+    fn notebook_language(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Shared<Task<Option<Arc<Language>>>> {
+        let notebook_language = self.notebook_item.read(cx).notebook_language();
+        cx.spawn_in(window, async move |_, _| notebook_language.await)
+            .shared()
+    }
 
-        Self {
-            project,
-            languages: languages.clone(),
-            focus_handle,
-            notebook_item,
-            remote_id: None,
-            cell_list,
-            selected_cell_index: 0,
-            cell_order: cell_order.clone(),
-            cell_map: cell_map.clone(),
+    fn subscribe_to_cell(&mut self, cell: &Cell, window: &mut Window, cx: &mut Context<Self>) {
+        let Cell::Code(code_cell) = cell else {
+            return;
+        };
+        let mut subscriptions = Vec::new();
+        if let Some(buffer) = code_cell.read(cx).buffer(cx) {
+            subscriptions.push(cx.subscribe(&buffer, |_, _, event, cx| {
+                if let BufferEvent::DirtyChanged = event {
+                    cx.emit(NotebookEditorEvent::DirtyChanged);
+                }
+            }));
         }
+        subscriptions.push(cx.subscribe_in(
+            code_cell,
+            window,
+            |this, code_cell, event, window, cx| match event {
+                CodeCellEvent::Run => {
+                    let cell_id = code_cell.read(cx).id().clone();
+                    if let Some(ix) = this.cell_order.iter().position(|id| *id == cell_id) {
+                        this.set_selected_index(ix, false, window, cx);
+                        this.run_cells_in_range(ix..ix + 1, window, cx);
+                    }
+                }
+            },
+        ));
+        self.cell_subscriptions.insert(cell.id(cx), subscriptions);
+    }
+
+    /// Reloads the notebook when it changed on disk, unless it has unsaved changes,
+    /// in which case it's marked as conflicted, like a text buffer.
+    fn handle_notebook_item_event(
+        &mut self,
+        _: &Entity<NotebookItem>,
+        event: &NotebookItemEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            NotebookItemEvent::ChangedOnDisk => {
+                if self.is_dirty(cx) {
+                    self.has_conflict = true;
+                    cx.emit(NotebookEditorEvent::DirtyChanged);
+                } else {
+                    self.notebook_item
+                        .update(cx, |notebook_item, cx| notebook_item.reload(cx))
+                        .detach_and_log_err(cx);
+                }
+            }
+            NotebookItemEvent::Reloaded => {
+                if !self.is_dirty(cx) {
+                    self.load_cells(window, cx);
+                }
+            }
+        }
+    }
+
+    /// Builds the notebook to save from the cells, keeping the notebook's metadata.
+    fn to_notebook(&self, cx: &App) -> nbformat::v4::Notebook {
+        let mut notebook = self.notebook_item.read(cx).notebook.clone();
+        notebook.cells = self
+            .cell_order
+            .iter()
+            .filter_map(|cell_id| self.cell_map.get(cell_id))
+            .map(|cell| cell.to_nbformat(cx))
+            .collect();
+        notebook
+    }
+
+    fn did_save(&mut self, cx: &mut Context<Self>) {
+        for cell in self.cell_map.values() {
+            if let Cell::Code(code_cell) = cell {
+                code_cell.update(cx, |cell, cx| cell.did_save(cx));
+            }
+        }
+        self.edited = false;
+        self.has_conflict = false;
+        cx.emit(NotebookEditorEvent::DirtyChanged);
+        cx.notify();
     }
 
     fn has_outputs(&self, window: &mut Window, cx: &mut Context<Self>) -> bool {
         self.cell_map.values().any(|cell| {
             if let Cell::Code(code_cell) = cell {
-                code_cell.read(cx).has_outputs()
+                code_cell.read(cx).has_outputs(cx)
             } else {
                 false
             }
@@ -152,17 +379,211 @@ impl NotebookEditor {
     }
 
     fn clear_outputs(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.has_outputs(window, cx) {
+            return;
+        }
         for cell in self.cell_map.values() {
             if let Cell::Code(code_cell) = cell {
-                code_cell.update(cx, |cell, _cx| {
-                    cell.clear_outputs();
+                code_cell.update(cx, |cell, cx| {
+                    cell.clear_outputs(cx);
                 });
             }
         }
+        self.edited = true;
+        cx.emit(NotebookEditorEvent::DirtyChanged);
+        cx.notify();
+    }
+
+    /// Returns the kernel session running the notebook's cells, starting one if needed.
+    fn session(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Result<Entity<Session>> {
+        if let Some(session) = ReplStore::global(cx)
+            .read(cx)
+            .get_session(cx.entity_id())
+            .cloned()
+        {
+            return Ok(session);
+        }
+        let kernel_specification = self.kernel_specification(cx).with_context(|| {
+            let language = self.notebook_item.read(cx).language_name();
+            format!(
+                "No kernel found for language: {}",
+                language.as_deref().unwrap_or("unknown")
+            )
+        })?;
+        Ok(self.start_session(kernel_specification, window, cx))
+    }
+
+    /// Picks the kernel named in the notebook's metadata, falling back to one for its language.
+    fn kernel_specification(&self, cx: &App) -> Option<KernelSpecification> {
+        let notebook_item = self.notebook_item.read(cx);
+        let worktree_id = notebook_item.project_path.worktree_id;
+        let kernel_name = notebook_item
+            .notebook
+            .metadata
+            .kernelspec
+            .as_ref()
+            .map(|kernelspec| kernelspec.name.to_lowercase());
+        let language = notebook_item
+            .language_name()
+            .map(|language| language.to_lowercase());
+
+        let store = ReplStore::global(cx);
+        let kernel_specifications = store
+            .read(cx)
+            .kernel_specifications_for_worktree(worktree_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        kernel_specifications
+            .iter()
+            .find(|spec| kernel_name.as_deref() == Some(spec.name().to_lowercase().as_str()))
+            .or_else(|| {
+                kernel_specifications.iter().find(|spec| {
+                    language.as_deref() == Some(spec.language().to_lowercase().as_str())
+                })
+            })
+            .cloned()
+    }
+
+    fn start_session(
+        &mut self,
+        kernel_specification: KernelSpecification,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Session> {
+        let store = ReplStore::global(cx);
+        let fs = store.read(cx).fs().clone();
+        let working_directory = self
+            .notebook_item
+            .read(cx)
+            .path
+            .parent()
+            .map(|path| path.to_path_buf())
+            .unwrap_or_else(std::env::temp_dir);
+        let notebook_id = cx.entity_id();
+        let session = cx.new(|cx| {
+            Session::for_notebook(
+                notebook_id,
+                working_directory,
+                fs,
+                kernel_specification,
+                window,
+                cx,
+            )
+        });
+
+        cx.subscribe(&session, {
+            let store = store.clone();
+            move |_, session, event, cx| match event {
+                SessionEvent::Shutdown(entity_id) => {
+                    store.update(cx, |store, _| {
+                        // The session may have been replaced by one with another kernel.
+                        if store.get_session(*entity_id) == Some(&session) {
+                            store.remove_session(*entity_id);
+                        }
+                    });
+                }
+            }
+        })
+        .detach();
+        store.update(cx, |store, _| {
+            store.insert_session(notebook_id, session.clone());
+        });
+        cx.notify();
+        session
+    }
+
+    fn select_kernel(
+        &mut self,
+        kernel_specification: KernelSpecification,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let store = ReplStore::global(cx);
+        let worktree_id = self.notebook_item.read(cx).project_path.worktree_id;
+        store.update(cx, |store, cx| {
+            store.set_active_kernelspec(worktree_id, kernel_specification.clone(), cx);
+        });
+        if let Some(session) = store.read(cx).get_session(cx.entity_id()).cloned() {
+            session.update(cx, |session, cx| session.shutdown(window, cx));
+        }
+        self.start_session(kernel_specification, window, cx);
+    }
+
+    /// Runs the code cells among the given cells, in order.
+    fn run_cells_in_range(
+        &mut self,
+        range: Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let code_cells = self.cell_order[range]
+            .iter()
+            .filter_map(|cell_id| match self.cell_map.get(cell_id) {
+                Some(Cell::Code(code_cell)) => Some(code_cell.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if code_cells.is_empty() {
+            return;
+        }
+
+        let session = match self.session(window, cx) {
+            Ok(session) => session,
+            Err(error) => {
+                self.workspace
+                    .update(cx, |workspace, cx| workspace.show_error(&error, cx))
+                    .ok();
+                return;
+            }
+        };
+        for code_cell in code_cells {
+            let code = code_cell.read(cx).source_text(cx);
+            session.update(cx, |session, cx| session.execute_cell(code, &code_cell, cx));
+        }
+        self.edited = true;
+        cx.emit(NotebookEditorEvent::DirtyChanged);
+        cx.notify();
+    }
+
+    fn run_cell(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_cell_index;
+        if ix < self.cell_count() {
+            self.run_cells_in_range(ix..ix + 1, window, cx);
+        }
     }
 
     fn run_cells(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Cells would all run here, if that was implemented!");
+        self.run_cells_in_range(0..self.cell_count(), window, cx);
+    }
+
+    fn run_cells_above(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_cell_index.min(self.cell_count());
+        self.run_cells_in_range(0..ix, window, cx);
+    }
+
+    fn run_cells_below(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_cell_index.min(self.cell_count());
+        self.run_cells_in_range(ix..self.cell_count(), window, cx);
+    }
+
+    fn interrupt_kernel(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(session) = ReplStore::global(cx)
+            .read(cx)
+            .get_session(cx.entity_id())
+            .cloned()
+        {
+            session.update(cx, |session, cx| session.interrupt(cx));
+        }
+    }
+
+    fn restart_kernel(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(session) = ReplStore::global(cx)
+            .read(cx)
+            .get_session(cx.entity_id())
+            .cloned()
+        {
+            session.update(cx, |session, cx| session.restart(window, cx));
+        }
     }
 
     fn open_notebook(&mut self, _: &OpenNotebook, _window: &mut Window, _cx: &mut Context<Self>) {
@@ -170,19 +591,118 @@ impl NotebookEditor {
     }
 
     fn move_cell_up(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Move cell up triggered");
+        let ix = self.selected_cell_index;
+        if ix > 0 && ix < self.cell_count() {
+            self.push_operation(
+                CellOperation::Move {
+                    from: ix,
+                    to: ix - 1,
+                },
+                window,
+                cx,
+            );
+        }
     }
 
     fn move_cell_down(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Move cell down triggered");
+        let ix = self.selected_cell_index;
+        if ix + 1 < self.cell_count() {
+            self.push_operation(
+                CellOperation::Move {
+                    from: ix,
+                    to: ix + 1,
+                },
+                window,
+                cx,
+            );
+        }
     }
 
     fn add_markdown_block(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Add markdown block triggered");
+        self.add_cell(CellType::Markdown, window, cx);
     }
 
     fn add_code_block(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Add code block triggered");
+        self.add_cell(CellType::Code, window, cx);
+    }
+
+    /// Adds an empty cell below the current one.
+    fn add_cell(&mut self, cell_type: CellType, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(cell) = new_cell(cell_type).log_err() else {
+            return;
+        };
+        let cell = Cell::load(
+            &cell,
+            &self.languages,
+            self.notebook_language(window, cx),
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+        self.subscribe_to_cell(&cell, window, cx);
+
+        let ix = if self.cell_order.is_empty() {
+            0
+        } else {
+            self.selected_cell_index + 1
+        };
+        let cell_id = cell.id(cx);
+        self.push_operation(CellOperation::Insert { ix, cell_id, cell }, window, cx);
+    }
+
+    fn delete_cell(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_cell_index;
+        if ix < self.cell_count() {
+            self.push_operation(CellOperation::Remove { ix }, window, cx);
+        }
+    }
+
+    fn push_operation(
+        &mut self,
+        operation: CellOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let inverse = self.apply_operation(operation, window, cx);
+        self.undo_stack.push(inverse);
+        // The cells that only the redo stack kept are gone for good.
+        for operation in self.redo_stack.drain(..) {
+            if let CellOperation::Insert { cell_id, .. } = operation {
+                self.cell_subscriptions.remove(&cell_id);
+            }
+        }
+    }
+
+    fn undo(&mut self, _: &editor::actions::Undo, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(operation) = self.undo_stack.pop() {
+            let inverse = self.apply_operation(operation, window, cx);
+            self.redo_stack.push(inverse);
+        }
+    }
+
+    fn redo(&mut self, _: &editor::actions::Redo, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(operation) = self.redo_stack.pop() {
+            let inverse = self.apply_operation(operation, window, cx);
+            self.undo_stack.push(inverse);
+        }
+    }
+
+    /// Applies the operation, selecting the affected cell, and returns the operation undoing it.
+    fn apply_operation(
+        &mut self,
+        operation: CellOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> CellOperation {
+        let (list_range, list_count) = operation.list_splice();
+        self.cell_list.splice(list_range, list_count);
+        let (inverse, selected_ix) = operation.apply(&mut self.cell_order, &mut self.cell_map);
+
+        self.set_selected_index(selected_ix, true, window, cx);
+        self.edited = true;
+        cx.emit(NotebookEditorEvent::DirtyChanged);
+        cx.notify();
+        inverse
     }
 
     fn cell_count(&self) -> usize {
@@ -304,6 +824,10 @@ impl NotebookEditor {
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
         let has_outputs = self.has_outputs(window, cx);
+        let has_session = ReplStore::global(cx)
+            .read(cx)
+            .get_session(cx.entity_id())
+            .is_some();
 
         v_flex()
             .max_w(px(CONTROL_SIZE + 4.0))
@@ -377,6 +901,20 @@ impl NotebookEditor {
                                 .on_click(|_, window, cx| {
                                     window.dispatch_action(Box::new(MoveCellDown), cx);
                                 }),
+                            )
+                            .child(
+                                Self::render_notebook_control(
+                                    "delete-cell",
+                                    IconName::Trash,
+                                    window,
+                                    cx,
+                                )
+                                .tooltip(move |window, cx| {
+                                    Tooltip::for_action("Delete cell", &DeleteCell, cx)
+                                })
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(Box::new(DeleteCell), cx);
+                                }),
                             ),
                     )
                     .child(
@@ -423,11 +961,66 @@ impl NotebookEditor {
                     ))
                     .child(
                         Self::button_group(window, cx)
-                            .child(IconButton::new("repl", IconName::ReplNeutral)),
+                            .child(
+                                Self::render_notebook_control(
+                                    "interrupt-kernel",
+                                    IconName::Stop,
+                                    window,
+                                    cx,
+                                )
+                                .disabled(!has_session)
+                                .tooltip(move |window, cx| {
+                                    Tooltip::for_action("Interrupt kernel", &InterruptKernel, cx)
+                                })
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(Box::new(InterruptKernel), cx);
+                                }),
+                            )
+                            .child(
+                                Self::render_notebook_control(
+                                    "restart-kernel",
+                                    IconName::RotateCw,
+                                    window,
+                                    cx,
+                                )
+                                .disabled(!has_session)
+                                .tooltip(move |window, cx| {
+                                    Tooltip::for_action("Restart kernel", &RestartKernel, cx)
+                                })
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(Box::new(RestartKernel), cx);
+                                }),
+                            )
+                            .child(self.render_kernel_selector(cx)),
                     ),
             )
     }
 
+    fn render_kernel_selector(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let kernel_name = ReplStore::global(cx)
+            .read(cx)
+            .get_session(cx.entity_id())
+            .map(|session| session.read(cx).kernel_specification.name());
+        let tooltip = match kernel_name {
+            Some(kernel_name) => format!("Kernel: {kernel_name}"),
+            None => "Select kernel".to_string(),
+        };
+        let worktree_id = self.notebook_item.read(cx).project_path.worktree_id;
+        let this = cx.entity().downgrade();
+
+        KernelSelector::new(
+            Box::new(move |kernel_specification, window, cx| {
+                this.update(cx, |this, cx| {
+                    this.select_kernel(kernel_specification, window, cx)
+                })
+                .ok();
+            }),
+            worktree_id,
+            IconButton::new("repl", IconName::ReplNeutral).width(px(CONTROL_SIZE)),
+            Tooltip::text(tooltip),
+        )
+    }
+
     fn cell_position(&self, index: usize) -> CellPosition {
         match index {
             0 => CellPosition::First,
@@ -484,7 +1077,20 @@ impl Render for NotebookEditor {
             .on_action(
                 cx.listener(|this, &ClearOutputs, window, cx| this.clear_outputs(window, cx)),
             )
+            .on_action(cx.listener(|this, &RunCell, window, cx| this.run_cell(window, cx)))
             .on_action(cx.listener(|this, &RunAll, window, cx| this.run_cells(window, cx)))
+            .on_action(
+                cx.listener(|this, &RunCellsAbove, window, cx| this.run_cells_above(window, cx)),
+            )
+            .on_action(
+                cx.listener(|this, &RunCellsBelow, window, cx| this.run_cells_below(window, cx)),
+            )
+            .on_action(
+                cx.listener(|this, &InterruptKernel, window, cx| this.interrupt_kernel(window, cx)),
+            )
+            .on_action(
+                cx.listener(|this, &RestartKernel, window, cx| this.restart_kernel(window, cx)),
+            )
             .on_action(cx.listener(|this, &MoveCellUp, window, cx| this.move_cell_up(window, cx)))
             .on_action(
                 cx.listener(|this, &MoveCellDown, window, cx| this.move_cell_down(window, cx)),
@@ -495,6 +1101,9 @@ impl Render for NotebookEditor {
             .on_action(
                 cx.listener(|this, &AddCodeBlock, window, cx| this.add_code_block(window, cx)),
             )
+            .on_action(cx.listener(|this, &DeleteCell, window, cx| this.delete_cell(window, cx)))
+            .on_action(cx.listener(Self::undo))
+            .on_action(cx.listener(Self::redo))
             .on_action(cx.listener(Self::select_next))
             .on_action(cx.listener(Self::select_previous))
             .on_action(cx.listener(Self::select_first))
@@ -519,7 +1128,16 @@ impl Render for NotebookEditor {
                                 .get(ix)
                                 .and_then(|cell_id| this.cell_map.get(cell_id))
                                 .map(|cell| {
-                                    this.render_cell(ix, cell, window, cx).into_any_element()
+                                    div()
+                                        .on_mouse_down(
+                                            MouseButton::Left,
+                                            cx.listener(move |this, _, window, cx| {
+                                                this.set_selected_index(ix, false, window, cx);
+                                                cx.notify();
+                                            }),
+                                        )
+                                        .child(this.render_cell(ix, cell, window, cx))
+                                        .into_any_element()
                                 })
                                 .unwrap_or_else(|| div().into_any())
                         }),
@@ -540,13 +1158,31 @@ impl Focusable for NotebookEditor {
 pub struct NotebookItem {
     path: PathBuf,
     project_path: ProjectPath,
+    project: WeakEntity<Project>,
+    fs: Arc<dyn Fs>,
     languages: Arc<LanguageRegistry>,
     // Raw notebook data
     notebook: nbformat::v4::Notebook,
+    /// The JSON the notebook was last read from or written to, used to keep
+    /// the fields nbformat doesn't model when saving.
+    notebook_json: Value,
     // Store our version of the notebook in memory (cell_order, cell_map)
     id: ProjectEntryId,
+    saved_mtime: Option<MTime>,
+    saving: bool,
+    /// The editors of the notebook with unsaved changes.
+    dirty_editors: HashSet<EntityId>,
+    _project_subscription: Subscription,
+}
+
+pub enum NotebookItemEvent {
+    /// The file was changed on disk by something else than saving the notebook.
+    ChangedOnDisk,
+    Reloaded,
 }
 
+impl EventEmitter<NotebookItemEvent> for NotebookItem {}
+
 impl project::ProjectItem for NotebookItem {
     fn try_open(
         project: &Entity<Project>,
@@ -564,37 +1200,30 @@ impl project::ProjectItem for NotebookItem {
                     .read_with(cx, |project, cx| project.absolute_path(&path, cx))?
                     .with_context(|| format!("finding the absolute path of {path:?}"))?;
 
-                // todo: watch for changes to the file
                 let file_content = fs.load(abs_path.as_path()).await?;
-                let notebook = nbformat::parse_notebook(&file_content);
+                let (notebook, notebook_json) = parse_notebook(&file_content)?;
 
-                let notebook = match notebook {
-                    Ok(nbformat::Notebook::V4(notebook)) => notebook,
-                    // 4.1 - 4.4 are converted to 4.5
-                    Ok(nbformat::Notebook::Legacy(legacy_notebook)) => {
-                        // TODO: Decide if we want to mutate the notebook by including Cell IDs
-                        // and any other conversions
-
-                        nbformat::upgrade_legacy_notebook(legacy_notebook)?
-                    }
-                    // Bad notebooks and notebooks v4.0 and below are not supported
-                    Err(e) => {
-                        anyhow::bail!("Failed to parse notebook: {:?}", e);
-                    }
-                };
-
-                let id = project
+                let (id, saved_mtime) = project
                     .update(cx, |project, cx| {
-                        project.entry_for_path(&path, cx).map(|entry| entry.id)
+                        project
+                            .entry_for_path(&path, cx)
+                            .map(|entry| (entry.id, entry.mtime))
                     })?
                     .context("Entry not found")?;
 
-                cx.new(|_| NotebookItem {
+                cx.new(|cx| NotebookItem {
                     path: abs_path,
                     project_path: path,
+                    project: project.downgrade(),
+                    fs,
                     languages,
                     notebook,
+                    notebook_json,
                     id,
+                    saved_mtime,
+                    saving: false,
+                    dirty_editors: HashSet::default(),
+                    _project_subscription: cx.subscribe(&project, Self::handle_project_event),
                 })
             }))
         } else {
@@ -611,11 +1240,112 @@ impl project::ProjectItem for NotebookItem {
     }
 
     fn is_dirty(&self) -> bool {
-        false
+        !self.dirty_editors.is_empty()
     }
 }
 
 impl NotebookItem {
+    fn handle_project_event(
+        &mut self,
+        project: Entity<Project>,
+        event: &project::Event,
+        cx: &mut Context<Self>,
+    ) {
+        let project::Event::WorktreeUpdatedEntries(worktree_id, changes) = event else {
+            return;
+        };
+        if self.saving
+            || *worktree_id != self.project_path.worktree_id
+            || !changes
+                .iter()
+                .any(|(path, _, _)| *path == self.project_path.path)
+        {
+            return;
+        }
+        let mtime = project
+            .read(cx)
+            .entry_for_path(&self.project_path, cx)
+            .and_then(|entry| entry.mtime);
+        if mtime.is_some() && mtime != self.saved_mtime {
+            cx.emit(NotebookItemEvent::ChangedOnDisk);
+        }
+    }
+
+    fn set_editor_dirty(&mut self, editor_id: EntityId, dirty: bool) {
+        if dirty {
+            self.dirty_editors.insert(editor_id);
+        } else {
+            self.dirty_editors.remove(&editor_id);
+        }
+    }
+
+    /// Reads the notebook from disk again, discarding the notebook in memory.
+    pub fn reload(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        let abs_path = self.path.clone();
+        cx.spawn(async move |this, cx| {
+            let file_content = fs.load(&abs_path).await?;
+            let mtime = fs.metadata(&abs_path).await?.map(|metadata| metadata.mtime);
+            let (notebook, notebook_json) = parse_notebook(&file_content)?;
+            this.update(cx, |this, cx| {
+                this.notebook = notebook;
+                this.notebook_json = notebook_json;
+                this.saved_mtime = mtime;
+                cx.emit(NotebookItemEvent::Reloaded);
+            })
+        })
+    }
+
+    /// Writes the notebook to the given path, which becomes the notebook's path.
+    pub fn save(
+        &mut self,
+        notebook: nbformat::v4::Notebook,
+        project_path: ProjectPath,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(project) = self.project.upgrade() else {
+            return Task::ready(Err(anyhow!("project was dropped")));
+        };
+        let project = project.read(cx);
+        let Some((worktree, abs_path)) = project
+            .worktree_for_id(project_path.worktree_id, cx)
+            .zip(project.absolute_path(&project_path, cx))
+        else {
+            return Task::ready(Err(anyhow!("worktree not found for {project_path:?}")));
+        };
+        let (notebook_json, text) =
+            match serialize_notebook(&notebook, &self.notebook, &self.notebook_json) {
+                Ok(serialized) => serialized,
+                Err(error) => return Task::ready(Err(error)),
+            };
+
+        let write = worktree.update(cx, |worktree, cx| {
+            worktree.write_file(
+                project_path.path.clone(),
+                text.as_str().into(),
+                LineEnding::Unix,
+                cx,
+            )
+        });
+        self.saving = true;
+        cx.spawn(async move |this, cx| {
+            let file = write.await;
+            this.update(cx, |this, _| {
+                this.saving = false;
+                let file = file?;
+                if let Some(entry_id) = file.entry_id {
+                    this.id = entry_id;
+                }
+                this.saved_mtime = file.disk_state.mtime();
+                this.path = abs_path;
+                this.project_path = project_path;
+                this.notebook = notebook;
+                this.notebook_json = notebook_json;
+                anyhow::Ok(())
+            })?
+        })
+    }
+
     pub fn language_name(&self) -> Option<String> {
         self.notebook
             .metadata
@@ -644,7 +1374,138 @@ impl NotebookItem {
     }
 }
 
-impl EventEmitter<()> for NotebookEditor {}
+/// Creates an empty cell, built from JSON like the cells of the notebooks that are read.
+fn new_cell(cell_type: CellType) -> Result<nbformat::v4::Cell> {
+    // Jupyter generates ids of 8 hex digits.
+    let id = Uuid::new_v4().simple().to_string()[..8].to_string();
+    let cell = match cell_type {
+        CellType::Code => json!({
+            "cell_type": "code",
+            "id": id,
+            "metadata": {},
+            "execution_count": null,
+            "outputs": [],
+            "source": [],
+        }),
+        CellType::Markdown => json!({
+            "cell_type": "markdown",
+            "id": id,
+            "metadata": {},
+            "source": [],
+        }),
+        CellType::Raw => json!({
+            "cell_type": "raw",
+            "id": id,
+            "metadata": {},
+            "source": [],
+        }),
+    };
+    Ok(serde_json::from_value(cell)?)
+}
+
+/// Parses a notebook, upgrading notebooks older than nbformat 4.5.
+fn parse_notebook(file_content: &str) -> Result<(nbformat::v4::Notebook, Value)> {
+    let notebook = match nbformat::parse_notebook(file_content) {
+        Ok(nbformat::Notebook::V4(notebook)) => notebook,
+        // 4.1 - 4.4 are converted to 4.5
+        Ok(nbformat::Notebook::Legacy(legacy_notebook)) => {
+            // TODO: Decide if we want to mutate the notebook by including Cell IDs
+            // and any other conversions
+
+            nbformat::upgrade_legacy_notebook(legacy_notebook)?
+        }
+        // Bad notebooks and notebooks v4.0 and below are not supported
+        Err(e) => {
+            anyhow::bail!("Failed to parse notebook: {:?}", e);
+        }
+    };
+    let notebook_json = serde_json::from_str(file_content)?;
+    Ok((notebook, notebook_json))
+}
+
+/// Serializes a notebook the way Jupyter does, with sorted keys and a one-space indent.
+///
+/// Fields of the previously read notebook that nbformat doesn't model, like unknown
+/// metadata, are carried over, matching cells by their id. Fields that nbformat models are
+/// left out when they were removed from the notebook.
+fn serialize_notebook(
+    notebook: &nbformat::v4::Notebook,
+    previous: &nbformat::v4::Notebook,
+    previous_json: &Value,
+) -> Result<(Value, String)> {
+    let mut json = serde_json::to_value(notebook)?;
+    let modeled_json = serde_json::to_value(previous)?;
+    merge_missing_fields(&mut json, previous_json, &modeled_json);
+    if let (Some(Value::Array(cells)), Some(Value::Array(previous_cells))) =
+        (json.get_mut("cells"), previous_json.get("cells"))
+    {
+        let modeled_cells = modeled_json
+            .get("cells")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let find_cell = |cells: &[Value], id: Option<&Value>| {
+            cells
+                .iter()
+                .find(|cell| cell.get("id").is_some() && cell.get("id") == id)
+                .cloned()
+        };
+        for cell in cells {
+            if let Some(previous_cell) = find_cell(previous_cells, cell.get("id")) {
+                let modeled_cell = find_cell(modeled_cells, cell.get("id")).unwrap_or(Value::Null);
+                merge_missing_fields(cell, &previous_cell, &modeled_cell);
+            }
+        }
+    }
+    sort_keys(&mut json);
+
+    let mut text = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b" ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut text, formatter);
+    serde::Serialize::serialize(&json, &mut serializer)?;
+    let mut text = String::from_utf8(text)?;
+    text.push('\n');
+    Ok((json, text))
+}
+
+/// Adds the object fields of `previous` missing from `json`, recursing into objects but not arrays.
+///
+/// Only fields missing from `modeled`, which is `previous` as nbformat serializes it, are
+/// added, since the other ones were removed on purpose.
+fn merge_missing_fields(json: &mut Value, previous: &Value, modeled: &Value) {
+    let (Value::Object(object), Value::Object(previous)) = (json, previous) else {
+        return;
+    };
+    for (key, previous_value) in previous {
+        let modeled_value = modeled.get(key);
+        match object.get_mut(key) {
+            Some(value) => {
+                merge_missing_fields(value, previous_value, modeled_value.unwrap_or(&Value::Null))
+            }
+            None if modeled_value.is_none() => {
+                object.insert(key.clone(), previous_value.clone());
+            }
+            None => {}
+        }
+    }
+}
+
+fn sort_keys(json: &mut Value) {
+    match json {
+        Value::Object(object) => {
+            let mut entries = std::mem::take(object).into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, mut value) in entries {
+                sort_keys(&mut value);
+                object.insert(key, value);
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+impl EventEmitter<NotebookEditorEvent> for NotebookEditor {}
 
 // pub struct NotebookControls {
 //     pane_focused: bool,
@@ -692,7 +1553,13 @@ impl EventEmitter<()> for NotebookEditor {}
 // }
 
 impl Item for NotebookEditor {
-    type Event = ();
+    type Event = NotebookEditorEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        match event {
+            NotebookEditorEvent::DirtyChanged => f(ItemEvent::UpdateTab),
+        }
+    }
 
     fn can_split(&self) -> bool {
         true
@@ -769,48 +1636,86 @@ impl Item for NotebookEditor {
         // TODO
     }
 
-    // TODO
     fn can_save(&self, _cx: &App) -> bool {
-        false
+        true
     }
-    // TODO
+
+    fn can_save_as(&self, _cx: &App) -> bool {
+        true
+    }
+
     fn save(
         &mut self,
         _options: SaveOptions,
         _project: Entity<Project>,
-        _window: &mut Window,
-        _cx: &mut Context<Self>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        unimplemented!("save() must be implemented if can_save() returns true")
+        let project_path = self.notebook_item.read(cx).project_path.clone();
+        self.save_to(project_path, window, cx)
     }
 
-    // TODO
     fn save_as(
         &mut self,
         _project: Entity<Project>,
-        _path: ProjectPath,
-        _window: &mut Window,
-        _cx: &mut Context<Self>,
+        path: ProjectPath,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        unimplemented!("save_as() must be implemented if can_save() returns true")
+        self.save_to(path, window, cx)
     }
-    // TODO
+
     fn reload(
         &mut self,
         _project: Entity<Project>,
-        _window: &mut Window,
-        _cx: &mut Context<Self>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        unimplemented!("reload() must be implemented if can_save() returns true")
+        let reload = self
+            .notebook_item
+            .update(cx, |notebook_item, cx| notebook_item.reload(cx));
+        cx.spawn_in(window, async move |this, cx| {
+            reload.await?;
+            // Clean editors already reloaded their cells when the notebook was reloaded.
+            this.update_in(cx, |this, window, cx| {
+                if this.is_dirty(cx) {
+                    this.load_cells(window, cx);
+                }
+            })
+        })
     }
 
     fn is_dirty(&self, cx: &App) -> bool {
-        self.cell_map.values().any(|cell| {
-            if let Cell::Code(code_cell) = cell {
-                code_cell.read(cx).is_dirty(cx)
-            } else {
-                false
-            }
+        self.edited
+            || self.has_conflict
+            || self.cell_map.values().any(|cell| {
+                if let Cell::Code(code_cell) = cell {
+                    code_cell.read(cx).is_dirty(cx)
+                } else {
+                    false
+                }
+            })
+    }
+
+    fn has_conflict(&self, _cx: &App) -> bool {
+        self.has_conflict
+    }
+}
+
+impl NotebookEditor {
+    fn save_to(
+        &mut self,
+        project_path: ProjectPath,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let notebook = self.to_notebook(cx);
+        let save = self.notebook_item.update(cx, |notebook_item, cx| {
+            notebook_item.save(notebook, project_path, cx)
+        });
+        cx.spawn_in(window, async move |this, cx| {
+            save.await?;
+            this.update(cx, |this, cx| this.did_save(cx))
         })
     }
 }
@@ -834,3 +1739,164 @@ impl ProjectItem for NotebookEditor {
         Self::new(project, item, window, cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize_notebook() {
+        let file_content = r##"{
+            "cells": [
+                {
+                    "cell_type": "code",
+                    "execution_count": null,
+                    "id": "code-cell",
+                    "metadata": {"custom": "kept"},
+                    "outputs": [],
+                    "source": ["print(1)\n", "print(2)"]
+                },
+                {
+                    "cell_type": "markdown",
+                    "id": "markdown-cell",
+                    "metadata": {},
+                    "source": ["# Title"]
+                }
+            ],
+            "metadata": {
+                "kernelspec": {"display_name": "Python 3", "language": "python", "name": "python3"},
+                "custom_tool": {"setting": true}
+            },
+            "nbformat": 4,
+            "nbformat_minor": 5
+        }"##;
+        let (previous, notebook_json) = parse_notebook(file_content).unwrap();
+        let mut notebook = previous.clone();
+        notebook.cells.reverse();
+
+        let (_, text) = serialize_notebook(&notebook, &previous, &notebook_json).unwrap();
+        assert!(text.starts_with("{\n \"cells\": [\n  {\n"));
+        assert!(text.ends_with("}\n"));
+
+        let json: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(json["metadata"]["custom_tool"], json!({"setting": true}));
+        assert_eq!(json["metadata"]["kernelspec"]["name"], "python3");
+        assert_eq!(json["cells"][0]["id"], "markdown-cell");
+        assert_eq!(json["cells"][1]["id"], "code-cell");
+        assert_eq!(json["cells"][1]["metadata"]["custom"], "kept");
+        assert_eq!(
+            json["cells"][1]["source"],
+            json!(["print(1)\n", "print(2)"])
+        );
+        parse_notebook(&text).unwrap();
+    }
+
+    #[test]
+    fn test_serialize_notebook_with_removed_fields() {
+        let file_content = r##"{
+            "cells": [],
+            "metadata": {
+                "kernelspec": {"display_name": "Python 3", "language": "python", "name": "python3"},
+                "custom_tool": {"setting": true}
+            },
+            "nbformat": 4,
+            "nbformat_minor": 5
+        }"##;
+        let (previous, notebook_json) = parse_notebook(file_content).unwrap();
+        let mut notebook = previous.clone();
+        notebook.metadata.kernelspec = None;
+
+        let (json, _) = serialize_notebook(&notebook, &previous, &notebook_json).unwrap();
+        assert!(json["metadata"]["kernelspec"].is_null());
+        assert_eq!(json["metadata"]["custom_tool"], json!({"setting": true}));
+
+        let mut json = json!({"kept": 1});
+        merge_missing_fields(
+            &mut json,
+            &json!({"kept": 1, "removed": 2, "unknown": 3}),
+            &json!({"kept": 1, "removed": 2}),
+        );
+        assert_eq!(json, json!({"kept": 1, "unknown": 3}));
+    }
+
+    #[test]
+    fn test_cell_operations() {
+        fn id(id: &str) -> CellId {
+            serde_json::from_value(json!(id)).unwrap()
+        }
+
+        let mut cell_order = vec![id("a"), id("b"), id("c")];
+        let mut cell_map = cell_order
+            .iter()
+            .map(|cell_id| (cell_id.clone(), ()))
+            .collect::<HashMap<_, _>>();
+        let mut undo_stack = Vec::new();
+
+        let operations = [
+            CellOperation::Insert {
+                ix: 1,
+                cell_id: id("d"),
+                cell: (),
+            },
+            CellOperation::Remove { ix: 3 },
+            CellOperation::Move { from: 0, to: 2 },
+        ];
+        let expected_states = [
+            (vec![id("a"), id("d"), id("b"), id("c")], 1),
+            (vec![id("a"), id("d"), id("b")], 2),
+            (vec![id("d"), id("b"), id("a")], 2),
+        ];
+        for (operation, (expected_order, expected_selection)) in
+            operations.into_iter().zip(expected_states.clone())
+        {
+            let (inverse, selected_ix) = operation.apply(&mut cell_order, &mut cell_map);
+            assert_eq!(cell_order, expected_order);
+            assert_eq!(selected_ix, expected_selection);
+            assert_eq!(cell_map.len(), cell_order.len());
+            undo_stack.push(inverse);
+        }
+
+        // Undoing all operations restores the cells, and redoing them applies them again.
+        let mut redo_stack = Vec::new();
+        let undone_states = [
+            vec![id("a"), id("d"), id("b")],
+            vec![id("a"), id("d"), id("b"), id("c")],
+            vec![id("a"), id("b"), id("c")],
+        ];
+        for expected_order in undone_states {
+            let operation = undo_stack.pop().unwrap();
+            let (inverse, _) = operation.apply(&mut cell_order, &mut cell_map);
+            assert_eq!(cell_order, expected_order);
+            redo_stack.push(inverse);
+        }
+        assert!(!cell_map.contains_key(&id("d")));
+
+        for (expected_order, expected_selection) in expected_states {
+            let operation = redo_stack.pop().unwrap();
+            let (inverse, selected_ix) = operation.apply(&mut cell_order, &mut cell_map);
+            assert_eq!(cell_order, expected_order);
+            assert_eq!(selected_ix, expected_selection);
+            undo_stack.push(inverse);
+        }
+        assert!(cell_map.contains_key(&id("d")));
+        assert!(!cell_map.contains_key(&id("c")));
+    }
+
+    #[test]
+    fn test_cell_operation_list_splice() {
+        assert_eq!(
+            CellOperation::<()>::Remove { ix: 2 }.list_splice(),
+            (2..3, 0)
+        );
+        assert_eq!(
+            CellOperation::<()>::Move { from: 3, to: 1 }.list_splice(),
+            (1..4, 3)
+        );
+        assert_eq!(
+            CellOperation::<()>::Move { from: 1, to: 2 }.list_splice(),
+            (1..3, 2)
+        );
+    }
+}
@@ -14,7 +14,10 @@
 //! The module supports several output types, including:
 //! - Plain text
 //! - Markdown
-//! - Images (PNG and JPEG)
+//! - HTML (converted to Markdown)
+//! - LaTeX (converted to Unicode text)
+//! - JSON (as an expandable tree)
+//! - Images (SVG, PNG and JPEG)
 //! - Tables
 //! - Error messages
 //!
@@ -42,12 +45,23 @@ use ui::{
     Window, div, h_flex, prelude::*, v_flex,
 };
 
+mod html;
+
 mod image;
 use image::ImageView;
 
+mod json;
+use json::JsonView;
+
+mod latex;
+use latex::LatexView;
+
 mod markdown;
 use markdown::MarkdownView;
 
+mod svg;
+use svg::SvgView;
+
 mod table;
 use table::TableView;
 
@@ -61,10 +75,14 @@ use workspace::Workspace;
 /// When deciding what to render from a collection of mediatypes, we need to rank them in order of importance
 fn rank_mime_type(mimetype: &MimeType) -> usize {
     match mimetype {
-        MimeType::DataTable(_) => 6,
-        MimeType::Png(_) => 4,
-        MimeType::Jpeg(_) => 3,
-        MimeType::Markdown(_) => 2,
+        MimeType::DataTable(_) => 9,
+        MimeType::Svg(_) => 8,
+        MimeType::Png(_) => 7,
+        MimeType::Jpeg(_) => 6,
+        MimeType::Markdown(_) => 5,
+        MimeType::Html(_) => 4,
+        MimeType::Latex(_) => 3,
+        MimeType::Json(_) => 2,
         MimeType::Plain(_) => 1,
         // All other media types are not supported in Zed at this time
         _ => 0,
@@ -114,6 +132,10 @@ pub enum Output {
         content: Entity<ImageView>,
         display_id: Option<String>,
     },
+    Svg {
+        content: Entity<SvgView>,
+        display_id: Option<String>,
+    },
     ErrorOutput(ErrorView),
     Message(String),
     Table {
@@ -124,6 +146,14 @@ pub enum Output {
         content: Entity<MarkdownView>,
         display_id: Option<String>,
     },
+    Latex {
+        content: Entity<LatexView>,
+        display_id: Option<String>,
+    },
+    Json {
+        content: Entity<JsonView>,
+        display_id: Option<String>,
+    },
     ClearOutputWaitMarker,
 }
 
@@ -211,6 +241,9 @@ impl Output {
             Self::Markdown { content, .. } => Some(content.clone().into_any_element()),
             Self::Stream { content, .. } => Some(content.clone().into_any_element()),
             Self::Image { content, .. } => Some(content.clone().into_any_element()),
+            Self::Svg { content, .. } => Some(content.clone().into_any_element()),
+            Self::Latex { content, .. } => Some(content.clone().into_any_element()),
+            Self::Json { content, .. } => Some(content.clone().into_any_element()),
             Self::Message(message) => Some(div().child(message.clone()).into_any_element()),
             Self::Table { content, .. } => Some(content.clone().into_any_element()),
             Self::ErrorOutput(error_view) => error_view.render(window, cx),
@@ -236,6 +269,15 @@ impl Output {
                 Self::Image { content, .. } => {
                     Self::render_output_controls(content.clone(), workspace, window, cx)
                 }
+                Self::Svg { content, .. } => {
+                    Self::render_output_controls(content.clone(), workspace, window, cx)
+                }
+                Self::Latex { content, .. } => {
+                    Self::render_output_controls(content.clone(), workspace, window, cx)
+                }
+                Self::Json { content, .. } => {
+                    Self::render_output_controls(content.clone(), workspace, window, cx)
+                }
                 Self::ErrorOutput(err) => {
                     // Add buttons for the traceback section
                     Some(
@@ -332,10 +374,13 @@ impl Output {
             Output::Plain { display_id, .. } => display_id.clone(),
             Output::Stream { .. } => None,
             Output::Image { display_id, .. } => display_id.clone(),
+            Output::Svg { display_id, .. } => display_id.clone(),
             Output::ErrorOutput(_) => None,
             Output::Message(_) => None,
             Output::Table { display_id, .. } => display_id.clone(),
             Output::Markdown { display_id, .. } => display_id.clone(),
+            Output::Latex { display_id, .. } => display_id.clone(),
+            Output::Json { display_id, .. } => display_id.clone(),
             Output::ClearOutputWaitMarker => None,
         }
     }
@@ -365,6 +410,25 @@ impl Output {
                 },
                 Err(error) => Output::Message(format!("Failed to load image: {}", error)),
             },
+            Some(MimeType::Svg(text)) => Output::Svg {
+                content: cx.new(|cx| SvgView::from(text.clone(), cx)),
+                display_id,
+            },
+            Some(MimeType::Html(text)) => match html::html_to_markdown(text) {
+                Ok(markdown) => Output::Markdown {
+                    content: cx.new(|cx| MarkdownView::from(markdown, cx)),
+                    display_id,
+                },
+                Err(error) => Output::Message(format!("Failed to render HTML: {}", error)),
+            },
+            Some(MimeType::Latex(text)) => Output::Latex {
+                content: cx.new(|_| LatexView::from(text.clone())),
+                display_id,
+            },
+            Some(MimeType::Json(value)) => Output::Json {
+                content: cx.new(|_| JsonView::from(value.clone())),
+                display_id,
+            },
             Some(MimeType::DataTable(data)) => Output::Table {
                 content: cx.new(|cx| TableView::new(data, window, cx)),
                 display_id,
@@ -0,0 +1,46 @@
+//! # HTML Output for REPL
+//!
+//! Kernels commonly send `text/html`, e.g. for pandas DataFrames. Rather than embedding
+//! a browser, the HTML is converted to Markdown and rendered like Markdown outputs.
+//!
+//! The conversion only keeps the document's text structure: paragraphs, headings, lists,
+//! tables, code and styled text. Scripts, styles and any other markup are dropped.
+
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use html_to_markdown::{TagHandler, convert_html_to_markdown, markdown};
+
+pub fn html_to_markdown(html: &str) -> Result<String> {
+    let mut handlers: Vec<TagHandler> = vec![
+        Rc::new(RefCell::new(markdown::WebpageChromeRemover)),
+        Rc::new(RefCell::new(markdown::ParagraphHandler)),
+        Rc::new(RefCell::new(markdown::HeadingHandler)),
+        Rc::new(RefCell::new(markdown::ListHandler)),
+        Rc::new(RefCell::new(markdown::TableHandler::new())),
+        Rc::new(RefCell::new(markdown::StyledTextHandler)),
+        Rc::new(RefCell::new(markdown::CodeHandler)),
+    ];
+    convert_html_to_markdown(html.as_bytes(), &mut handlers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_markdown() {
+        let markdown = html_to_markdown(
+            "<style>.dataframe { color: red; }</style>\
+             <h2>Results</h2>\
+             <p>Hello <strong>world</strong></p>\
+             <script>alert(1)</script>",
+        )
+        .unwrap();
+        assert!(markdown.contains("## Results"), "{markdown}");
+        assert!(markdown.contains("Hello"), "{markdown}");
+        assert!(markdown.contains("**world**"), "{markdown}");
+        assert!(!markdown.contains("alert"), "{markdown}");
+        assert!(!markdown.contains("color"), "{markdown}");
+    }
+}
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use collections::HashSet;
+use gpui::{AnyElement, App, ClipboardItem, Entity, Window};
+use language::Buffer;
+use serde_json::Value;
+use ui::{Disclosure, div, h_flex, prelude::*, v_flex};
+
+use crate::outputs::OutputContent;
+
+const INDENT_SIZE: f32 = 16.;
+
+/// JsonView renders `application/json` outputs as a tree, where objects and arrays can be
+/// expanded and collapsed. Only the root is expanded at first.
+pub struct JsonView {
+    value: Value,
+    /// The JSON pointers of the expanded objects and arrays.
+    expanded: HashSet<String>,
+}
+
+impl JsonView {
+    pub fn from(value: Value) -> Self {
+        let mut expanded = HashSet::default();
+        expanded.insert(String::new());
+        Self { value, expanded }
+    }
+
+    fn toggle(&mut self, pointer: &str, cx: &mut Context<Self>) {
+        if !self.expanded.remove(pointer) {
+            self.expanded.insert(pointer.to_string());
+        }
+        cx.notify();
+    }
+
+    fn render_node(
+        &self,
+        key: Option<String>,
+        value: &Value,
+        pointer: String,
+        depth: usize,
+        rows: &mut Vec<AnyElement>,
+        cx: &mut Context<Self>,
+    ) {
+        let children: Vec<(String, &Value)> = match value {
+            Value::Object(object) => object.iter().map(|(k, v)| (k.clone(), v)).collect(),
+            Value::Array(array) => array
+                .iter()
+                .enumerate()
+                .map(|(ix, v)| (ix.to_string(), v))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let is_container = value.is_object() || value.is_array();
+        let is_expanded = is_container && self.expanded.contains(&pointer);
+
+        let pointer: Arc<str> = pointer.into();
+        rows.push(
+            h_flex()
+                .pl(px(INDENT_SIZE * depth as f32))
+                .gap_1()
+                .map(|row| {
+                    if is_container {
+                        let id = SharedString::from(format!("json{pointer}"));
+                        let pointer = pointer.clone();
+                        row.child(
+                            Disclosure::new(id, is_expanded).on_click(
+                                cx.listener(move |this, _, _, cx| this.toggle(&pointer, cx)),
+                            ),
+                        )
+                    } else {
+                        row.child(div().w(px(INDENT_SIZE)))
+                    }
+                })
+                .when_some(key, |row, key| {
+                    row.child(Label::new(format!("{key}:")).color(Color::Muted))
+                })
+                .child(render_value(value))
+                .into_any_element(),
+        );
+
+        if is_expanded {
+            for (key, child) in children {
+                let child_pointer =
+                    format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"));
+                self.render_node(Some(key), child, child_pointer, depth + 1, rows, cx);
+            }
+        }
+    }
+}
+
+fn render_value(value: &Value) -> Label {
+    match value {
+        Value::Null => Label::new("null").color(Color::Muted),
+        Value::Bool(value) => Label::new(value.to_string()).color(Color::Accent),
+        Value::Number(value) => Label::new(value.to_string()).color(Color::Accent),
+        Value::String(value) => Label::new(format!("{value:?}")),
+        Value::Array(array) => Label::new(match array.len() {
+            1 => "[1 item]".to_string(),
+            len => format!("[{len} items]"),
+        })
+        .color(Color::Muted),
+        Value::Object(object) => Label::new(match object.len() {
+            1 => "{1 key}".to_string(),
+            len => format!("{{{len} keys}}"),
+        })
+        .color(Color::Muted),
+    }
+}
+
+impl Render for JsonView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut rows = Vec::new();
+        self.render_node(None, &self.value, String::new(), 0, &mut rows, cx);
+        v_flex().py_1().font_buffer(cx).children(rows)
+    }
+}
+
+impl OutputContent for JsonView {
+    fn clipboard_content(&self, _window: &Window, _cx: &App) -> Option<ClipboardItem> {
+        let text = serde_json::to_string_pretty(&self.value).ok()?;
+        Some(ClipboardItem::new_string(text))
+    }
+
+    fn has_clipboard_content(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+
+    fn has_buffer_content(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+
+    fn buffer_content(&mut self, _: &mut Window, cx: &mut App) -> Option<Entity<Buffer>> {
+        let text = serde_json::to_string_pretty(&self.value).ok()?;
+        let buffer = cx.new(|cx| {
+            let mut buffer =
+                Buffer::local(text, cx).with_language(language::PLAIN_TEXT.clone(), cx);
+            buffer.set_capability(language::Capability::ReadOnly, cx);
+            buffer
+        });
+        Some(buffer)
+    }
+}
@@ -0,0 +1,506 @@
+//! # LaTeX Output for REPL
+//!
+//! Kernels like sympy send `text/latex` for math expressions. Zed has no TeX engine,
+//! so the math is rendered as Unicode text instead: Greek letters and operators become
+//! their symbols, scripts become superscript and subscript characters where they exist,
+//! and fractions and roots are written inline, e.g. `\frac{1}{\sqrt{x}}` becomes `1/√x`.
+
+use std::{iter::Peekable, str::Chars};
+
+use gpui::{App, ClipboardItem, Window};
+use ui::{div, prelude::*};
+
+use crate::outputs::OutputContent;
+
+pub struct LatexView {
+    raw_text: String,
+    text: SharedString,
+}
+
+impl LatexView {
+    pub fn from(raw_text: String) -> Self {
+        let text = latex_to_unicode(&raw_text).into();
+        Self { raw_text, text }
+    }
+}
+
+impl Render for LatexView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div().py_1().font_buffer(cx).child(self.text.clone())
+    }
+}
+
+impl OutputContent for LatexView {
+    fn clipboard_content(&self, _window: &Window, _cx: &App) -> Option<ClipboardItem> {
+        Some(ClipboardItem::new_string(self.raw_text.clone()))
+    }
+
+    fn has_clipboard_content(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+}
+
+/// Converts text with `$`, `$$`, `\[` or `\(` delimited math to Unicode.
+/// Text without any delimiters is treated as math.
+pub fn latex_to_unicode(text: &str) -> String {
+    let text = text
+        .trim()
+        .replace("$$", "$")
+        .replace("\\[", "$")
+        .replace("\\]", "$")
+        .replace("\\(", "$")
+        .replace("\\)", "$");
+    if !text.contains('$') {
+        return math_to_unicode(&text);
+    }
+
+    // Segments alternate between text and math.
+    text.split('$')
+        .enumerate()
+        .map(|(ix, segment)| {
+            if ix % 2 == 1 {
+                math_to_unicode(segment)
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn math_to_unicode(math: &str) -> String {
+    let mut parser = MathParser {
+        chars: math.chars().peekable(),
+    };
+    parser.parse_group().trim().to_string()
+}
+
+struct MathParser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl MathParser<'_> {
+    /// Parses until the end of the input or the closing brace of the current group.
+    fn parse_group(&mut self) -> String {
+        let mut output = String::new();
+        while let Some(c) = self.chars.next() {
+            match c {
+                '}' => break,
+                '{' => output.push_str(&self.parse_group()),
+                '\\' => self.parse_command(&mut output),
+                '^' => output.push_str(&script(&self.parse_argument(), SUPERSCRIPTS, '^')),
+                '_' => output.push_str(&script(&self.parse_argument(), SUBSCRIPTS, '_')),
+                '~' => output.push(' '),
+                '&' => output.push_str("  "),
+                '=' | '<' | '>' | '+' => push_operator(&mut output, &c.to_string()),
+                '-' if is_binary_position(&output) => push_operator(&mut output, "-"),
+                // Spacing in math mode comes from the operators rather than the source.
+                c if c.is_whitespace() => {}
+                c => output.push(c),
+            }
+        }
+        output
+    }
+
+    /// Parses the argument of a command or script: a group, a command or a single character.
+    fn parse_argument(&mut self) -> String {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match self.chars.next() {
+            Some('{') => self.parse_group(),
+            Some('\\') => {
+                let mut output = String::new();
+                self.parse_command(&mut output);
+                output
+            }
+            Some(c) => c.to_string(),
+            None => String::new(),
+        }
+    }
+
+    fn parse_optional_argument(&mut self) -> Option<String> {
+        self.chars.next_if_eq(&'[')?;
+        let mut argument = String::new();
+        while let Some(c) = self.chars.next_if(|c| *c != ']') {
+            argument.push(c);
+        }
+        self.chars.next();
+        Some(math_to_unicode(&argument))
+    }
+
+    fn parse_command(&mut self, output: &mut String) {
+        let mut name = String::new();
+        while let Some(c) = self.chars.next_if(|c| c.is_ascii_alphabetic()) {
+            name.push(c);
+        }
+        if name.is_empty() {
+            match self.chars.next() {
+                Some(',' | ':' | ';' | ' ') => output.push(' '),
+                Some('\\') => output.push('\n'),
+                Some('!') | None => {}
+                Some(c) => output.push(c),
+            }
+            return;
+        }
+
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.parse_argument();
+                let denominator = self.parse_argument();
+                output.push_str(&parenthesize(&numerator));
+                output.push('/');
+                output.push_str(&parenthesize(&denominator));
+            }
+            "sqrt" => {
+                let index = self.parse_optional_argument();
+                let radicand = parenthesize(&self.parse_argument());
+                match index.as_deref() {
+                    None => output.push('√'),
+                    Some("3") => output.push('∛'),
+                    Some("4") => output.push('∜'),
+                    Some(index) => {
+                        output.push_str(&script(index, SUPERSCRIPTS, '^'));
+                        output.push('√');
+                    }
+                }
+                output.push_str(&radicand);
+            }
+            "text" | "textrm" | "textbf" | "textit" | "mathrm" | "mathit" | "mathbf" | "mathsf"
+            | "mathtt" | "mathcal" | "boldsymbol" | "operatorname" | "mbox" => {
+                output.push_str(&self.parse_argument());
+            }
+            "mathbb" => {
+                let argument = self.parse_argument();
+                output.extend(argument.chars().map(|c| match c {
+                    'C' => 'ℂ',
+                    'N' => 'ℕ',
+                    'Q' => 'ℚ',
+                    'R' => 'ℝ',
+                    'Z' => 'ℤ',
+                    c => c,
+                }));
+            }
+            "left" | "right" | "bigl" | "bigr" | "Bigl" | "Bigr" => {
+                // `.` stands for no delimiter.
+                self.chars.next_if_eq(&'.');
+            }
+            "displaystyle" | "textstyle" | "limits" | "nolimits" | "big" | "Big" | "bigg"
+            | "Bigg" => {}
+            "begin" | "end" => {
+                self.parse_argument();
+            }
+            "quad" => output.push_str("  "),
+            "qquad" => output.push_str("    "),
+            name => match symbol(name) {
+                Some(symbol) if is_spaced_operator(symbol) => push_operator(output, symbol),
+                Some(symbol) => output.push_str(symbol),
+                None => {
+                    output.push('\\');
+                    output.push_str(name);
+                }
+            },
+        }
+    }
+}
+
+fn push_operator(output: &mut String, operator: &str) {
+    output.truncate(output.trim_end().len());
+    if !output.is_empty() {
+        output.push(' ');
+    }
+    output.push_str(operator);
+    output.push(' ');
+}
+
+/// Whether a `-` would subtract from what precedes it, rather than negate what follows.
+fn is_binary_position(output: &str) -> bool {
+    output
+        .trim_end()
+        .chars()
+        .last()
+        .is_some_and(|c| !matches!(c, '(' | '[' | '{' | ',' | '=' | '<' | '>' | '+' | '-'))
+        && !output
+            .trim_end()
+            .ends_with(|c: char| is_spaced_operator(&c.to_string()))
+}
+
+fn parenthesize(text: &str) -> String {
+    let text = text.trim();
+    if is_atomic(text) {
+        text.to_string()
+    } else {
+        format!("({text})")
+    }
+}
+
+/// Whether the text reads as a single term, like `x`, `(x + 1)` or `√x`.
+fn is_atomic(text: &str) -> bool {
+    text.chars().count() <= 1
+        || (text.starts_with('(') && text.ends_with(')'))
+        || text
+            .strip_prefix(['√', '∛', '∜'])
+            .is_some_and(|radicand| is_atomic(radicand))
+}
+
+const SUPERSCRIPTS: &[(char, char)] = &[
+    ('0', '⁰'),
+    ('1', '¹'),
+    ('2', '²'),
+    ('3', '³'),
+    ('4', '⁴'),
+    ('5', '⁵'),
+    ('6', '⁶'),
+    ('7', '⁷'),
+    ('8', '⁸'),
+    ('9', '⁹'),
+    ('+', '⁺'),
+    ('-', '⁻'),
+    ('=', '⁼'),
+    ('(', '⁽'),
+    (')', '⁾'),
+    ('a', 'ᵃ'),
+    ('b', 'ᵇ'),
+    ('c', 'ᶜ'),
+    ('d', 'ᵈ'),
+    ('e', 'ᵉ'),
+    ('f', 'ᶠ'),
+    ('g', 'ᵍ'),
+    ('h', 'ʰ'),
+    ('i', 'ⁱ'),
+    ('j', 'ʲ'),
+    ('k', 'ᵏ'),
+    ('l', 'ˡ'),
+    ('m', 'ᵐ'),
+    ('n', 'ⁿ'),
+    ('o', 'ᵒ'),
+    ('p', 'ᵖ'),
+    ('r', 'ʳ'),
+    ('s', 'ˢ'),
+    ('t', 'ᵗ'),
+    ('u', 'ᵘ'),
+    ('v', 'ᵛ'),
+    ('w', 'ʷ'),
+    ('x', 'ˣ'),
+    ('y', 'ʸ'),
+    ('z', 'ᶻ'),
+    ('′', '′'),
+];
+
+const SUBSCRIPTS: &[(char, char)] = &[
+    ('0', '₀'),
+    ('1', '₁'),
+    ('2', '₂'),
+    ('3', '₃'),
+    ('4', '₄'),
+    ('5', '₅'),
+    ('6', '₆'),
+    ('7', '₇'),
+    ('8', '₈'),
+    ('9', '₉'),
+    ('+', '₊'),
+    ('-', '₋'),
+    ('=', '₌'),
+    ('(', '₍'),
+    (')', '₎'),
+    ('a', 'ₐ'),
+    ('e', 'ₑ'),
+    ('h', 'ₕ'),
+    ('i', 'ᵢ'),
+    ('j', 'ⱼ'),
+    ('k', 'ₖ'),
+    ('l', 'ₗ'),
+    ('m', 'ₘ'),
+    ('n', 'ₙ'),
+    ('o', 'ₒ'),
+    ('p', 'ₚ'),
+    ('r', 'ᵣ'),
+    ('s', 'ₛ'),
+    ('t', 'ₜ'),
+    ('u', 'ᵤ'),
+    ('v', 'ᵥ'),
+    ('x', 'ₓ'),
+];
+
+/// Writes a script with the given script characters, or with its marker when some
+/// of its characters have no script form, e.g. `x^(Q)`.
+fn script(text: &str, characters: &[(char, char)], marker: char) -> String {
+    let text = text.split_whitespace().collect::<String>();
+    let scripted = text
+        .chars()
+        .map(|c| {
+            characters
+                .iter()
+                .find(|(plain, _)| *plain == c)
+                .map(|(_, scripted)| *scripted)
+        })
+        .collect::<Option<String>>();
+    match scripted {
+        Some(scripted) => scripted,
+        None if text.chars().count() == 1 => format!("{marker}{text}"),
+        None => format!("{marker}({text})"),
+    }
+}
+
+fn is_spaced_operator(symbol: &str) -> bool {
+    matches!(
+        symbol,
+        "=" | "<"
+            | ">"
+            | "+"
+            | "±"
+            | "∓"
+            | "×"
+            | "≤"
+            | "≥"
+            | "≠"
+            | "≈"
+            | "≡"
+            | "∼"
+            | "∝"
+            | "→"
+            | "←"
+            | "↦"
+            | "⇒"
+            | "⇔"
+            | "∈"
+            | "∉"
+            | "⊂"
+            | "⊆"
+    )
+}
+
+fn symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" | "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" | "vartheta" => "θ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "rho" | "varrho" => "ρ",
+        "sigma" => "σ",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" | "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Upsilon" => "Υ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "cdot" => "·",
+        "times" => "×",
+        "div" => "÷",
+        "pm" => "±",
+        "mp" => "∓",
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "propto" => "∝",
+        "infty" => "∞",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "sum" => "∑",
+        "prod" => "∏",
+        "int" => "∫",
+        "iint" => "∬",
+        "oint" => "∮",
+        "to" | "rightarrow" => "→",
+        "leftarrow" => "←",
+        "mapsto" => "↦",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftrightarrow" | "iff" => "⇔",
+        "in" => "∈",
+        "notin" => "∉",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "cup" => "∪",
+        "cap" => "∩",
+        "emptyset" | "varnothing" => "∅",
+        "forall" => "∀",
+        "exists" => "∃",
+        "neg" | "lnot" => "¬",
+        "land" | "wedge" => "∧",
+        "lor" | "vee" => "∨",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "circ" => "∘",
+        "prime" => "′",
+        "hbar" => "ℏ",
+        "ell" => "ℓ",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "vert" | "mid" => "|",
+        "Vert" => "‖",
+        "sin" => "sin",
+        "cos" => "cos",
+        "tan" => "tan",
+        "cot" => "cot",
+        "sec" => "sec",
+        "csc" => "csc",
+        "sinh" => "sinh",
+        "cosh" => "cosh",
+        "tanh" => "tanh",
+        "arcsin" => "arcsin",
+        "arccos" => "arccos",
+        "arctan" => "arctan",
+        "log" => "log",
+        "ln" => "ln",
+        "exp" => "exp",
+        "lim" => "lim",
+        "max" => "max",
+        "min" => "min",
+        "det" => "det",
+        "gcd" => "gcd",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latex_to_unicode() {
+        assert_eq!(
+            latex_to_unicode(r"$\displaystyle x^{2} + 2 x + 1$"),
+            "x² + 2x + 1"
+        );
+        assert_eq!(
+            latex_to_unicode(r"$$\sin{\left(x \right)} = -1$$"),
+            "sin(x) = -1"
+        );
+        assert_eq!(latex_to_unicode(r"\sqrt{x + 1} \leq \pi"), "√(x + 1) ≤ π");
+        assert_eq!(latex_to_unicode(r"\frac{1}{\sqrt{x}}"), "1/√x");
+        assert_eq!(latex_to_unicode(r"e^{-x_{1}}"), "e^(-x₁)");
+        assert_eq!(latex_to_unicode(r"\mathbb{R}^{n}"), "ℝⁿ");
+        assert_eq!(
+            latex_to_unicode(r"The root is $\alpha_{i}$."),
+            "The root is αᵢ."
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use gpui::{App, ClipboardItem, Image, ImageFormat, RenderImage, Task, Window, img};
+use ui::{IntoElement, Styled, div, prelude::*};
+
+use crate::outputs::OutputContent;
+
+/// SvgView renders `image/svg+xml` outputs, like matplotlib figures, with gpui's svg renderer.
+pub struct SvgView {
+    raw_text: String,
+    image: Option<Result<Arc<RenderImage>, SharedString>>,
+    _render_task: Task<()>,
+}
+
+impl SvgView {
+    pub fn from(text: String, cx: &mut Context<Self>) -> Self {
+        let renderer = cx.svg_renderer();
+        let render = {
+            let text = text.clone();
+            cx.background_spawn(
+                async move { renderer.render_single_frame(text.as_bytes(), 1.0, true) },
+            )
+        };
+        let render_task = cx.spawn(async move |this, cx| {
+            let image = render.await;
+            this.update(cx, |this, cx| {
+                this.image = Some(image.map_err(|error| error.to_string().into()));
+                cx.notify();
+            })
+            .ok();
+        });
+
+        Self {
+            raw_text: text,
+            image: None,
+            _render_task: render_task,
+        }
+    }
+}
+
+impl Render for SvgView {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        match &self.image {
+            Some(Ok(image)) => div().child(img(image.clone()).max_w_full()),
+            Some(Err(error)) => div()
+                .child(Label::new(format!("Failed to render SVG: {error}")).color(Color::Error)),
+            None => div(),
+        }
+    }
+}
+
+impl OutputContent for SvgView {
+    fn clipboard_content(&self, _window: &Window, _cx: &App) -> Option<ClipboardItem> {
+        let image = Image::from_bytes(ImageFormat::Svg, self.raw_text.clone().into_bytes());
+        Some(ClipboardItem::new_image(&image))
+    }
+
+    fn has_clipboard_content(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+}
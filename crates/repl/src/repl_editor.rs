@@ -54,9 +54,9 @@ pub fn assign_kernelspec(
             cx.subscribe(&session, {
                 let store = store.clone();
                 move |_this, _session, event, cx| match event {
-                    SessionEvent::Shutdown(shutdown_event) => {
+                    SessionEvent::Shutdown(entity_id) => {
                         store.update(cx, |store, _cx| {
-                            store.remove_session(shutdown_event.entity_id());
+                            store.remove_session(*entity_id);
                         });
                     }
                 }
@@ -129,9 +129,9 @@ pub fn run(
                 cx.subscribe(&session, {
                     let store = store.clone();
                     move |_this, _session, event, cx| match event {
-                        SessionEvent::Shutdown(shutdown_event) => {
+                        SessionEvent::Shutdown(entity_id) => {
                             store.update(cx, |store, _cx| {
-                                store.remove_session(shutdown_event.entity_id());
+                                store.remove_session(*entity_id);
                             });
                         }
                     }
@@ -1,5 +1,6 @@
 use crate::components::KernelListItem;
 use crate::kernels::RemoteRunningKernel;
+use crate::notebook::CodeCell;
 use crate::setup_editor_session_actions;
 use crate::{
     KernelStatus,
@@ -19,7 +20,8 @@ use editor::{
 };
 use futures::FutureExt as _;
 use gpui::{
-    Context, Entity, EventEmitter, Render, Subscription, Task, WeakEntity, Window, div, prelude::*,
+    Context, Entity, EntityId, EventEmitter, Render, Subscription, Task, WeakEntity, Window, div,
+    prelude::*,
 };
 use language::Point;
 use project::Fs;
@@ -27,7 +29,7 @@ use runtimelib::{
     ExecuteRequest, ExecutionState, InterruptRequest, JupyterMessage, JupyterMessageContent,
     ShutdownRequest,
 };
-use std::{env::temp_dir, ops::Range, sync::Arc, time::Duration};
+use std::{env::temp_dir, ops::Range, path::PathBuf, sync::Arc, time::Duration};
 use theme::ActiveTheme;
 use ui::{IconButtonShape, Tooltip, prelude::*};
 use util::ResultExt as _;
@@ -35,8 +37,14 @@ use util::ResultExt as _;
 pub struct Session {
     fs: Arc<dyn Fs>,
     editor: WeakEntity<Editor>,
+    /// The editor or notebook the session runs code for.
+    owner_id: EntityId,
+    /// Where the kernel starts when there's no editor to take it from.
+    working_directory: Option<PathBuf>,
     pub kernel: Kernel,
     blocks: HashMap<String, EditorBlock>,
+    /// The notebook cells that were executed, keyed by the id of their execute request.
+    cells: HashMap<String, WeakEntity<CodeCell>>,
     pub kernel_specification: KernelSpecification,
     _buffer_subscription: Subscription,
 }
@@ -217,9 +225,12 @@ impl Session {
 
         let mut session = Self {
             fs,
+            owner_id: editor.entity_id(),
             editor,
+            working_directory: None,
             kernel: Kernel::StartingKernel(Task::ready(()).shared()),
             blocks: HashMap::default(),
+            cells: HashMap::default(),
             kernel_specification,
             _buffer_subscription: subscription,
         };
@@ -228,13 +239,40 @@ impl Session {
         session
     }
 
+    /// Creates a session running the cells of a notebook, whose outputs are shown in the cells
+    /// rather than in editor blocks.
+    pub fn for_notebook(
+        notebook_id: EntityId,
+        working_directory: PathBuf,
+        fs: Arc<dyn Fs>,
+        kernel_specification: KernelSpecification,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut session = Self {
+            fs,
+            editor: WeakEntity::new_invalid(),
+            owner_id: notebook_id,
+            working_directory: Some(working_directory),
+            kernel: Kernel::StartingKernel(Task::ready(()).shared()),
+            blocks: HashMap::default(),
+            cells: HashMap::default(),
+            kernel_specification,
+            _buffer_subscription: Subscription::new(|| {}),
+        };
+
+        session.start_kernel(window, cx);
+        session
+    }
+
     fn start_kernel(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let kernel_language = self.kernel_specification.language();
-        let entity_id = self.editor.entity_id();
+        let entity_id = self.owner_id;
         let working_directory = self
             .editor
             .upgrade()
             .and_then(|editor| editor.read(cx).working_directory(cx))
+            .or_else(|| self.working_directory.clone())
             .unwrap_or_else(temp_dir);
 
         telemetry::event!(
@@ -294,8 +332,18 @@ impl Session {
     pub fn kernel_errored(&mut self, error_message: String, cx: &mut Context<Self>) {
         self.kernel(Kernel::ErroredLaunch(error_message.clone()), cx);
 
-        self.blocks.values().for_each(|block| {
-            block.execution_view.update(cx, |execution_view, cx| {
+        let execution_views = self
+            .blocks
+            .values()
+            .map(|block| block.execution_view.clone())
+            .chain(
+                self.cells
+                    .values()
+                    .filter_map(|cell| Some(cell.upgrade()?.read(cx).execution_view().clone())),
+            )
+            .collect::<Vec<_>>();
+        execution_views.into_iter().for_each(|execution_view| {
+            execution_view.update(cx, |execution_view, cx| {
                 match execution_view.status {
                     ExecutionStatus::Finished => {
                         // Do nothing when the output was good
@@ -361,6 +409,11 @@ impl Session {
             .ok();
 
         self.blocks.clear();
+
+        // The executions sent to the kernel before a restart never finish.
+        for cell in self.cells.drain().filter_map(|(_, cell)| cell.upgrade()) {
+            cell.update(cx, |cell, cx| cell.abandon_execution(cx));
+        }
     }
 
     pub fn execute(
@@ -406,14 +459,7 @@ impl Session {
             })
             .ok();
 
-        let status = match &self.kernel {
-            Kernel::Restarting => ExecutionStatus::Restarting,
-            Kernel::RunningKernel(_) => ExecutionStatus::Queued,
-            Kernel::StartingKernel(_) => ExecutionStatus::ConnectingToKernel,
-            Kernel::ErroredLaunch(error) => ExecutionStatus::KernelErrored(error.clone()),
-            Kernel::ShuttingDown => ExecutionStatus::ShuttingDown,
-            Kernel::Shutdown => ExecutionStatus::Shutdown,
-        };
+        let status = self.execution_status();
 
         let parent_message_id = message.header.msg_id.clone();
         let session_view = cx.entity().downgrade();
@@ -453,6 +499,54 @@ impl Session {
         self.blocks
             .insert(message.header.msg_id.clone(), editor_block);
 
+        self.send_when_started(message, cx);
+
+        if move_down {
+            editor.update(cx, move |editor, cx| {
+                editor.change_selections(
+                    SelectionEffects::scroll(Autoscroll::top_relative(8)),
+                    window,
+                    cx,
+                    |selections| {
+                        selections.select_ranges([new_cursor_pos..new_cursor_pos]);
+                    },
+                );
+            });
+        }
+    }
+
+    /// Executes a notebook cell, streaming the kernel's replies into the cell.
+    pub fn execute_cell(&mut self, code: String, cell: &Entity<CodeCell>, cx: &mut Context<Self>) {
+        let execute_request = ExecuteRequest {
+            code,
+            ..ExecuteRequest::default()
+        };
+        let message: JupyterMessage = execute_request.into();
+
+        let status = self.execution_status();
+        cell.update(cx, |cell, cx| cell.start_execution(status, cx));
+        // Forget the previous executions of the cell, and the cells that are gone.
+        self.cells.retain(|_, executed_cell| {
+            executed_cell.entity_id() != cell.entity_id() && executed_cell.upgrade().is_some()
+        });
+        self.cells
+            .insert(message.header.msg_id.clone(), cell.downgrade());
+
+        self.send_when_started(message, cx);
+    }
+
+    fn execution_status(&self) -> ExecutionStatus {
+        match &self.kernel {
+            Kernel::Restarting => ExecutionStatus::Restarting,
+            Kernel::RunningKernel(_) => ExecutionStatus::Queued,
+            Kernel::StartingKernel(_) => ExecutionStatus::ConnectingToKernel,
+            Kernel::ErroredLaunch(error) => ExecutionStatus::KernelErrored(error.clone()),
+            Kernel::ShuttingDown => ExecutionStatus::ShuttingDown,
+            Kernel::Shutdown => ExecutionStatus::Shutdown,
+        }
+    }
+
+    fn send_when_started(&mut self, message: JupyterMessage, cx: &mut Context<Self>) {
         match &self.kernel {
             Kernel::RunningKernel(_) => {
                 self.send(message, cx).ok();
@@ -472,19 +566,6 @@ impl Session {
             }
             _ => {}
         }
-
-        if move_down {
-            editor.update(cx, move |editor, cx| {
-                editor.change_selections(
-                    SelectionEffects::scroll(Autoscroll::top_relative(8)),
-                    window,
-                    cx,
-                    |selections| {
-                        selections.select_ranges([new_cursor_pos..new_cursor_pos]);
-                    },
-                );
-            });
-        }
     }
 
     pub fn route(&mut self, message: &JupyterMessage, window: &mut Window, cx: &mut Context<Self>) {
@@ -522,6 +603,11 @@ impl Session {
                         execution_view.update_display_data(&update.data, &display_id, window, cx);
                     });
                 });
+                for cell in self.cells.values().filter_map(|cell| cell.upgrade()) {
+                    cell.update(cx, |cell, cx| {
+                        cell.update_display_data(&update.data, &display_id, window, cx);
+                    });
+                }
                 return;
             }
             _ => {}
@@ -529,6 +615,12 @@ impl Session {
 
         if let Some(block) = self.blocks.get_mut(parent_message_id) {
             block.handle_message(message, window, cx);
+        } else if let Some(cell) = self
+            .cells
+            .get(parent_message_id)
+            .and_then(|cell| cell.upgrade())
+        {
+            cell.update(cx, |cell, cx| cell.handle_message(message, window, cx));
         }
     }
 
@@ -546,7 +638,7 @@ impl Session {
 
     pub fn kernel(&mut self, kernel: Kernel, cx: &mut Context<Self>) {
         if let Kernel::Shutdown = kernel {
-            cx.emit(SessionEvent::Shutdown(self.editor.clone()));
+            cx.emit(SessionEvent::Shutdown(self.owner_id));
         }
 
         let kernel_status = KernelStatus::from(&kernel).to_string();
@@ -640,7 +732,8 @@ impl Session {
 }
 
 pub enum SessionEvent {
-    Shutdown(WeakEntity<Editor>),
+    /// The kernel of the given editor's or notebook's session shut down.
+    Shutdown(EntityId),
 }
 
 impl EventEmitter<SessionEvent> for Session {}
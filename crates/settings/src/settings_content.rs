@@ -119,6 +119,9 @@ pub struct SettingsContent {
 
     pub outline_panel: Option<OutlinePanelSettingsContent>,
 
+    /// Configuration for the Hierarchy Panel
+    pub hierarchy_panel: Option<HierarchyPanelSettingsContent>,
+
     pub project_panel: Option<ProjectPanelSettingsContent>,
 
     /// Configuration for the Message Editor
@@ -801,6 +804,29 @@ pub struct OutlinePanelSettingsContent {
     pub expand_outlines_with_depth: Option<usize>,
 }
 
+#[with_fallible_options]
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq)]
+pub struct HierarchyPanelSettingsContent {
+    /// Whether to show the hierarchy panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Customize default width (in pixels) taken by hierarchy panel
+    ///
+    /// Default: 300
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub default_width: Option<f32>,
+    /// The position of hierarchy panel
+    ///
+    /// Default: right
+    pub dock: Option<DockSide>,
+    /// Amount of indentation (in pixels) for nested items.
+    ///
+    /// Default: 20
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub indent_size: Option<f32>,
+}
+
 #[derive(
     Clone,
     Copy,
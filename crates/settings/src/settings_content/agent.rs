@@ -61,6 +61,12 @@ pub struct AgentSettingsContent {
     ///
     /// Default: false
     pub always_allow_tool_actions: Option<bool>,
+    /// Rules for allowing, denying or asking for permission to run each tool, keyed by the tool's name.
+    ///
+    /// These rules take precedence over `always_allow_tool_actions`.
+    ///
+    /// Default: {}
+    pub tool_permissions: Option<IndexMap<Arc<str>, ToolPermissionRulesContent>>,
     /// Where to show a popup notification when the agent is waiting for user input.
     ///
     /// Default: "primary_screen"
@@ -148,6 +154,26 @@ impl AgentSettingsContent {
         self.always_allow_tool_actions = Some(allow);
     }
 
+    /// Remembers that the user always allows the given tool to run with the given subjects,
+    /// or with any subject when there are none.
+    pub fn allow_tool_action(&mut self, tool_name: &str, subjects: Vec<String>) {
+        let rules = self
+            .tool_permissions
+            .get_or_insert_default()
+            .entry(tool_name.into())
+            .or_default();
+        if subjects.is_empty() {
+            rules.default = Some(ToolPermissionMode::Allow);
+        } else {
+            for subject in subjects {
+                let pattern = escape_glob(&subject);
+                if !rules.allow.contains(&pattern) {
+                    rules.allow.push(pattern);
+                }
+            }
+        }
+    }
+
     pub fn set_play_sound_when_agent_done(&mut self, allow: bool) {
         self.play_sound_when_agent_done = Some(allow);
     }
@@ -179,6 +205,56 @@ pub struct AgentProfileContent {
     pub default_model: Option<LanguageModelSelection>,
 }
 
+/// Escapes the glob metacharacters in `text`, so that it's only matched literally.
+fn escape_glob(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '*' | '?' | '[' | ']' | '{' | '}' => {
+                escaped.push('[');
+                escaped.push(c);
+                escaped.push(']');
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[with_fallible_options]
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
+pub struct ToolPermissionRulesContent {
+    /// What to do when none of the patterns match.
+    ///
+    /// When unset, the tool only asks for permission when it normally would.
+    pub default: Option<ToolPermissionMode>,
+    /// Glob patterns for the tool's subjects, like the terminal command or the edited path,
+    /// that the tool can run with without asking.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Glob patterns for the tool's subjects that always require permission.
+    ///
+    /// These take precedence over `allow`.
+    #[serde(default)]
+    pub ask: Vec<String>,
+    /// Glob patterns for the tool's subjects that the tool must never run with.
+    ///
+    /// These take precedence over `allow` and `ask`.
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolPermissionMode {
+    /// Run the tool without asking.
+    Allow,
+    /// Ask for permission before running the tool.
+    Ask,
+    /// Never run the tool.
+    Deny,
+}
+
 #[with_fallible_options]
 #[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
 pub struct ContextServerPresetContent {
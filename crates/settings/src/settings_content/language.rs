@@ -439,6 +439,11 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: false
     pub colorize_brackets: Option<bool>,
+    /// Whether to highlight the code with semantic tokens provided by language servers,
+    /// on top of the tree-sitter highlights.
+    ///
+    /// Default: false
+    pub semantic_tokens: Option<bool>,
 }
 
 /// Controls how whitespace should be displayedin the editor.
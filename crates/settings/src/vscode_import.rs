@@ -188,6 +188,7 @@ impl VsCodeSettings {
             git_panel: self.git_panel_settings_content(),
             global_lsp_settings: None,
             helix_mode: None,
+            hierarchy_panel: None,
             image_viewer: None,
             journal: None,
             language_models: None,
@@ -451,6 +452,7 @@ impl VsCodeSettings {
             remove_trailing_whitespace_on_save: self.read_bool("editor.trimAutoWhitespace"),
             show_completion_documentation: None,
             colorize_brackets: self.read_bool("editor.bracketPairColorization.enabled"),
+            semantic_tokens: self.read_bool("editor.semanticHighlighting.enabled"),
             show_completions_on_input: self.read_bool("editor.suggestOnTriggerCharacters"),
             show_edit_predictions: self.read_bool("editor.inlineSuggest.enabled"),
             show_whitespaces: self.read_enum("editor.renderWhitespace", |s| {
@@ -4187,6 +4187,84 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                     ),
                     metadata: None,
                 }),
+                SettingsPageItem::SectionHeader("Hierarchy Panel"),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Hierarchy Panel Button",
+                    description: "Show the hierarchy panel button in the status bar.",
+                    field: Box::new(SettingField {
+                        json_path: Some("hierarchy_panel.button"),
+                        pick: |settings_content| {
+                            settings_content.hierarchy_panel.as_ref()?.button.as_ref()
+                        },
+                        write: |settings_content, value| {
+                            settings_content
+                                .hierarchy_panel
+                                .get_or_insert_default()
+                                .button = value;
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Hierarchy Panel Dock",
+                    description: "Where to dock the hierarchy panel.",
+                    field: Box::new(SettingField {
+                        json_path: Some("hierarchy_panel.dock"),
+                        pick: |settings_content| {
+                            settings_content.hierarchy_panel.as_ref()?.dock.as_ref()
+                        },
+                        write: |settings_content, value| {
+                            settings_content.hierarchy_panel.get_or_insert_default().dock = value;
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Hierarchy Panel Default Width",
+                    description: "Default width of the hierarchy panel in pixels.",
+                    field: Box::new(SettingField {
+                        json_path: Some("hierarchy_panel.default_width"),
+                        pick: |settings_content| {
+                            settings_content
+                                .hierarchy_panel
+                                .as_ref()?
+                                .default_width
+                                .as_ref()
+                        },
+                        write: |settings_content, value| {
+                            settings_content
+                                .hierarchy_panel
+                                .get_or_insert_default()
+                                .default_width = value;
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Hierarchy Panel Indent Size",
+                    description: "Amount of indentation for nested items in the hierarchy panel.",
+                    field: Box::new(SettingField {
+                        json_path: Some("hierarchy_panel.indent_size"),
+                        pick: |settings_content| {
+                            settings_content
+                                .hierarchy_panel
+                                .as_ref()?
+                                .indent_size
+                                .as_ref()
+                        },
+                        write: |settings_content, value| {
+                            settings_content
+                                .hierarchy_panel
+                                .get_or_insert_default()
+                                .indent_size = value;
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
                 SettingsPageItem::SectionHeader("Git Panel"),
                 SettingsPageItem::SettingItem(SettingItem {
                     title: "Git Panel Button",
@@ -7177,6 +7255,25 @@ fn language_settings_data() -> Vec<SettingsPageItem> {
             metadata: None,
             files: USER | PROJECT,
         }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Semantic Tokens",
+            description: "Whether to highlight the code with semantic tokens provided by language servers.",
+            field: Box::new(SettingField {
+                json_path: Some("languages.$(language).semantic_tokens"),
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        language.semantic_tokens.as_ref()
+                    })
+                },
+                write: |settings_content, value| {
+                    language_settings_field_mut(settings_content, value, |language, value| {
+                        language.semantic_tokens = value;
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | PROJECT,
+        }),
     ]);
 
     if current_language().is_none() {
@@ -0,0 +1,507 @@
+//! Problem matchers: regex-based extraction of compiler errors and other problems from the task output.
+//!
+//! The format follows VS Code's `problemMatcher` closely, so that the definitions can be translated from `tasks.json` files.
+
+use std::path::{Path, PathBuf};
+
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A problem matcher a [`crate::TaskTemplate`] refers to: either a name of a built-in matcher, or an inline definition.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum TaskProblemMatcher {
+    /// A name of a built-in problem matcher:
+    /// * `$rustc` — errors and warnings from `rustc` and `cargo`
+    /// * `$tsc` — errors and warnings from the TypeScript compiler
+    /// * `$gcc` — errors and warnings from `gcc` and `clang`
+    /// * `$eslint-stylish` — problems from `eslint`, in its default output format
+    /// * `$eslint-compact` — problems from `eslint`, with `--format compact`
+    BuiltIn(String),
+    /// A custom problem matcher definition.
+    Custom(ProblemMatcher),
+}
+
+impl TaskProblemMatcher {
+    /// Returns the problem matcher definition, looking up the built-in matchers by name.
+    pub fn definition(&self) -> Option<ProblemMatcher> {
+        match self {
+            Self::BuiltIn(name) => {
+                let matcher = built_in_problem_matcher(name);
+                if matcher.is_none() {
+                    log::warn!("Unknown problem matcher `{name}`");
+                }
+                matcher
+            }
+            Self::Custom(matcher) => Some(matcher.clone()),
+        }
+    }
+}
+
+/// Describes how to find problems in the task output.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemMatcher {
+    /// A name of the tool reporting the problems, shown as the source of the diagnostics.
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// A directory the relative file paths of the problems are resolved against.
+    /// Defaults to the working directory of the task.
+    #[serde(default)]
+    pub base_directory: Option<String>,
+    /// A severity to use for problems that do not report one.
+    #[serde(default)]
+    pub severity: ProblemSeverity,
+    /// Patterns to match consecutive lines of the output with.
+    /// A problem is reported when all of them match, with its properties collected from all the patterns matched.
+    pub patterns: Vec<ProblemPattern>,
+}
+
+/// A pattern to match a single line of the task output with.
+/// The properties of the problem are extracted from the capture groups with the indices given.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemPattern {
+    /// A regular expression to match the line with.
+    pub regexp: String,
+    #[serde(default)]
+    pub file: Option<usize>,
+    #[serde(default)]
+    pub line: Option<usize>,
+    #[serde(default)]
+    pub column: Option<usize>,
+    #[serde(default)]
+    pub end_line: Option<usize>,
+    #[serde(default)]
+    pub end_column: Option<usize>,
+    #[serde(default)]
+    pub severity: Option<usize>,
+    #[serde(default)]
+    pub code: Option<usize>,
+    #[serde(default)]
+    pub message: Option<usize>,
+    /// Whether this pattern may match multiple lines in a row, reporting a problem for each of them.
+    /// Only allowed for the last pattern.
+    #[serde(default, rename = "loop")]
+    pub repeat: bool,
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl ProblemSeverity {
+    fn parse(severity: &str) -> Option<Self> {
+        match severity.trim().to_lowercase().as_str() {
+            "error" | "fatal" => Some(Self::Error),
+            "warning" | "warn" => Some(Self::Warning),
+            "info" | "information" | "note" => Some(Self::Info),
+            "hint" | "help" => Some(Self::Hint),
+            _ => None,
+        }
+    }
+}
+
+/// A problem found in the task output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskProblem {
+    /// A path to the file with the problem, absolute if it could be resolved.
+    pub path: PathBuf,
+    /// A 1-based line of the problem start.
+    pub line: u32,
+    /// A 1-based column of the problem start, if reported.
+    pub column: Option<u32>,
+    /// A 1-based line of the problem end, if reported.
+    pub end_line: Option<u32>,
+    /// A 1-based column of the problem end, if reported.
+    pub end_column: Option<u32>,
+    pub severity: ProblemSeverity,
+    pub message: String,
+    pub code: Option<String>,
+    /// A name of the tool that reported the problem.
+    pub source: Option<String>,
+}
+
+#[derive(Clone, Default)]
+struct ProblemData {
+    file: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    end_line: Option<u32>,
+    end_column: Option<u32>,
+    severity: Option<ProblemSeverity>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl ProblemData {
+    fn fill(&mut self, pattern: &ProblemPattern, captures: &Captures) {
+        let group = |index: Option<usize>| {
+            index
+                .and_then(|index| captures.get(index))
+                .map(|group| group.as_str())
+                .filter(|group| !group.is_empty())
+        };
+        let number = |index: Option<usize>| group(index).and_then(|group| group.parse().ok());
+
+        if let Some(file) = group(pattern.file) {
+            self.file = Some(file.to_owned());
+        }
+        self.line = number(pattern.line).or(self.line);
+        self.column = number(pattern.column).or(self.column);
+        self.end_line = number(pattern.end_line).or(self.end_line);
+        self.end_column = number(pattern.end_column).or(self.end_column);
+        if let Some(severity) = group(pattern.severity).and_then(ProblemSeverity::parse) {
+            self.severity = Some(severity);
+        }
+        if let Some(code) = group(pattern.code) {
+            self.code = Some(code.to_owned());
+        }
+        if let Some(message) = group(pattern.message) {
+            self.message = Some(message.trim().to_owned());
+        }
+    }
+
+    fn into_problem(
+        self,
+        matcher: &ProblemMatcher,
+        base_directory: Option<&Path>,
+    ) -> Option<TaskProblem> {
+        let file = PathBuf::from(self.file?);
+        let path = match base_directory {
+            Some(base_directory) if file.is_relative() => base_directory.join(file),
+            _ => file,
+        };
+        Some(TaskProblem {
+            path,
+            line: self.line?.max(1),
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+            severity: self.severity.unwrap_or(matcher.severity),
+            message: self.message?,
+            code: self.code,
+            source: matcher.owner.clone(),
+        })
+    }
+}
+
+impl ProblemMatcher {
+    /// Finds all problems in the output given.
+    /// Relative file paths are resolved against the [`ProblemMatcher::base_directory`], or the `cwd` given if there is none.
+    pub fn match_output(&self, output: &str, cwd: Option<&Path>) -> Vec<TaskProblem> {
+        let patterns = match self
+            .patterns
+            .iter()
+            .map(|pattern| Regex::new(&pattern.regexp).map(|regex| (pattern, regex)))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(patterns) => patterns,
+            Err(e) => {
+                log::error!("Invalid problem matcher pattern: {e}");
+                return Vec::new();
+            }
+        };
+        let Some(((last_pattern, last_regex), prefix)) = patterns.split_last() else {
+            return Vec::new();
+        };
+        let (prefix, repeated) = if last_pattern.repeat {
+            (prefix, Some((*last_pattern, last_regex)))
+        } else {
+            (patterns.as_slice(), None)
+        };
+        let base_directory = self
+            .base_directory
+            .as_deref()
+            .map(|base_directory| match cwd {
+                Some(cwd) => cwd.join(base_directory),
+                None => PathBuf::from(base_directory),
+            })
+            .or_else(|| cwd.map(Path::to_path_buf));
+
+        let lines = output.lines().collect::<Vec<_>>();
+        let mut problems = Vec::new();
+        let mut line_ix = 0;
+        'lines: while line_ix < lines.len() {
+            let mut data = ProblemData::default();
+            let mut next_line_ix = line_ix;
+            for (pattern, regex) in prefix {
+                let Some(captures) = lines
+                    .get(next_line_ix)
+                    .and_then(|line| regex.captures(line))
+                else {
+                    line_ix += 1;
+                    continue 'lines;
+                };
+                data.fill(pattern, &captures);
+                next_line_ix += 1;
+            }
+
+            match repeated {
+                Some((pattern, regex)) => {
+                    let first_repeated_line_ix = next_line_ix;
+                    while let Some(captures) = lines
+                        .get(next_line_ix)
+                        .and_then(|line| regex.captures(line))
+                    {
+                        let mut data = data.clone();
+                        data.fill(pattern, &captures);
+                        problems.extend(data.into_problem(self, base_directory.as_deref()));
+                        next_line_ix += 1;
+                    }
+                    if next_line_ix == first_repeated_line_ix {
+                        line_ix += 1;
+                        continue;
+                    }
+                }
+                None => {
+                    problems.extend(data.into_problem(self, base_directory.as_deref()));
+                }
+            }
+            line_ix = next_line_ix.max(line_ix + 1);
+        }
+        problems
+    }
+}
+
+/// Returns a built-in problem matcher by its name, see [`TaskProblemMatcher::BuiltIn`] for the ones available.
+pub fn built_in_problem_matcher(name: &str) -> Option<ProblemMatcher> {
+    let matcher = match name {
+        "$rustc" => ProblemMatcher {
+            owner: Some("rustc".to_owned()),
+            patterns: vec![
+                ProblemPattern {
+                    regexp: r"^(warning|error)(?:\[(\w+)\])?: (.*)$".to_owned(),
+                    severity: Some(1),
+                    code: Some(2),
+                    message: Some(3),
+                    ..ProblemPattern::default()
+                },
+                ProblemPattern {
+                    regexp: r"^\s*-->\s+(.*?):(\d+):(\d+)\s*$".to_owned(),
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    ..ProblemPattern::default()
+                },
+            ],
+            ..ProblemMatcher::default()
+        },
+        "$tsc" => ProblemMatcher {
+            owner: Some("tsc".to_owned()),
+            patterns: vec![ProblemPattern {
+                regexp: r"^([^\s].*)[\(:](\d+)[,:](\d+)(?:\):\s+|\s+-\s+)(error|warning|info)\s+(TS\d+)\s*:\s*(.*)$".to_owned(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                code: Some(5),
+                message: Some(6),
+                ..ProblemPattern::default()
+            }],
+            ..ProblemMatcher::default()
+        },
+        "$gcc" => ProblemMatcher {
+            owner: Some("gcc".to_owned()),
+            patterns: vec![ProblemPattern {
+                regexp: r"^(.*?):(\d+):(\d*):?\s+(?:fatal\s+)?(warning|error):\s+(.*)$".to_owned(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                message: Some(5),
+                ..ProblemPattern::default()
+            }],
+            ..ProblemMatcher::default()
+        },
+        "$eslint-compact" => ProblemMatcher {
+            owner: Some("eslint".to_owned()),
+            patterns: vec![ProblemPattern {
+                regexp: r"^(.+):\sline\s(\d+),\scol\s(\d+),\s(Error|Warning|Info)\s-\s(.+)\s\((.+)\)$"
+                    .to_owned(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                message: Some(5),
+                code: Some(6),
+                ..ProblemPattern::default()
+            }],
+            ..ProblemMatcher::default()
+        },
+        "$eslint-stylish" => ProblemMatcher {
+            owner: Some("eslint".to_owned()),
+            patterns: vec![
+                ProblemPattern {
+                    regexp: r"^((?:[a-zA-Z]:)?[./\\].*?)\s*$".to_owned(),
+                    file: Some(1),
+                    ..ProblemPattern::default()
+                },
+                ProblemPattern {
+                    regexp: r"^\s+(\d+):(\d+)\s+(error|warning|info)\s+(.+?)(?:\s\s+(\S+))?$"
+                        .to_owned(),
+                    line: Some(1),
+                    column: Some(2),
+                    severity: Some(3),
+                    message: Some(4),
+                    code: Some(5),
+                    repeat: true,
+                    ..ProblemPattern::default()
+                },
+            ],
+            ..ProblemMatcher::default()
+        },
+        _ => return None,
+    };
+    Some(matcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn problems(matcher: &str, output: &str) -> Vec<TaskProblem> {
+        built_in_problem_matcher(matcher)
+            .unwrap()
+            .match_output(output, Some(Path::new("/project")))
+    }
+
+    #[test]
+    fn test_rustc_problems() {
+        let output = r#"   Compiling example v0.1.0 (/project)
+warning: unused variable: `x`
+ --> src/main.rs:2:9
+  |
+2 |     let x = 5;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_x`
+
+error[E0425]: cannot find value `y` in this scope
+  --> src/lib.rs:10:5
+   |
+10 |     y
+   |     ^ not found in this scope
+
+error: could not compile `example` (bin "example") due to 1 previous error; 1 warning emitted
+"#;
+        assert_eq!(
+            problems("$rustc", output),
+            vec![
+                TaskProblem {
+                    path: PathBuf::from("/project/src/main.rs"),
+                    line: 2,
+                    column: Some(9),
+                    end_line: None,
+                    end_column: None,
+                    severity: ProblemSeverity::Warning,
+                    message: "unused variable: `x`".to_owned(),
+                    code: None,
+                    source: Some("rustc".to_owned()),
+                },
+                TaskProblem {
+                    path: PathBuf::from("/project/src/lib.rs"),
+                    line: 10,
+                    column: Some(5),
+                    end_line: None,
+                    end_column: None,
+                    severity: ProblemSeverity::Error,
+                    message: "cannot find value `y` in this scope".to_owned(),
+                    code: Some("E0425".to_owned()),
+                    source: Some("rustc".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_line_problems() {
+        let tsc_problems = problems(
+            "$tsc",
+            "src/index.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.",
+        );
+        assert_eq!(tsc_problems.len(), 1);
+        assert_eq!(tsc_problems[0].path, PathBuf::from("/project/src/index.ts"));
+        assert_eq!((tsc_problems[0].line, tsc_problems[0].column), (3, Some(7)));
+        assert_eq!(tsc_problems[0].code.as_deref(), Some("TS2322"));
+
+        let gcc_problems = problems(
+            "$gcc",
+            "/abs/main.c:5:12: warning: unused variable 'a' [-Wunused-variable]\nmain.c:8:1: error: expected ';' before '}' token",
+        );
+        assert_eq!(
+            gcc_problems
+                .iter()
+                .map(|problem| (problem.path.clone(), problem.line, problem.severity))
+                .collect::<Vec<_>>(),
+            vec![
+                (PathBuf::from("/abs/main.c"), 5, ProblemSeverity::Warning),
+                (PathBuf::from("/project/main.c"), 8, ProblemSeverity::Error),
+            ]
+        );
+
+        let eslint_problems = problems(
+            "$eslint-compact",
+            "/project/src/app.js: line 4, col 10, Warning - 'foo' is defined but never used. (no-unused-vars)",
+        );
+        assert_eq!(eslint_problems.len(), 1);
+        assert_eq!(eslint_problems[0].severity, ProblemSeverity::Warning);
+        assert_eq!(eslint_problems[0].code.as_deref(), Some("no-unused-vars"));
+    }
+
+    #[test]
+    fn test_looped_problems() {
+        let output = r#"
+/project/src/app.js
+   4:10  warning  'foo' is defined but never used  no-unused-vars
+  12:3   error    Unexpected console statement     no-console
+
+/project/src/other.js
+  1:1  error  Parsing error: Unexpected token
+
+✖ 3 problems (2 errors, 1 warning)
+"#;
+        assert_eq!(
+            problems("$eslint-stylish", output)
+                .into_iter()
+                .map(|problem| (
+                    problem.path,
+                    problem.line,
+                    problem.column,
+                    problem.message,
+                    problem.code
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    PathBuf::from("/project/src/app.js"),
+                    4,
+                    Some(10),
+                    "'foo' is defined but never used".to_owned(),
+                    Some("no-unused-vars".to_owned())
+                ),
+                (
+                    PathBuf::from("/project/src/app.js"),
+                    12,
+                    Some(3),
+                    "Unexpected console statement".to_owned(),
+                    Some("no-console".to_owned())
+                ),
+                (
+                    PathBuf::from("/project/src/other.js"),
+                    1,
+                    Some(1),
+                    "Parsing error: Unexpected token".to_owned(),
+                    None
+                ),
+            ]
+        );
+    }
+}
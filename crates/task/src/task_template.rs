@@ -10,7 +10,8 @@ use util::{ResultExt, truncate_and_remove_front};
 
 use crate::{
     AttachRequest, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext, TaskId,
-    VariableName, ZED_VARIABLE_NAME_PREFIX, serde_helpers::non_empty_string_vec,
+    TaskProblemMatcher, VariableName, ZED_VARIABLE_NAME_PREFIX,
+    serde_helpers::non_empty_string_vec,
 };
 
 /// A template definition of a Zed task to run.
@@ -25,6 +26,8 @@ pub struct TaskTemplate {
     /// Human readable name of the task to display in the UI.
     pub label: String,
     /// Executable command to spawn.
+    /// May be omitted for tasks that only run the tasks they depend on.
+    #[serde(default)]
     pub command: String,
     /// Arguments to the command.
     #[serde(default)]
@@ -72,6 +75,19 @@ pub struct TaskTemplate {
     /// Whether to show the command line in the task output.
     #[serde(default = "default_true")]
     pub show_command: bool,
+    /// Labels of the tasks that have to finish successfully before this task starts.
+    /// If any of them fails, this task is not started.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// How to run the tasks this task depends on:
+    /// * `parallel` — start all of them at once (default)
+    /// * `sequence` — start them one by one, in the order they are listed
+    #[serde(default, skip_serializing_if = "DependsOrder::is_parallel")]
+    pub depends_order: DependsOrder,
+    /// Problem matchers to find errors and warnings in the task output with, reported as project diagnostics after the task finishes.
+    /// Either names of the built-in matchers (`$rustc`, `$tsc`, `$gcc`, `$eslint-stylish`, `$eslint-compact`), or custom matcher definitions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problem_matchers: Vec<TaskProblemMatcher>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -109,6 +125,23 @@ pub enum HideStrategy {
     OnSuccess,
 }
 
+/// How to run the tasks a task depends on.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependsOrder {
+    /// Start all dependencies at once.
+    #[default]
+    Parallel,
+    /// Start the dependencies one by one, each after the previous one has finished successfully.
+    Sequence,
+}
+
+impl DependsOrder {
+    fn is_parallel(&self) -> bool {
+        *self == Self::Parallel
+    }
+}
+
 /// A group of Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TaskTemplates(pub Vec<TaskTemplate>);
@@ -133,8 +166,12 @@ impl TaskTemplate {
     ///
     /// Every [`ResolvedTask`] gets a [`TaskId`], based on the `id_base` (to avoid collision with various task sources),
     /// and hashes of its template and [`TaskContext`], see [`ResolvedTask`] fields' documentation for more details.
+    ///
+    /// Tasks with [`TaskTemplate::depends_on`] may have no command, and resolve into tasks that only run their dependencies.
     pub fn resolve_task(&self, id_base: &str, cx: &TaskContext) -> Option<ResolvedTask> {
-        if self.label.trim().is_empty() || self.command.trim().is_empty() {
+        if self.label.trim().is_empty()
+            || (self.command.trim().is_empty() && self.depends_on.is_empty())
+        {
             return None;
         }
 
@@ -214,6 +251,24 @@ impl TaskTemplate {
             &mut substituted_variables,
         )?;
 
+        let problem_matchers = self
+            .problem_matchers
+            .iter()
+            .filter_map(|matcher| matcher.definition())
+            .map(|mut matcher| {
+                matcher.base_directory = match matcher.base_directory.take() {
+                    Some(base_directory) => Some(substitute_all_template_variables_in_str(
+                        &base_directory,
+                        &task_variables,
+                        &variable_names,
+                        &mut substituted_variables,
+                    )?),
+                    None => None,
+                };
+                Some(matcher)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
         let task_hash = to_hex_hash(self)
             .context("hashing task template")
             .log_err()?;
@@ -246,6 +301,7 @@ impl TaskTemplate {
             id: id.clone(),
             substituted_variables,
             original_task: self.clone(),
+            dependencies_context: (!self.depends_on.is_empty()).then(|| cx.clone()),
             resolved_label: full_label.clone(),
             resolved: SpawnInTerminal {
                 id,
@@ -260,7 +316,7 @@ impl TaskTemplate {
                         command_label
                     },
                 ),
-                command: Some(command),
+                command: (!command.trim().is_empty()).then_some(command),
                 args: args_with_substitutions,
                 env,
                 use_new_terminal: self.use_new_terminal,
@@ -272,6 +328,7 @@ impl TaskTemplate {
                 show_summary: self.show_summary,
                 show_command: self.show_command,
                 show_rerun: true,
+                problem_matchers,
             },
         })
     }
@@ -470,6 +527,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolving_templates_with_dependencies() {
+        let composite_task = TaskTemplate {
+            label: "build all".to_string(),
+            depends_on: vec!["build server".to_string(), "build client".to_string()],
+            ..TaskTemplate::default()
+        };
+        let context = TaskContext {
+            cwd: Some(PathBuf::from("/dir")),
+            ..TaskContext::default()
+        };
+
+        let resolved_task = composite_task
+            .resolve_task(TEST_ID_BASE, &context)
+            .expect("tasks with dependencies should resolve without a command");
+        assert_eq!(resolved_task.resolved.command, None);
+        assert_eq!(resolved_task.dependencies_context(), Some(&context));
+
+        let plain_task = TaskTemplate {
+            label: "build server".to_string(),
+            command: "cargo".to_string(),
+            ..TaskTemplate::default()
+        };
+        let resolved_task = plain_task.resolve_task(TEST_ID_BASE, &context).unwrap();
+        assert_eq!(resolved_task.resolved.command.as_deref(), Some("cargo"));
+        assert_eq!(resolved_task.dependencies_context(), None);
+    }
+
     #[test]
     fn test_template_cwd_resolution() {
         let task_without_cwd = TaskTemplate {
@@ -3,7 +3,10 @@ use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt;
 
-use crate::{EnvVariableReplacer, TaskTemplate, TaskTemplates, VariableName};
+use crate::{
+    DependsOrder, EnvVariableReplacer, ProblemMatcher, ProblemPattern, ProblemSeverity,
+    TaskProblemMatcher, TaskTemplate, TaskTemplates, VariableName, built_in_problem_matcher,
+};
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -19,11 +22,139 @@ struct VsCodeTaskDefinition {
     label: String,
     #[serde(flatten)]
     command: Option<Command>,
+    /// Either a single task label, or a list of them.
+    depends_on: Option<serde_json_lenient::Value>,
+    depends_order: Option<DependsOrder>,
+    /// Either a single problem matcher, or a list of them; each is either a name or a definition.
+    problem_matcher: Option<serde_json_lenient::Value>,
     #[serde(flatten)]
     other_attributes: HashMap<String, serde_json_lenient::Value>,
     options: Option<TaskOptions>,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            Self::One(item) => vec![item],
+            Self::Many(items) => items,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum VsCodeProblemMatcher {
+    Named(String),
+    Definition(VsCodeProblemMatcherDefinition),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeProblemMatcherDefinition {
+    owner: Option<String>,
+    base: Option<String>,
+    file_location: Option<OneOrMany<String>>,
+    severity: Option<String>,
+    pattern: Option<VsCodeProblemPatterns>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum VsCodeProblemPatterns {
+    Named(String),
+    Patterns(OneOrMany<VsCodeProblemPattern>),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeProblemPattern {
+    regexp: String,
+    file: Option<usize>,
+    line: Option<usize>,
+    column: Option<usize>,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+    severity: Option<usize>,
+    code: Option<usize>,
+    message: Option<usize>,
+    #[serde(default, rename = "loop")]
+    repeat: bool,
+}
+
+impl VsCodeProblemMatcher {
+    fn into_zed_format(self, replacer: &EnvVariableReplacer) -> Option<TaskProblemMatcher> {
+        let definition = match self {
+            Self::Named(name) => {
+                if built_in_problem_matcher(&name).is_none() {
+                    log::warn!("Skipping unsupported problem matcher `{name}`");
+                    return None;
+                }
+                return Some(TaskProblemMatcher::BuiltIn(name));
+            }
+            Self::Definition(definition) => definition,
+        };
+
+        let mut matcher = match (definition.base, definition.pattern) {
+            (_, Some(VsCodeProblemPatterns::Patterns(patterns))) => ProblemMatcher {
+                patterns: patterns
+                    .into_vec()
+                    .into_iter()
+                    .map(|pattern| ProblemPattern {
+                        regexp: pattern.regexp,
+                        file: pattern.file,
+                        line: pattern.line,
+                        column: pattern.column,
+                        end_line: pattern.end_line,
+                        end_column: pattern.end_column,
+                        severity: pattern.severity,
+                        code: pattern.code,
+                        message: pattern.message,
+                        repeat: pattern.repeat,
+                    })
+                    .collect(),
+                ..ProblemMatcher::default()
+            },
+            (_, Some(VsCodeProblemPatterns::Named(name))) | (Some(name), None) => {
+                let Some(matcher) = built_in_problem_matcher(&name) else {
+                    log::warn!("Skipping unsupported problem matcher `{name}`");
+                    return None;
+                };
+                matcher
+            }
+            (None, None) => {
+                log::warn!("Skipping problem matcher without a pattern");
+                return None;
+            }
+        };
+        if let Some(owner) = definition.owner {
+            matcher.owner = Some(owner);
+        }
+        if let Some(severity) = definition.severity {
+            matcher.severity = match severity.as_str() {
+                "warning" => ProblemSeverity::Warning,
+                "info" => ProblemSeverity::Info,
+                _ => ProblemSeverity::Error,
+            };
+        }
+        // `["relative", "${workspaceFolder}/some/dir"]` sets the directory to resolve relative paths against,
+        // other forms only select whether the paths are absolute or relative, which is detected automatically.
+        if let Some(OneOrMany::Many(file_location)) = definition.file_location
+            && let [kind, base_directory] = file_location.as_slice()
+            && kind == "relative"
+        {
+            matcher.base_directory = Some(replacer.replace(base_directory));
+        }
+        Some(TaskProblemMatcher::Custom(matcher))
+    }
+}
+
 #[derive(Clone, Deserialize, PartialEq, Debug)]
 #[serde(tag = "type")]
 #[serde(rename_all = "camelCase")]
@@ -46,25 +177,31 @@ impl VsCodeTaskDefinition {
         self,
         replacer: &EnvVariableReplacer,
     ) -> anyhow::Result<Option<TaskTemplate>> {
-        if self.other_attributes.contains_key("dependsOn") {
-            log::warn!(
-                "Skipping deserializing of a task `{}` with the unsupported `dependsOn` key",
+        let depends_on = match self.depends_on {
+            None => Vec::new(),
+            Some(serde_json_lenient::Value::String(label)) => vec![label],
+            Some(serde_json_lenient::Value::Array(labels)) => labels
+                .into_iter()
+                .map(|label| match label {
+                    serde_json_lenient::Value::String(label) => Ok(label),
+                    other => bail!(
+                        "Unsupported `dependsOn` entry {other} in task `{}`, only task labels are supported",
+                        self.label
+                    ),
+                })
+                .collect::<anyhow::Result<_>>()?,
+            Some(other) => bail!(
+                "Unsupported `dependsOn` value {other} in task `{}`",
                 self.label
-            );
-            return Ok(None);
-        }
-        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
-        // as that way we can provide more specific description of why deserialization failed.
-        // E.g. if the command is missing due to `dependsOn` presence, we can check other_attributes first before doing this (and provide nice error message)
-        // catch-all if on value.command presence.
-        let Some(command) = self.command else {
-            bail!("Missing `type` field in task");
+            ),
         };
-
-        let (command, args) = match command {
-            Command::Npm { script } => ("npm".to_owned(), vec!["run".to_string(), script]),
-            Command::Shell { command, args } => (command, args),
-            Command::Gulp { task } => ("gulp".to_owned(), vec![task]),
+        // `type` might not be set in tasks that only run the tasks they depend on.
+        let (command, args) = match self.command {
+            Some(Command::Npm { script }) => ("npm".to_owned(), vec!["run".to_string(), script]),
+            Some(Command::Shell { command, args }) => (command, args),
+            Some(Command::Gulp { task }) => ("gulp".to_owned(), vec![task]),
+            None if !depends_on.is_empty() => (String::new(), Vec::new()),
+            None => bail!("Missing `type` field in task"),
         };
         // Per VSC docs, only `command`, `args` and `options` support variable substitution.
         let command = replacer.replace(&command);
@@ -73,8 +210,26 @@ impl VsCodeTaskDefinition {
             label: self.label,
             command,
             args,
+            depends_on,
+            depends_order: self.depends_order.unwrap_or_default(),
             ..TaskTemplate::default()
         };
+        if let Some(problem_matcher) = self.problem_matcher {
+            match serde_json_lenient::from_value::<OneOrMany<VsCodeProblemMatcher>>(problem_matcher)
+            {
+                Ok(problem_matchers) => {
+                    template.problem_matchers = problem_matchers
+                        .into_vec()
+                        .into_iter()
+                        .filter_map(|matcher| matcher.into_zed_format(replacer))
+                        .collect();
+                }
+                Err(e) => log::warn!(
+                    "Skipping unsupported problem matchers of task `{}`: {e}",
+                    template.label
+                ),
+            }
+        }
         if let Some(options) = self.options {
             template.cwd = options.cwd.map(|cwd| replacer.replace(&cwd));
             template.env = options.env;
@@ -124,20 +279,24 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::{
-        TaskTemplate, TaskTemplates, VsCodeTaskFile,
+        DependsOrder, ProblemMatcher, TaskProblemMatcher, TaskTemplate, TaskTemplates,
+        VsCodeTaskFile, built_in_problem_matcher,
         vscode_format::{Command, VsCodeTaskDefinition},
     };
 
     use super::EnvVariableReplacer;
 
+    /// Problem matchers are compared in their translated form, see [`TaskTemplate::problem_matchers`].
     fn compare_without_other_attributes(lhs: VsCodeTaskDefinition, rhs: VsCodeTaskDefinition) {
         assert_eq!(
             VsCodeTaskDefinition {
                 other_attributes: Default::default(),
+                problem_matcher: None,
                 ..lhs
             },
             VsCodeTaskDefinition {
                 other_attributes: Default::default(),
+                problem_matcher: None,
                 ..rhs
             },
         );
@@ -180,6 +339,9 @@ mod tests {
                 command: Some(Command::Npm {
                     script: "build:tests:notypecheck".to_string(),
                 }),
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: None,
                 other_attributes: Default::default(),
                 options: None,
             },
@@ -194,6 +356,9 @@ mod tests {
                         "--watch".to_string(),
                     ],
                 }),
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: None,
                 other_attributes: Default::default(),
                 options: None,
             },
@@ -202,6 +367,9 @@ mod tests {
                 command: Some(Command::Npm {
                     script: "build:compiler".to_string(),
                 }),
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: None,
                 other_attributes: Default::default(),
                 options: None,
             },
@@ -210,6 +378,9 @@ mod tests {
                 command: Some(Command::Npm {
                     script: "build:tests:notypecheck".to_string(),
                 }),
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: None,
                 other_attributes: Default::default(),
                 options: None,
             },
@@ -227,6 +398,7 @@ mod tests {
                 label: "gulp: tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![TaskProblemMatcher::BuiltIn("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
@@ -244,12 +416,14 @@ mod tests {
                 label: "npm: build:compiler".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:compiler".to_string()],
+                problem_matchers: vec![TaskProblemMatcher::BuiltIn("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![TaskProblemMatcher::BuiltIn("$tsc".to_string())],
                 ..Default::default()
             },
         ];
@@ -270,6 +444,9 @@ mod tests {
                     script: "watch".to_string(),
                 }),
                 options: None,
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: None,
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
@@ -278,6 +455,9 @@ mod tests {
                     script: "build".to_string(),
                 }),
                 options: None,
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: None,
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
@@ -287,6 +467,9 @@ mod tests {
                     args: Default::default(),
                 }),
                 options: None,
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: None,
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
@@ -296,6 +479,9 @@ mod tests {
                     args: Default::default(),
                 }),
                 options: None,
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: None,
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
@@ -304,18 +490,33 @@ mod tests {
                     script: "pretest".to_string(),
                 }),
                 options: None,
+                depends_on: None,
+                depends_order: None,
+                problem_matcher: None,
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
                 label: "Build Server and Extension".to_string(),
                 command: None,
                 options: None,
+                depends_on: Some(serde_json_lenient::Value::Array(vec![
+                    serde_json_lenient::Value::String("Build Server".to_string()),
+                    serde_json_lenient::Value::String("Build Extension".to_string()),
+                ])),
+                depends_order: None,
+                problem_matcher: None,
                 other_attributes: Default::default(),
             },
             VsCodeTaskDefinition {
                 label: "Build Server (Release) and Extension".to_string(),
                 command: None,
                 options: None,
+                depends_on: Some(serde_json_lenient::Value::Array(vec![
+                    serde_json_lenient::Value::String("Build Server (Release)".to_string()),
+                    serde_json_lenient::Value::String("Build Extension".to_string()),
+                ])),
+                depends_order: None,
+                problem_matcher: None,
                 other_attributes: Default::default(),
             },
         ];
@@ -336,26 +537,97 @@ mod tests {
                 label: "Build Extension".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build".to_string()],
+                problem_matchers: vec![TaskProblemMatcher::Custom(ProblemMatcher {
+                    base_directory: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                    ..built_in_problem_matcher("$tsc").unwrap()
+                })],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server".to_string(),
                 command: "cargo build --package rust-analyzer".to_string(),
+                problem_matchers: vec![TaskProblemMatcher::BuiltIn("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server (Release)".to_string(),
                 command: "cargo build --release --package rust-analyzer".to_string(),
+                problem_matchers: vec![TaskProblemMatcher::BuiltIn("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Pretest".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "pretest".to_string()],
+                problem_matchers: vec![TaskProblemMatcher::Custom(ProblemMatcher {
+                    base_directory: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                    ..built_in_problem_matcher("$tsc").unwrap()
+                })],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server and Extension".to_string(),
+                depends_on: vec!["Build Server".to_string(), "Build Extension".to_string()],
+                problem_matchers: vec![TaskProblemMatcher::BuiltIn("$rustc".to_string())],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server (Release) and Extension".to_string(),
+                depends_on: vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ],
+                problem_matchers: vec![TaskProblemMatcher::BuiltIn("$rustc".to_string())],
                 ..Default::default()
             },
         ];
         let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
         assert_eq!(tasks.0, expected);
     }
+
+    #[test]
+    fn can_deserialize_task_dependencies() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "version": "2.0.0",
+                "tasks": [
+                    {
+                        "label": "Build",
+                        "type": "shell",
+                        "command": "make",
+                        "dependsOn": "Configure"
+                    },
+                    {
+                        "label": "Release",
+                        "dependsOn": ["Build", "Package"],
+                        "dependsOrder": "sequence"
+                    },
+                    {
+                        "label": "Unsupported",
+                        "dependsOn": [{ "type": "npm", "script": "build" }]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(
+            tasks.0,
+            vec![
+                TaskTemplate {
+                    label: "Build".to_string(),
+                    command: "make".to_string(),
+                    depends_on: vec!["Configure".to_string()],
+                    ..Default::default()
+                },
+                TaskTemplate {
+                    label: "Release".to_string(),
+                    depends_on: vec!["Build".to_string(), "Package".to_string()],
+                    depends_order: DependsOrder::Sequence,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
 }
@@ -45,7 +45,7 @@ use pty_info::{ProcessIdGetter, PtyProcessInfo};
 use serde::{Deserialize, Serialize};
 use settings::Settings;
 use smol::channel::{Receiver, Sender};
-use task::{HideStrategy, Shell, SpawnInTerminal};
+use task::{HideStrategy, Shell, SpawnInTerminal, TaskProblem};
 use terminal_hyperlinks::RegexSearches;
 use terminal_settings::{AlternateScroll, CursorShape, TerminalSettings};
 use theme::{ActiveTheme, Theme};
@@ -125,6 +125,11 @@ pub enum Event {
     SelectionsChanged,
     NewNavigationTarget(Option<MaybeNavigationTarget>),
     Open(MaybeNavigationTarget),
+    /// The task finished, and its problem matchers found the problems given in its output.
+    TaskProblems {
+        task_label: String,
+        problems: Vec<TaskProblem>,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -1601,10 +1606,7 @@ impl Terminal {
     }
 
     pub fn get_content(&self) -> String {
-        let term = self.term.lock_unfair();
-        let start = AlacPoint::new(term.topmost_line(), Column(0));
-        let end = AlacPoint::new(term.bottommost_line(), term.last_column());
-        term.bounds_to_string(start, end)
+        term_content(&self.term.lock_unfair())
     }
 
     pub fn last_n_non_empty_lines(&self, n: usize) -> Vec<String> {
@@ -2136,6 +2138,21 @@ impl Terminal {
             }
         };
 
+        if !task.spawned_task.problem_matchers.is_empty() {
+            let output = term_content(&self.term.lock_unfair());
+            let cwd = task.spawned_task.cwd.as_deref();
+            let problems = task
+                .spawned_task
+                .problem_matchers
+                .iter()
+                .flat_map(|matcher| matcher.match_output(&output, cwd))
+                .collect();
+            cx.emit(Event::TaskProblems {
+                task_label: task.spawned_task.full_label.clone(),
+                problems,
+            });
+        }
+
         let (finished_successfully, task_line, command_line) = task_summary(task, error_code);
         let mut lines_to_show = Vec::new();
         if task.spawned_task.show_summary {
@@ -2200,6 +2217,13 @@ pub fn row_to_string(row: &Row<Cell>) -> String {
 }
 
 const TASK_DELIMITER: &str = "⏵ ";
+
+fn term_content(term: &Term<ZedListener>) -> String {
+    let start = AlacPoint::new(term.topmost_line(), Column(0));
+    let end = AlacPoint::new(term.bottommost_line(), term.last_column());
+    term.bounds_to_string(start, end)
+}
+
 fn task_summary(task: &TaskState, error_code: Option<i32>) -> (bool, String, String) {
     let escaped_full_label = task
         .spawned_task
@@ -949,6 +949,20 @@ fn subscribe_for_terminal_events(
                 },
                 Event::BreadcrumbsChanged => cx.emit(ItemEvent::UpdateBreadcrumbs),
                 Event::CloseTerminal => cx.emit(ItemEvent::CloseItem),
+                Event::TaskProblems {
+                    task_label,
+                    problems,
+                } => {
+                    if let Some(project) = terminal_view.project.upgrade() {
+                        project.update(cx, |project, cx| {
+                            project.update_task_diagnostics(
+                                task_label.clone(),
+                                problems.clone(),
+                                cx,
+                            )
+                        });
+                    }
+                }
                 Event::SelectionsChanged => {
                     window.invalidate_character_coordinates();
                     cx.emit(SearchEvent::ActiveMatchChanged)
@@ -5,8 +5,8 @@ use gpui::{
     AsyncWindowContext, Context, Entity, IntoElement, ParentElement, Render, Styled, Subscription,
     Task, WeakEntity, Window, div,
 };
-use language::{Buffer, BufferEvent, LanguageName, Toolchain, ToolchainScope};
-use project::{Project, ProjectPath, Toolchains, WorktreeId, toolchain_store::ToolchainStoreEvent};
+use language::{Buffer, BufferEvent, LanguageName, Toolchain};
+use project::{Project, ProjectPath, WorktreeId, toolchain_store::ToolchainStoreEvent};
 use ui::{Button, ButtonCommon, Clickable, LabelSize, SharedString, Tooltip};
 use util::{maybe, rel_path::RelPath};
 use workspace::{StatusItemView, Workspace, item::ItemHandle};
@@ -167,11 +167,7 @@ impl ActiveToolchain {
                 let project = workspace
                     .read_with(cx, |this, _| this.project().clone())
                     .ok()?;
-                let Toolchains {
-                    toolchains,
-                    root_path: relative_path,
-                    user_toolchains,
-                } = cx
+                let toolchains = cx
                     .update(|_, cx| {
                         project.read(cx).available_toolchains(
                             ProjectPath {
@@ -185,18 +181,8 @@ impl ActiveToolchain {
                     .ok()?
                     .await?;
                 // Since we don't have a selected toolchain, pick one for user here.
-                let default_choice = user_toolchains
-                    .iter()
-                    .find_map(|(scope, toolchains)| {
-                        if scope == &ToolchainScope::Global {
-                            // Ignore global toolchains when making a default choice. They're unlikely to be the right choice.
-                            None
-                        } else {
-                            toolchains.first()
-                        }
-                    })
-                    .or_else(|| toolchains.toolchains.first())
-                    .cloned();
+                let default_choice = toolchains.default_choice();
+                let relative_path = toolchains.root_path;
                 if let Some(toolchain) = &default_choice {
                     workspace::WORKSPACE_DB
                         .set_toolchain(
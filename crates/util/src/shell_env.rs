@@ -1,9 +1,9 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context as _, Result};
 use collections::HashMap;
 
-use crate::shell::ShellKind;
+use crate::{ResultExt as _, shell::ShellKind};
 
 pub fn print_env() {
     let env_vars: HashMap<String, String> = std::env::vars().collect();
@@ -14,6 +14,31 @@ pub fn print_env() {
     println!("{}", json);
 }
 
+/// Puts the directories in front of the other entries of the environment's `PATH`.
+pub fn prepend_to_path(
+    env: &mut HashMap<String, String>,
+    directories: impl IntoIterator<Item = PathBuf>,
+) {
+    // On Windows, the variable is usually spelled `Path`.
+    let key = if env.contains_key("PATH") {
+        "PATH".to_string()
+    } else {
+        env.keys()
+            .find(|key| key.eq_ignore_ascii_case("PATH"))
+            .cloned()
+            .unwrap_or_else(|| "PATH".to_string())
+    };
+    let existing_paths = env
+        .get(&key)
+        .map(|path| std::env::split_paths(path).collect::<Vec<_>>())
+        .unwrap_or_default();
+    if let Some(path) =
+        std::env::join_paths(directories.into_iter().chain(existing_paths)).log_err()
+    {
+        env.insert(key, path.to_string_lossy().into_owned());
+    }
+}
+
 /// Capture all environment variables from the login shell in the given directory.
 pub async fn capture(
     shell_path: impl AsRef<Path>,
@@ -219,3 +244,26 @@ async fn capture_windows(
         format!("Failed to deserialize environment variables from json: {env_output}")
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prepend_to_path() {
+        let separator = if cfg!(windows) { ";" } else { ":" };
+        let mut env = HashMap::from_iter([("Path".to_string(), "b".to_string())]);
+        prepend_to_path(&mut env, [PathBuf::from("a")]);
+        assert_eq!(
+            env,
+            HashMap::from_iter([("Path".to_string(), format!("a{separator}b"))])
+        );
+
+        let mut env = HashMap::default();
+        prepend_to_path(&mut env, [PathBuf::from("a"), PathBuf::from("b")]);
+        assert_eq!(
+            env,
+            HashMap::from_iter([("PATH".to_string(), format!("a{separator}b"))])
+        );
+    }
+}
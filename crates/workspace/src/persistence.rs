@@ -16,7 +16,7 @@ use db::{
     sqlez_macros::sql,
 };
 use gpui::{Axis, Bounds, Task, WindowBounds, WindowId, point, size};
-use project::debugger::breakpoint_store::{BreakpointState, SourceBreakpoint};
+use project::debugger::breakpoint_store::{BreakpointState, FunctionBreakpoint, SourceBreakpoint};
 
 use language::{LanguageName, Toolchain, ToolchainScope};
 use project::WorktreeId;
@@ -157,6 +157,7 @@ impl Column for SerializedWindowBounds {
 #[derive(Debug)]
 pub struct Breakpoint {
     pub position: u32,
+    pub column: Option<u32>,
     pub message: Option<Arc<str>>,
     pub condition: Option<Arc<str>>,
     pub hit_condition: Option<Arc<str>>,
@@ -198,8 +199,8 @@ impl Column for BreakpointStateWrapper<'_> {
 
 impl sqlez::bindable::StaticColumnCount for Breakpoint {
     fn column_count() -> usize {
-        // Position, log message, condition message, and hit condition message
-        4 + BreakpointStateWrapper::column_count()
+        // Position, log message, condition message, and hit condition message, then the column
+        4 + BreakpointStateWrapper::column_count() + 1
     }
 }
 
@@ -213,10 +214,11 @@ impl sqlez::bindable::Bind for Breakpoint {
         let next_index = statement.bind(&self.message, next_index)?;
         let next_index = statement.bind(&self.condition, next_index)?;
         let next_index = statement.bind(&self.hit_condition, next_index)?;
-        statement.bind(
+        let next_index = statement.bind(
             &BreakpointStateWrapper(Cow::Borrowed(&self.state)),
             next_index,
-        )
+        )?;
+        statement.bind(&self.column, next_index)
     }
 }
 
@@ -230,10 +232,12 @@ impl Column for Breakpoint {
         let (condition, next_index) = Option::<String>::column(statement, next_index)?;
         let (hit_condition, next_index) = Option::<String>::column(statement, next_index)?;
         let (state, next_index) = BreakpointStateWrapper::column(statement, next_index)?;
+        let (column, next_index) = Option::<u32>::column(statement, next_index)?;
 
         Ok((
             Breakpoint {
                 position,
+                column,
                 message: message.map(Arc::from),
                 condition: condition.map(Arc::from),
                 hit_condition: hit_condition.map(Arc::from),
@@ -708,6 +712,20 @@ impl Domain for WorkspaceDb {
             ALTER TABLE remote_connections ADD COLUMN name TEXT;
             ALTER TABLE remote_connections ADD COLUMN container_id TEXT;
         ),
+        sql!(
+            ALTER TABLE breakpoints ADD COLUMN breakpoint_column INTEGER;
+
+            CREATE TABLE function_breakpoints (
+                workspace_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                condition TEXT,
+                hit_condition TEXT,
+                state INTEGER DEFAULT(0) NOT NULL,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            );
+        ),
     ];
 
     // Allow recovering from bad migration that was initially shipped to nightly
@@ -837,6 +855,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
         })
@@ -845,7 +864,7 @@ impl WorkspaceDb {
     fn breakpoints(&self, workspace_id: WorkspaceId) -> BTreeMap<Arc<Path>, Vec<SourceBreakpoint>> {
         let breakpoints: Result<Vec<(PathBuf, Breakpoint)>> = self
             .select_bound(sql! {
-                SELECT path, breakpoint_location, log_message, condition, hit_condition, state
+                SELECT path, breakpoint_location, log_message, condition, hit_condition, state, breakpoint_column
                 FROM breakpoints
                 WHERE workspace_id = ?
             })
@@ -863,6 +882,7 @@ impl WorkspaceDb {
                     let path: Arc<Path> = path.into();
                     map.entry(path.clone()).or_default().push(SourceBreakpoint {
                         row: breakpoint.position,
+                        column: breakpoint.column,
                         path,
                         message: breakpoint.message,
                         condition: breakpoint.condition,
@@ -888,6 +908,41 @@ impl WorkspaceDb {
         }
     }
 
+    fn function_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<FunctionBreakpoint> {
+        let breakpoints: Result<
+            Vec<(
+                String,
+                Option<String>,
+                Option<String>,
+                BreakpointStateWrapper<'static>,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT name, condition, hit_condition, state
+                FROM function_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => breakpoints
+                .into_iter()
+                .map(
+                    |(name, condition, hit_condition, state)| FunctionBreakpoint {
+                        name: name.into(),
+                        condition: condition.map(Arc::from),
+                        hit_condition: hit_condition.map(Arc::from),
+                        state: state.0.into_owned(),
+                    },
+                )
+                .collect(),
+            Err(msg) => {
+                log::error!("Function breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     fn user_toolchains(
         &self,
         workspace_id: WorkspaceId,
@@ -991,8 +1046,8 @@ impl WorkspaceDb {
                     for bp in breakpoints {
                         let state = BreakpointStateWrapper::from(bp.state);
                         match conn.exec_bound(sql!(
-                            INSERT INTO breakpoints (workspace_id, path, breakpoint_location,  log_message, condition, hit_condition, state)
-                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);))?
+                            INSERT INTO breakpoints (workspace_id, path, breakpoint_location,  log_message, condition, hit_condition, state, breakpoint_column)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);))?
 
                         ((
                             workspace.id,
@@ -1002,6 +1057,7 @@ impl WorkspaceDb {
                             bp.condition,
                             bp.hit_condition,
                             state,
+                            bp.column,
                         )) {
                             Ok(_) => {
                                 log::debug!("Stored breakpoint at row: {} in path: {}", bp.row, path.to_string_lossy())
@@ -1014,6 +1070,28 @@ impl WorkspaceDb {
                     }
                 }
 
+                conn.exec_bound(
+                    sql!(
+                        DELETE FROM function_breakpoints WHERE workspace_id = ?1;
+                    )
+                )?(workspace.id).context("Clearing old function breakpoints")?;
+
+                for bp in workspace.function_breakpoints {
+                    let state = BreakpointStateWrapper::from(bp.state);
+                    if let Err(err) = conn.exec_bound(sql!(
+                        INSERT INTO function_breakpoints (workspace_id, name, condition, hit_condition, state)
+                        VALUES (?1, ?2, ?3, ?4, ?5);))?
+                    ((
+                        workspace.id,
+                        bp.name.as_ref(),
+                        bp.condition,
+                        bp.hit_condition,
+                        state,
+                    )) {
+                        log::error!("{err}");
+                    }
+                }
+
                 conn.exec_bound(
                     sql!(
                         DELETE FROM user_toolchains WHERE workspace_id = ?1;
@@ -1851,6 +1929,7 @@ mod tests {
 
         let breakpoint = Breakpoint {
             position: 123,
+            column: None,
             message: None,
             state: BreakpointState::Enabled,
             condition: None,
@@ -1859,6 +1938,7 @@ mod tests {
 
         let log_breakpoint = Breakpoint {
             position: 456,
+            column: None,
             message: Some("Test log message".into()),
             state: BreakpointState::Enabled,
             condition: None,
@@ -1867,6 +1947,7 @@ mod tests {
 
         let disable_breakpoint = Breakpoint {
             position: 578,
+            column: None,
             message: None,
             state: BreakpointState::Disabled,
             condition: None,
@@ -1875,6 +1956,7 @@ mod tests {
 
         let condition_breakpoint = Breakpoint {
             position: 789,
+            column: Some(8),
             message: None,
             state: BreakpointState::Enabled,
             condition: Some("x > 5".into()),
@@ -1883,6 +1965,7 @@ mod tests {
 
         let hit_condition_breakpoint = Breakpoint {
             position: 999,
+            column: None,
             message: None,
             state: BreakpointState::Enabled,
             condition: None,
@@ -1905,6 +1988,7 @@ mod tests {
                     vec![
                         SourceBreakpoint {
                             row: breakpoint.position,
+                            column: breakpoint.column,
                             path: Arc::from(path),
                             message: breakpoint.message.clone(),
                             state: breakpoint.state,
@@ -1913,6 +1997,7 @@ mod tests {
                         },
                         SourceBreakpoint {
                             row: log_breakpoint.position,
+                            column: log_breakpoint.column,
                             path: Arc::from(path),
                             message: log_breakpoint.message.clone(),
                             state: log_breakpoint.state,
@@ -1921,6 +2006,7 @@ mod tests {
                         },
                         SourceBreakpoint {
                             row: disable_breakpoint.position,
+                            column: disable_breakpoint.column,
                             path: Arc::from(path),
                             message: disable_breakpoint.message.clone(),
                             state: disable_breakpoint.state,
@@ -1929,6 +2015,7 @@ mod tests {
                         },
                         SourceBreakpoint {
                             row: condition_breakpoint.position,
+                            column: condition_breakpoint.column,
                             path: Arc::from(path),
                             message: condition_breakpoint.message.clone(),
                             state: condition_breakpoint.state,
@@ -1937,6 +2024,7 @@ mod tests {
                         },
                         SourceBreakpoint {
                             row: hit_condition_breakpoint.position,
+                            column: hit_condition_breakpoint.column,
                             path: Arc::from(path),
                             message: hit_condition_breakpoint.message.clone(),
                             state: hit_condition_breakpoint.state,
@@ -1947,6 +2035,15 @@ mod tests {
                 );
                 map
             },
+            function_breakpoints: vec![
+                FunctionBreakpoint::new("rust_begin_unwind".into()),
+                FunctionBreakpoint {
+                    name: "panic".into(),
+                    condition: Some("x > 5".into()),
+                    hit_condition: Some(">= 3".into()),
+                    state: BreakpointState::Disabled,
+                },
+            ],
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -1955,6 +2052,7 @@ mod tests {
         db.save_workspace(workspace.clone()).await;
 
         let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.function_breakpoints, workspace.function_breakpoints);
         let loaded_breakpoints = loaded.breakpoints.get(&Arc::from(path)).unwrap();
 
         assert_eq!(loaded_breakpoints.len(), 5);
@@ -1997,6 +2095,7 @@ mod tests {
 
         // condition breakpoint
         assert_eq!(loaded_breakpoints[3].row, condition_breakpoint.position);
+        assert_eq!(loaded_breakpoints[3].column, condition_breakpoint.column);
         assert_eq!(loaded_breakpoints[3].message, condition_breakpoint.message);
         assert_eq!(
             loaded_breakpoints[3].condition,
@@ -2038,6 +2137,7 @@ mod tests {
 
         let breakpoint_to_remove = Breakpoint {
             position: 100,
+            column: None,
             message: None,
             state: BreakpointState::Enabled,
             condition: None,
@@ -2059,6 +2159,7 @@ mod tests {
                     Arc::from(singular_path),
                     vec![SourceBreakpoint {
                         row: breakpoint_to_remove.position,
+                        column: None,
                         path: Arc::from(singular_path),
                         message: None,
                         state: BreakpointState::Enabled,
@@ -2068,6 +2169,7 @@ mod tests {
                 );
                 map
             },
+            function_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2102,6 +2204,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: collections::BTreeMap::default(),
+            function_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2200,6 +2303,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2215,6 +2319,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2319,6 +2424,7 @@ mod tests {
             center_group,
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2353,6 +2459,7 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2371,6 +2478,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
             user_toolchains: Default::default(),
@@ -2410,6 +2518,7 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2451,6 +2560,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
             user_toolchains: Default::default(),
@@ -2466,6 +2576,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
             user_toolchains: Default::default(),
@@ -2481,6 +2592,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
             user_toolchains: Default::default(),
@@ -2496,6 +2608,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
             user_toolchains: Default::default(),
@@ -2522,6 +2635,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
             user_toolchains: Default::default(),
@@ -2534,6 +2648,7 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2588,6 +2703,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             centered_layout: false,
             session_id: None,
             window_id: None,
@@ -2625,6 +2741,7 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             window_id: Some(window_id),
             user_toolchains: Default::default(),
         })
@@ -2723,6 +2840,7 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
             window_id: Some(window_id),
             user_toolchains: Default::default(),
         })
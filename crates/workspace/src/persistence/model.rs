@@ -13,7 +13,10 @@ use db::sqlez::{
 use gpui::{AsyncWindowContext, Entity, WeakEntity};
 
 use language::{Toolchain, ToolchainScope};
-use project::{Project, debugger::breakpoint_store::SourceBreakpoint};
+use project::{
+    Project,
+    debugger::breakpoint_store::{FunctionBreakpoint, SourceBreakpoint},
+};
 use remote::RemoteConnectionOptions;
 use std::{
     collections::BTreeMap,
@@ -60,6 +63,7 @@ pub(crate) struct SerializedWorkspace {
     pub(crate) docks: DockStructure,
     pub(crate) session_id: Option<String>,
     pub(crate) breakpoints: BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>,
+    pub(crate) function_breakpoints: Vec<FunctionBreakpoint>,
     pub(crate) user_toolchains: BTreeMap<ToolchainScope, IndexSet<Toolchain>>,
     pub(crate) window_id: Option<u64>,
 }
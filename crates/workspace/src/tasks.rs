@@ -1,11 +1,18 @@
-use std::process::ExitStatus;
+use std::{cell::RefCell, process::ExitStatus, rc::Rc, sync::Arc};
 
-use anyhow::Result;
-use gpui::{AppContext, Context, Entity, Task};
+use anyhow::{Context as _, Result, anyhow};
+use collections::HashMap;
+use futures::{
+    FutureExt as _,
+    future::{LocalBoxFuture, Shared},
+};
+use gpui::{AppContext, AsyncWindowContext, Context, Entity, Task, WeakEntity};
 use language::Buffer;
-use project::{TaskSourceKind, WorktreeId};
+use project::{TaskDependencies, TaskDependency, TaskSourceKind, WorktreeId};
 use remote::ConnectionState;
-use task::{DebugScenario, ResolvedTask, SpawnInTerminal, TaskContext, TaskTemplate};
+use task::{
+    DebugScenario, DependsOrder, ResolvedTask, SpawnInTerminal, TaskContext, TaskId, TaskTemplate,
+};
 use ui::Window;
 
 use crate::{Toast, Workspace, notifications::NotificationId};
@@ -55,6 +62,22 @@ impl Workspace {
         cx: &mut Context<Workspace>,
     ) {
         let spawn_in_terminal = resolved_task.resolved.clone();
+        let dependencies = if resolved_task.original_task().depends_on.is_empty() {
+            None
+        } else {
+            Some(
+                match self.project.read(cx).task_store().read(cx).task_inventory() {
+                    Some(inventory) => inventory.read(cx).resolve_task_dependencies(
+                        &task_source_kind,
+                        &resolved_task,
+                        cx,
+                    ),
+                    None => Task::ready(Err(anyhow!(
+                        "task dependencies are not available in this project"
+                    ))),
+                },
+            )
+        };
         if !omit_history {
             if let Some(debugger_provider) = self.debugger_provider.as_ref() {
                 debugger_provider.task_scheduled(cx);
@@ -72,7 +95,41 @@ impl Workspace {
         }
 
         if let Some(terminal_provider) = self.terminal_provider.as_ref() {
-            let task_status = terminal_provider.spawn(spawn_in_terminal, window, cx);
+            let task_status = match dependencies {
+                // Tasks without a command only run their dependencies.
+                None if spawn_in_terminal.command.is_none() => {
+                    Task::ready(Some(Ok(ExitStatus::default())))
+                }
+                None => terminal_provider.spawn(spawn_in_terminal, window, cx),
+                Some(dependencies) => cx.spawn_in(window, async move |workspace, cx| {
+                    let label = spawn_in_terminal.label.clone();
+                    let dependencies_result = match dependencies.await {
+                        Ok(dependencies) => {
+                            run_task_dependencies(
+                                workspace.clone(),
+                                dependencies,
+                                DependencyRuns::default(),
+                                cx.clone(),
+                            )
+                            .await
+                        }
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = dependencies_result {
+                        return Some(Err(anyhow!("Task `{label}` was not started: {e:#}")));
+                    }
+                    // Tasks without a command only run their dependencies.
+                    if spawn_in_terminal.command.is_none() {
+                        return Some(Ok(ExitStatus::default()));
+                    }
+                    workspace
+                        .update_in(cx, |workspace, window, cx| {
+                            workspace.spawn_in_terminal(spawn_in_terminal, window, cx)
+                        })
+                        .ok()?
+                        .await
+                }),
+            };
 
             let task = cx.spawn(async |w, cx| {
                 let res = cx.background_spawn(task_status).await;
@@ -132,3 +189,174 @@ impl Workspace {
         }
     }
 }
+
+type DependencyRun = Shared<LocalBoxFuture<'static, Result<(), Arc<anyhow::Error>>>>;
+
+/// The dependencies started while spawning a task, by their ids.
+///
+/// A dependency shared by several tasks is spawned once, and the tasks depending on it wait for the same run.
+#[derive(Clone, Default)]
+struct DependencyRuns(Rc<RefCell<HashMap<TaskId, DependencyRun>>>);
+
+/// Spawns the tasks given in their order, spawning the tasks they depend on first.
+/// Fails as soon as any of the tasks fails to spawn or exits unsuccessfully, not starting the remaining ones.
+fn run_task_dependencies(
+    workspace: WeakEntity<Workspace>,
+    dependencies: TaskDependencies,
+    runs: DependencyRuns,
+    cx: AsyncWindowContext,
+) -> LocalBoxFuture<'static, Result<()>> {
+    async move {
+        match dependencies.order {
+            DependsOrder::Sequence => {
+                for dependency in dependencies.tasks {
+                    run_task_dependency(workspace.clone(), dependency, runs.clone(), cx.clone())
+                        .await
+                        .map_err(|e| anyhow!("{e:#}"))?;
+                }
+            }
+            DependsOrder::Parallel => {
+                futures::future::try_join_all(dependencies.tasks.into_iter().map(|dependency| {
+                    run_task_dependency(workspace.clone(), dependency, runs.clone(), cx.clone())
+                }))
+                .await
+                .map_err(|e| anyhow!("{e:#}"))?;
+            }
+        }
+        Ok(())
+    }
+    .boxed_local()
+}
+
+fn run_task_dependency(
+    workspace: WeakEntity<Workspace>,
+    dependency: TaskDependency,
+    runs: DependencyRuns,
+    mut cx: AsyncWindowContext,
+) -> DependencyRun {
+    let id = dependency.task.id.clone();
+    if let Some(run) = runs.0.borrow().get(&id) {
+        return run.clone();
+    }
+
+    let dependency_runs = runs.clone();
+    let run = async move {
+        run_task_dependencies(
+            workspace.clone(),
+            dependency.dependencies,
+            dependency_runs,
+            cx.clone(),
+        )
+        .await?;
+        if dependency.task.resolved.command.is_none() {
+            return Ok(());
+        }
+
+        let label = dependency.task.resolved_label.clone();
+        let task_status = workspace.update_in(&mut cx, |workspace, window, cx| {
+            workspace.spawn_in_terminal(dependency.task.resolved, window, cx)
+        })?;
+        match task_status.await {
+            Some(Ok(status)) if status.success() => Ok(()),
+            Some(Ok(status)) => Err(anyhow!("Task `{label}` failed: {status}")),
+            Some(Err(e)) => Err(e).with_context(|| format!("spawning task `{label}`")),
+            None => Err(anyhow!("Task `{label}` got cancelled")),
+        }
+    };
+    let run = run
+        .map(|result| result.map_err(Arc::new))
+        .boxed_local()
+        .shared();
+    runs.0.borrow_mut().insert(id, run.clone());
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use fs::FakeFs;
+    use gpui::{App, TestAppContext, Window};
+    use project::{Project, TaskDependencies, TaskDependency, TaskSourceKind};
+    use task::{DependsOrder, SpawnInTerminal, TaskContext, TaskTemplate};
+
+    use super::*;
+    use crate::{TerminalProvider, tests::init_test};
+
+    struct RecordingTerminalProvider(Rc<RefCell<Vec<String>>>);
+
+    impl TerminalProvider for RecordingTerminalProvider {
+        fn spawn(
+            &self,
+            task: SpawnInTerminal,
+            _: &mut Window,
+            _: &mut App,
+        ) -> Task<Option<Result<ExitStatus>>> {
+            self.0.borrow_mut().push(task.label);
+            Task::ready(Some(Ok(ExitStatus::default())))
+        }
+    }
+
+    fn dependency(label: &str, dependencies: Vec<TaskDependency>) -> TaskDependency {
+        let task = TaskTemplate {
+            label: label.to_string(),
+            command: format!("echo {label}"),
+            ..TaskTemplate::default()
+        }
+        .resolve_task("test", &TaskContext::default())
+        .unwrap();
+        TaskDependency {
+            source_kind: TaskSourceKind::UserInput,
+            task,
+            dependencies: TaskDependencies {
+                order: DependsOrder::Parallel,
+                tasks: dependencies,
+            },
+        }
+    }
+
+    #[gpui::test]
+    async fn test_shared_dependencies_run_once(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let spawned = Rc::new(RefCell::new(Vec::new()));
+        workspace.update(cx, |workspace, _| {
+            workspace.set_terminal_provider(RecordingTerminalProvider(spawned.clone()))
+        });
+
+        // A diamond: both `b` and `c` depend on `d`.
+        for order in [DependsOrder::Sequence, DependsOrder::Parallel] {
+            spawned.borrow_mut().clear();
+            let dependencies = TaskDependencies {
+                order,
+                tasks: vec![
+                    dependency("b", vec![dependency("d", Vec::new())]),
+                    dependency("c", vec![dependency("d", Vec::new())]),
+                ],
+            };
+            workspace
+                .update_in(cx, |_, window, cx| {
+                    cx.spawn_in(window, async move |workspace, cx| {
+                        run_task_dependencies(
+                            workspace,
+                            dependencies,
+                            DependencyRuns::default(),
+                            cx.clone(),
+                        )
+                        .await
+                    })
+                })
+                .await
+                .unwrap();
+
+            let mut spawned = spawned.borrow().clone();
+            assert_eq!(spawned.first().map(String::as_str), Some("d"));
+            spawned.sort();
+            assert_eq!(spawned, ["b", "c", "d"], "{order:?}");
+        }
+    }
+}
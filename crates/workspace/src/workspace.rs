@@ -5501,11 +5501,12 @@ impl Workspace {
 
         match self.serialize_workspace_location(cx) {
             WorkspaceLocation::Location(location, paths) => {
-                let breakpoints = self.project.update(cx, |project, cx| {
-                    project
-                        .breakpoint_store()
-                        .read(cx)
-                        .all_source_breakpoints(cx)
+                let (breakpoints, function_breakpoints) = self.project.update(cx, |project, cx| {
+                    let breakpoint_store = project.breakpoint_store().read(cx);
+                    (
+                        breakpoint_store.all_source_breakpoints(cx),
+                        breakpoint_store.function_breakpoints().to_vec(),
+                    )
                 });
                 let user_toolchains = self
                     .project
@@ -5528,6 +5529,7 @@ impl Workspace {
                     centered_layout: self.centered_layout,
                     session_id: self.session_id.clone(),
                     breakpoints,
+                    function_breakpoints,
                     window_id: Some(window.window_handle().window_id().as_u64()),
                     user_toolchains,
                 };
@@ -5709,8 +5711,11 @@ impl Workspace {
                     project
                         .breakpoint_store()
                         .update(cx, |breakpoint_store, cx| {
-                            breakpoint_store
-                                .with_serialized_breakpoints(serialized_workspace.breakpoints, cx)
+                            breakpoint_store.with_serialized_breakpoints(
+                                serialized_workspace.breakpoints,
+                                serialized_workspace.function_breakpoints,
+                                cx,
+                            )
                         })
                 })?
                 .await;